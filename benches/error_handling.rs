@@ -5,6 +5,8 @@
 //!
 //! Run with: cargo bench
 
+#![allow(clippy::result_large_err)]
+
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use handle_this::{handle, Result, Handled};
 use std::io;
@@ -257,16 +259,13 @@ fn bench_typed_fallback_rust(c: &mut Criterion) {
         b.iter(|| {
             let result: i32 = fallible_err()
                 .map_err(|e| -> Box<dyn Error + Send + Sync> { Box::new(e) })
-                .map_or_else(
-                    |boxed| {
-                        if boxed.downcast_ref::<io::Error>().is_some() {
-                            black_box(42)
-                        } else {
-                            black_box(0)
-                        }
-                    },
-                    |v| v,
-                );
+                .unwrap_or_else(|boxed| {
+                    if boxed.downcast_ref::<io::Error>().is_some() {
+                        black_box(42)
+                    } else {
+                        black_box(0)
+                    }
+                });
             black_box(result)
         })
     });
@@ -297,20 +296,17 @@ fn bench_multi_typed_rust(c: &mut Criterion) {
         b.iter(|| {
             let result: i32 = fallible_err()
                 .map_err(|e| -> Box<dyn Error + Send + Sync> { Box::new(e) })
-                .map_or_else(
-                    |boxed| {
-                        if boxed.downcast_ref::<std::fmt::Error>().is_some() {
-                            black_box(1)
-                        } else if boxed.downcast_ref::<std::num::ParseIntError>().is_some() {
-                            black_box(2)
-                        } else if boxed.downcast_ref::<io::Error>().is_some() {
-                            black_box(3)
-                        } else {
-                            black_box(0)
-                        }
-                    },
-                    |v| v,
-                );
+                .unwrap_or_else(|boxed| {
+                    if boxed.downcast_ref::<std::fmt::Error>().is_some() {
+                        black_box(1)
+                    } else if boxed.downcast_ref::<std::num::ParseIntError>().is_some() {
+                        black_box(2)
+                    } else if boxed.downcast_ref::<io::Error>().is_some() {
+                        black_box(3)
+                    } else {
+                        black_box(0)
+                    }
+                });
             black_box(result)
         })
     });
@@ -436,18 +432,15 @@ fn bench_realistic_rust(c: &mut Criterion) {
                 let value = parse_config(&content)?;
                 Ok(value)
             })()
-            .map_or_else(
-                |e| {
-                    if e.downcast_ref::<io::Error>().is_some() {
-                        black_box(-1)
-                    } else if e.downcast_ref::<std::num::ParseIntError>().is_some() {
-                        black_box(-2)
-                    } else {
-                        black_box(-3)
-                    }
-                },
-                |v| v,
-            );
+            .unwrap_or_else(|e| {
+                if e.downcast_ref::<io::Error>().is_some() {
+                    black_box(-1)
+                } else if e.downcast_ref::<std::num::ParseIntError>().is_some() {
+                    black_box(-2)
+                } else {
+                    black_box(-3)
+                }
+            });
             black_box(result)
         })
     });
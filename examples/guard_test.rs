@@ -1,3 +1,5 @@
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Result};
 use std::io;
 
@@ -1,3 +1,5 @@
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Handled};
 use std::io::{self, ErrorKind};
 type Result<T> = std::result::Result<T, Handled>;
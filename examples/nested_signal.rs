@@ -1,5 +1,7 @@
 //! Test nested try blocks with signal mode (break/continue in fallible mode)
 
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Result};
 
 fn main() {
@@ -14,7 +16,7 @@ fn main() {
             try {
                 try {
                     if i < 3 {
-                        Err(std::io::Error::other("inner fail"))?
+                        Err(std::io::Error::new(std::io::ErrorKind::Other, "inner fail"))?
                     }
                     i * 10
                 }
@@ -38,7 +40,7 @@ fn main() {
         try {
             try {
                 try {
-                    Err::<i32, _>(std::io::Error::other("deep"))?
+                    Err::<i32, _>(std::io::Error::new(std::io::ErrorKind::Other, "deep"))?
                 }
                 with "level 2"
             }
@@ -64,7 +66,7 @@ fn main() {
             try {
                 try {
                     if i < 3 {
-                        Err(std::io::Error::other("inner"))?
+                        Err(std::io::Error::new(std::io::ErrorKind::Other, "inner"))?
                     }
                     i * 10
                 }
@@ -88,7 +90,7 @@ fn main() {
             let result: Result<i32> = handle! {
                 try {
                     if i < 5 {
-                        Err(std::io::Error::other("not yet"))?
+                        Err(std::io::Error::new(std::io::ErrorKind::Other, "not yet"))?
                     }
                     i * 100
                 }
@@ -107,7 +109,7 @@ fn main() {
         let result: Result<i32> = handle! {
             try {
                 match i {
-                    0 => Err(std::io::Error::other("io"))?,
+                    0 => Err(std::io::Error::new(std::io::ErrorKind::Other, "io"))?,
                     1 => Err("parse".parse::<i32>().unwrap_err())?,
                     _ => i,
                 }
@@ -132,7 +134,7 @@ fn main() {
         let result: Result<i32> = handle! {
             try {
                 try {
-                    Err::<i32, _>(std::io::Error::other("inner"))?
+                    Err::<i32, _>(std::io::Error::new(std::io::ErrorKind::Other, "inner"))?
                 }
                 throw _ {
                     if i >= 2 {
@@ -155,7 +157,7 @@ fn main() {
                 try {
                     try {
                         match i {
-                            0 => Err(std::io::Error::other("deep"))?,
+                            0 => Err(std::io::Error::new(std::io::ErrorKind::Other, "deep"))?,
                             1 => Err("x".parse::<i32>().unwrap_err())?,
                             _ => i * 10,
                         }
@@ -178,7 +180,7 @@ fn main() {
             try {
                 try {
                     if i < 2 {
-                        Err(std::io::Error::other("fail"))?
+                        Err(std::io::Error::new(std::io::ErrorKind::Other, "fail"))?
                     }
                     i * 10
                 }
@@ -199,14 +201,14 @@ fn main() {
             try {
                 try {
                     try {
-                        if i == 0 { Err(std::io::Error::other("L3"))? }
+                        if i == 0 { Err(std::io::Error::new(std::io::ErrorKind::Other, "L3"))? }
                         if i == 1 { Err("x".parse::<i32>().unwrap_err())? }
-                        if i == 2 { Err(std::io::Error::other("L3-break"))? }
+                        if i == 2 { Err(std::io::Error::new(std::io::ErrorKind::Other, "L3-break"))? }
                         i * 100
                     }
                     throw std::io::Error(e) {
                         trace.push(format!("throw-{}", i));
-                        std::io::Error::other(format!("wrapped: {}", e))
+                        std::io::Error::new(std::io::ErrorKind::Other, format!("wrapped: {}", e))
                     }
                 }
                 catch std::num::ParseIntError(_) {
@@ -232,7 +234,7 @@ fn main() {
             try {
                 try {
                     if i < 2 {
-                        Err(std::io::Error::other("fail"))?
+                        Err(std::io::Error::new(std::io::ErrorKind::Other, "fail"))?
                     }
                     i * 10
                 }
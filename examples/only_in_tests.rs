@@ -0,0 +1,22 @@
+//! `only_in_tests` bodies vanish outside `cfg(test)`.
+//!
+//! The handler below calls a `#[cfg(test)]`-only function; this example
+//! only compiles because the body is stripped in non-test builds.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::io;
+
+#[cfg(test)]
+fn test_only_check(_e: &handle_this::Handled) {}
+
+fn main() {
+    let result: Result<i32> = handle! {
+        try { Err(io::Error::new(io::ErrorKind::Other, "boom"))? }
+        only_in_tests e { test_only_check(&e) }
+        catch { 0 }
+    };
+    assert_eq!(result.unwrap(), 0);
+    println!("only_in_tests body stripped outside cfg(test)");
+}
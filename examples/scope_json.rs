@@ -2,6 +2,8 @@
 //!
 //! Run with: cargo run --example scope_json --features serde
 
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Handled, Result, Value};
 
 /// Format a Value as JSON (preserving types)
@@ -13,6 +15,14 @@ fn value_to_json(v: &Value) -> String {
         Value::Float(n) => n.to_string(),
         Value::Bool(b) => b.to_string(),
         Value::Null => "null".to_string(),
+        Value::List(items) => format!("[{}]", items.iter().map(value_to_json).collect::<Vec<_>>().join(", ")),
+        Value::Map(entries) => format!(
+            "{{ {} }}",
+            entries.iter().map(|(k, v)| format!(r#""{}": {}"#, k, value_to_json(v))).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Bytes(bytes) => format!("{:?}", bytes),
+        Value::Duration(d) => format!(r#"{{ "secs": {}, "nanos": {} }}"#, d.as_secs(), d.subsec_nanos()),
+        Value::Redacted(hash) => format!(r#""redacted:{:016x}""#, hash),
     }
 }
 
@@ -56,7 +66,7 @@ fn main() {
     let result: Result<i32> = handle! {
         scope "auth_flow",
         try {
-            Err(Handled::from(std::io::Error::other("invalid token")))?
+            Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "invalid token")))?
         }
         catch e {
             println!("{}\n", to_json(&e));
@@ -70,7 +80,7 @@ fn main() {
     let result: Result<i32> = handle! {
         scope "api_call", { endpoint: "/users", method: "GET" },
         try {
-            Err(Handled::from(std::io::Error::other("rate limited")))?
+            Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "rate limited")))?
         }
         catch e {
             println!("{}\n", to_json(&e));
@@ -85,7 +95,7 @@ fn main() {
         scope "level1",
         try {
             scope "level2", try {
-                Err(Handled::from(std::io::Error::other("deep error")))?
+                Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "deep error")))?
             }
             catch e {
                 println!("Level 2 caught:");
@@ -109,7 +119,7 @@ fn main() {
             let inner_result: Result<i32> = handle! {
                 scope "inner", { inner_data: 2 },
                 try {
-                    Err(Handled::from(std::io::Error::other("inner failure")))?
+                    Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "inner failure")))?
                 }
                 try catch e {
                     println!("Inner scope caught:");
@@ -137,7 +147,7 @@ fn main() {
             try {
                 scope "token_validation",
                 try {
-                    Err(Handled::from(std::io::Error::other("token expired")))?
+                    Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "token expired")))?
                 }
                 catch e {
                     println!("Token validation failed:");
@@ -168,7 +178,7 @@ fn main() {
             try {
                 scope "token_validation", { cache_hit: false },
                 try {
-                    Err(Handled::from(std::io::Error::other("token expired")))?
+                    Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "token expired")))?
                 }
                 // No catch here - error propagates up
             }
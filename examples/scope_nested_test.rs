@@ -1,5 +1,7 @@
 //! Test nested scope functionality
 
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Result, Handled};
 
 fn main() {
@@ -10,7 +12,7 @@ fn main() {
     let result: Result<i32> = handle! {
         scope "outer",
         try {
-            Err(Handled::from(std::io::Error::other("test")))?
+            Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "test")))?
         }
         catch { 42 }
     };
@@ -21,7 +23,7 @@ fn main() {
     let result: Result<i32> = handle! {
         try {
             scope "inner", try {
-                Err(Handled::from(std::io::Error::other("inner error")))?
+                Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "inner error")))?
             }
             catch { 1 }
         }
@@ -35,7 +37,7 @@ fn main() {
         scope "level1",
         try {
             scope "level2", try {
-                Err(Handled::from(std::io::Error::other("deep error")))?
+                Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "deep error")))?
             }
             catch { 1 }
         }
@@ -47,7 +49,7 @@ fn main() {
     println!("Test 4: Scope in catch body");
     let result: Result<i32> = handle! {
         try {
-            Err(Handled::from(std::io::Error::other("outer error")))?
+            Err(Handled::from(std::io::Error::new(std::io::ErrorKind::Other, "outer error")))?
         }
         catch {
             scope "in_catch", try {
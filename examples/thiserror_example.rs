@@ -2,6 +2,8 @@
 //!
 //! This shows how handle-this integrates naturally with thiserror-defined errors.
 
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Result};
 use thiserror::Error;
 
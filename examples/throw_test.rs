@@ -1,3 +1,5 @@
+#![allow(clippy::result_large_err)]
+
 use handle_this::{handle, Handled};
 use std::io;
 type Result<T> = std::result::Result<T, Handled>;
@@ -5,7 +7,7 @@ type Result<T> = std::result::Result<T, Handled>;
 fn main() {
     // Test 1: throw + untyped catch -> Ok
     let result: Result<i32> = handle! {
-        try { Err(io::Error::other("test"))? }
+        try { Err(io::Error::new(io::ErrorKind::Other, "test"))? }
         throw e { format!("transformed: {}", e) }
         catch _e { 42 }
     };
@@ -14,7 +16,7 @@ fn main() {
 
     // Test 2: throw only -> Err
     let result: Result<i32> = handle! {
-        try { Err(io::Error::other("test"))? }
+        try { Err(io::Error::new(io::ErrorKind::Other, "test"))? }
         throw e { format!("transformed: {}", e) }
     };
     assert!(result.is_err(), "throw only should be Err");
@@ -25,7 +27,7 @@ fn main() {
 
     // Test 3: throw + typed catch (wrong type) -> Err
     let result: Result<i32> = handle! {
-        try { Err(io::Error::other("test"))? }
+        try { Err(io::Error::new(io::ErrorKind::Other, "test"))? }
         throw e { format!("transformed: {}", e) }
         catch io::Error(_e) { 42 }
     };
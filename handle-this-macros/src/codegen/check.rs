@@ -71,7 +71,7 @@ pub fn wrap_with_type_check(
         ChainVariant::All => {
             quote! {
                 {
-                    let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                    let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                     if !#binding.is_empty() {
                         #inner
                     }
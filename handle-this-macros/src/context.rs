@@ -0,0 +1,46 @@
+//! `#[context("message")]`: `with` context for a whole function.
+//!
+//! The original body runs first (in a closure, or an async block for an
+//! `async fn`) so `return` and `?` keep their meaning. Its result then goes
+//! through `handle! { try { result? } with ... }`, where the attribute's
+//! arguments are the `with` clause: a message, `{ key: value }`
+//! attachments, or both.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, ItemFn, Result, ReturnType};
+
+/// Expand `#[context(...)]` on a function.
+pub fn expand(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    if attr.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`#[context]` needs a message: `#[context(\"loading config\")]`",
+        ));
+    }
+    let mut func: ItemFn = syn::parse2(item)?;
+    if let ReturnType::Default = func.sig.output {
+        return Err(syn::Error::new_spanned(
+            &func.sig,
+            "`#[context]` needs a function returning `handle_this::Result`",
+        ));
+    }
+
+    // The helpers fix the body's output as `Result<_>` so `?` converts
+    // without naming the return type, which may be `impl Trait`
+    let body = &func.block;
+    let run = if func.sig.asyncness.is_some() {
+        quote! {
+            let __handle_this_result = ::handle_this::__context_async(async move #body).await;
+        }
+    } else {
+        quote! {
+            let __handle_this_result = ::handle_this::__context_body(move || #body);
+        }
+    };
+    func.block = parse_quote! {{
+        #run
+        ::handle_this::handle! { try { __handle_this_result? } with #attr }
+    }};
+    Ok(quote! { #func })
+}
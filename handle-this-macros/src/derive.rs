@@ -0,0 +1,94 @@
+//! `#[derive(HandleThis)]` for domain error types.
+//!
+//! Generates:
+//! - `From<T> for Handled`, so `?` converts `T` outside `handle!` too
+//!   (the source stays `T`, so `catch T(e)` and `downcast_ref` see it)
+//! - `IntoValue for T`, so the error can be attached with `.kv()`: unit
+//!   variants become their name, everything else its `Display` text
+//! - with `#[handle_this(error)]`, an empty `std::error::Error` impl for
+//!   types that only have `Debug` and `Display`; the message is `Display`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Result};
+
+/// Expand `#[derive(HandleThis)]`.
+pub fn expand(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let impl_error = parse_options(&input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let value = match &input.data {
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let label = ident.to_string();
+                match variant.fields {
+                    Fields::Unit => quote! {
+                        Self::#ident => ::handle_this::Value::String(#label.into()),
+                    },
+                    Fields::Named(_) => quote! {
+                        Self::#ident { .. } => ::handle_this::Value::String(self.to_string()),
+                    },
+                    Fields::Unnamed(_) => quote! {
+                        Self::#ident(..) => ::handle_this::Value::String(self.to_string()),
+                    },
+                }
+            });
+            quote! {
+                match &self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Struct(_) => quote! { ::handle_this::Value::String(self.to_string()) },
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`HandleThis` can't be derived for unions",
+            ));
+        }
+    };
+
+    let error_impl = impl_error.then(|| {
+        quote! {
+            impl #impl_generics ::std::error::Error for #name #ty_generics #where_clause {}
+        }
+    });
+
+    Ok(quote! {
+        #error_impl
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::handle_this::Handled
+        #where_clause
+        {
+            fn from(e: #name #ty_generics) -> Self {
+                ::handle_this::Handled::wrap(e)
+            }
+        }
+
+        impl #impl_generics ::handle_this::IntoValue for #name #ty_generics #where_clause {
+            fn into_value(self) -> ::handle_this::Value {
+                #value
+            }
+        }
+    })
+}
+
+/// Parse `#[handle_this(...)]`; returns whether `error` was given.
+fn parse_options(input: &DeriveInput) -> Result<bool> {
+    let mut impl_error = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("handle_this")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("error") {
+                impl_error = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `handle_this` option, expected `error`"))
+            }
+        })?;
+    }
+    Ok(impl_error)
+}
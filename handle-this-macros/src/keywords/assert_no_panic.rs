@@ -0,0 +1,36 @@
+//! Assert-no-panic modifier - contain panics raised by a catch handler.
+//!
+//! Syntax: `try { } catch e { recovery } assert_no_panic`
+//!
+//! The catch body runs inside `catch_unwind`. A panicking handler becomes a
+//! fallback error instead of unwinding past the `handle!`, so the modified
+//! catch behaves like `try catch` and the expression yields a `Result`.
+
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::catch::CatchClause;
+use super::try_catch::TryCatchClause;
+use super::parse_keyword;
+
+/// Parse the `assert_no_panic` modifier following a catch body.
+pub fn parse(input: ParseStream) -> Result<Span> {
+    parse_keyword(input, "assert_no_panic").map(|kw| kw.span())
+}
+
+/// Turn a catch clause into a `try catch` whose body can't unwind.
+pub fn wrap(clause: CatchClause) -> TryCatchClause {
+    let body = clause.body;
+    TryCatchClause {
+        variant: clause.variant,
+        type_path: clause.type_path,
+        binding: clause.binding,
+        guard: clause.guard,
+        body: quote! {
+            ::handle_this::__catch_no_panic(|| { #body })
+                .map_err(|__e| __e.frame(file!(), line!(), column!()))
+        },
+    }
+}
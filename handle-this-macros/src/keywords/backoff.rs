@@ -0,0 +1,71 @@
+//! Backoff keyword - sleep between `try while` attempts.
+//!
+//! Syntax: `try while cond, backoff KIND { body }` where KIND is one of
+//!
+//! - `fixed(d)` - the same delay before every retry
+//! - `linear(step[, max cap])` - `step`, `2*step`, ... up to `cap`
+//! - `exponential(base[, max cap])` - `base`, `2*base`, `4*base`, ... up to `cap`
+//!
+//! Durations are literals with a unit suffix (`250ms`, `5s`, `2min`, `1hour`) or any
+//! `Duration` expression. Builds a `::handle_this::Backoff`; the retry loop
+//! sleeps `backoff.delay(n)` before retry `n` and tags the final error with
+//! an `attempts` kv.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{parenthesized, Ident, Result};
+
+use super::{parse_keyword, parsing, peek_keyword};
+
+/// Whether the input continues with `, backoff ...`.
+pub fn peek(input: ParseStream) -> bool {
+    if !input.peek(syn::Token![,]) {
+        return false;
+    }
+    let fork = input.fork();
+    let _ = fork.parse::<syn::Token![,]>();
+    peek_keyword(&fork, "backoff")
+}
+
+/// Parse `, backoff KIND(...)` into a `Backoff` expression.
+pub fn parse(input: ParseStream) -> Result<TokenStream> {
+    input.parse::<syn::Token![,]>()?;
+    let kw = parse_keyword(input, "backoff")?;
+    if input.is_empty() || input.peek(syn::token::Brace) {
+        return Err(syn::Error::new(
+            kw.span(),
+            "expected a backoff: `backoff fixed(100ms)`, `linear(100ms, max 2s)` or `exponential(100ms, max 5s)`",
+        ));
+    }
+
+    let kind: Ident = input.parse()?;
+    let content;
+    parenthesized!(content in input);
+    let first = parsing::parse_duration(&content)?;
+    let max = if content.peek(syn::Token![,]) {
+        content.parse::<syn::Token![,]>()?;
+        parse_keyword(&content, "max")?;
+        Some(parsing::parse_duration(&content)?)
+    } else {
+        None
+    };
+    if !content.is_empty() {
+        return Err(content.error("unexpected tokens in backoff; expected `(delay)` or `(delay, max cap)`"));
+    }
+    let max = max.unwrap_or_else(|| quote! { ::core::time::Duration::MAX });
+
+    match kind.to_string().as_str() {
+        "fixed" => Ok(quote! { ::handle_this::Backoff::Fixed(#first) }),
+        "linear" => Ok(quote! {
+            ::handle_this::Backoff::Linear { step: #first, max: #max }
+        }),
+        "exponential" => Ok(quote! {
+            ::handle_this::Backoff::Exponential { base: #first, max: #max }
+        }),
+        other => Err(syn::Error::new(
+            kind.span(),
+            format!("unknown backoff `{}`, expected fixed/linear/exponential", other),
+        )),
+    }
+}
@@ -0,0 +1,108 @@
+//! Branch keyword - match-like type switch over the error.
+//!
+//! Syntax:
+//! ```text
+//! branch {
+//!     io::Error(e) => { recovery },
+//!     ParseIntError(e) => recovery,
+//!     _ => { fallback },
+//! }
+//! ```
+//!
+//! Each arm names a type path with an optional `(binding)`; the last arm may
+//! be `_` or a lowercase binding (`e => ...`) to catch everything else.
+//! Desugars to the equivalent sequence of `catch Type(binding) { }` clauses.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{braced, Ident, Result};
+
+use super::catch::CatchClause;
+use super::{is_lowercase_ident, parse_keyword, parsing, ChainVariant};
+
+/// Parse a branch block into one catch clause per arm.
+pub fn parse(input: ParseStream) -> Result<Vec<CatchClause>> {
+    let kw = parse_keyword(input, "branch")?;
+    let catch_span = kw.span();
+
+    if !parsing::peek_brace(input) {
+        return Err(syn::Error::new(
+            input.span(),
+            "expected arms after `branch`: `branch { Type(e) => { ... }, _ => { ... } }`",
+        ));
+    }
+    let content;
+    braced!(content in input);
+
+    let mut clauses: Vec<CatchClause> = Vec::new();
+    while !content.is_empty() {
+        let arm_span = content.span();
+        if let Some(prev) = clauses.last() {
+            if prev.type_path.is_none() {
+                return Err(syn::Error::new(
+                    arm_span,
+                    "catch-all arm must be last in `branch`; arms after it never run",
+                ));
+            }
+        }
+
+        let (type_path, binding) = parse_pattern(&content)?;
+        content.parse::<syn::Token![=>]>()?;
+
+        let body = if parsing::peek_brace(&content) {
+            parsing::parse_braced_body(&content)?
+        } else {
+            let expr: syn::Expr = content.parse()?;
+            quote! { #expr }
+        };
+
+        if !content.is_empty() {
+            content.parse::<syn::Token![,]>()?;
+        }
+
+        clauses.push(CatchClause {
+            catch_span,
+            variant: ChainVariant::Root,
+            type_path,
+            binding,
+            guard: None,
+            body,
+        });
+    }
+
+    if clauses.is_empty() {
+        return Err(syn::Error::new(catch_span, "`branch` needs at least one arm"));
+    }
+
+    Ok(clauses)
+}
+
+/// Parse an arm pattern: `_`, `binding`, `Type`, or `Type(binding)`.
+fn parse_pattern(input: ParseStream) -> Result<(Option<TokenStream>, Ident)> {
+    if input.peek(syn::Token![_]) {
+        input.parse::<syn::Token![_]>()?;
+        return Ok((None, parsing::underscore_ident()));
+    }
+
+    let fork = input.fork();
+    let first: Ident = fork.parse()?;
+    if fork.peek(syn::Token![=>]) && is_lowercase_ident(&first) {
+        input.parse::<Ident>()?;
+        if parsing::is_reserved_binding(&first.to_string()) {
+            return Err(syn::Error::new(
+                first.span(),
+                format!("`{}` is reserved for internal use; choose a different binding name", first),
+            ));
+        }
+        return Ok((None, first));
+    }
+
+    let type_path = parsing::parse_type_path(input)?;
+    let binding = if input.peek(syn::token::Paren) {
+        parsing::parse_binding(input, ChainVariant::Root)?
+    } else {
+        parsing::underscore_ident()
+    };
+    Ok((Some(type_path), binding))
+}
@@ -4,16 +4,31 @@
 //! - `catch { recovery }` - catch-all, consume binding
 //! - `catch e { recovery }` - catch-all with binding
 //! - `catch Type(e) { recovery }` - typed catch
+//! - `catch A | B (e) { recovery }` - first matching type, `e: &dyn Error`
 //! - `catch Type(e) when guard { recovery }` - typed with guard
 //! - `catch Type(e) match expr { arms }` - typed with match
+//! - `catch Enum::Variant { x } { ... }` - enum variant pattern (also `(..)` or unit)
 //! - `catch any Type(e) { ... }` - search cause chain
 //! - `catch all Type |errors| { ... }` - collect all from chain
+//! - `catch e delay expr { recovery }` - sleep for `expr` before recovering
+//! - `catch panic p { recovery }` - typed catch on `Panicked` (see `catch_panic`)
+//! - `catch e { if fatal { rethrow } recovery }` - give up from the body (see `rethrow`)
+//!
+//! The delay is prepended to the body, so it only runs when the clause
+//! matches. `async try` handlers run in a synchronous closure, so there the
+//! body only records the delay and the caller awaits a non-blocking sleep
+//! before returning the handled result.
+//!
+//! A multi-type catch expands to one typed clause per type, tried in order,
+//! sharing the body. Each rebinds `e` as `&(dyn Error + 'static)` so the body
+//! type-checks the same whichever type matched.
 
 use proc_macro2::TokenStream;
+use quote::quote;
 use syn::parse::ParseStream;
 use syn::{Ident, Result};
 
-use super::{ChainVariant, Guard, parse_keyword};
+use super::{ChainVariant, Guard, catch_panic, parse_keyword, rethrow};
 use super::clause::{parse_clause, ClauseConfig};
 use super::parsing;
 
@@ -35,18 +50,112 @@ pub struct CatchClause {
 }
 
 /// Parse a catch clause.
-pub fn parse(input: ParseStream) -> Result<CatchClause> {
+///
+/// Returns one clause per type for `catch A | B (e)`, a single clause otherwise.
+pub fn parse(input: ParseStream) -> Result<Vec<CatchClause>> {
+    let clauses = parse_rethrowable(input)?;
+    rethrow::reject(&clauses[0])?;
+    Ok(clauses)
+}
+
+/// Parse a catch clause whose body may `rethrow`; the caller must turn
+/// such clauses into `try catch` with [`rethrow::wrap`].
+pub fn parse_rethrowable(input: ParseStream) -> Result<Vec<CatchClause>> {
+    parse_with(input, false).map(|(clauses, _)| clauses)
+}
+
+/// Parse a catch clause inside `async try`, where `delay` must not block.
+///
+/// Returns whether the clause has a delay; the body then awaits it, so the
+/// caller must run the checks in an async block.
+pub fn parse_async(input: ParseStream) -> Result<(Vec<CatchClause>, bool)> {
+    let (clauses, has_delay) = parse_with(input, true)?;
+    rethrow::reject(&clauses[0])?;
+    Ok((clauses, has_delay))
+}
+
+fn parse_with(input: ParseStream, is_async: bool) -> Result<(Vec<CatchClause>, bool)> {
     let catch_kw = parse_keyword(input, "catch")?;
     let catch_span = catch_kw.span();
 
-    let clause = parse_clause(input, catch_span, ClauseConfig::catch())?;
+    let panic_span = catch_panic::parse_marker(input);
+    let mut clause = parse_clause(input, catch_span, ClauseConfig::catch())?;
+    if let Some(span) = panic_span {
+        if clause.variant != ChainVariant::Root || clause.type_path.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "`catch panic` takes a binding, not a type: `catch panic p { ... }`",
+            ));
+        }
+        clause.type_path = Some(catch_panic::type_path());
+    }
+
+    let has_delay = clause.delay.is_some();
+    let body = match clause.delay {
+        Some(_) if matches!(clause.guard, Some(Guard::Match { .. })) => {
+            return Err(syn::Error::new(
+                catch_span,
+                "`delay` can't be combined with a `match` guard; use `when` instead",
+            ));
+        }
+        Some(delay) if is_async => {
+            let body = clause.body;
+            quote! { ::handle_this::__async_delay(#delay).await; #body }
+        }
+        Some(delay) => {
+            let body = clause.body;
+            quote! { ::std::thread::sleep(#delay); #body }
+        }
+        None => clause.body,
+    };
+
+    let binding = clause.binding.unwrap_or_else(parsing::underscore_ident);
+    if clause.alt_types.is_empty() {
+        let clause = CatchClause {
+            catch_span,
+            variant: clause.variant,
+            type_path: clause.type_path,
+            binding,
+            guard: clause.guard,
+            body,
+        };
+        return Ok((vec![clause], has_delay));
+    }
+
+    if matches!(clause.guard, Some(Guard::Match { .. })) {
+        return Err(syn::Error::new(
+            catch_span,
+            "a multi-type catch can't use a `match` guard; use `when` instead",
+        ));
+    }
+
+    // Rebind as `&dyn Error` so the shared body and guard see one type
+    let (body, guard) = if binding == "_" {
+        (body, clause.guard)
+    } else {
+        let as_dyn = quote! {
+            #[allow(unused_variables)]
+            let #binding: &(dyn ::handle_this::__StdError + 'static) = #binding;
+        };
+        let guard = clause.guard.map(|guard| match guard {
+            Guard::When(cond) => Guard::When(quote! { { #as_dyn #cond } }),
+            other => other,
+        });
+        (quote! { #as_dyn #body }, guard)
+    };
 
-    Ok(CatchClause {
-        catch_span,
-        variant: clause.variant,
-        type_path: clause.type_path,
-        binding: clause.binding.unwrap_or_else(parsing::underscore_ident),
-        guard: clause.guard,
-        body: clause.body,
-    })
+    let clauses = clause
+        .type_path
+        .into_iter()
+        .chain(clause.alt_types)
+        .map(|type_path| CatchClause {
+            catch_span,
+            variant: clause.variant,
+            type_path: Some(type_path),
+            binding: binding.clone(),
+            guard: guard.clone(),
+            body: body.clone(),
+        })
+        .collect();
+    Ok((clauses, has_delay))
 }
@@ -0,0 +1,81 @@
+//! Catch-panic handler - recover from panics raised by the try body.
+//!
+//! Syntax: `try { body } catch panic p { recovery }` (also in `async try`)
+//!
+//! `catch panic p` is a typed catch on `handle_this::Panicked`, binding `p`
+//! as `&Panicked`; it takes `when` guards and `delay` like any catch. Its
+//! presence makes the try pattern run the body under `catch_unwind`, so a
+//! panic becomes a `Panicked` error framed at the macro site and flows
+//! through every handler in order, not just this one.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
+use syn::{Ident, Result};
+
+use super::{parse_keyword, peek_keyword};
+
+/// Whether the input starts with `catch panic BINDING`.
+///
+/// `catch panic { }` stays a catch-all whose binding is named `panic`.
+pub fn peek(input: ParseStream) -> bool {
+    let fork = input.fork();
+    parse_keyword(&fork, "catch").is_ok() && parse_marker(&fork).is_some()
+}
+
+/// Consume the `panic` marker after `catch`, returning its span.
+pub fn parse_marker(input: ParseStream) -> Option<proc_macro2::Span> {
+    if !peek_keyword(input, "panic") || !input.peek2(Ident::peek_any) {
+        return None;
+    }
+    parse_keyword(input, "panic").ok().map(|kw| kw.span())
+}
+
+/// Type matched by `catch panic`.
+pub fn type_path() -> TokenStream {
+    quote! { ::handle_this::Panicked }
+}
+
+/// Reject `catch panic` in patterns that don't run the body themselves.
+pub fn reject(input: ParseStream) -> Result<()> {
+    if peek(input) {
+        return Err(syn::Error::new(
+            input.span(),
+            "`catch panic` is only supported on plain `try { }` and `async try { }`",
+        ));
+    }
+    Ok(())
+}
+
+/// Run the try body under `catch_unwind`.
+///
+/// The first `?` turns `Panicked` into the body's error, the second
+/// propagates the body's own error.
+pub fn wrap_body(body: TokenStream, enabled: bool) -> TokenStream {
+    if !enabled {
+        return body;
+    }
+    // A body that always panics (`try { panic!(..) }`) makes the `Ok` unreachable
+    quote! {
+        {
+            #[allow(unreachable_code)]
+            let __caught = ::handle_this::__catch_panic(|| ::handle_this::__try_block!(#body));
+            __caught
+        }??
+    }
+}
+
+/// Async [`wrap_body`]: every poll of the body runs under `catch_unwind`.
+pub fn wrap_body_async(body: TokenStream, enabled: bool) -> TokenStream {
+    if !enabled {
+        return body;
+    }
+    quote! {
+        {
+            #[allow(unreachable_code)]
+            let __caught = ::handle_this::__catch_panic_async(::handle_this::__async_try_block!(#body));
+            __caught
+        }.await??
+    }
+}
@@ -0,0 +1,48 @@
+//! Classify keyword - compute a value from the error and attach it as a typed field.
+//!
+//! Syntax variants:
+//! - `classify |e| { category }` - catch-all with binding
+//! - `classify |e| -> Type { category }` - with explicit field type
+//! - `classify |e| when guard { category }` - only when guard holds
+//!
+//! Desugars to an inspect clause that stores the body's value with
+//! `Handled::set_field`, so downstream code can read it via `field::<Type>()`.
+//! The error keeps propagating to later handlers.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a classify clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "classify")?;
+    let inspect_span = kw.span();
+
+    let binding = parsing::parse_pipe_binding(input)?;
+
+    let field_type = if input.peek(syn::Token![->]) {
+        input.parse::<syn::Token![->]>()?;
+        let ty: syn::Type = input.parse()?;
+        quote! { : #ty }
+    } else {
+        quote! {}
+    };
+
+    let guard = parsing::parse_optional_guard(input)?;
+    let body = parsing::parse_braced_body(input)?;
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding,
+        guard,
+        body: quote! {
+            let __handle_classified #field_type = { #body };
+            __err = __err.set_field(__handle_classified);
+        },
+    })
+}
@@ -2,18 +2,20 @@
 //!
 //! All handler clauses share the same basic structure:
 //! - Optional chain variant (any/all)
-//! - Optional type filter
+//! - Optional type filter (catch also takes `A | B`), or an enum variant
+//!   pattern (`MyError::NotFound { id }`)
 //! - Binding identifier
+//! - Optional delay (catch only)
 //! - Optional guard (when/match)
 //! - Body expression
 //!
 //! This module provides a unified parsing function that handles
 //! all the variations, controlled by `ClauseConfig`.
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::quote;
-use syn::parse::ParseStream;
-use syn::{Ident, Result};
+use syn::parse::{ParseStream, Parser};
+use syn::{Ident, Pat, Result};
 
 use super::{ChainVariant, Guard, is_lowercase_ident, peek_keyword};
 use super::parsing::{self, parse_chain_variant, parse_type_path, parse_binding, parse_optional_guard};
@@ -27,6 +29,10 @@ pub struct ClauseConfig {
     pub allow_no_binding_catchall: bool,
     /// Binding is optional even for typed clauses (throw uses Option<Ident>)
     pub binding_optional: bool,
+    /// Allow `delay expr` before the guard (catch only)
+    pub allow_delay: bool,
+    /// Allow several types separated by `|`: `A | B (e)` (catch only)
+    pub allow_alt_types: bool,
 }
 
 impl ClauseConfig {
@@ -36,6 +42,8 @@ impl ClauseConfig {
             keyword: "catch",
             allow_no_binding_catchall: true,
             binding_optional: false,
+            allow_delay: true,
+            allow_alt_types: true,
         }
     }
 
@@ -45,6 +53,8 @@ impl ClauseConfig {
             keyword: "throw",
             allow_no_binding_catchall: true,
             binding_optional: true,
+            allow_delay: false,
+            allow_alt_types: false,
         }
     }
 
@@ -54,6 +64,19 @@ impl ClauseConfig {
             keyword: "inspect",
             allow_no_binding_catchall: false,
             binding_optional: false,
+            allow_delay: false,
+            allow_alt_types: false,
+        }
+    }
+
+    /// Config for only_in_tests clauses (inspect gated on `cfg(test)`)
+    pub fn only_in_tests() -> Self {
+        Self {
+            keyword: "only_in_tests",
+            allow_no_binding_catchall: true,
+            binding_optional: false,
+            allow_delay: false,
+            allow_alt_types: false,
         }
     }
 
@@ -63,6 +86,8 @@ impl ClauseConfig {
             keyword: "try catch",
             allow_no_binding_catchall: true,
             binding_optional: false,
+            allow_delay: false,
+            allow_alt_types: false,
         }
     }
 }
@@ -74,23 +99,201 @@ pub struct ParsedClause {
     pub variant: ChainVariant,
     /// Type path (None for catch-all)
     pub type_path: Option<TokenStream>,
+    /// Further types after `|` (`catch A | B (e)`); empty otherwise
+    pub alt_types: Vec<TokenStream>,
     /// Error binding identifier (None if no binding specified and config allows)
     pub binding: Option<Ident>,
+    /// Optional delay before the body runs (`delay expr`)
+    pub delay: Option<syn::Expr>,
     /// Optional guard (when or match)
     pub guard: Option<Guard>,
     /// Body expression
     pub body: TokenStream,
 }
 
+/// Parse `delay expr` if present and allowed by `config`.
+fn parse_optional_delay(input: ParseStream, config: ClauseConfig) -> Result<Option<syn::Expr>> {
+    if !config.allow_delay || !peek_keyword(input, "delay") {
+        return Ok(None);
+    }
+    input.parse::<Ident>()?; // consume `delay`
+    if parsing::peek_brace(input) || input.is_empty() {
+        return Err(syn::Error::new(
+            input.span(),
+            "expected duration after `delay`: `catch e delay Duration::from_millis(100) { ... }`",
+        ));
+    }
+    Ok(Some(syn::Expr::parse_without_eager_brace(input)?))
+}
+
+/// Check whether a fork positioned after a binding continues a clause.
+fn peek_after_binding(fork: ParseStream, config: ClauseConfig) -> bool {
+    parsing::peek_brace(fork)
+        || peek_keyword(fork, "when")
+        || (config.allow_delay && peek_keyword(fork, "delay"))
+}
+
+/// Whether the input starts with an enum variant path (`MyError::NotFound`).
+///
+/// A type path can't end in two capitalized segments (modules are
+/// lowercase), so this never shadows a typed clause.
+fn peek_variant_path(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let mut segments = Vec::new();
+    while let Ok(seg) = fork.parse::<Ident>() {
+        segments.push(seg);
+        if fork.parse::<syn::Token![::]>().is_err() {
+            break;
+        }
+    }
+    segments.len() >= 2 && segments[segments.len() - 2..].iter().all(|s| !is_lowercase_ident(s))
+}
+
+/// Parse a variant pattern clause: `Enum::Variant`, `Enum::Variant { .. }`
+/// or `Enum::Variant(..)`, then the usual delay, guard, and body.
+///
+/// The clause downcasts to `Enum` and only matches when the pattern does;
+/// otherwise the error falls through to the next handler. Pattern bindings
+/// are references into the error, in scope for the guard and the body.
+fn parse_variant_clause(input: ParseStream, variant: ChainVariant, config: ClauseConfig) -> Result<ParsedClause> {
+    if variant == ChainVariant::All {
+        return Err(syn::Error::new(
+            input.span(),
+            format!("`all` takes a type, not a variant pattern: `{} all Type |errors| {{ ... }}`", config.keyword),
+        ));
+    }
+
+    let mut segments: Vec<Ident> = vec![input.parse()?];
+    while input.peek(syn::Token![::]) {
+        input.parse::<syn::Token![::]>()?;
+        segments.push(input.parse()?);
+    }
+    let enum_segments = &segments[..segments.len() - 1];
+    let type_path = quote! { #(#enum_segments)::* };
+
+    // A brace group is only the pattern's fields if the body follows it
+    let fields = if input.peek(syn::token::Paren) {
+        Some(input.parse::<TokenTree>()?)
+    } else if parsing::peek_brace(input) {
+        let fork = input.fork();
+        fork.parse::<TokenTree>()?;
+        if peek_after_binding(&fork, config) {
+            Some(input.parse::<TokenTree>()?)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    let pat = Pat::parse_single.parse2(quote! { #(#segments)::* #fields })?;
+
+    if peek_keyword(input, "match") {
+        return Err(syn::Error::new(
+            input.span(),
+            "a variant pattern can't take a `match` guard; use `when` instead",
+        ));
+    }
+
+    let delay = parse_optional_delay(input, config)?;
+    let guard = parse_optional_guard(input)?;
+    let body = parsing::parse_braced_body(input)?;
+
+    let binding = Ident::new("__handle_variant", Span::call_site());
+    let matched = match guard {
+        Some(Guard::When(cond)) => quote! { { #cond } },
+        _ => quote! { true },
+    };
+    let guard = quote! {
+        {
+            match #binding {
+                #[allow(unused_variables)]
+                #pat => #matched,
+                #[allow(unreachable_patterns)]
+                _ => false,
+            }
+        }
+    };
+    let body = quote! {
+        match #binding {
+            // Bindings may be used by the guard alone
+            #[allow(unused_variables)]
+            #pat => { #body }
+            #[allow(unreachable_patterns)]
+            _ => ::core::unreachable!(),
+        }
+    };
+
+    Ok(ParsedClause {
+        variant,
+        type_path: Some(type_path),
+        alt_types: Vec::new(),
+        binding: Some(binding),
+        delay,
+        guard: Some(Guard::When(guard)),
+        body,
+    })
+}
+
 /// Parse a handler clause after the keyword has been consumed.
 ///
 /// The keyword should already be parsed; this function handles:
 /// - Chain variant (any/all)
 /// - Catch-all patterns
 /// - Typed patterns with binding
+/// - Enum variant patterns
 /// - Guards
 /// - Body
 pub fn parse_clause(
+    input: ParseStream,
+    keyword_span: proc_macro2::Span,
+    config: ClauseConfig,
+) -> Result<ParsedClause> {
+    let clause = parse_clause_inner(input, keyword_span, config)?;
+    desugar_kind_guard(clause, config)
+}
+
+/// Expand `when kind Timeout | NotFound` into a check of the binding's
+/// `ErrorKind`. Any other guard is left alone.
+fn desugar_kind_guard(mut clause: ParsedClause, config: ClauseConfig) -> Result<ParsedClause> {
+    let Some(Guard::When(cond)) = &clause.guard else { return Ok(clause) };
+    let tokens: Vec<TokenTree> = cond.clone().into_iter().collect();
+    let kind_span = match tokens.first() {
+        Some(TokenTree::Ident(kw)) if kw == "kind" && tokens.len() > 1 => kw.span(),
+        _ => return Ok(clause),
+    };
+
+    // `Variant (| Variant)*` and nothing else
+    let mut kinds = Vec::new();
+    for (i, tt) in tokens[1..].iter().enumerate() {
+        match tt {
+            TokenTree::Ident(kind) if i % 2 == 0 => kinds.push(kind.clone()),
+            TokenTree::Punct(p) if i % 2 == 1 && p.as_char() == '|' => {}
+            _ => return Ok(clause),
+        }
+    }
+    if tokens.len() % 2 == 1 {
+        return Ok(clause);
+    }
+
+    let binding = match &clause.binding {
+        Some(binding) if binding != "_" => binding,
+        _ => {
+            return Err(syn::Error::new(
+                kind_span,
+                format!("`when kind` needs a binding: `{} e when kind Timeout {{ ... }}`", config.keyword),
+            ));
+        }
+    };
+    clause.guard = Some(Guard::When(quote! {
+        {
+            use ::handle_this::__KindOf as _;
+            ::core::matches!(#binding.__kind(), #(::handle_this::ErrorKind::#kinds)|*)
+        }
+    }));
+    Ok(clause)
+}
+
+fn parse_clause_inner(
     input: ParseStream,
     _keyword_span: proc_macro2::Span,
     config: ClauseConfig,
@@ -117,6 +320,26 @@ pub fn parse_clause(
         }
     }
 
+    // Check for catch-all with delay but no binding: `delay expr { }`
+    if variant == ChainVariant::Root && config.allow_delay && peek_keyword(input, "delay") {
+        let fork = input.fork();
+        fork.parse::<Ident>().ok();
+        if !peek_after_binding(&fork, config) {
+            let delay = parse_optional_delay(input, config)?;
+            let guard = parse_optional_guard(input)?;
+            let body = parsing::parse_braced_body(input)?;
+            return Ok(ParsedClause {
+                variant,
+                type_path: None,
+                alt_types: Vec::new(),
+                binding: None,
+                delay,
+                guard,
+                body,
+            });
+        }
+    }
+
     // Check for catch-all: `{ }` without binding
     if variant == ChainVariant::Root && parsing::peek_brace(input) {
         if config.allow_no_binding_catchall {
@@ -124,7 +347,9 @@ pub fn parse_clause(
             return Ok(ParsedClause {
                 variant,
                 type_path: None,
+                alt_types: Vec::new(),
                 binding: None,
+                delay: None,
                 guard: None,
                 body,
             });
@@ -141,10 +366,12 @@ pub fn parse_clause(
     if variant == ChainVariant::Root && input.peek(syn::Token![_]) {
         let fork = input.fork();
         fork.parse::<syn::Token![_]>().ok();
-        if parsing::peek_brace(&fork) || peek_keyword(&fork, "when") {
+        if peek_after_binding(&fork, config) {
             input.parse::<syn::Token![_]>()?; // consume _
             let binding = parsing::underscore_ident();
 
+            let delay = parse_optional_delay(input, config)?;
+
             // Parse optional guard
             let guard = parse_optional_guard(input)?;
 
@@ -154,7 +381,9 @@ pub fn parse_clause(
             return Ok(ParsedClause {
                 variant,
                 type_path: None,
+                alt_types: Vec::new(),
                 binding: Some(binding),
+                delay,
                 guard,
                 body,
             });
@@ -165,7 +394,7 @@ pub fn parse_clause(
     let fork = input.fork();
     if let Ok(ident) = fork.parse::<Ident>() {
         if is_lowercase_ident(&ident) && variant == ChainVariant::Root {
-            if parsing::peek_brace(&fork) || peek_keyword(&fork, "when") {
+            if peek_after_binding(&fork, config) {
                 input.parse::<Ident>()?; // consume binding
 
                 // Check for reserved internal names
@@ -177,6 +406,8 @@ pub fn parse_clause(
                     ));
                 }
 
+                let delay = parse_optional_delay(input, config)?;
+
                 // Parse optional guard
                 let guard = parse_optional_guard(input)?;
 
@@ -186,7 +417,9 @@ pub fn parse_clause(
                 return Ok(ParsedClause {
                     variant,
                     type_path: None,
+                    alt_types: Vec::new(),
                     binding: Some(ident),
+                    delay,
                     guard,
                     body,
                 });
@@ -206,10 +439,23 @@ pub fn parse_clause(
         }
     }
 
+    if peek_variant_path(input) {
+        return parse_variant_clause(input, variant, config);
+    }
+
     // Typed clause: Type(binding) or Type |binding| (for all variant)
     // Also supports shorthand: Type { } without binding
     let type_path = parse_type_path(input)?;
 
+    // `A | B (e)` - `|` can't start an `all` binding here since `all` has no alternatives
+    let mut alt_types = Vec::new();
+    if config.allow_alt_types && variant != ChainVariant::All {
+        while input.peek(syn::Token![|]) {
+            input.parse::<syn::Token![|]>()?;
+            alt_types.push(parse_type_path(input)?);
+        }
+    }
+
     // Check for binding
     let binding = if input.peek(syn::token::Paren) || input.peek(syn::Token![|]) {
         Some(parse_binding(input, variant)?)
@@ -221,6 +467,8 @@ pub fn parse_clause(
         Some(parsing::underscore_ident())
     };
 
+    let delay = parse_optional_delay(input, config)?;
+
     // Parse optional guard (when or match)
     let guard = parse_optional_guard(input)?;
 
@@ -234,7 +482,9 @@ pub fn parse_clause(
     Ok(ParsedClause {
         variant,
         type_path: Some(type_path),
+        alt_types,
         binding,
+        delay,
         guard,
         body,
     })
@@ -289,6 +539,37 @@ mod tests {
         assert_eq!(clause.binding.unwrap().to_string(), "_");
     }
 
+    #[test]
+    fn test_catch_alt_types() {
+        let clause = parse_test(
+            parse_quote! { io::Error | fmt::Error (e) { 42 } },
+            ClauseConfig::catch(),
+        ).unwrap();
+        assert!(clause.type_path.is_some());
+        assert_eq!(clause.alt_types.len(), 1);
+        assert_eq!(clause.binding.unwrap().to_string(), "e");
+    }
+
+    #[test]
+    fn test_catch_variant_pattern() {
+        let clause = parse_test(
+            parse_quote! { app::AppError::NotFound { id } { 42 } },
+            ClauseConfig::catch(),
+        ).unwrap();
+        assert_eq!(clause.type_path.unwrap().to_string(), "app :: AppError");
+        assert!(matches!(clause.guard, Some(Guard::When(_))));
+    }
+
+    #[test]
+    fn test_catch_unit_variant() {
+        // The brace group is the body, not struct fields
+        let clause = parse_test(
+            parse_quote! { AppError::Timeout { 42 } },
+            ClauseConfig::catch(),
+        ).unwrap();
+        assert_eq!(clause.type_path.unwrap().to_string(), "AppError");
+    }
+
     #[test]
     fn test_throw_no_binding() {
         let clause = parse_test(parse_quote! { { "error" } }, ClauseConfig::throw()).unwrap();
@@ -302,4 +583,29 @@ mod tests {
         // This will fail because `{` is not a valid type path start
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_kind_guard_expands() {
+        let clause = parse_test(
+            parse_quote! { e when kind Timeout | NotFound { 42 } },
+            ClauseConfig::catch(),
+        ).unwrap();
+        let Some(Guard::When(cond)) = clause.guard else { panic!("expected when guard") };
+        let cond = cond.to_string();
+        assert!(cond.contains("e . __kind ()"));
+        assert!(cond.contains(":: handle_this :: ErrorKind :: Timeout | :: handle_this :: ErrorKind :: NotFound"));
+    }
+
+    #[test]
+    fn test_kind_guard_needs_binding() {
+        let result = parse_test(parse_quote! { io::Error when kind Timeout { 42 } }, ClauseConfig::catch());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_plain_kind_variable_is_not_sugar() {
+        let clause = parse_test(parse_quote! { e when kind == expected { 42 } }, ClauseConfig::catch()).unwrap();
+        let Some(Guard::When(cond)) = clause.guard else { panic!("expected when guard") };
+        assert_eq!(cond.to_string(), "kind == expected");
+    }
 }
@@ -0,0 +1,42 @@
+//! Continue-with keyword - run a handler, then continue the enclosing loop.
+//!
+//! Syntax variants:
+//! - `continue_with { body }` - catch-all, no binding
+//! - `continue_with |e| { body }` - catch-all with binding
+//! - `continue_with |e| when guard { body }` - only when guard holds
+//!
+//! Desugars to a catch-all clause. A `()` body runs and then `continue`s;
+//! an `Option` body recovers with `Some(v)` and `continue`s on `None`.
+//! The resulting control flow puts the try block in signal mode, so the
+//! `continue` targets whatever loop lexically encloses the `handle!`.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::catch::CatchClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a continue_with clause into an equivalent catch-all clause.
+pub fn parse(input: ParseStream) -> Result<CatchClause> {
+    let kw = parse_keyword(input, "continue_with")?;
+    let catch_span = kw.span();
+
+    let binding = parsing::parse_pipe_binding(input)?;
+    let guard = parsing::parse_optional_guard(input)?;
+    let body = parsing::parse_braced_body(input)?;
+
+    Ok(CatchClause {
+        catch_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding,
+        guard,
+        body: quote! {
+            match ::handle_this::__ContinueWith::__recover({ #body }) {
+                ::core::option::Option::Some(__v) => __v,
+                ::core::option::Option::None => continue,
+            }
+        },
+    })
+}
@@ -0,0 +1,45 @@
+//! Convert keyword - turn the error into a domain type via `From`.
+//!
+//! Syntax: `convert MyError` or `convert<MyError>`
+//!
+//! Desugars to an inspect clause that replaces the error with
+//! `MyError::from(err)` (requires `MyError: From<Handled>` and `Error`),
+//! wrapped as the new root with a frame at the clause. Later handlers see
+//! the converted error; unhandled, it propagates like `throw`.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a convert clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "convert")?;
+    let inspect_span = kw.span();
+
+    if input.is_empty() {
+        return Err(syn::Error::new(inspect_span, "expected target type: `convert MyError`"));
+    }
+    let target: syn::Type = if input.peek(syn::Token![<]) {
+        input.parse::<syn::Token![<]>()?;
+        let ty = input.parse()?;
+        input.parse::<syn::Token![>]>()?;
+        ty
+    } else {
+        input.parse()?
+    };
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            __err = ::handle_this::__convert_err::<#target>(__err)
+                .frame(file!(), line!(), column!());
+        },
+    })
+}
@@ -0,0 +1,44 @@
+//! Exit keyword - terminate the process on unrecoverable errors.
+//!
+//! Syntax:
+//! - `exit(code)` - print the error and trace to stderr, then exit with `code`
+//! - `exit code` - same, for a literal or simple expression
+//!
+//! Desugars to a catch-all clause whose body never returns. Std-only.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Ident, Result};
+
+use super::catch::CatchClause;
+use super::{parse_keyword, ChainVariant};
+
+/// Parse an exit clause into an equivalent catch-all clause.
+pub fn parse(input: ParseStream) -> Result<CatchClause> {
+    let kw = parse_keyword(input, "exit")?;
+    let catch_span = kw.span();
+
+    let code: syn::Expr = if input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in input);
+        content.parse()?
+    } else if input.is_empty() {
+        return Err(syn::Error::new(catch_span, "expected exit code: `exit(1)`"));
+    } else {
+        syn::Expr::parse_without_eager_brace(input)?
+    };
+
+    let binding = Ident::new("__handle_exit_err", proc_macro2::Span::call_site());
+
+    Ok(CatchClause {
+        catch_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: binding.clone(),
+        guard: None,
+        body: quote! {
+            ::std::eprintln!("error: {}", #binding);
+            ::std::process::exit(#code)
+        },
+    })
+}
@@ -4,20 +4,90 @@
 //!
 //! The finally block is inlined (not wrapped in closures) to allow
 //! mutable borrows to work naturally across try/finally blocks.
+//!
+//! `async try { } finally async { conn.close().await }` runs the cleanup as
+//! its own future, awaited after the body (async patterns only). `return`
+//! and `?` inside it stay within the cleanup.
+//!
+//! `async try { } finally { } cancel_safe` instead moves the finally body
+//! into a drop guard so it also runs if the future is cancelled.
+//!
+//! `try { } finally { } catch_finally_with |body_err, finally_err| { }` makes
+//! the finally body fallible (`?` fails the finally instead of returning
+//! from the function). When both the body and the finally fail, the
+//! combiner produces the final error.
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::parse::ParseStream;
-use syn::{Result, braced};
+use syn::{Ident, Result, braced};
 
-use super::parse_keyword;
+use super::{parse_keyword, parsing};
+
+/// A parsed `catch_finally_with |body_err, finally_err| { }` clause.
+#[derive(Debug, Clone)]
+pub struct FinallyCombiner {
+    /// Span of the `catch_finally_with` keyword (for error reporting)
+    pub span: proc_macro2::Span,
+    /// Binding for the try body's error
+    pub body_err: Ident,
+    /// Binding for the finally block's error
+    pub finally_err: Ident,
+    /// Combiner body; evaluates to the final error (`Into<Handled>`)
+    pub body: TokenStream,
+}
+
+/// Parse a `catch_finally_with` clause.
+pub fn parse_combiner(input: ParseStream) -> Result<FinallyCombiner> {
+    let kw = parse_keyword(input, "catch_finally_with")?;
+    input.parse::<syn::Token![|]>()?;
+    let body_err = parse_binding(input)?;
+    input.parse::<syn::Token![,]>()?;
+    let finally_err = parse_binding(input)?;
+    input.parse::<syn::Token![|]>()?;
+    let body = parsing::parse_braced_body(input)?;
+
+    Ok(FinallyCombiner {
+        span: kw.span(),
+        body_err,
+        finally_err,
+        body,
+    })
+}
+
+fn parse_binding(input: ParseStream) -> Result<Ident> {
+    if input.peek(syn::Token![_]) {
+        input.parse::<syn::Token![_]>()?;
+        Ok(parsing::underscore_ident())
+    } else {
+        input.parse()
+    }
+}
 
 /// Parse a finally clause body.
+///
+/// `finally async { }` yields the body as an awaited async block; the
+/// `.await` carries the span of `async`, so using it outside an async
+/// pattern points there.
 pub fn parse(input: ParseStream) -> Result<TokenStream> {
     parse_keyword(input, "finally")?;
+    let async_token = if input.peek(syn::Token![async]) {
+        Some(input.parse::<syn::Token![async]>()?)
+    } else {
+        None
+    };
     let content;
     braced!(content in input);
-    content.parse()
+    let body: TokenStream = content.parse()?;
+    Ok(match async_token {
+        Some(token) => quote_spanned! {token.span=> async { #body }.await },
+        None => body,
+    })
+}
+
+/// Whether the next tokens are `finally async`.
+pub fn peek_async(input: ParseStream) -> bool {
+    input.peek2(syn::Token![async])
 }
 
 /// Wrap code with finally block.
@@ -37,3 +107,48 @@ pub fn wrap(inner: TokenStream, finally_body: &TokenStream) -> TokenStream {
         }
     }
 }
+
+/// Wrap code with a fallible finally block and a combiner for double failure.
+///
+/// `inner` must evaluate to `Result<T, Handled>`. The finally body runs in
+/// its own try block after `inner`; its error replaces a successful result,
+/// and the combiner decides when both failed.
+pub fn wrap_fallible(
+    inner: TokenStream,
+    finally_body: &TokenStream,
+    combiner: &FinallyCombiner,
+) -> TokenStream {
+    let FinallyCombiner { body_err, finally_err, body, .. } = combiner;
+    quote! {
+        {
+            #[allow(unreachable_code)]
+            let __finally_result = { #inner };
+            let __finally_status = ::handle_this::__try_block!({ #finally_body; })
+                .map_err(|__e| ::handle_this::__wrap_frame(__e, file!(), line!(), column!()));
+            ::handle_this::__combine_finally(
+                __finally_result,
+                __finally_status,
+                |#body_err: ::handle_this::Handled, #finally_err: ::handle_this::Handled| -> ::handle_this::Handled {
+                    ::core::convert::Into::into({ #body })
+                },
+            )
+        }
+    }
+}
+
+/// Wrap async code with a cancellation-safe finally block (`cancel_safe`).
+///
+/// The finally body moves into a drop guard: it runs after `inner` completes,
+/// or when the future is dropped mid-await. Because the body is captured by
+/// a closure, it cannot borrow state that `inner` borrows mutably.
+pub fn wrap_cancel_safe(inner: TokenStream, finally_body: &TokenStream) -> TokenStream {
+    quote! {
+        {
+            let __handle_finally_guard = ::handle_this::__FinallyGuard::new(|| { #finally_body });
+            #[allow(unreachable_code)]
+            let __finally_result = { #inner };
+            __handle_finally_guard.run();
+            __finally_result
+        }
+    }
+}
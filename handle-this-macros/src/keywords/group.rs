@@ -0,0 +1,36 @@
+//! Group keyword - place errors passing through into a named group.
+//!
+//! Syntax: `group "name"` or `group expr`
+//!
+//! Desugars to an inspect clause that calls `Handled::in_group`, so
+//! `Handled::combine` can section aggregated errors by group. The error
+//! keeps propagating to later handlers.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a group clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "group")?;
+    let inspect_span = kw.span();
+
+    if input.is_empty() {
+        return Err(syn::Error::new(inspect_span, "expected group name: `group \"name\"`"));
+    }
+    let name = syn::Expr::parse_without_eager_brace(input)?;
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            __err = __err.in_group(#name);
+        },
+    })
+}
@@ -7,12 +7,17 @@
 //! - `inspect Type(e) match expr { arms }` - typed with match
 //! - `inspect any Type(e) { ... }` - search cause chain
 //! - `inspect all Type |errors| { ... }` - collect all from chain
+//! - `inspect log LEVEL` - emit through the `log` facade (`log` feature)
+//!
+//! `only_in_tests` accepts the same forms (plus `only_in_tests { }`) and
+//! compiles the body only under `#[cfg(test)]` in the calling crate.
 
 use proc_macro2::TokenStream;
+use quote::quote;
 use syn::parse::ParseStream;
 use syn::{Ident, Result};
 
-use super::{ChainVariant, Guard, parse_keyword};
+use super::{ChainVariant, Guard, parse_keyword, peek_keyword};
 use super::clause::{parse_clause, ClauseConfig};
 use super::parsing;
 
@@ -33,11 +38,33 @@ pub struct InspectClause {
     pub body: TokenStream,
 }
 
-/// Parse an inspect clause.
+/// Check for `inspect` or `only_in_tests`.
+pub fn peek(input: ParseStream) -> bool {
+    peek_keyword(input, "inspect") || peek_keyword(input, "only_in_tests")
+}
+
+/// Parse an inspect clause (`inspect` or `only_in_tests`).
 pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    if peek_keyword(input, "only_in_tests") {
+        return parse_only_in_tests(input);
+    }
+
     let inspect_kw = parse_keyword(input, "inspect")?;
     let inspect_span = inspect_kw.span();
 
+    if let Some(level) = parse_log_level(input)? {
+        return Ok(InspectClause {
+            inspect_span,
+            variant: ChainVariant::Root,
+            type_path: None,
+            binding: parsing::underscore_ident(),
+            guard: None,
+            body: quote! {
+                __err.log(::handle_this::__log::Level::#level);
+            },
+        });
+    }
+
     let clause = parse_clause(input, inspect_span, ClauseConfig::inspect())?;
 
     Ok(InspectClause {
@@ -49,3 +76,59 @@ pub fn parse(input: ParseStream) -> Result<InspectClause> {
         body: clause.body,
     })
 }
+
+/// Parse `log LEVEL` after `inspect`, returning the `log::Level` variant.
+///
+/// `inspect log { }` stays a catch-all whose binding is named `log`.
+fn parse_log_level(input: ParseStream) -> Result<Option<Ident>> {
+    let fork = input.fork();
+    if parse_keyword(&fork, "log").is_err() {
+        return Ok(None);
+    }
+    let Ok(level) = fork.parse::<Ident>() else {
+        return Ok(None);
+    };
+    let variant = match level.to_string().as_str() {
+        "error" => "Error",
+        "warn" => "Warn",
+        "info" => "Info",
+        "debug" => "Debug",
+        "trace" => "Trace",
+        _ => return Ok(None),
+    };
+    parse_keyword(input, "log")?;
+    input.parse::<Ident>()?;
+    Ok(Some(Ident::new(variant, level.span())))
+}
+
+/// Parse `only_in_tests`, gating the body on `#[cfg(test)]`.
+///
+/// Outside test builds the body is removed entirely; the binding is
+/// still touched so it doesn't trigger unused-variable warnings.
+fn parse_only_in_tests(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "only_in_tests")?;
+    let inspect_span = kw.span();
+
+    let clause = parse_clause(input, inspect_span, ClauseConfig::only_in_tests())?;
+    let binding = clause.binding.unwrap_or_else(parsing::underscore_ident);
+    let user_body = clause.body;
+
+    let silence = if binding == "_" {
+        quote! {}
+    } else {
+        quote! { #[cfg(not(test))] let _ = &#binding; }
+    };
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: clause.variant,
+        type_path: clause.type_path,
+        binding,
+        guard: clause.guard,
+        body: quote! {
+            #silence
+            #[cfg(test)]
+            { #user_body }
+        },
+    })
+}
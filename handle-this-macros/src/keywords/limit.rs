@@ -0,0 +1,32 @@
+//! Limit keyword - cap the attempts of a `try while` loop.
+//!
+//! Syntax: `try while cond limit N { body }`, before or after `, backoff KIND`
+//!
+//! The loop gives up after `N` failed attempts even if `cond` still holds.
+//! Each failure is chained after the previous one, like `try any`, and the
+//! final error records an `attempts` kv.
+
+use proc_macro2::TokenTree;
+use syn::parse::ParseStream;
+use syn::{Expr, Ident, Result};
+
+use super::{parse_keyword, peek_keyword};
+
+/// Whether the input continues with `limit N`.
+///
+/// `prev` is the last condition token: `limit` right after an operator
+/// (`n < limit`) is a variable, as is a `limit` followed by the body.
+pub fn peek(input: ParseStream, prev: Option<&TokenTree>) -> bool {
+    if matches!(prev, None | Some(TokenTree::Punct(_))) || !peek_keyword(input, "limit") {
+        return false;
+    }
+    let fork = input.fork();
+    let _ = parse_keyword(&fork, "limit");
+    fork.peek(syn::Lit) || fork.peek(Ident) || fork.peek(syn::token::Paren)
+}
+
+/// Parse `limit N` into the attempt cap expression.
+pub fn parse(input: ParseStream) -> Result<Expr> {
+    parse_keyword(input, "limit")?;
+    Expr::parse_without_eager_brace(input)
+}
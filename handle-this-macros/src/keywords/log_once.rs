@@ -0,0 +1,47 @@
+//! Log-once keyword - report an error only the first time it's seen here.
+//!
+//! Syntax variants:
+//! - `log_once` - print the error to stderr
+//! - `log_once |e| { body }` - run `body` with `e: &Handled` instead
+//!
+//! Desugars to an inspect clause with its own `static` fingerprint set
+//! (see `Handled::fingerprint`), so repeats of the same error at this call
+//! site are silent. The error keeps propagating to later handlers.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a log_once clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "log_once")?;
+    let inspect_span = kw.span();
+
+    let report = if input.peek(syn::Token![|]) {
+        let binding = parsing::parse_pipe_binding(input)?;
+        let body = parsing::parse_braced_body(input)?;
+        quote! {
+            let #binding: &::handle_this::Handled = &__err;
+            { #body }
+        }
+    } else {
+        quote! { ::std::eprintln!("{}", __err); }
+    };
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            static __HANDLE_LOG_ONCE: ::handle_this::__LogOnce = ::handle_this::__LogOnce::new();
+            if __HANDLE_LOG_ONCE.first_seen(&__err) {
+                #report
+            }
+        },
+    })
+}
@@ -0,0 +1,36 @@
+//! Metric keyword - count errors passing through in the built-in registry.
+//!
+//! Syntax: `metric_inc "name"` or `metric_inc expr`
+//!
+//! Desugars to an inspect clause that calls
+//! `handle_this::metrics::counter(name).inc()`. Requires the `metrics`
+//! feature. The error keeps propagating to later handlers.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a metric_inc clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "metric_inc")?;
+    let inspect_span = kw.span();
+
+    if input.is_empty() {
+        return Err(syn::Error::new(inspect_span, "expected counter name: `metric_inc \"name\"`"));
+    }
+    let name = syn::Expr::parse_without_eager_brace(input)?;
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            ::handle_this::metrics::counter(#name).inc();
+        },
+    })
+}
@@ -17,10 +17,71 @@ pub mod inspect;
 pub mod finally;
 pub mod with_ctx;
 pub mod try_catch;
+pub mod continue_with;
+pub mod classify;
+pub mod exit;
+pub mod group;
+pub mod snapshot;
+pub mod metric_inc;
+pub mod trace_error;
+pub mod on_ok;
+pub mod on_err;
+pub mod partial;
+pub mod to_option;
+pub mod with_correlation;
+pub mod throttle;
+pub mod log_once;
+pub mod report_and_continue;
+pub mod convert;
+pub mod assert_no_panic;
+pub mod rethrow;
+pub mod catch_panic;
+pub mod branch;
+pub mod unwrap_infallible;
+pub mod ratelimit_propagate;
+pub mod timeout;
+pub mod spawn;
+pub mod backoff;
+pub mod limit;
 
 use proc_macro2::TokenStream;
 use syn::Ident;
 
+/// Keywords that open a core handler and end the one before it.
+pub const HANDLER_KEYWORDS: &[&str] = &["catch", "throw", "inspect", "finally", "with", "try", "else"];
+
+/// Sugar clause keywords, in the order they were added.
+///
+/// Kept apart from `HANDLER_KEYWORDS` because they are plausible variable or
+/// function names inside guards (`when classify(&e)`). A new clause adds its
+/// keyword here and nowhere else; the nested-`try` scanners read this table.
+pub const CLAUSE_KEYWORDS: &[&str] = &[
+    "only_in_tests",
+    "classify",
+    "snapshot",
+    "continue_with",
+    "exit",
+    "group",
+    "metric_inc",
+    "trace_error",
+    "cancel_safe",
+    "on_ok",
+    "on_err",
+    "to_option",
+    "unwrap_infallible",
+    "with_correlation",
+    "throttle",
+    "log_once",
+    "report_and_continue",
+    "convert",
+    "catch_finally_with",
+    "assert_no_panic",
+    "branch",
+    "ratelimit_propagate",
+    "partial",
+    "escalate",
+];
+
 /// Chain variant for catch/throw/inspect.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChainVariant {
@@ -49,10 +110,14 @@ pub enum Guard {
 pub struct GenContext {
     /// Whether generating async code
     pub is_async: bool,
-    /// Context expression from `with "context"`
+    /// Context call from `with`: `ctx("context")` or `ctx_with(|e| ...)`
     pub ctx_expr: Option<TokenStream>,
     /// Key-value pairs from `with key: value`
-    pub kv_pairs: Vec<(TokenStream, TokenStream)>,
+    pub kv_pairs: Vec<with_ctx::KvPair>,
+    /// Error code from `with code expr`
+    pub code: Option<TokenStream>,
+    /// Severity from `with severity Level`
+    pub severity: Option<TokenStream>,
 }
 
 impl GenContext {
@@ -0,0 +1,33 @@
+//! On-err keyword - tap the error path, the counterpart of `on_ok`.
+//!
+//! Syntax variants:
+//! - `on_err |e| { body }` - for every error
+//! - `on_err |e| when guard { body }` - only when guard holds
+//!
+//! Desugars to an inspect clause; the error keeps propagating to later
+//! handlers unchanged.
+
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse an on_err clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "on_err")?;
+    let inspect_span = kw.span();
+
+    let binding = parsing::parse_pipe_binding(input)?;
+    let guard = parsing::parse_optional_guard(input)?;
+    let body = parsing::parse_braced_body(input)?;
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding,
+        guard,
+        body,
+    })
+}
@@ -0,0 +1,85 @@
+//! On-ok keyword - tap the try body's success value.
+//!
+//! Syntax: `on_ok |v| { body }`
+//!
+//! The binding is a shared reference to the value the try body produced.
+//! The tap runs only when the body completes without an error (recovered
+//! values from `catch` don't trigger it) and can't change the value.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Ident, Result};
+
+use super::{parse_keyword, parsing};
+
+/// A parsed on_ok clause.
+#[derive(Debug, Clone)]
+pub struct OnOkClause {
+    /// Span of the `on_ok` keyword (for error reporting)
+    pub on_ok_span: proc_macro2::Span,
+    /// Binding for `&value`
+    pub binding: Ident,
+    /// Tap body
+    pub body: TokenStream,
+}
+
+/// Parse an on_ok clause.
+pub fn parse(input: ParseStream) -> Result<OnOkClause> {
+    let kw = parse_keyword(input, "on_ok")?;
+    let binding = parsing::parse_pipe_binding(input)?;
+    let body = parsing::parse_braced_body(input)?;
+
+    Ok(OnOkClause {
+        on_ok_span: kw.span(),
+        binding,
+        body,
+    })
+}
+
+/// Wrap a try body so each tap sees its value on success, in declaration order.
+///
+/// An early `?` in the body skips the taps, so they only observe success.
+pub fn wrap_body(body: TokenStream, taps: &[OnOkClause]) -> TokenStream {
+    if taps.is_empty() {
+        return body;
+    }
+    let taps = gen_taps(taps);
+    quote! {
+        let __handle_ok_value = { #body };
+        #taps
+        __handle_ok_value
+    }
+}
+
+/// Generate a `.map(..)` running the taps on an `Ok` result.
+///
+/// Used by `async try`, whose body runs in an `async move` block: taps
+/// applied after `.await` borrow the caller's state instead of moving it.
+pub fn gen_map(taps: &[OnOkClause]) -> TokenStream {
+    if taps.is_empty() {
+        return quote! {};
+    }
+    let taps = gen_taps(taps);
+    quote! {
+        .map(|__handle_ok_value| {
+            #taps
+            __handle_ok_value
+        })
+    }
+}
+
+fn gen_taps(taps: &[OnOkClause]) -> TokenStream {
+    let taps = taps.iter().map(|tap| {
+        let binding = &tap.binding;
+        let tap_body = &tap.body;
+        quote! {
+            {
+                #[allow(unused_variables)]
+                let #binding = &__handle_ok_value;
+                #tap_body
+            };
+        }
+    });
+    quote! { #(#taps)* }
+}
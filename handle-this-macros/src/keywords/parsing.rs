@@ -5,7 +5,7 @@
 use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
 use syn::parse::ParseStream;
-use syn::{Ident, Result, braced, token};
+use syn::{Ident, LitInt, Result, braced, token};
 
 use super::{ChainVariant, Guard, peek_keyword, parse_keyword};
 
@@ -23,9 +23,20 @@ pub fn parse_chain_variant(input: ParseStream) -> Result<ChainVariant> {
 }
 
 /// Parse type path (handles paths like `std::io::Error`).
+///
+/// `dyn Trait` and `impl Trait` both become `dyn Trait`, matched through
+/// the trait's `catchable!` registration.
 pub fn parse_type_path(input: ParseStream) -> Result<TokenStream> {
     let mut tokens = Vec::new();
 
+    if input.peek(syn::Token![dyn]) {
+        input.parse::<syn::Token![dyn]>()?;
+        tokens.push(quote! { dyn });
+    } else if input.peek(syn::Token![impl]) {
+        input.parse::<syn::Token![impl]>()?;
+        tokens.push(quote! { dyn });
+    }
+
     // First segment
     let ident: Ident = input.parse()?;
     tokens.push(quote! { #ident });
@@ -40,6 +51,11 @@ pub fn parse_type_path(input: ParseStream) -> Result<TokenStream> {
     Ok(tokens.into_iter().collect())
 }
 
+/// Whether a parsed type path is a trait object (`dyn Trait`).
+pub fn is_dyn_type(type_path: &TokenStream) -> bool {
+    matches!(type_path.clone().into_iter().next(), Some(TokenTree::Ident(id)) if id == "dyn")
+}
+
 /// Reserved internal binding names that would conflict with generated code.
 const RESERVED_BINDINGS: &[&str] = &[
     "__err", "__signal", "__signal_value", "__new_err", "__result", "__ok_value",
@@ -92,6 +108,33 @@ pub fn parse_binding(input: ParseStream, variant: ChainVariant) -> Result<Ident>
     Ok(binding)
 }
 
+/// Parse optional closure-style binding: `|e|` or `|_|`.
+/// Returns an underscore binding when no `|` follows.
+pub fn parse_pipe_binding(input: ParseStream) -> Result<Ident> {
+    if !input.peek(syn::Token![|]) {
+        return Ok(underscore_ident());
+    }
+
+    input.parse::<syn::Token![|]>()?;
+    let binding: Ident = if input.peek(syn::Token![_]) {
+        input.parse::<syn::Token![_]>()?;
+        underscore_ident()
+    } else {
+        input.parse()?
+    };
+    input.parse::<syn::Token![|]>()?;
+
+    let name = binding.to_string();
+    if is_reserved_binding(&name) {
+        return Err(syn::Error::new(
+            binding.span(),
+            format!("`{}` is reserved for internal use; choose a different binding name", name),
+        ));
+    }
+
+    Ok(binding)
+}
+
 /// Parse optional guard (when or match).
 pub fn parse_optional_guard(input: ParseStream) -> Result<Option<Guard>> {
     if peek_keyword(input, "when") {
@@ -191,3 +234,52 @@ pub fn peek_brace(input: ParseStream) -> bool {
 pub fn underscore_ident() -> Ident {
     Ident::new("_", proc_macro2::Span::call_site())
 }
+
+/// `Duration` expression for `n` of `unit`: `ms`, `s`/`sec`, `min` or `hour`.
+fn unit_duration(n: u64, unit: &str) -> Option<TokenStream> {
+    let per_unit: u64 = match unit {
+        "ms" => 1,
+        "s" | "sec" => 1_000,
+        "min" => 60_000,
+        "hour" => 3_600_000,
+        _ => return None,
+    };
+    let millis = n.saturating_mul(per_unit);
+    Some(quote! { ::core::time::Duration::from_millis(#millis) })
+}
+
+fn unknown_unit(span: proc_macro2::Span, unit: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!("unknown duration unit `{}`, expected ms/s/sec/min/hour", unit),
+    )
+}
+
+/// Parse a duration: a suffixed integer literal (`250ms`, `5s`, `2min`) or an expression.
+pub fn parse_duration(input: ParseStream) -> Result<TokenStream> {
+    if input.peek(LitInt) {
+        let fork = input.fork();
+        let lit: LitInt = fork.parse()?;
+        if !lit.suffix().is_empty() && (fork.is_empty() || fork.peek(syn::Token![,])) {
+            input.parse::<LitInt>()?;
+            let n: u64 = lit.base10_parse()?;
+            return unit_duration(n, lit.suffix()).ok_or_else(|| unknown_unit(lit.span(), lit.suffix()));
+        }
+    }
+    let expr: syn::Expr = input.parse()?;
+    Ok(quote! { #expr })
+}
+
+/// Parse a rate `N/unit` into the count and a one-unit window, e.g. `5/sec`.
+///
+/// `what` names the count in the error for `0/unit`.
+pub fn parse_rate(input: ParseStream, what: &str) -> Result<(LitInt, TokenStream)> {
+    let count: LitInt = input.parse()?;
+    if count.base10_parse::<u32>()? == 0 {
+        return Err(syn::Error::new(count.span(), format!("{} must be at least 1", what)));
+    }
+    input.parse::<syn::Token![/]>()?;
+    let unit: Ident = input.parse()?;
+    let window = unit_duration(1, &unit.to_string()).ok_or_else(|| unknown_unit(unit.span(), &unit.to_string()))?;
+    Ok((count, window))
+}
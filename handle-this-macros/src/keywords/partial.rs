@@ -0,0 +1,70 @@
+//! Partial keyword - keep every result of a `try all`.
+//!
+//! Syntax: `try all x in iter { body } partial |oks, errs| { report }`
+//!
+//! Instead of failing on the first chained error, every iteration's outcome
+//! is kept: `oks` is a `Vec` of the successful values and `errs` a
+//! `Vec<Handled>` of the failures, both in input order. The expression yields
+//! `report`'s value; an error raised with `?` inside it flows on to the
+//! handlers that follow.
+
+use proc_macro2::{Span, TokenStream};
+use syn::parse::ParseStream;
+use syn::{Ident, Result};
+
+use super::{parse_keyword, parsing};
+
+/// A parsed partial clause.
+#[derive(Debug, Clone)]
+pub struct PartialClause {
+    /// Span of the `partial` keyword (for error reporting)
+    pub partial_span: Span,
+    /// Binding for the successful values
+    pub oks: Ident,
+    /// Binding for the failures
+    pub errs: Ident,
+    /// Report body
+    pub body: TokenStream,
+}
+
+/// Parse a partial clause.
+pub fn parse(input: ParseStream) -> Result<PartialClause> {
+    let kw = parse_keyword(input, "partial")?;
+    if !input.peek(syn::Token![|]) {
+        return Err(syn::Error::new(
+            kw.span(),
+            "expected bindings: `partial |oks, errs| { ... }`",
+        ));
+    }
+
+    input.parse::<syn::Token![|]>()?;
+    let oks = parse_binding(input)?;
+    input.parse::<syn::Token![,]>()?;
+    let errs = parse_binding(input)?;
+    input.parse::<syn::Token![|]>()?;
+    let body = parsing::parse_braced_body(input)?;
+
+    Ok(PartialClause {
+        partial_span: kw.span(),
+        oks,
+        errs,
+        body,
+    })
+}
+
+/// Parse one binding of `|oks, errs|`, allowing `_`.
+fn parse_binding(input: ParseStream) -> Result<Ident> {
+    if input.peek(syn::Token![_]) {
+        input.parse::<syn::Token![_]>()?;
+        return Ok(parsing::underscore_ident());
+    }
+    let binding: Ident = input.parse()?;
+    let name = binding.to_string();
+    if parsing::is_reserved_binding(&name) {
+        return Err(syn::Error::new(
+            binding.span(),
+            format!("`{}` is reserved for internal use; choose a different binding name", name),
+        ));
+    }
+    Ok(binding)
+}
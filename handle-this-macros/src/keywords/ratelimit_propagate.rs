@@ -0,0 +1,84 @@
+//! Ratelimit-propagate keyword - circuit breaking per call site.
+//!
+//! Syntax: `ratelimit_propagate N/unit else { fallback }` where unit is `ms`,
+//! `s`/`sec`, `min` or `hour`
+//!
+//! `N` failures of the try body within one window open the circuit for a
+//! window-long cooldown. While open, the body is skipped and the expression
+//! yields `Ok(fallback)`. Afterwards one trial call runs: success closes the
+//! circuit, failure reopens it. The call site (`file:line:col` of the
+//! keyword) is the key in `::handle_this::circuit`'s registry.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::ParseStream;
+use syn::{LitInt, Result};
+
+use super::{parse_keyword, parsing};
+
+/// A parsed ratelimit_propagate clause.
+#[derive(Debug, Clone)]
+pub struct RatelimitClause {
+    /// Span of the `ratelimit_propagate` keyword (for error reporting and the site key)
+    pub span: proc_macro2::Span,
+    /// Failures allowed per window before the circuit opens
+    pub threshold: LitInt,
+    /// Window (and cooldown) as a `Duration` expression
+    pub window: TokenStream,
+    /// Value returned while the circuit is open
+    pub fallback: TokenStream,
+}
+
+/// Parse a ratelimit_propagate clause.
+pub fn parse(input: ParseStream) -> Result<RatelimitClause> {
+    let kw = parse_keyword(input, "ratelimit_propagate")?;
+    let span = kw.span();
+
+    if input.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "expected threshold: `ratelimit_propagate 5/min else { fallback }`",
+        ));
+    }
+    let (threshold, window) = parsing::parse_rate(input, "failure threshold")?;
+
+    if !input.peek(syn::Token![else]) {
+        return Err(syn::Error::new(
+            span,
+            "`ratelimit_propagate` needs a fallback: `ratelimit_propagate 5/min else { fallback }`",
+        ));
+    }
+    input.parse::<syn::Token![else]>()?;
+    let fallback = parsing::parse_braced_body(input)?;
+
+    Ok(RatelimitClause {
+        span,
+        threshold,
+        window,
+        fallback,
+    })
+}
+
+/// Guard a try body with the call site's circuit.
+///
+/// An open circuit evaluates the fallback instead of the body. Otherwise the
+/// attempt guard records `?` exits (and panics) as failures when dropped.
+pub fn wrap_body(body: TokenStream, clause: Option<&RatelimitClause>) -> TokenStream {
+    let Some(clause) = clause else {
+        return body;
+    };
+    let RatelimitClause { span, threshold, window, fallback } = clause;
+    let site = quote_spanned! {*span=>
+        ::core::concat!(::core::file!(), ":", ::core::line!(), ":", ::core::column!())
+    };
+    quote! {
+        match ::handle_this::circuit::Attempt::begin(#site, #threshold, #window) {
+            ::core::option::Option::Some(__handle_circuit) => {
+                let __handle_circuit_value = { #body };
+                __handle_circuit.succeed();
+                __handle_circuit_value
+            }
+            ::core::option::Option::None => { #fallback }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Report-and-continue keyword - report the error, then continue the loop.
+//!
+//! Syntax variants:
+//! - `report_and_continue` - log the error at `error` level through the
+//!   `log` facade (`log` feature), or print it to stderr
+//! - `report_and_continue |e| { body }` - run `body` with the error instead
+//!
+//! Desugars to a catch-all clause like `continue_with`, so the try block
+//! runs in signal mode and `continue` targets the enclosing loop. Works in
+//! `try` and the loop forms (`try for`/`any`/`all`/`while`); an `async try`
+//! body can't reach an enclosing loop, so it's rejected there.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Ident, Result};
+
+use super::catch::CatchClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Reject `report_and_continue` in `async try`.
+pub fn reject_async(input: ParseStream) -> Result<()> {
+    let kw = parse_keyword(input, "report_and_continue")?;
+    Err(syn::Error::new(
+        kw.span(),
+        "`report_and_continue` isn't supported in `async try`: the body can't `continue` an enclosing loop; \
+         use `catch e { report(e); None }` and skip on `None`",
+    ))
+}
+
+/// Parse a report_and_continue clause into an equivalent catch-all clause.
+pub fn parse(input: ParseStream) -> Result<CatchClause> {
+    let kw = parse_keyword(input, "report_and_continue")?;
+    let catch_span = kw.span();
+
+    let (binding, report) = if input.peek(syn::Token![|]) {
+        let binding = parsing::parse_pipe_binding(input)?;
+        let body = parsing::parse_braced_body(input)?;
+        (binding, quote! { let _ = { #body }; })
+    } else {
+        let binding = Ident::new("__handle_report_err", proc_macro2::Span::call_site());
+        let report = quote! { ::handle_this::__report(&#binding); };
+        (binding, report)
+    };
+
+    Ok(CatchClause {
+        catch_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding,
+        guard: None,
+        body: quote! {
+            #report
+            continue
+        },
+    })
+}
@@ -0,0 +1,232 @@
+//! Rethrow - give up on an error from inside a catch body.
+//!
+//! Syntax, inside a `try { }` catch body:
+//! - `rethrow` - propagate the caught error, with a frame for the rethrow
+//! - `rethrow expr` - propagate `expr` (anything `throw` accepts), chained
+//!   after the caught error
+//! - `escalate` - propagate the caught error with its severity raised one
+//!   level (`Handled::escalate`); `catch e { body } escalate` is the same
+//!   as ending the body with it
+//!
+//! A catch that can rethrow is fallible, so it becomes a `try catch` whose
+//! body is `Ok(body)` and the expression yields a `Result`. Each `rethrow`
+//! lowers to `?` on an `Err` carrying the error, which leaves the handler
+//! with it. A `rethrow` inside a nested `try` or `handle!` belongs to that one.
+
+use proc_macro2::{Delimiter, Group, Ident, TokenStream, TokenTree};
+use quote::quote;
+use syn::Result;
+
+use super::Guard;
+use super::catch::CatchClause;
+use super::try_catch::TryCatchClause;
+use crate::nested::skip_nested_try_pattern;
+
+/// The first `rethrow` or `escalate` in a catch body or its `match` arms, if any.
+pub fn find(clause: &CatchClause) -> Option<Ident> {
+    let mut found = None;
+    lower(clause.body.clone(), &TokenStream::new(), &mut found);
+    if let Some(Guard::Match { arms, .. }) = &clause.guard {
+        lower(arms.clone(), &TokenStream::new(), &mut found);
+    }
+    found
+}
+
+/// Reject `rethrow` in patterns that don't support `try catch`.
+pub fn reject(clause: &CatchClause) -> Result<()> {
+    match find(clause) {
+        Some(keyword) => Err(syn::Error::new(
+            keyword.span(),
+            format!("`{}` is only supported in `try {{ }}` catch handlers; use `throw` to replace the error", keyword),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Turn a catch clause that rethrows into a `try catch`.
+pub fn wrap(clause: CatchClause) -> Result<TryCatchClause> {
+    // A typed binding borrows the wrapped error; a catch-all binding owns it
+    let original = if clause.type_path.is_none() && clause.binding != "_" {
+        let binding = &clause.binding;
+        quote! { #binding }
+    } else {
+        quote! { __err }
+    };
+    let body = lower(clause.body, &original, &mut None);
+    let guard = match clause.guard {
+        Some(Guard::Match { expr, arms }) => {
+            // Every arm now yields a `Result`, like a `try catch` match
+            let arms = lower(arms, &original, &mut None);
+            let mut matched: syn::ExprMatch = syn::parse2(quote! { match () { #arms } })?;
+            for arm in &mut matched.arms {
+                let value = &arm.body;
+                *arm.body = syn::parse_quote! { ::core::result::Result::Ok(#value) };
+                arm.comma.get_or_insert_with(Default::default);
+            }
+            let arms = matched.arms;
+            Some(Guard::Match { expr, arms: quote! { #(#arms)* } })
+        }
+        other => other,
+    };
+    Ok(TryCatchClause {
+        variant: clause.variant,
+        type_path: clause.type_path,
+        binding: clause.binding,
+        guard,
+        body: quote! { ::core::result::Result::Ok({ #body }) },
+    })
+}
+
+/// Replace each `rethrow [expr]` or `escalate` with an early error return of `original`.
+fn lower(stream: TokenStream, original: &TokenStream, found: &mut Option<Ident>) -> TokenStream {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let mut out = TokenStream::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Some(skip) = skip_nested(&tokens[i..]) {
+            out.extend(tokens[i..i + skip].iter().cloned());
+            i += skip;
+            continue;
+        }
+        match &tokens[i] {
+            TokenTree::Ident(ident) if ident == "escalate" && is_keyword(&tokens, i) => {
+                found.get_or_insert_with(|| ident.clone());
+                out.extend(gen_escalate(original));
+                i += 1;
+            }
+            TokenTree::Ident(ident) if ident == "rethrow" && is_keyword(&tokens, i) => {
+                found.get_or_insert_with(|| ident.clone());
+                let end = (i + 1..tokens.len())
+                    .find(|&j| matches!(&tokens[j], TokenTree::Punct(p) if p.as_char() == ';' || p.as_char() == ','))
+                    .unwrap_or(tokens.len());
+                let expr: TokenStream = tokens[i + 1..end].iter().cloned().collect();
+                out.extend(gen_rethrow(&expr, original));
+                i = end;
+            }
+            TokenTree::Group(group) => {
+                let mut lowered = Group::new(group.delimiter(), lower(group.stream(), original, found));
+                lowered.set_span(group.span());
+                out.extend([TokenTree::Group(lowered)]);
+                i += 1;
+            }
+            other => {
+                out.extend([other.clone()]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Tokens to copy unchanged: a nested `try` pattern or `handle! { }` call.
+fn skip_nested(tokens: &[TokenTree]) -> Option<usize> {
+    if let [TokenTree::Ident(ident), TokenTree::Punct(bang), TokenTree::Group(_), ..] = tokens {
+        if ident == "handle" && bang.as_char() == '!' {
+            return Some(3);
+        }
+    }
+    skip_nested_try_pattern(tokens)
+}
+
+/// A `rethrow` ident used as a variable or field is left alone.
+fn is_keyword(tokens: &[TokenTree], i: usize) -> bool {
+    let prev_ok = match i.checked_sub(1).map(|p| &tokens[p]) {
+        Some(TokenTree::Punct(p)) => !matches!(p.as_char(), '.' | ':' | '&' | '!'),
+        Some(TokenTree::Ident(kw)) => {
+            !matches!(kw.to_string().as_str(), "let" | "mut" | "ref" | "fn" | "if" | "while" | "match" | "in")
+        }
+        _ => true,
+    };
+    let next_ok = match tokens.get(i + 1) {
+        Some(TokenTree::Punct(p)) => !matches!(p.as_char(), '=' | '.' | ':'),
+        Some(TokenTree::Group(g)) => g.delimiter() != Delimiter::Parenthesis,
+        _ => true,
+    };
+    prev_ok && next_ok
+}
+
+fn gen_rethrow(expr: &TokenStream, original: &TokenStream) -> TokenStream {
+    let err = if expr.is_empty() {
+        quote! { #original.frame(file!(), line!(), column!()) }
+    } else {
+        // Same conversion and framing as `throw`
+        quote! {
+            {
+                #[allow(unused_imports)]
+                use ::handle_this::__Thrown;
+                ::handle_this::__ThrowExpr(#expr).__thrown().frame(file!(), line!(), column!())
+            }
+            .chain_after(#original)
+        }
+    };
+    quote! { match ::handle_this::__rethrow(#err)? {} }
+}
+
+fn gen_escalate(original: &TokenStream) -> TokenStream {
+    quote! { match ::handle_this::__rethrow(#original.escalate().frame(file!(), line!(), column!()))? {} }
+}
+
+/// `catch ... { body } escalate`: the body runs, then the error escalates.
+pub fn append_escalate(mut clause: CatchClause, keyword: Ident) -> Result<CatchClause> {
+    if matches!(clause.guard, Some(Guard::Match { .. })) {
+        return Err(syn::Error::new(
+            keyword.span(),
+            "`escalate` after a `match` guard is ambiguous; put `escalate` in the arms that should escalate",
+        ));
+    }
+    let body = clause.body;
+    clause.body = quote! {
+        let _ = { #body };
+        #keyword
+    };
+    Ok(clause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_in(body: TokenStream) -> Option<Ident> {
+        let mut found = None;
+        lower(body, &TokenStream::new(), &mut found);
+        found
+    }
+
+    #[test]
+    fn test_finds_rethrow_in_nested_blocks() {
+        assert!(find_in(quote! { if fatal { rethrow } 0 }).is_some());
+        assert!(find_in(quote! { log(e); rethrow MyError::new(); }).is_some());
+        assert!(find_in(quote! { 0 }).is_none());
+    }
+
+    #[test]
+    fn test_ignores_variables_and_nested_handlers() {
+        assert!(find_in(quote! { let rethrow = true; if rethrow { 0 } else { 1 } }).is_none());
+        assert!(find_in(quote! { let rethrow = cfg.rethrow; 0 }).is_none());
+        assert!(find_in(quote! { try { op()? } catch e { rethrow } }).is_none());
+        assert!(find_in(quote! { handle! { try { op()? } catch e { rethrow } } }).is_none());
+    }
+
+    #[test]
+    fn test_lowers_to_question_mark() {
+        let lowered = lower(quote! { if x { rethrow } 0 }, &quote! { __err }, &mut None).to_string();
+        assert!(lowered.contains(":: handle_this :: __rethrow (__err . frame"));
+        assert!(lowered.contains("? { }"));
+        assert!(!lowered.contains("rethrow }"));
+    }
+
+    #[test]
+    fn test_escalate_lowers_with_raised_severity() {
+        let lowered = lower(quote! { log(e); escalate }, &quote! { e }, &mut None).to_string();
+        assert!(lowered.contains("__rethrow (e . escalate () . frame"));
+        assert!(find_in(quote! { let escalate = 1; escalate.max(2) }).is_none());
+    }
+
+    #[test]
+    fn test_rethrow_expr_stops_at_semicolon() {
+        let lowered = lower(quote! { rethrow Wrapped(e); 0 }, &quote! { e }, &mut None).to_string();
+        assert!(lowered.contains("__ThrowExpr (Wrapped (e))"));
+        assert!(lowered.contains(". chain_after (e)"));
+        assert!(lowered.ends_with("; 0"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Snapshot keyword - hand an owned `ErrorRecord` of the error to a block.
+//!
+//! Syntax variants:
+//! - `snapshot |rec| { body }` - for every error
+//! - `snapshot |rec| when guard { body }` - only when guard holds (guard sees `rec`)
+//!
+//! Desugars to an inspect clause that builds the record with
+//! `Handled::snapshot` and binds it before the guard and body run.
+//! The error keeps propagating to later handlers.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant, Guard};
+
+/// Parse a snapshot clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "snapshot")?;
+    let inspect_span = kw.span();
+
+    let binding = parsing::parse_pipe_binding(input)?;
+    let guard = parsing::parse_optional_guard(input)?;
+    let body = parsing::parse_braced_body(input)?;
+
+    let body = match guard {
+        Some(Guard::When(cond)) => quote! {
+            let #binding: ::handle_this::ErrorRecord = __err.snapshot();
+            if #cond { #body }
+        },
+        Some(_) => {
+            return Err(syn::Error::new(inspect_span, "`snapshot` only supports `when` guards"));
+        }
+        None => quote! {
+            let #binding: ::handle_this::ErrorRecord = __err.snapshot();
+            { #body }
+        },
+    };
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body,
+    })
+}
@@ -0,0 +1,42 @@
+//! Spawn keyword - run an async try body as its own task.
+//!
+//! Syntax: `async try spawn [timeout DURATION] { body } [handlers...]`
+//!
+//! The body is moved into a `tokio::spawn`ed task (`tokio` feature) and the
+//! try awaits its `JoinHandle`. A panic in the task becomes `Panicked`
+//! (so `catch panic p` sees it) and a cancelled task becomes
+//! `tokio::task::JoinError`; either is framed at the macro site and routed
+//! through the handlers like any other error.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::{parse_keyword, peek_keyword};
+
+/// Parse an optional `spawn` prefix before the async try body.
+pub fn parse(input: ParseStream) -> Result<Option<Span>> {
+    if !peek_keyword(input, "spawn") {
+        return Ok(None);
+    }
+    Ok(Some(parse_keyword(input, "spawn")?.span()))
+}
+
+/// Run the try body on a spawned task and join it.
+///
+/// The first `?` turns a `JoinError` into the body's error, the second
+/// propagates the body's own error.
+pub fn wrap_body(body: TokenStream, spawn: Option<Span>) -> TokenStream {
+    if spawn.is_none() {
+        return body;
+    }
+    // A body that always panics (`spawn { panic!(..) }`) makes the `Ok` unreachable
+    quote! {
+        {
+            #[allow(unreachable_code, clippy::diverging_sub_expression)]
+            let __joined = ::handle_this::__spawn(::handle_this::__async_try_block!(#body));
+            __joined
+        }.await??
+    }
+}
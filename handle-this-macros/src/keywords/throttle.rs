@@ -0,0 +1,59 @@
+//! Throttle keyword - client-side rate limiting by key.
+//!
+//! Syntax: `throttle key, N/unit` where unit is `ms`, `s`/`sec`, `min` or `hour`
+//!
+//! Prepends `::handle_this::throttle::check(key, N, per)?` to the try body,
+//! so an attempt over the limit fails with `Throttled` before the body runs
+//! and flows through the handlers like any other error.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{LitInt, Result};
+
+use super::{parse_keyword, parsing};
+
+/// A parsed throttle clause.
+#[derive(Debug, Clone)]
+pub struct ThrottleClause {
+    /// Span of the `throttle` keyword (for error reporting)
+    pub throttle_span: proc_macro2::Span,
+    /// Key expression (`impl AsRef<str>`)
+    pub key: syn::Expr,
+    /// Attempts allowed per window
+    pub limit: LitInt,
+    /// Window as a `Duration` expression
+    pub per: TokenStream,
+}
+
+/// Parse a throttle clause.
+pub fn parse(input: ParseStream) -> Result<ThrottleClause> {
+    let kw = parse_keyword(input, "throttle")?;
+    let throttle_span = kw.span();
+
+    if input.is_empty() {
+        return Err(syn::Error::new(throttle_span, "expected key: `throttle key, 1/sec`"));
+    }
+    let key = syn::Expr::parse_without_eager_brace(input)?;
+    input.parse::<syn::Token![,]>()?;
+    let (limit, per) = parsing::parse_rate(input, "throttle limit")?;
+
+    Ok(ThrottleClause {
+        throttle_span,
+        key,
+        limit,
+        per,
+    })
+}
+
+/// Prepend the rate-limit check to a try body.
+pub fn wrap_body(body: TokenStream, clause: Option<&ThrottleClause>) -> TokenStream {
+    let Some(clause) = clause else {
+        return body;
+    };
+    let ThrottleClause { key, limit, per, .. } = clause;
+    quote! {
+        ::handle_this::throttle::check(#key, #limit, #per)?;
+        #body
+    }
+}
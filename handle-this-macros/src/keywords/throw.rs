@@ -9,13 +9,27 @@
 //! - `throw Type(e) match expr { arms }` - typed with match
 //! - `throw any Type(e) { ... }` - search cause chain
 //! - `throw all Type |errors| { ... }` - collect all from chain
+//! - `throw snapshot ... { new_error }` - any of the above, but the new error
+//!   is captured by its `Display` text (a `Handled` keeps its trace and kv),
+//!   so it may borrow; see `Handled::snapshot_of`
+//! - `throw as Enum::Variant` - wrap the root error in a tuple variant when
+//!   its type is the variant's payload, keeping the trace
+//! - `throw as Enum::Variant { field: value }` - same for a struct variant;
+//!   the root becomes its `source` field
+//!
+//! `throw as` replaces the error in place rather than chaining it, and
+//! desugars to an inspect clause like `classify`.
 
 use proc_macro2::TokenStream;
+use quote::quote;
 use syn::parse::ParseStream;
-use syn::{Ident, Result};
+use syn::punctuated::Punctuated;
+use syn::{braced, FieldValue, Ident, Path, Result};
 
-use super::{ChainVariant, Guard, parse_keyword};
+use super::{ChainVariant, Guard, parse_keyword, peek_keyword};
 use super::clause::{parse_clause, ClauseConfig};
+use super::inspect::InspectClause;
+use super::parsing;
 
 /// A parsed throw clause.
 #[derive(Debug, Clone)]
@@ -32,17 +46,81 @@ pub struct ThrowClause {
     pub throw_expr: TokenStream,
 }
 
+/// Check for `throw as`.
+pub fn peek_as(input: ParseStream) -> bool {
+    peek_keyword(input, "throw") && input.peek2(syn::Token![as])
+}
+
+/// Parse `throw as Enum::Variant [{ fields }]` into an equivalent inspect clause.
+pub fn parse_as(input: ParseStream) -> Result<InspectClause> {
+    let throw_kw = parse_keyword(input, "throw")?;
+    input.parse::<syn::Token![as]>()?;
+    let path: Path = input.parse()?;
+
+    let variant = if input.peek(syn::token::Brace) {
+        let content;
+        braced!(content in input);
+        let fields: Punctuated<FieldValue, syn::Token![,]> = Punctuated::parse_terminated(&content)?;
+        let fields = fields.iter();
+        quote! { |__throw_as_source| #path { #(#fields,)* source: __throw_as_source } }
+    } else {
+        quote! { #path }
+    };
+
+    Ok(InspectClause {
+        inspect_span: throw_kw.span(),
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            __err = ::handle_this::__throw_as(__err, #variant, file!(), line!(), column!());
+        },
+    })
+}
+
 /// Parse a throw clause.
 pub fn parse(input: ParseStream) -> Result<ThrowClause> {
     let throw_kw = parse_keyword(input, "throw")?;
+    if input.peek(syn::Token![as]) {
+        return Err(syn::Error::new(
+            throw_kw.span(),
+            "`throw as` isn't supported here; use it in a `try` block or `try for`/`try while`",
+        ));
+    }
+    let snapshot = if peek_keyword(input, "snapshot") {
+        Some(parse_keyword(input, "snapshot")?)
+    } else {
+        None
+    };
 
     let clause = parse_clause(input, throw_kw.span(), ClauseConfig::throw())?;
 
+    let throw_expr = match snapshot {
+        Some(kw) if matches!(clause.guard, Some(Guard::Match { .. })) => {
+            return Err(syn::Error::new(
+                kw.span(),
+                "`throw snapshot` can't be combined with `match`; use `when` or snapshot inside the arms",
+            ));
+        }
+        Some(_) => {
+            let body = clause.body;
+            quote! {
+                {
+                    #[allow(unused_imports)]
+                    use ::handle_this::__Snapshot;
+                    ::handle_this::__ThrowExpr({ #body }).__snapshot()
+                }
+            }
+        }
+        None => clause.body,
+    };
+
     Ok(ThrowClause {
         variant: clause.variant,
         type_path: clause.type_path,
         binding: clause.binding,
         guard: clause.guard,
-        throw_expr: clause.body,
+        throw_expr,
     })
 }
@@ -0,0 +1,43 @@
+//! Timeout keyword - bound an async try body by a deadline.
+//!
+//! Syntax: `async try timeout DURATION { body } [handlers...]`
+//!
+//! The body runs under `::handle_this::__timeout`; if the duration passes
+//! first, the body is dropped and the try fails with `handle_this::Elapsed`,
+//! framed at the macro site and routed through the handlers like any other
+//! error.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::{parse_keyword, peek_keyword};
+
+/// Parse an optional `timeout DURATION` prefix before the async try body.
+pub fn parse(input: ParseStream) -> Result<Option<syn::Expr>> {
+    if !peek_keyword(input, "timeout") {
+        return Ok(None);
+    }
+    let kw = parse_keyword(input, "timeout")?;
+    if input.is_empty() || input.peek(syn::token::Brace) {
+        return Err(syn::Error::new(
+            kw.span(),
+            "expected a duration: `async try timeout Duration::from_secs(5) { ... }`",
+        ));
+    }
+    Ok(Some(syn::Expr::parse_without_eager_brace(input)?))
+}
+
+/// Race the try body against the deadline.
+///
+/// The first `?` turns `Elapsed` into the body's error, the second
+/// propagates the body's own error.
+pub fn wrap_body(body: TokenStream, duration: Option<&syn::Expr>) -> TokenStream {
+    let Some(duration) = duration else {
+        return body;
+    };
+    quote! {
+        ::handle_this::__timeout(#duration, ::handle_this::__async_try_block!(#body)).await??
+    }
+}
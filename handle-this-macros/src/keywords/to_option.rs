@@ -0,0 +1,34 @@
+//! To-option keyword - discard the error and yield an `Option`.
+//!
+//! Syntax: `try { } [inspect ...] to_option`
+//!
+//! A trailing marker: the finished `Result` (after handlers and `finally`)
+//! is converted with `Result::ok`, so the whole expression evaluates to
+//! `Some(value)` on success and `None` on any unhandled error. Inspect
+//! handlers still see the error before it is dropped.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::parse_keyword;
+
+/// Parse the `to_option` marker, which must end the handler list.
+pub fn parse(input: ParseStream) -> Result<Span> {
+    let kw = parse_keyword(input, "to_option")?;
+    if !input.is_empty() {
+        return Err(syn::Error::new(
+            kw.span(),
+            "`to_option` must be the last clause: `try { } catch ... to_option`",
+        ));
+    }
+    Ok(kw.span())
+}
+
+/// Convert the generated `Result` expression into an `Option`.
+pub fn wrap(inner: TokenStream) -> TokenStream {
+    quote! {
+        ::core::result::Result::ok({ #inner })
+    }
+}
@@ -0,0 +1,31 @@
+//! Trace-error keyword - emit a `tracing` event on the error path.
+//!
+//! Syntax: `trace_error`
+//!
+//! Desugars to an inspect clause that calls `handle_this::trace::emit`,
+//! recording the message, frames and attachments as event fields.
+//! Requires the `tracing` feature. The error keeps propagating to later
+//! handlers.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a trace_error clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "trace_error")?;
+
+    Ok(InspectClause {
+        inspect_span: kw.span(),
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            ::handle_this::trace::emit(&__err);
+        },
+    })
+}
@@ -0,0 +1,41 @@
+//! Unwrap-infallible keyword - yield `T` from a fallible try whose handlers
+//! are known to cover every error.
+//!
+//! Syntax: `try { } catch Type(e) { } ... unwrap_infallible`
+//!
+//! A trailing marker: the finished `Result` (after handlers and `finally`)
+//! is unwrapped, so the whole expression evaluates to `T`. Meant for typed
+//! catches that together cover every error the body can produce, which the
+//! compiler can't prove. An error that escapes anyway panics with its trace.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::parse_keyword;
+
+/// Parse the `unwrap_infallible` marker, which must end the handler list.
+pub fn parse(input: ParseStream) -> Result<Span> {
+    let kw = parse_keyword(input, "unwrap_infallible")?;
+    if !input.is_empty() {
+        return Err(syn::Error::new(
+            kw.span(),
+            "`unwrap_infallible` must be the last clause: `try { } catch ... unwrap_infallible`",
+        ));
+    }
+    Ok(kw.span())
+}
+
+/// Unwrap the generated `Result` expression, treating an error as a bug.
+pub fn wrap(inner: TokenStream) -> TokenStream {
+    quote! {
+        match { #inner } {
+            ::core::result::Result::Ok(__v) => __v,
+            ::core::result::Result::Err(__e) => ::core::unreachable!(
+                "`unwrap_infallible`: an error escaped every handler: {}",
+                __e
+            ),
+        }
+    }
+}
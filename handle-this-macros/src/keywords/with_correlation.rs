@@ -0,0 +1,43 @@
+//! With-correlation keyword - attach a request correlation id.
+//!
+//! Syntax: `with_correlation expr`
+//!
+//! Desugars to an inspect clause that calls `Handled::with_correlation`.
+//! The id is a well-known field read back with `Handled::correlation_id`,
+//! which also looks through chained errors, so it survives a later `throw`.
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::Result;
+
+use super::inspect::InspectClause;
+use super::{parse_keyword, parsing, ChainVariant};
+
+/// Parse a with_correlation clause into an equivalent inspect clause.
+pub fn parse(input: ParseStream) -> Result<InspectClause> {
+    let kw = parse_keyword(input, "with_correlation")?;
+    let inspect_span = kw.span();
+
+    if input.is_empty() {
+        return Err(syn::Error::new(
+            inspect_span,
+            "expected correlation id: `with_correlation(id)`",
+        ));
+    }
+    // `with_correlation(id)` - drop the parens so they aren't linted as unused
+    let id = match syn::Expr::parse_without_eager_brace(input)? {
+        syn::Expr::Paren(p) => *p.expr,
+        other => other,
+    };
+
+    Ok(InspectClause {
+        inspect_span,
+        variant: ChainVariant::Root,
+        type_path: None,
+        binding: parsing::underscore_ident(),
+        guard: None,
+        body: quote! {
+            __err = __err.with_correlation(#id);
+        },
+    })
+}
@@ -4,19 +4,44 @@
 //! - `with "context message"`
 //! - `with { key: value }`
 //! - `with "context", { key: value, key2: value2 }`
+//! - `with { "x-request-id" => value }` - arbitrary key expression
+//! - `with { password: secret pw }` - store only a redacted hash of the value
+//! - `with "context", code "E1042", severity Warn` - typed code and severity
+//! - `with lazy || format!("user {}", id)` - context built only on error
+//! - `with |e| format!("after {} frames", e.depth())` - context built from the error
+//!
+//! `code` takes any `Into<Cow<'static, str>>` expression; `severity` a
+//! `Severity` variant name or any `Severity` expression. Both become typed
+//! fields (`Handled::code`, `Handled::severity`) rather than kv strings.
 
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::ParseStream;
 use syn::{braced, Expr, Ident, Result, Token};
 
-use super::{parse_keyword, GenContext};
+use super::{is_lowercase_ident, parse_keyword, peek_keyword, GenContext};
 
 /// A key-value pair for structured error context.
 #[derive(Debug, Clone)]
 pub struct KvPair {
+    /// Key expression: `stringify!(ident)` or a user-provided expression
     pub key: TokenStream,
     pub value: TokenStream,
+    /// Key came from `expr => value` and may not be `&'static str`
+    pub owned_key: bool,
+}
+
+impl KvPair {
+    /// Generate the `.kv(...)` / `.kv_owned(...)` call for this pair.
+    pub fn chain_call(&self) -> TokenStream {
+        let key = &self.key;
+        let value = &self.value;
+        if self.owned_key {
+            quote! { .kv_owned(#key, #value) }
+        } else {
+            quote! { .kv(#key, #value) }
+        }
+    }
 }
 
 /// Parsed with clause.
@@ -24,22 +49,36 @@ pub struct KvPair {
 pub struct WithClause {
     pub context: Option<Expr>,
     pub kv_pairs: Vec<KvPair>,
+    pub code: Option<Expr>,
+    pub severity: Option<TokenStream>,
 }
 
-/// Parse key-value pairs from inside braces: { key: value, key2: value2 }
-fn parse_kv_braced(input: ParseStream) -> Result<Vec<KvPair>> {
+/// Parse key-value pairs from inside braces: `{ key: value, "key-2" => value2 }`
+pub fn parse_kv_braced(input: ParseStream) -> Result<Vec<KvPair>> {
     let content;
     braced!(content in input);
 
     let mut pairs = Vec::new();
     while !content.is_empty() {
-        let key: Ident = content.parse()?;
-        content.parse::<Token![:]>()?;
-        let value: Expr = content.parse()?;
-        pairs.push(KvPair {
-            key: quote! { stringify!(#key) },
-            value: quote! { #value },
-        });
+        if content.peek(Ident) && content.peek2(Token![:]) {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+            let value = parse_value(&content)?;
+            pairs.push(KvPair {
+                key: quote! { stringify!(#key) },
+                value,
+                owned_key: false,
+            });
+        } else {
+            let key: Expr = content.parse()?;
+            content.parse::<Token![=>]>()?;
+            let value = parse_value(&content)?;
+            pairs.push(KvPair {
+                key: quote! { #key },
+                value,
+                owned_key: true,
+            });
+        }
 
         if content.peek(Token![,]) {
             content.parse::<Token![,]>()?;
@@ -50,47 +89,142 @@ fn parse_kv_braced(input: ParseStream) -> Result<Vec<KvPair>> {
     Ok(pairs)
 }
 
+/// Parse a kv value, redacting it if it starts with `secret`.
+///
+/// `secret` followed by another expression is the marker; a lone
+/// `secret` or `secret.field` is a variable.
+fn parse_value(input: ParseStream) -> Result<TokenStream> {
+    let fork = input.fork();
+    let is_secret = parse_keyword(&fork, "secret").is_ok()
+        && (fork.peek(Ident) || fork.peek(syn::Lit) || fork.peek(Token![&]));
+    if is_secret {
+        parse_keyword(input, "secret")?;
+        let value: Expr = input.parse()?;
+        return Ok(quote! { ::handle_this::Value::redact(#value) });
+    }
+    let value: Expr = input.parse()?;
+    Ok(quote! { #value })
+}
+
 /// Parse a with clause.
 ///
 /// Supports:
 /// - `with "context"`
 /// - `with { key: value }`
 /// - `with "context", { key: value }`
+/// - `with lazy || expr` or `with |e| expr` in place of the context string
+/// - any of the above followed by `, code expr` and/or `, severity Level`
 pub fn parse(input: ParseStream) -> Result<WithClause> {
     parse_keyword(input, "with")?;
 
     let mut clause = WithClause::default();
 
-    // First item could be context string or braced kv pairs
-    if input.peek(syn::LitStr) {
-        let ctx: Expr = input.parse()?;
+    // First item could be context string, lazy context or braced kv pairs
+    let mut more = true;
+    if input.peek(syn::LitStr) || peek_keyword(input, "lazy") || input.peek(Token![|]) {
+        let ctx: Expr = if peek_keyword(input, "lazy") {
+            let lazy = parse_lazy(input)?;
+            syn::parse2(lazy)?
+        } else if input.peek(Token![|]) {
+            Expr::Closure(parse_from_error(input)?)
+        } else {
+            input.parse()?
+        };
         clause.context = Some(ctx);
 
         // Check for comma and optional braced kv pairs
-        if input.peek(Token![,]) {
+        more = input.peek(Token![,]);
+        if more {
             input.parse::<Token![,]>()?;
             // Check for braced kv pairs after comma
             if input.peek(syn::token::Brace) {
                 clause.kv_pairs = parse_kv_braced(input)?;
+                more = input.peek(Token![,]) && parse_comma(input)?;
             }
         }
     } else if input.peek(syn::token::Brace) {
         // Just braced kv pairs, no context message
         clause.kv_pairs = parse_kv_braced(input)?;
+        more = input.peek(Token![,]) && parse_comma(input)?;
+    }
+
+    // Then `code expr` / `severity Level`, comma separated
+    while more {
+        if peek_keyword(input, "code") && clause.code.is_none() {
+            parse_keyword(input, "code")?;
+            clause.code = Some(Expr::parse_without_eager_brace(input)?);
+        } else if peek_keyword(input, "severity") && clause.severity.is_none() {
+            parse_keyword(input, "severity")?;
+            clause.severity = Some(parse_severity(input)?);
+        } else {
+            break;
+        }
+        more = input.peek(Token![,]) && parse_comma(input)?;
     }
 
     Ok(clause)
 }
 
+/// Parse `lazy || expr` into a call of the closure.
+///
+/// Context is only attached on the error path, so the closure runs only
+/// when an error actually passes through.
+pub fn parse_lazy(input: ParseStream) -> Result<TokenStream> {
+    let kw = parse_keyword(input, "lazy")?;
+    if !input.peek(Token![||]) {
+        return Err(syn::Error::new(
+            kw.span(),
+            "expected a closure without arguments: `lazy || format!(\"user {}\", id)`",
+        ));
+    }
+    let closure: syn::ExprClosure = input.parse()?;
+    Ok(quote! { (#closure)() })
+}
+
+/// Parse `|e| expr`: a closure given the wrapped error by reference.
+fn parse_from_error(input: ParseStream) -> Result<syn::ExprClosure> {
+    let closure: syn::ExprClosure = input.parse()?;
+    if closure.inputs.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            &closure.inputs,
+            "expected a closure taking the error: `with |e| format!(\"after {} frames\", e.depth())`",
+        ));
+    }
+    Ok(closure)
+}
+
+fn parse_comma(input: ParseStream) -> Result<bool> {
+    input.parse::<Token![,]>()?;
+    Ok(true)
+}
+
+/// Parse a severity: a bare variant name (`Warn`) or any expression.
+fn parse_severity(input: ParseStream) -> Result<TokenStream> {
+    let fork = input.fork();
+    if let Ok(level) = fork.parse::<Ident>() {
+        if !is_lowercase_ident(&level) && !fork.peek(Token![::]) && !fork.peek(syn::token::Paren) {
+            input.parse::<Ident>()?;
+            return Ok(quote! { ::handle_this::Severity::#level });
+        }
+    }
+    let expr = Expr::parse_without_eager_brace(input)?;
+    Ok(quote! { #expr })
+}
+
 /// Apply context to a GenContext.
 pub fn apply_to_context(with_clause: &WithClause, ctx: &mut GenContext) {
     if let Some(ref context) = with_clause.context {
-        ctx.ctx_expr = Some(quote! { #context });
+        ctx.ctx_expr = Some(match context {
+            Expr::Closure(from_error) => quote! { ctx_with(#from_error) },
+            context => quote! { ctx(#context) },
+        });
+    }
+    ctx.kv_pairs.extend(with_clause.kv_pairs.iter().cloned());
+    if let Some(ref code) = with_clause.code {
+        ctx.code = Some(quote! { #code });
     }
-    for kv in &with_clause.kv_pairs {
-        let key = &kv.key;
-        let value = &kv.value;
-        ctx.kv_pairs.push((quote! { #key }, quote! { #value }));
+    if let Some(ref severity) = with_clause.severity {
+        ctx.severity = Some(severity.clone());
     }
 }
 
@@ -99,11 +233,19 @@ pub fn gen_ctx_chain(ctx: &GenContext) -> TokenStream {
     let mut chain = TokenStream::new();
 
     if let Some(ref ctx_expr) = ctx.ctx_expr {
-        chain.extend(quote! { .ctx(#ctx_expr) });
+        chain.extend(quote! { .#ctx_expr });
+    }
+
+    for kv in &ctx.kv_pairs {
+        chain.extend(kv.chain_call());
+    }
+
+    if let Some(ref code) = ctx.code {
+        chain.extend(quote! { .with_code(#code) });
     }
 
-    for (key, value) in &ctx.kv_pairs {
-        chain.extend(quote! { .kv(#key, #value) });
+    if let Some(ref severity) = ctx.severity {
+        chain.extend(quote! { .with_severity(#severity) });
     }
 
     chain
@@ -10,16 +10,25 @@ mod keywords;
 mod patterns;
 mod nested;
 mod codegen;
+mod derive;
+mod traced;
+mod context;
 
 /// Single proc macro entry point for all handle! patterns.
 ///
 /// The declarative macro converts pattern keywords to markers:
 /// - `try { }` -> `SYNC { }`
 /// - `async try { }` -> `ASYNC { }`
+/// - `async try concurrent(N) ...` -> `CONCURRENT concurrent(N) ...`
+/// - `async try stream x in s { }` -> `STREAM x in s { }`
+/// - `async try join { ... }` -> `JOIN join { ... }`
+/// - `async try race { ... }` -> `RACE race { ... }`
+/// - `async try while` -> `ASYNC_WHILE`
 /// - `try for` -> `FOR`
 /// - `try any` -> `ANY`
 /// - `try all` -> `ALL`
 /// - `try while` -> `WHILE`
+/// - `try<E> { }` -> `TYPED E> { }`
 #[proc_macro]
 pub fn __handle_proc(input: TokenStream) -> TokenStream {
     router::route(input.into())
@@ -81,6 +90,60 @@ pub fn __async_try_proc(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Direct entry point for concurrent async iteration (async try concurrent(N) for/any/all ...).
+#[proc_macro]
+pub fn __async_concurrent_proc(input: TokenStream) -> TokenStream {
+    patterns::r#try::concurrent::process(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive `From<T> for Handled` and `IntoValue` for a domain error type.
+///
+/// Re-exported as `handle_this::HandleThis`; see there for details.
+#[proc_macro_derive(HandleThis, attributes(handle_this))]
+pub fn derive_handle_this(input: TokenStream) -> TokenStream {
+    derive::expand(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Record the function's `module::function` path in frames pushed while it runs.
+///
+/// Re-exported as `handle_this::traced`; see there for details.
+#[proc_macro_attribute]
+pub fn traced(attr: TokenStream, item: TokenStream) -> TokenStream {
+    traced::expand(attr.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Add `with` context to every error leaving the function.
+///
+/// Re-exported as `handle_this::context`; see there for details.
+#[proc_macro_attribute]
+pub fn context(attr: TokenStream, item: TokenStream) -> TokenStream {
+    context::expand(attr.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Direct entry point for concurrent async join (async try join { a: fut_a, b: fut_b }).
+#[proc_macro]
+pub fn __async_join_proc(input: TokenStream) -> TokenStream {
+    patterns::r#try::join::process(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Direct entry point for async race (async try race { primary(), fallback() }).
+#[proc_macro]
+pub fn __async_race_proc(input: TokenStream) -> TokenStream {
+    patterns::r#try::race::process(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 // ============================================================
 // Then chain routing helpers
 // These detect whether `then` appears and route to then_chain or regular pattern
@@ -64,15 +64,11 @@ fn contains_question_mark_impl(tokens: &TokenStream, in_nested_try: bool) -> boo
         }
 
         match token {
-            TokenTree::Punct(p) if p.as_char() == '?' => {
-                if !in_nested_try {
-                    return true;
-                }
+            TokenTree::Punct(p) if p.as_char() == '?' && !in_nested_try => {
+                return true;
             }
-            TokenTree::Group(g) => {
-                if contains_question_mark_impl(&g.stream(), in_nested_try) {
-                    return true;
-                }
+            TokenTree::Group(g) if contains_question_mark_impl(&g.stream(), in_nested_try) => {
+                return true;
             }
             _ => {}
         }
@@ -334,10 +330,8 @@ pub fn contains_control_flow(tokens: &TokenStream) -> bool {
                     return true;
                 }
             }
-            TokenTree::Group(g) => {
-                if contains_control_flow(&g.stream()) {
-                    return true;
-                }
+            TokenTree::Group(g) if contains_control_flow(&g.stream()) => {
+                return true;
             }
             _ => {}
         }
@@ -24,5 +24,5 @@ mod detection;
 mod transform;
 
 // Re-export public API
-pub use detection::{contains_control_flow, contains_question_mark};
+pub use detection::{contains_control_flow, contains_question_mark, skip_nested_try_pattern};
 pub use transform::transform_nested;
@@ -7,6 +7,7 @@ use proc_macro2::{TokenStream, TokenTree, Delimiter};
 use quote::{quote, quote_spanned};
 
 use super::detection::{contains_control_flow, contains_question_mark};
+use crate::keywords::{CLAUSE_KEYWORDS, HANDLER_KEYWORDS};
 
 /// Recursively transform any nested handle! patterns in a token stream.
 pub fn transform_nested(tokens: TokenStream) -> TokenStream {
@@ -39,6 +40,13 @@ fn transform_tokens(tokens: &[TokenTree]) -> TokenStream {
             continue;
         }
 
+        // `defer { }` expands to a statement, so it can't go in a group
+        if let Some((stmt, consumed)) = try_transform_defer(&tokens[i..]) {
+            result.extend(stmt);
+            i += consumed;
+            continue;
+        }
+
         // Check if we're at the start of a nested pattern
         if let Some((transformed, consumed)) = try_transform_pattern(&tokens[i..]) {
             result.push(TokenTree::Group(proc_macro2::Group::new(
@@ -115,6 +123,35 @@ fn try_transform_pattern(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     }
 }
 
+/// Transform `defer { cleanup }[;]` into a drop guard.
+///
+/// Guards drop in reverse order when the enclosing block exits, whether it
+/// finishes or returns early through `?`. The closure borrows what the
+/// block uses, and its value is discarded.
+fn try_transform_defer(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
+    let kw = match tokens.first() {
+        Some(TokenTree::Ident(ident)) if *ident == "defer" => ident,
+        _ => return None,
+    };
+    let block = match tokens.get(1) {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => g,
+        _ => return None,
+    };
+    let consumed = match tokens.get(2) {
+        Some(TokenTree::Punct(p)) if p.as_char() == ';' => 3,
+        _ => 2,
+    };
+
+    let body = transform_nested(block.stream());
+    let stmt = quote_spanned! {kw.span()=>
+        let __handle_defer = ::handle_this::__FinallyGuard::new(|| {
+            #[allow(clippy::let_unit_value)]
+            let _ = { #body };
+        });
+    };
+    Some((stmt, consumed))
+}
+
 /// Transform a `try` pattern - dispatches to specific pattern handlers
 fn try_transform_try(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     if tokens.len() < 2 {
@@ -163,18 +200,39 @@ fn try_transform_async_try(tokens: &[TokenTree]) -> Option<(TokenStream, usize)>
         return None;
     }
 
-    let try_body = match &tokens[2] {
-        TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+    if matches!(&tokens[2], TokenTree::Ident(id) if *id == "concurrent") {
+        return try_transform_async_head(tokens, quote! { __async_concurrent_proc });
+    }
+    if matches!(&tokens[2], TokenTree::Ident(id) if *id == "join") {
+        return try_transform_async_head(tokens, quote! { __async_join_proc });
+    }
+    if matches!(&tokens[2], TokenTree::Ident(id) if *id == "race") {
+        return try_transform_async_head(tokens, quote! { __async_race_proc });
+    }
+
+    // `timeout DURATION` prefix: keep the tokens up to the body
+    let mut i = 2;
+    let mut prefix = Vec::new();
+    if matches!(&tokens[2], TokenTree::Ident(id) if *id == "timeout") {
+        while i < tokens.len() && !matches!(&tokens[i], TokenTree::Group(g) if g.delimiter() == Delimiter::Brace) {
+            prefix.push(tokens[i].clone());
+            i += 1;
+        }
+    }
+    let prefix: TokenStream = prefix.into_iter().collect();
+
+    let try_body = match tokens.get(i) {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
             transform_nested(g.stream())
         }
         _ => return None,
     };
 
-    let mut i = 3;
+    i += 1;
     let (handler_tokens, _, _, consumed) = collect_handlers(&tokens[i..]);
     i += consumed;
 
-    let transformed = if handler_tokens.is_empty() {
+    let transformed = if handler_tokens.is_empty() && prefix.is_empty() {
         // No handlers - inline the async try expansion to preserve type inference
         // Use or_else instead of map_err to avoid type inference issues
         quote! {
@@ -186,17 +244,65 @@ fn try_transform_async_try(tokens: &[TokenTree]) -> Option<(TokenStream, usize)>
                     ))?
             }
         }
+    } else if ends_with_value_marker(&handler_tokens) {
+        // `to_option` / `unwrap_infallible` don't yield a `Result` - nothing to propagate
+        let handler_stream: TokenStream = handler_tokens.into_iter().collect();
+        quote! {
+            ::handle_this::handle_this_macros::__async_try_proc!(#prefix { #try_body } #handler_stream)
+        }
     } else {
         let handler_stream: TokenStream = handler_tokens.into_iter().collect();
         // Use ? to propagate - allows catch bodies with ? to propagate new errors
         quote! {
-            ::handle_this::handle_this_macros::__async_try_proc!({ #try_body } #handler_stream)?
+            ::handle_this::handle_this_macros::__async_try_proc!(#prefix { #try_body } #handler_stream)?
         }
     };
 
     Some((transformed, i))
 }
 
+/// Transform `async try concurrent(N) for/any/all PAT in ITER { body } ...`
+/// and `async try join/race { entries } ...` via the given direct entry point.
+fn try_transform_async_head(tokens: &[TokenTree], proc: TokenStream) -> Option<(TokenStream, usize)> {
+    // tokens[0] = "async", tokens[1] = "try", tokens[2] = "concurrent"/"join"/"race"
+    let mut i = 2;
+    let mut head = Vec::new();
+    while i < tokens.len() {
+        if let TokenTree::Group(g) = &tokens[i] {
+            if g.delimiter() == Delimiter::Brace {
+                break;
+            }
+        }
+        head.push(tokens[i].clone());
+        i += 1;
+    }
+
+    let body = match tokens.get(i) {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => transform_nested(g.stream()),
+        _ => return None,
+    };
+    i += 1;
+
+    let (handler_tokens, has_catch_all, _, consumed) = collect_handlers(&tokens[i..]);
+    i += consumed;
+    let head: TokenStream = head.into_iter().collect();
+    let handlers: TokenStream = handler_tokens.into_iter().collect();
+
+    let transformed = if has_catch_all {
+        quote! { ::handle_this::handle_this_macros::#proc!(#head { #body } #handlers).unwrap() }
+    } else {
+        quote! { ::handle_this::handle_this_macros::#proc!(#head { #body } #handlers)? }
+    };
+
+    Some((transformed, i))
+}
+
+/// Whether collected handlers end with a marker that yields a plain value
+/// (`to_option` or `unwrap_infallible`) rather than a `Result`.
+fn ends_with_value_marker(handler_tokens: &[TokenTree]) -> bool {
+    matches!(handler_tokens.last(), Some(TokenTree::Ident(id)) if id == "to_option" || id == "unwrap_infallible")
+}
+
 /// Transform `try -> Type { } catch/throw/inspect/finally/else ...`
 /// Handles explicit return type annotation which forces direct mode.
 fn try_transform_try_block_with_type(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
@@ -285,6 +391,12 @@ fn try_transform_try_block(tokens: &[TokenTree]) -> Option<(TokenStream, usize)>
                 ::handle_this::handle_this_macros::__sync_try_proc!({ #try_body })?
             }
         }
+    } else if ends_with_value_marker(&handler_tokens) {
+        // `to_option` / `unwrap_infallible` don't yield a `Result` - nothing to propagate or unwrap
+        let handler_stream: TokenStream = handler_tokens.into_iter().collect();
+        quote_spanned! {try_span=>
+            ::handle_this::handle_this_macros::__sync_try_proc!({ #try_body } #handler_stream)
+        }
     } else {
         let handler_stream: TokenStream = handler_tokens.into_iter().collect();
         // Control flow can come from either:
@@ -495,7 +607,13 @@ fn try_transform_try_all(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
 /// Check if an identifier is a handler keyword that ends the current handler.
 #[inline]
 fn is_handler_keyword(s: &str) -> bool {
-    matches!(s, "catch" | "throw" | "inspect" | "finally" | "with" | "try" | "else")
+    HANDLER_KEYWORDS.contains(&s)
+}
+
+/// Check if an identifier starts any handler, including sugar clauses.
+#[inline]
+fn starts_handler(s: &str) -> bool {
+    is_handler_keyword(s) || CLAUSE_KEYWORDS.contains(&s)
 }
 
 /// Collect tokens for a handler until hitting the body brace group.
@@ -546,7 +664,35 @@ fn analyze_handler_body(tokens: &[TokenTree]) -> (bool, bool, bool) {
     (false, false, false)
 }
 
-/// Collect handler tokens (catch/throw/inspect/finally/with) and detect catch-all
+/// Check whether a `branch { }` block has a catch-all arm (`_ =>` or `e =>`).
+/// Arms start the block or follow a top-level comma.
+fn branch_has_catch_all(stream: &TokenStream) -> bool {
+    let tokens: Vec<TokenTree> = stream.clone().into_iter().collect();
+    let mut at_arm_start = true;
+    for (idx, token) in tokens.iter().enumerate() {
+        if at_arm_start {
+            let is_pattern = match token {
+                TokenTree::Ident(ident) => {
+                    let s = ident.to_string();
+                    s == "_" || s.starts_with(|c: char| c.is_lowercase() || c == '_')
+                }
+                _ => false,
+            };
+            let arrow_follows = matches!(
+                (tokens.get(idx + 1), tokens.get(idx + 2)),
+                (Some(TokenTree::Punct(a)), Some(TokenTree::Punct(b)))
+                    if a.as_char() == '=' && b.as_char() == '>'
+            );
+            if is_pattern && arrow_follows {
+                return true;
+            }
+        }
+        at_arm_start = matches!(token, TokenTree::Punct(p) if p.as_char() == ',');
+    }
+    false
+}
+
+/// Collect handler tokens (catch/throw/inspect/continue_with/finally/with) and detect catch-all
 /// Returns (handler_tokens, has_catch_all, has_control_flow_catch, tokens_consumed)
 fn collect_handlers(tokens: &[TokenTree]) -> (Vec<TokenTree>, bool, bool, usize) {
     let mut handler_tokens = Vec::new();
@@ -617,6 +763,25 @@ fn collect_handlers(tokens: &[TokenTree]) -> (Vec<TokenTree>, bool, bool, usize)
                 "throw" => {
                     handler_tokens.push(tokens[i].clone());
                     i += 1;
+                    // `throw as Enum::Variant` may have no brace body; stop at `;` too
+                    if matches!(tokens.get(i), Some(TokenTree::Ident(id)) if id == "as") {
+                        while i < tokens.len() {
+                            match &tokens[i] {
+                                TokenTree::Ident(next) if is_handler_keyword(&next.to_string()) => break,
+                                TokenTree::Punct(p) if p.as_char() == ';' => break,
+                                TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+                                    handler_tokens.push(tokens[i].clone());
+                                    i += 1;
+                                    break;
+                                }
+                                _ => {
+                                    handler_tokens.push(tokens[i].clone());
+                                    i += 1;
+                                }
+                            }
+                        }
+                        continue;
+                    }
                     // Check throw body for control flow
                     let (has_cf, _, _) = analyze_handler_body(&tokens[i..]);
                     if has_cf {
@@ -624,7 +789,115 @@ fn collect_handlers(tokens: &[TokenTree]) -> (Vec<TokenTree>, bool, bool, usize)
                     }
                     i += collect_handler_body(tokens, i, &mut handler_tokens);
                 }
-                "inspect" | "finally" => {
+                // `exit(code)` / `exit code` - catch-all that never returns; no brace body
+                "exit" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    has_catch_all = true;
+                    if i < tokens.len() {
+                        handler_tokens.push(tokens[i].clone());
+                        i += 1;
+                    }
+                }
+                // `branch { Type(e) => ..., _ => ... }` - single brace group of arms
+                "branch" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    if let Some(TokenTree::Group(g)) = tokens.get(i) {
+                        if contains_control_flow(&g.stream()) {
+                            has_control_flow_catch = true;
+                        }
+                        if branch_has_catch_all(&g.stream()) {
+                            has_catch_all = true;
+                        }
+                        handler_tokens.push(tokens[i].clone());
+                        i += 1;
+                    }
+                }
+                // `group "name"` / `metric_inc "name"` - single-token argument, no brace body
+                "group" | "metric_inc" | "with_correlation" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    if i < tokens.len() {
+                        handler_tokens.push(tokens[i].clone());
+                        i += 1;
+                    }
+                }
+                // `throttle key, N/unit` and `convert Type` run up to the next handler
+                "throttle" | "convert" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    while i < tokens.len() {
+                        if let TokenTree::Ident(next) = &tokens[i] {
+                            if starts_handler(&next.to_string()) {
+                                break;
+                            }
+                        }
+                        handler_tokens.push(tokens[i].clone());
+                        i += 1;
+                    }
+                }
+                // `ratelimit_propagate N/unit else { fallback }` - the `else` belongs to it
+                "ratelimit_propagate" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    while i < tokens.len() {
+                        let is_else = matches!(&tokens[i], TokenTree::Ident(id) if id == "else");
+                        handler_tokens.push(tokens[i].clone());
+                        i += 1;
+                        if is_else {
+                            if i < tokens.len() {
+                                handler_tokens.push(tokens[i].clone());
+                                i += 1;
+                            }
+                            break;
+                        }
+                    }
+                }
+                // `partial |oks, errs| { body }` - runs to its brace body
+                "partial" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    i += collect_handler_body(tokens, i, &mut handler_tokens);
+                }
+                // `log_once` alone, or `log_once |e| { body }`
+                "log_once" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    if matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == '|') {
+                        i += collect_handler_body(tokens, i, &mut handler_tokens);
+                    }
+                }
+                // `assert_no_panic` and `escalate` turn the preceding catch into a
+                // `try catch`, so it no longer guarantees a value
+                "assert_no_panic" | "escalate" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    has_catch_all = false;
+                }
+                // `cancel_safe` / `to_option` / `unwrap_infallible` / `trace_error` - bare keywords
+                "cancel_safe" | "to_option" | "unwrap_infallible" | "trace_error" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                }
+                // `continue_with` always continues the enclosing loop (signal mode)
+                "continue_with" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    has_control_flow_catch = true;
+                    i += collect_handler_body(tokens, i, &mut handler_tokens);
+                }
+                // `report_and_continue` alone, or with `|e| { body }`
+                "report_and_continue" => {
+                    handler_tokens.push(tokens[i].clone());
+                    i += 1;
+                    has_control_flow_catch = true;
+                    if matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == '|') {
+                        i += collect_handler_body(tokens, i, &mut handler_tokens);
+                    }
+                }
+                "inspect" | "only_in_tests" | "classify" | "snapshot" | "on_ok" | "on_err"
+                | "finally" | "catch_finally_with" => {
                     handler_tokens.push(tokens[i].clone());
                     i += 1;
                     i += collect_handler_body(tokens, i, &mut handler_tokens);
@@ -657,7 +930,7 @@ fn collect_handlers(tokens: &[TokenTree]) -> (Vec<TokenTree>, bool, bool, usize)
                     // `with` doesn't have a brace body - collect until handler keyword or semicolon
                     while i < tokens.len() {
                         if let TokenTree::Ident(next) = &tokens[i] {
-                            if is_handler_keyword(&next.to_string()) {
+                            if starts_handler(&next.to_string()) {
                                 break;
                             }
                         }
@@ -709,6 +982,7 @@ fn collect_handlers(tokens: &[TokenTree]) -> (Vec<TokenTree>, bool, bool, usize)
 /// Transform `scope "name", rest...` or `scope "name", { kv }, rest...` pattern
 fn try_transform_scope(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     // tokens[0] = "scope", tokens[1] = string literal, tokens[2] = comma, tokens[3..] = rest or { kv }
+    // (with `lazy`, the closure tokens sit between `lazy` and the comma)
     if tokens.len() < 4 {
         return None;
     }
@@ -716,11 +990,22 @@ fn try_transform_scope(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     // Get span from the scope keyword for accurate line/column
     let scope_span = tokens[0].span();
 
-    // tokens[1] should be the scope name (string literal)
-    let name = tokens[1].clone();
+    // tokens[1] should be the scope name (string literal), or `lazy || expr`
+    // running up to the comma
+    let (name, comma) = match &tokens[1] {
+        TokenTree::Ident(id) if id == "lazy" => {
+            let end = tokens[2..]
+                .iter()
+                .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ','))?
+                + 2;
+            let closure: TokenStream = tokens[2..end].iter().cloned().collect();
+            (quote! { (#closure)() }, end)
+        }
+        name => (quote! { #name }, 2),
+    };
 
-    // tokens[2] should be a comma
-    if let TokenTree::Punct(p) = &tokens[2] {
+    // then a comma
+    if let Some(TokenTree::Punct(p)) = tokens.get(comma) {
         if p.as_char() != ',' {
             return None;
         }
@@ -728,20 +1013,20 @@ fn try_transform_scope(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
         return None;
     }
 
-    let mut i = 3;
+    let mut i = comma + 1;
     let mut kv_chain = TokenStream::new();
 
-    // Check if tokens[3] is a brace group (kv data)
-    if let Some(TokenTree::Group(g)) = tokens.get(3) {
+    // Check if the next token is a brace group (kv data)
+    if let Some(TokenTree::Group(g)) = tokens.get(i) {
         if g.delimiter() == Delimiter::Brace {
             // Parse kv pairs from the braces
             kv_chain = parse_kv_chain(g.stream());
-            i = 4;
+            i += 1;
 
             // Skip optional comma after kv braces
-            if let Some(TokenTree::Punct(p)) = tokens.get(4) {
+            if let Some(TokenTree::Punct(p)) = tokens.get(i) {
                 if p.as_char() == ',' {
-                    i = 5;
+                    i += 1;
                 }
             }
         }
@@ -793,7 +1078,7 @@ fn try_transform_scope(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
             match __scope_result {
                 ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
                 ::core::result::Result::Err(__e) => ::core::result::Result::Err(
-                    __e.frame(file!(), #line_call, #col_call).ctx(#name) #kv_chain
+                    __e.scope(file!(), #line_call, #col_call, #name) #kv_chain
                 ),
             }
         }?
@@ -802,25 +1087,43 @@ fn try_transform_scope(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     Some((transformed, i + rest_consumed))
 }
 
-/// Parse kv pairs from a brace group and generate `.kv(...)` chain
+/// Parse kv pairs from a brace group and generate `.kv(...)` chain.
+/// Accepts `ident: expr` (static key) and `key_expr => expr` (routes to `kv_owned`).
 fn parse_kv_chain(tokens: TokenStream) -> TokenStream {
     let tokens_vec: Vec<TokenTree> = tokens.into_iter().collect();
     let mut chain = TokenStream::new();
     let mut i = 0;
 
     while i < tokens_vec.len() {
-        // Expect: ident : expr [, ...]
-        let key = match &tokens_vec.get(i) {
-            Some(TokenTree::Ident(id)) => id.clone(),
-            _ => break,
+        // Expect: ident : expr  or  key => expr
+        let ident_key = match (tokens_vec.get(i), tokens_vec.get(i + 1)) {
+            (Some(TokenTree::Ident(id)), Some(TokenTree::Punct(p))) if p.as_char() == ':' => {
+                i += 2;
+                Some(id.clone())
+            }
+            _ => None,
         };
-        i += 1;
 
-        // Expect colon
-        match tokens_vec.get(i) {
-            Some(TokenTree::Punct(p)) if p.as_char() == ':' => i += 1,
-            _ => break,
-        }
+        let owned_key = if ident_key.is_none() {
+            // Collect key tokens until `=>`
+            let mut key_tokens = Vec::new();
+            while i < tokens_vec.len() {
+                if let (TokenTree::Punct(p1), Some(TokenTree::Punct(p2))) = (&tokens_vec[i], tokens_vec.get(i + 1)) {
+                    if p1.as_char() == '=' && p2.as_char() == '>' {
+                        break;
+                    }
+                }
+                key_tokens.push(tokens_vec[i].clone());
+                i += 1;
+            }
+            if key_tokens.is_empty() || i >= tokens_vec.len() {
+                break;
+            }
+            i += 2; // Skip `=>`
+            Some(key_tokens.into_iter().collect::<TokenStream>())
+        } else {
+            None
+        };
 
         // Collect value tokens until comma or end
         let mut value_tokens = Vec::new();
@@ -836,13 +1139,17 @@ fn parse_kv_chain(tokens: TokenStream) -> TokenStream {
         }
         let value: TokenStream = value_tokens.into_iter().collect();
 
-        chain.extend(quote! { .kv(stringify!(#key), #value) });
+        match (ident_key, owned_key) {
+            (Some(key), _) => chain.extend(quote! { .kv(stringify!(#key), #value) }),
+            (None, Some(key)) => chain.extend(quote! { .kv_owned(#key, #value) }),
+            (None, None) => break,
+        }
     }
 
     chain
 }
 
-/// Transform `require COND else "msg", rest...` pattern
+/// Transform `require COND else "msg" [with ctx], rest...` pattern
 fn try_transform_require(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     // tokens[0] = "require", then COND tokens, then "else", then msg, then ",", then rest
     if tokens.len() < 5 {
@@ -866,7 +1173,7 @@ fn try_transform_require(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     for (idx, token) in tokens[else_idx + 1..].iter().enumerate() {
         if let TokenTree::Punct(p) = token {
             if p.as_char() == ',' {
-                comma_idx = Some(else_idx + 1 + idx + 1); // adjusted for offset
+                comma_idx = Some(else_idx + 1 + idx);
                 break;
             }
         }
@@ -876,8 +1183,12 @@ fn try_transform_require(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
     // Collect condition tokens (between "require" and "else")
     let condition: TokenStream = tokens[1..else_idx].iter().cloned().collect();
 
-    // Collect message tokens (between "else" and comma)
-    let message: TokenStream = tokens[else_idx + 1..comma_idx].iter().cloned().collect();
+    // Message or error, with optional context (between "else" and comma)
+    let failure: TokenStream = tokens[else_idx + 1..comma_idx].iter().cloned().collect();
+    let error_expr = match crate::patterns::require::failure_expr(failure) {
+        Ok(expr) => expr,
+        Err(e) => return Some((e.to_compile_error(), tokens.len())),
+    };
 
     // Transform nested patterns in the rest (after the comma)
     let rest: TokenStream = tokens[comma_idx + 1..].iter().cloned().collect();
@@ -885,13 +1196,26 @@ fn try_transform_require(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
 
     // Generate require code directly (like patterns/require.rs does)
     // Wrap in block for #[allow] since attributes on if expressions aren't stable
+    if crate::patterns::require::is_let_condition(&condition) {
+        // `require let PAT = EXPR` - bindings must stay in scope for the rest
+        let transformed = quote! {
+            {
+                #[allow(unreachable_code, unused_braces)]
+                if #condition {
+                    #transformed_rest
+                } else {
+                    ::core::result::Result::Err(#error_expr)?
+                }
+            }
+        };
+        return Some((transformed, tokens.len()));
+    }
+
     let transformed = quote! {
         {
-            #[allow(unreachable_code)]
+            #[allow(unreachable_code, unused_braces)]
             if !(#condition) {
-                ::core::result::Result::Err(
-                    ::handle_this::Handled::msg(#message).frame(file!(), line!(), column!())
-                )?
+                ::core::result::Result::Err(#error_expr)?
             } else {
                 #transformed_rest
             }
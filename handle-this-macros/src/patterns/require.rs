@@ -1,16 +1,21 @@
 //! Require pattern: `require COND else "msg", rest...`
 //!
 //! Precondition checks that return early with error if condition fails.
+//! After `else` comes a message (`"msg"` or `{ expr }`) or any error value,
+//! converted the way `throw` converts its body: `else ConfigError::EmptyPath`.
+//!
+//! `require let PAT = EXPR else "msg", rest...` destructures instead of
+//! checking a boolean; bindings from `PAT` are visible in `rest`.
 
 use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
 use syn::ext::IdentExt;
-use syn::{Result, Error, Ident, braced, token, Token, LitStr};
+use syn::{Result, Error, Ident, Token, LitStr};
 
 /// Parsed require input
 struct RequireInput {
-    /// The condition expression
+    /// The condition expression, or `let PAT = EXPR`
     condition: TokenStream,
     /// The error message (literal or expression)
     message: MessageKind,
@@ -21,8 +26,90 @@ struct RequireInput {
 }
 
 enum MessageKind {
+    /// `else "message"`
     Literal(LitStr),
+    /// `else { expr }` - a message computed on failure
     Expr(TokenStream),
+    /// `else expr` - an error value, or a message, as `throw` takes it
+    Error(TokenStream),
+}
+
+/// Whether the input is at a top-level `with`.
+fn peek_with(input: ParseStream) -> bool {
+    let fork = input.fork();
+    matches!(Ident::parse_any(&fork), Ok(ident) if ident == "with")
+}
+
+/// Parse what follows `else`: the message or error, then an optional
+/// `with context`, up to the `,` before the rest.
+fn parse_failure(input: ParseStream) -> Result<(MessageKind, Option<TokenStream>)> {
+    let mut msg_tokens = Vec::new();
+    while !input.is_empty() && !input.peek(Token![,]) && !peek_with(input) {
+        let tt: TokenTree = input.parse()?;
+        msg_tokens.push(tt);
+    }
+
+    let message = match msg_tokens.as_slice() {
+        [] => {
+            return Err(Error::new(
+                input.span(),
+                "expected a message, `{ expression }`, or an error after 'else'",
+            ));
+        }
+        [TokenTree::Group(g)] if g.delimiter() == proc_macro2::Delimiter::Brace => {
+            MessageKind::Expr(g.stream())
+        }
+        [lit @ TokenTree::Literal(_)] => match syn::parse2::<LitStr>(lit.clone().into()) {
+            Ok(lit) => MessageKind::Literal(lit),
+            Err(_) => MessageKind::Error(msg_tokens.into_iter().collect()),
+        },
+        _ => MessageKind::Error(msg_tokens.into_iter().collect()),
+    };
+
+    // Check for optional `with context`
+    let mut context = None;
+    if peek_with(input) {
+        // Consume `with`
+        let _ = Ident::parse_any(input)?;
+        // Collect context expression until `,`
+        let mut ctx_tokens = Vec::new();
+        while !input.is_empty() && !input.peek(Token![,]) {
+            let tt: TokenTree = input.parse()?;
+            ctx_tokens.push(tt);
+        }
+        if ctx_tokens.is_empty() {
+            return Err(Error::new(input.span(), "expected context expression after 'with'"));
+        }
+        context = Some(ctx_tokens.into_iter().collect());
+    }
+
+    Ok((message, context))
+}
+
+/// Build the error for a failed check from the tokens after `else`
+/// (for the nested transform, which has already split off the rest).
+pub(crate) fn failure_expr(tokens: TokenStream) -> Result<TokenStream> {
+    let (message, context) = syn::parse::Parser::parse2(parse_failure, tokens)?;
+    Ok(error_expr(&message, &context))
+}
+
+/// The `Handled` a failed check returns, framed at the `require`.
+fn error_expr(message: &MessageKind, context: &Option<TokenStream>) -> TokenStream {
+    let err = match message {
+        MessageKind::Literal(lit) => quote! { ::handle_this::Handled::msg(#lit) },
+        MessageKind::Expr(expr) => quote! { ::handle_this::Handled::msg(#expr) },
+        MessageKind::Error(expr) => quote! {
+            {
+                #[allow(unused_imports)]
+                use ::handle_this::__Thrown;
+                ::handle_this::__ThrowExpr(#expr).__thrown()
+            }
+        },
+    };
+    match context {
+        Some(ctx) => quote! { #err.frame(file!(), line!(), column!()).ctx(#ctx) },
+        None => quote! { #err.frame(file!(), line!(), column!()) },
+    }
 }
 
 impl Parse for RequireInput {
@@ -76,39 +163,7 @@ impl Parse for RequireInput {
             return Err(Error::new(else_kw.span(), format!("expected 'else', found '{}'", else_kw)));
         }
 
-        // Parse message: either a string literal or { expr }
-        let message = if input.peek(token::Brace) {
-            let content;
-            braced!(content in input);
-            let expr: TokenStream = content.parse()?;
-            MessageKind::Expr(expr)
-        } else if input.peek(LitStr) {
-            let lit: LitStr = input.parse()?;
-            MessageKind::Literal(lit)
-        } else {
-            return Err(Error::new(input.span(), "expected string literal or { expression } after 'else'"));
-        };
-
-        // Check for optional `with context`
-        let mut context = None;
-        if input.peek(Ident::peek_any) {
-            let fork = input.fork();
-            let ident = Ident::parse_any(&fork)?;
-            if ident == "with" {
-                // Consume `with`
-                let _ = Ident::parse_any(input)?;
-                // Collect context expression until `,`
-                let mut ctx_tokens = Vec::new();
-                while !input.is_empty() && !input.peek(Token![,]) {
-                    let tt: TokenTree = input.parse()?;
-                    ctx_tokens.push(tt);
-                }
-                if ctx_tokens.is_empty() {
-                    return Err(Error::new(input.span(), "expected context expression after 'with'"));
-                }
-                context = Some(ctx_tokens.into_iter().collect());
-            }
-        }
+        let (message, context) = parse_failure(input)?;
 
         // Expect comma separator
         if !input.peek(Token![,]) {
@@ -139,7 +194,7 @@ fn is_direct_mode(rest: &TokenStream) -> Option<proc_macro2::Span> {
 
     // Look for `try` keyword
     let try_span = match iter.next() {
-        Some(TokenTree::Ident(id)) if id.to_string() == "try" => id.span(),
+        Some(TokenTree::Ident(id)) if id == "try" => id.span(),
         _ => return None,
     };
 
@@ -173,35 +228,33 @@ pub fn process(input: TokenStream) -> Result<TokenStream> {
     Ok(generate(parsed))
 }
 
+/// Check if a require condition is a `let PAT = EXPR` binding.
+pub(crate) fn is_let_condition(condition: &TokenStream) -> bool {
+    matches!(condition.clone().into_iter().next(), Some(TokenTree::Ident(id)) if id == "let")
+}
+
 /// Generate code for require.
 fn generate(input: RequireInput) -> TokenStream {
     let condition = &input.condition;
     let rest = &input.rest;
 
-    let error_expr = match (&input.message, &input.context) {
-        (MessageKind::Literal(lit), None) => {
-            quote! {
-                ::handle_this::Handled::msg(#lit).frame(file!(), line!(), column!())
-            }
-        }
-        (MessageKind::Literal(lit), Some(ctx)) => {
-            quote! {
-                ::handle_this::Handled::msg(#lit).frame(file!(), line!(), column!()).ctx(#ctx)
-            }
-        }
-        (MessageKind::Expr(expr), None) => {
-            quote! {
-                ::handle_this::Handled::msg(#expr).frame(file!(), line!(), column!())
-            }
-        }
-        (MessageKind::Expr(expr), Some(ctx)) => {
-            quote! {
-                ::handle_this::Handled::msg(#expr).frame(file!(), line!(), column!()).ctx(#ctx)
-            }
-        }
-    };
+    let error_expr = error_expr(&input.message, &input.context);
 
     // Wrap in block for #[allow] since attributes on if expressions aren't stable
+    if is_let_condition(condition) {
+        // `if let` keeps the pattern's bindings in scope for the rest
+        return quote! {
+            {
+                #[allow(unreachable_code)]
+                if #condition {
+                    ::handle_this::handle!(#rest)
+                } else {
+                    ::core::result::Result::Err(#error_expr)
+                }
+            }
+        };
+    }
+
     quote! {
         {
             #[allow(unreachable_code)]
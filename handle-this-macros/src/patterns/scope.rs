@@ -5,58 +5,41 @@
 //! Supports:
 //! - `scope "name", try { ... }` - just scope name
 //! - `scope "name", { key: value }, try { ... }` - scope with structured data
+//! - `scope "name", { "x-key" => value }, try { ... }` - arbitrary key expression
+//! - `scope lazy || format!("user {}", id), try { ... }` - name built only on error
 
 use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{Result, Error, Expr, Ident, LitStr, Token, braced};
+use syn::{Result, Error, LitStr, Token};
 
-/// A key-value pair for structured scope context.
-#[derive(Clone)]
-struct KvPair {
-    key: Ident,
-    value: Expr,
-}
+use crate::keywords::peek_keyword;
+use crate::keywords::with_ctx::{parse_kv_braced, parse_lazy, KvPair};
 
 /// Parsed scope input
 struct ScopeInput {
-    /// The scope name (context message)
-    name: LitStr,
+    /// The scope name (context message): a literal or a lazy closure call
+    name: TokenStream,
     /// Optional key-value pairs (inside braces)
     kv_pairs: Vec<KvPair>,
     /// The rest of the tokens to pass to handle!
     rest: TokenStream,
 }
 
-/// Parse key-value pairs from inside braces: { key: value, key2: value2 }
-fn parse_kv_braced(input: ParseStream) -> Result<Vec<KvPair>> {
-    let content;
-    braced!(content in input);
-
-    let mut pairs = Vec::new();
-    while !content.is_empty() {
-        let key: Ident = content.parse()?;
-        content.parse::<Token![:]>()?;
-        let value: Expr = content.parse()?;
-        pairs.push(KvPair { key, value });
-
-        if content.peek(Token![,]) {
-            content.parse::<Token![,]>()?;
-        } else {
-            break;
-        }
-    }
-    Ok(pairs)
-}
-
 impl Parse for ScopeInput {
     fn parse(input: ParseStream) -> Result<Self> {
-        // Expect string literal for scope name
-        let name: LitStr = input.parse()?;
+        // Expect string literal or `lazy || expr` for scope name
+        let name_span = input.span();
+        let name = if peek_keyword(input, "lazy") {
+            parse_lazy(input)?
+        } else {
+            let name: LitStr = input.parse()?;
+            quote! { #name }
+        };
 
         // Expect comma separator
         if !input.peek(Token![,]) {
-            return Err(Error::new(name.span(), "expected ',' after scope name: `scope \"name\", try { ... }`"));
+            return Err(Error::new(name_span, "expected ',' after scope name: `scope \"name\", try { ... }`"));
         }
         input.parse::<Token![,]>()?;
 
@@ -90,7 +73,7 @@ fn is_direct_mode(rest: &TokenStream) -> Option<proc_macro2::Span> {
 
     // Look for `try` keyword
     let try_span = match iter.next() {
-        Some(TokenTree::Ident(id)) if id.to_string() == "try" => id.span(),
+        Some(TokenTree::Ident(id)) if id == "try" => id.span(),
         _ => return None,
     };
 
@@ -130,11 +113,7 @@ fn generate(input: ScopeInput) -> TokenStream {
     let rest = &input.rest;
 
     // Build kv chain
-    let kv_chain: TokenStream = input.kv_pairs.iter().map(|kv| {
-        let key = &kv.key;
-        let value = &kv.value;
-        quote! { .kv(stringify!(#key), #value) }
-    }).collect();
+    let kv_chain: TokenStream = input.kv_pairs.iter().map(KvPair::chain_call).collect();
 
     // Scope wraps the inner result, adding context on error
     quote! {
@@ -143,7 +122,7 @@ fn generate(input: ScopeInput) -> TokenStream {
             match __scope_result {
                 ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
                 ::core::result::Result::Err(__e) => ::core::result::Result::Err(
-                    __e.frame(file!(), line!(), column!()).ctx(#name) #kv_chain
+                    __e.scope(file!(), line!(), column!(), #name) #kv_chain
                 ),
             }
         }
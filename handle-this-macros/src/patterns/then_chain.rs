@@ -9,6 +9,10 @@
 //! - `try all i in iter { }` - collect all
 //! - `try while cond { }` - retry
 //! - `async try { }` - async
+//!
+//! A step written `then async |x| { }` may `.await`; any async step (or an
+//! async source) makes the whole chain async, so it must sit in an async
+//! context. Steps after an async source may always `.await`.
 
 use proc_macro2::{TokenStream, TokenTree, Span};
 use quote::quote;
@@ -46,6 +50,8 @@ struct ThenStep {
     binding: Ident,
     /// Optional type annotation for the binding
     binding_type: Option<TokenStream>,
+    /// Whether the step was written `then async |x| { }`
+    is_async: bool,
     /// The body of this step
     body: TokenStream,
     /// Optional context (with "msg", { key: value })
@@ -151,6 +157,10 @@ impl Parse for ThenChainInput {
         while !input.is_empty() {
             if peek_keyword(input, "then") {
                 input.parse::<Ident>()?; // consume `then`
+                let is_async = input.peek(Token![async]);
+                if is_async {
+                    input.parse::<Token![async]>()?;
+                }
 
                 // Parse binding: |x| or |x: Type|
                 input.parse::<Token![|]>()?;
@@ -183,6 +193,7 @@ impl Parse for ThenChainInput {
                 then_steps.push(ThenStep {
                     binding,
                     binding_type,
+                    is_async,
                     body,
                     with_clause,
                 });
@@ -204,7 +215,8 @@ impl Parse for ThenChainInput {
 
         while !input.is_empty() {
             if peek_keyword(input, "catch") {
-                handlers.push(Handler::Catch(keywords::catch::parse(input)?));
+                keywords::catch_panic::reject(input)?;
+                handlers.extend(keywords::catch::parse(input)?.into_iter().map(Handler::Catch));
             } else if peek_keyword(input, "throw") {
                 handlers.push(Handler::Throw(keywords::throw::parse(input)?));
             } else if peek_keyword(input, "inspect") {
@@ -343,8 +355,8 @@ fn generate(input: ThenChainInput) -> TokenStream {
         SourceType::All { binding, iter, body } => {
             let body = nested::transform_nested(body.clone());
             quote! {
-                (|| -> ::core::result::Result<::std::vec::Vec<_>, ::handle_this::Handled> {
-                    let mut __results = ::std::vec::Vec::new();
+                (|| -> ::core::result::Result<::handle_this::__Vec<_>, ::handle_this::Handled> {
+                    let mut __results = ::handle_this::__Vec::new();
                     for #binding in #iter {
                         match ::handle_this::__try_block!(#body) {
                             ::core::result::Result::Ok(__v) => __results.push(__v),
@@ -378,10 +390,8 @@ fn generate(input: ThenChainInput) -> TokenStream {
         SourceType::Async { body } => {
             let body = nested::transform_nested(body.clone());
             quote! {
-                (async {
-                    ::handle_this::__try_block!(#body)
-                        .map_err(|__e| ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #source_ctx_chain)
-                }).await
+                ::handle_this::__async_try_block!(#body).await
+                    .map_err(|__e| ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #source_ctx_chain)
             }
         }
     };
@@ -389,6 +399,10 @@ fn generate(input: ThenChainInput) -> TokenStream {
     // Build the chain with then steps
     let mut chain = source_expr;
 
+    // Steps after an async source may all await; otherwise only `then async` ones
+    let source_async = matches!(input.source, SourceType::Async { .. });
+    let is_async = source_async || input.then_steps.iter().any(|s| s.is_async);
+
     for step in &input.then_steps {
         let binding = &step.binding;
         let body = nested::transform_nested(step.body.clone());
@@ -400,20 +414,22 @@ fn generate(input: ThenChainInput) -> TokenStream {
             quote! { #binding }
         };
 
-        // Check if source is async
-        let is_async = matches!(input.source, SourceType::Async { .. });
-
         if is_async {
+            let step_block = if source_async || step.is_async {
+                quote! { ::handle_this::__async_try_block!(#body).await }
+            } else {
+                quote! { ::handle_this::__try_block!(#body) }
+            };
             chain = quote! {
-                match #chain {
-                    ::core::result::Result::Ok(#binding_with_type) => {
-                        (async {
-                            ::handle_this::__try_block!(#body)
-                                .map_err(|__e| ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #ctx_chain)
-                        }).await
+                (match #chain {
+                    ::core::result::Result::Ok(__then_value) => {
+                        // A typed binding can't sit in the pattern itself
+                        let #binding_with_type = __then_value;
+                        #step_block
+                            .map_err(|__e| ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #ctx_chain)
                     }
                     ::core::result::Result::Err(__e) => ::core::result::Result::Err(__e),
-                }
+                })
             };
         } else {
             chain = quote! {
@@ -433,13 +449,13 @@ fn generate(input: ThenChainInput) -> TokenStream {
 
     let code = if is_direct && input.else_body.is_some() && !has_handlers {
         // Direct mode with else only (no other handlers): unwrap_or_else
-        let else_body = nested::transform_nested(input.else_body.as_ref().unwrap().clone());
+        let else_body = nested::transform_nested(input.else_body.clone().unwrap_or_default());
         quote! {
             #chain.unwrap_or_else(|_| { #else_body })
         }
     } else if is_direct && input.else_body.is_some() && has_handlers {
         // Direct mode with handlers AND else: run handlers first, else as fallback
-        let else_body = nested::transform_nested(input.else_body.as_ref().unwrap().clone());
+        let else_body = nested::transform_nested(input.else_body.clone().unwrap_or_default());
         let handler_checks = generate_handler_checks_with_fallback(&input, Some(&else_body));
 
         quote! {
@@ -467,7 +483,7 @@ fn generate(input: ThenChainInput) -> TokenStream {
         }
     } else if has_handlers && input.else_body.is_some() {
         // Non-direct mode with handlers AND else
-        let else_body = nested::transform_nested(input.else_body.as_ref().unwrap().clone());
+        let else_body = nested::transform_nested(input.else_body.clone().unwrap_or_default());
         let handler_checks = generate_handler_checks_with_fallback(&input, Some(&else_body));
 
         quote! {
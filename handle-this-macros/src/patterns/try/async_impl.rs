@@ -1,4 +1,4 @@
-//! Async try pattern: `async try { body } [handlers...]`
+//! Async try pattern: `async try [spawn] [timeout DURATION] { body } [handlers...]`
 //!
 //! Handles asynchronous try blocks with catch/throw/inspect/finally/with.
 //!
@@ -13,8 +13,9 @@ use crate::keywords::{self, GenContext, peek_keyword, ChainVariant, Guard};
 use crate::keywords::catch::CatchClause;
 use crate::keywords::throw::ThrowClause;
 use crate::keywords::inspect::InspectClause;
+use crate::keywords::on_ok::OnOkClause;
 use crate::keywords::with_ctx::WithClause;
-use crate::nested::{transform_nested, contains_question_mark};
+use crate::nested::{transform_nested, contains_control_flow, contains_question_mark};
 use super::checks::{self, CheckAction};
 use super::common::Handler;
 
@@ -25,10 +26,26 @@ struct AsyncTryInput {
     handlers: Vec<Handler>,
     finally: Option<TokenStream>,
     with_clause: Option<WithClause>,
+    /// `cancel_safe`: run `finally` from a drop guard so cancellation still cleans up
+    cancel_safe: bool,
+    /// `to_option`: yield `Option<T>` instead of `Result<T>`
+    to_option: bool,
+    /// `unwrap_infallible`: yield `T` instead of `Result<T>`
+    unwrap_infallible: bool,
+    /// Some catch has `delay`; its duration is awaited after the handlers run
+    has_delay: bool,
+    /// `on_ok` taps, applied to the body's result after `.await`
+    on_ok: Vec<OnOkClause>,
 }
 
 impl Parse for AsyncTryInput {
     fn parse(input: ParseStream) -> Result<Self> {
+        // Optional task: spawn
+        let spawn = keywords::spawn::parse(input)?;
+
+        // Optional deadline: timeout DURATION
+        let timeout = keywords::timeout::parse(input)?;
+
         // Parse try body: { ... }
         let content;
         braced!(content in input);
@@ -38,10 +55,23 @@ impl Parse for AsyncTryInput {
         let mut handlers = Vec::new();
         let mut finally = None;
         let mut with_clause = None;
+        let mut cancel_safe = None;
+        let mut finally_async = None;
+        let mut to_option = false;
+        let mut unwrap_infallible = false;
+        let mut throttle = None;
+        let mut ratelimit = None;
+        let mut has_delay = false;
+        let mut on_ok = Vec::new();
+        let mut catch_panic = false;
 
         while !input.is_empty() {
             if peek_keyword(input, "catch") {
-                let clause = keywords::catch::parse(input)?;
+                catch_panic |= keywords::catch_panic::peek(input);
+                let (clauses, delayed) = keywords::catch::parse_async(input)?;
+                has_delay |= delayed;
+                // `catch A | B` shares one body and guard across its clauses
+                let clause = clauses[clauses.len() - 1].clone();
                 // Catch bodies must be infallible - reject `?` operator
                 let has_question_mark = contains_question_mark(&clause.body)
                     || matches!(&clause.guard, Some(Guard::Match { arms, .. }) if contains_question_mark(arms));
@@ -51,7 +81,7 @@ impl Parse for AsyncTryInput {
                         "catch handlers must be infallible; use `try catch { ... }` to return Result",
                     ));
                 }
-                handlers.push(Handler::Catch(clause.clone()));
+                handlers.extend(clauses.into_iter().map(Handler::Catch));
 
                 // Check for `catch Type {} else {}` - creates catch-all after typed catch
                 if clause.type_path.is_some() && input.peek(syn::Token![else]) {
@@ -73,6 +103,8 @@ impl Parse for AsyncTryInput {
                     };
                     handlers.push(Handler::Catch(else_clause));
                 }
+            } else if keywords::throw::peek_as(input) {
+                handlers.push(Handler::Inspect(keywords::throw::parse_as(input)?));
             } else if peek_keyword(input, "throw") {
                 let clause = keywords::throw::parse(input)?;
                 handlers.push(Handler::Throw(clause.clone()));
@@ -98,7 +130,88 @@ impl Parse for AsyncTryInput {
                     };
                     handlers.push(Handler::Catch(else_clause));
                 }
-            } else if peek_keyword(input, "inspect") {
+            } else if peek_keyword(input, "exit") {
+                let clause = keywords::exit::parse(input)?;
+                handlers.push(Handler::Catch(clause));
+            } else if peek_keyword(input, "branch") {
+                for clause in keywords::branch::parse(input)? {
+                    if contains_question_mark(&clause.body) {
+                        return Err(syn::Error::new(
+                            clause.catch_span,
+                            "branch arms must be infallible; use `try catch { ... }` to return Result",
+                        ));
+                    }
+                    handlers.push(Handler::Catch(clause));
+                }
+            } else if peek_keyword(input, "group") {
+                let clause = keywords::group::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "throttle") {
+                let clause = keywords::throttle::parse(input)?;
+                if throttle.is_some() {
+                    return Err(syn::Error::new(
+                        clause.throttle_span,
+                        "multiple `throttle` clauses are not allowed; combine into a single key",
+                    ));
+                }
+                throttle = Some(clause);
+            } else if peek_keyword(input, "ratelimit_propagate") {
+                let clause = keywords::ratelimit_propagate::parse(input)?;
+                if ratelimit.is_some() {
+                    return Err(syn::Error::new(
+                        clause.span,
+                        "multiple `ratelimit_propagate` clauses are not allowed",
+                    ));
+                }
+                if contains_control_flow(&body) {
+                    return Err(syn::Error::new(
+                        clause.span,
+                        "`ratelimit_propagate` can't guard a try body containing break/continue",
+                    ));
+                }
+                if contains_question_mark(&clause.fallback) {
+                    return Err(syn::Error::new(
+                        clause.span,
+                        "ratelimit_propagate fallbacks must be infallible",
+                    ));
+                }
+                ratelimit = Some(clause);
+            } else if peek_keyword(input, "convert") {
+                let clause = keywords::convert::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "log_once") {
+                let clause = keywords::log_once::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "report_and_continue") {
+                keywords::report_and_continue::reject_async(input)?;
+            } else if peek_keyword(input, "with_correlation") {
+                let clause = keywords::with_correlation::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "metric_inc") {
+                let clause = keywords::metric_inc::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "trace_error") {
+                let clause = keywords::trace_error::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "on_ok") {
+                let clause = keywords::on_ok::parse(input)?;
+                if contains_question_mark(&clause.body) {
+                    return Err(syn::Error::new(
+                        clause.on_ok_span,
+                        "on_ok handlers must be infallible; they can't change the result",
+                    ));
+                }
+                on_ok.push(clause);
+            } else if peek_keyword(input, "on_err") {
+                let clause = keywords::on_err::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "snapshot") {
+                let clause = keywords::snapshot::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if peek_keyword(input, "classify") {
+                let clause = keywords::classify::parse(input)?;
+                handlers.push(Handler::Inspect(clause));
+            } else if keywords::inspect::peek(input) {
                 let clause = keywords::inspect::parse(input)?;
                 // Inspect bodies must be infallible - reject `?` operator
                 let has_question_mark = contains_question_mark(&clause.body)
@@ -118,7 +231,22 @@ impl Parse for AsyncTryInput {
                         "multiple `finally` blocks are not allowed; combine into a single block",
                     ));
                 }
+                if keywords::finally::peek_async(input) {
+                    finally_async = Some(finally_span);
+                }
                 finally = Some(keywords::finally::parse(input)?);
+            } else if peek_keyword(input, "cancel_safe") {
+                let kw = keywords::parse_keyword(input, "cancel_safe")?;
+                if cancel_safe.is_some() {
+                    return Err(syn::Error::new(kw.span(), "duplicate `cancel_safe` marker"));
+                }
+                cancel_safe = Some(kw.span());
+            } else if peek_keyword(input, "to_option") {
+                keywords::to_option::parse(input)?;
+                to_option = true;
+            } else if peek_keyword(input, "unwrap_infallible") {
+                keywords::unwrap_infallible::parse(input)?;
+                unwrap_infallible = true;
             } else if peek_keyword(input, "with") {
                 if with_clause.is_some() {
                     return Err(syn::Error::new(
@@ -141,17 +269,42 @@ impl Parse for AsyncTryInput {
         // Validate handler order: untyped catch must be last
         validate_handler_order(&handlers)?;
 
+        if let (Some(span), None) = (cancel_safe, &finally) {
+            return Err(syn::Error::new(
+                span,
+                "`cancel_safe` needs a `finally` block to run on cancellation: `async try { } finally { } cancel_safe`",
+            ));
+        }
+        if let (Some(_), Some(span)) = (cancel_safe, finally_async) {
+            return Err(syn::Error::new(
+                span,
+                "`finally async` can't be `cancel_safe`: a drop guard can't await; use a sync `finally` block",
+            ));
+        }
+
+        let body = keywords::catch_panic::wrap_body_async(body, catch_panic);
+        let body = keywords::spawn::wrap_body(body, spawn);
+        let body = keywords::timeout::wrap_body(body, timeout.as_ref());
+        let body = keywords::ratelimit_propagate::wrap_body(body, ratelimit.as_ref());
+        let body = keywords::throttle::wrap_body(body, throttle.as_ref());
+
         Ok(AsyncTryInput {
             body,
+            on_ok,
             handlers,
             finally,
             with_clause,
+            cancel_safe: cancel_safe.is_some(),
+            to_option,
+            unwrap_infallible,
+            has_delay,
         })
     }
 }
 
 /// Validate that no handlers follow an untyped catch.
 /// Untyped catch catches ALL errors, making subsequent handlers unreachable.
+/// Also rejects dead handlers (see `check_dead_handlers`).
 fn validate_handler_order(handlers: &[Handler]) -> Result<()> {
     let mut untyped_catch_span: Option<proc_macro2::Span> = None;
 
@@ -177,7 +330,7 @@ fn validate_handler_order(handlers: &[Handler]) -> Result<()> {
         }
     }
 
-    Ok(())
+    super::common::check_dead_handlers(handlers)
 }
 
 /// Process async try pattern.
@@ -195,6 +348,13 @@ fn generate(input: AsyncTryInput) -> TokenStream {
 
     let body = transform_nested(input.body.clone());
     let ctx_chain = keywords::with_ctx::gen_ctx_chain(&ctx);
+    let on_ok: Vec<OnOkClause> = input
+        .on_ok
+        .iter()
+        .cloned()
+        .map(|tap| OnOkClause { body: transform_nested(tap.body), ..tap })
+        .collect();
+    let on_ok_map = keywords::on_ok::gen_map(&on_ok);
 
     // Check if we have any handlers
     let has_handlers = !input.handlers.is_empty();
@@ -211,20 +371,39 @@ fn generate(input: AsyncTryInput) -> TokenStream {
         // Use or_else closure like sync.rs - the checks use `return` which needs closure context
         // Always add #[allow(unreachable_code)] - we can't statically detect guarded catch-alls
         // like `catch _ when true` that are semantically catch-all
-        quote! {
-            let __result: ::core::result::Result<_, ::handle_this::Handled> =
-                ::handle_this::__async_try_block!(#body)
-                    .await
-                    .or_else(|__raw_err| -> ::core::result::Result<_, ::handle_this::Handled> {
-                        #[allow(unreachable_code)]
-                        {
-                            // __err must be mutable because throw can transform it
-                            let mut __err: ::handle_this::Handled = ::handle_this::__wrap_frame(__raw_err, file!(), line!(), column!()) #ctx_chain;
-                            #handler_checks
-                            #fallback
+        let handlers = quote! {
+            #[allow(unreachable_code)]
+            {
+                // __err must be mutable because throw can transform it
+                let mut __err: ::handle_this::Handled = ::handle_this::__wrap_frame(__raw_err, file!(), line!(), column!()) #ctx_chain;
+                #handler_checks
+                #fallback
+            }
+        };
+        if input.has_delay {
+            // `catch e delay d` awaits before its body, so the checks run in
+            // an async block instead of a closure; `return` works the same
+            quote! {
+                let __result: ::core::result::Result<_, ::handle_this::Handled> =
+                    match ::handle_this::__async_try_block!(#body).await #on_ok_map {
+                        ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
+                        ::core::result::Result::Err(__raw_err) => {
+                            async { #handlers }.await
                         }
-                    });
-            __result
+                    };
+                __result
+            }
+        } else {
+            quote! {
+                let __result: ::core::result::Result<_, ::handle_this::Handled> =
+                    ::handle_this::__async_try_block!(#body)
+                        .await
+                        #on_ok_map
+                        .or_else(|__raw_err| -> ::core::result::Result<_, ::handle_this::Handled> {
+                            #handlers
+                        });
+                __result
+            }
         }
     } else {
         // No handlers - just wrap error with frame
@@ -232,6 +411,7 @@ fn generate(input: AsyncTryInput) -> TokenStream {
             let __result: ::core::result::Result<_, ::handle_this::Handled> =
                 ::handle_this::__async_try_block!(#body)
                     .await
+                    #on_ok_map
                     .map_err(|__e| ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #ctx_chain);
             __result
         }
@@ -240,7 +420,19 @@ fn generate(input: AsyncTryInput) -> TokenStream {
     // Wrap with async finally if present
     let code = if let Some(ref finally_body) = input.finally {
         let finally_transformed = transform_nested(finally_body.clone());
-        keywords::finally::wrap(code, &finally_transformed)
+        if input.cancel_safe {
+            keywords::finally::wrap_cancel_safe(code, &finally_transformed)
+        } else {
+            keywords::finally::wrap(code, &finally_transformed)
+        }
+    } else {
+        code
+    };
+
+    let code = if input.to_option {
+        keywords::to_option::wrap(code)
+    } else if input.unwrap_infallible {
+        keywords::unwrap_infallible::wrap(code)
     } else {
         code
     };
@@ -126,7 +126,7 @@ pub fn gen_all_variant_with_guard(
     match guard {
         Some(Guard::When(cond)) => quote! {
             {
-                let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                 if !#binding.is_empty() && #cond {
                     #body
                 } else {
@@ -136,7 +136,7 @@ pub fn gen_all_variant_with_guard(
         },
         Some(Guard::Match { expr, arms }) => quote! {
             {
-                let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                 if !#binding.is_empty() {
                     match #expr { #arms }
                 } else {
@@ -146,7 +146,7 @@ pub fn gen_all_variant_with_guard(
         },
         None => quote! {
             {
-                let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                 if !#binding.is_empty() {
                     #body
                 } else {
@@ -246,7 +246,7 @@ pub fn gen_inspect_handler(
             match guard {
                 Some(Guard::When(cond)) => quote! {
                     {
-                        let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                        let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                         if !#binding.is_empty() && #cond {
                             let _ = { #body };
                         }
@@ -255,7 +255,7 @@ pub fn gen_inspect_handler(
                 },
                 Some(Guard::Match { expr, arms }) => quote! {
                     {
-                        let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                        let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                         if !#binding.is_empty() {
                             let _ = match #expr { #arms };
                         }
@@ -264,7 +264,7 @@ pub fn gen_inspect_handler(
                 },
                 None => quote! {
                     {
-                        let #binding: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                        let #binding: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                         if !#binding.is_empty() {
                             let _ = { #body };
                         }
@@ -4,6 +4,9 @@
 //! Having a single source of truth for handler types prevents duplication and ensures
 //! consistent behavior across all try pattern variants.
 
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::keywords::{ChainVariant, Guard};
 use crate::keywords::catch::CatchClause;
 use crate::keywords::throw::ThrowClause;
 use crate::keywords::inspect::InspectClause;
@@ -44,4 +47,74 @@ impl Handler {
             _ => false,
         }
     }
+
+    /// Chain variant, type filter, and guard of this handler.
+    fn filter(&self) -> (ChainVariant, Option<&TokenStream>, Option<&Guard>) {
+        match self {
+            Handler::Catch(c) => (c.variant, c.type_path.as_ref(), c.guard.as_ref()),
+            Handler::Throw(t) => (t.variant, t.type_path.as_ref(), t.guard.as_ref()),
+            Handler::Inspect(i) => (i.variant, i.type_path.as_ref(), i.guard.as_ref()),
+            Handler::TryCatch(tc) => (tc.variant, tc.type_path.as_ref(), tc.guard.as_ref()),
+        }
+    }
+}
+
+/// Reject handlers that can never run: a `when false` guard, or a typed
+/// catch whose errors an earlier unguarded catch already takes.
+///
+/// `catch any T` and `catch all T` match whenever the chain holds a `T`,
+/// so they shadow later catches of `T` in every form; a root `catch T`
+/// only shadows later root catches of `T`.
+pub fn check_dead_handlers(handlers: &[Handler]) -> syn::Result<()> {
+    // (variant, type) of each unguarded typed catch so far
+    let mut taken: Vec<(ChainVariant, String)> = Vec::new();
+
+    for handler in handlers {
+        let (variant, type_path, guard) = handler.filter();
+        if let Some(Guard::When(cond)) = guard {
+            if let [TokenTree::Ident(id)] = cond.clone().into_iter().collect::<Vec<_>>().as_slice() {
+                if id == "false" {
+                    return Err(syn::Error::new(
+                        id.span(),
+                        format!("guard is always false; this `{}` never runs", handler.name()),
+                    ));
+                }
+            }
+        }
+
+        let span = match handler {
+            Handler::Catch(c) => c.catch_span,
+            Handler::TryCatch(tc) => tc.binding.span(),
+            _ => continue,
+        };
+        let Some(type_path) = type_path else { continue };
+        let ty = type_path.to_string().replace(" :: ", "::");
+
+        let shadow = taken
+            .iter()
+            .find(|(earlier, t)| *t == ty && (*earlier != ChainVariant::Root || variant == ChainVariant::Root));
+        if let Some((earlier, _)) = shadow {
+            return Err(unreachable_catch(span, handler.name(), *earlier, &ty));
+        }
+        if guard.is_none() {
+            taken.push((variant, ty));
+        }
+    }
+
+    Ok(())
+}
+
+fn unreachable_catch(span: Span, name: &str, earlier: ChainVariant, ty: &str) -> syn::Error {
+    let earlier = match earlier {
+        ChainVariant::Root => format!("catch {}", ty),
+        ChainVariant::Any => format!("catch any {}", ty),
+        ChainVariant::All => format!("catch all {}", ty),
+    };
+    syn::Error::new(
+        span,
+        format!(
+            "`{}` never runs: the unguarded `{}` before it already handles every `{}`",
+            name, earlier, ty
+        ),
+    )
 }
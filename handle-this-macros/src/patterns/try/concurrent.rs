@@ -0,0 +1,265 @@
+//! Bounded-concurrency async iteration.
+//!
+//! - `async try concurrent(N) for item in iter { body }` - first success wins (alias: any)
+//! - `async try concurrent(N) all item in iter { body }` - collect all, fail on any error
+//!
+//! Up to `N` bodies are in flight at once in a `FuturesUnordered` (`futures`
+//! feature). Each body is its own async block: it owns its `item` and borrows
+//! everything else, so shared state needs no `Arc` or `move` juggling.
+//!
+//! Errors are chained like the sequential `try any`/`try all`, in input order
+//! rather than completion order so the trace is deterministic. First-success
+//! drops the still-running bodies once one succeeds; collect-all keeps going
+//! after a failure and returns the results in input order.
+//!
+//! Handlers run once, on the chained error. They can't use `break`/`continue`
+//! since the iteration isn't a loop at the expansion site.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, parenthesized, token, Ident, Result};
+
+use crate::keywords::{self, GenContext};
+use crate::nested::transform_nested;
+use super::error_handler;
+use super::handlers::{self, Handlers};
+use super::iter::IterMode;
+
+/// Parsed concurrent iteration input.
+struct ConcurrentInput {
+    limit: TokenStream,
+    mode: IterMode,
+    binding: Ident,
+    iterator: TokenStream,
+    body: TokenStream,
+    handlers: Handlers,
+}
+
+impl Parse for ConcurrentInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Parse: concurrent(N) for|any|all binding in iterator { body }
+        let kw = keywords::parse_keyword(input, "concurrent")?;
+        if !input.peek(token::Paren) {
+            return Err(syn::Error::new(
+                kw.span(),
+                "expected a concurrency limit: `async try concurrent(8) for x in iter { ... }`",
+            ));
+        }
+        let content;
+        parenthesized!(content in input);
+        let limit: TokenStream = content.parse()?;
+        if limit.is_empty() {
+            return Err(syn::Error::new(kw.span(), "missing concurrency limit in `concurrent(...)`"));
+        }
+        if let Ok(lit) = syn::parse2::<syn::LitInt>(limit.clone()) {
+            if lit.base10_parse::<usize>()? == 0 {
+                return Err(syn::Error::new(lit.span(), "concurrency limit must be at least 1"));
+            }
+        }
+
+        let mode_kw = if input.peek(syn::Token![for]) {
+            input.parse::<syn::Token![for]>()?;
+            IterMode::FirstSuccess
+        } else if keywords::peek_keyword(input, "any") {
+            keywords::parse_keyword(input, "any")?;
+            IterMode::FirstSuccess
+        } else if keywords::peek_keyword(input, "all") {
+            keywords::parse_keyword(input, "all")?;
+            IterMode::CollectAll
+        } else {
+            return Err(syn::Error::new(
+                input.span(),
+                "expected `for`, `any` or `all` after `concurrent(N)`",
+            ));
+        };
+
+        // Use parse_any to allow `_` as a binding
+        let binding = Ident::parse_any(input)?;
+        input.parse::<syn::Token![in]>()?;
+
+        // Collect iterator tokens until `{`
+        let mut iter_tokens = Vec::new();
+        while !input.is_empty() && !input.peek(token::Brace) {
+            let tt: TokenTree = input.parse()?;
+            iter_tokens.push(tt);
+        }
+        if iter_tokens.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "missing iterator expression: `async try concurrent(N) for x in ITERATOR { ... }`",
+            ));
+        }
+        let iterator: TokenStream = iter_tokens.into_iter().collect();
+
+        let content;
+        braced!(content in input);
+        let body: TokenStream = content.parse()?;
+
+        let handlers = handlers::parse(input)?;
+        if handlers.has_control_flow() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "handlers of `async try concurrent` can't use `break`/`continue`/`return`; \
+                 the iterations don't run in a loop at this site",
+            ));
+        }
+
+        Ok(ConcurrentInput {
+            limit,
+            mode: mode_kw,
+            binding,
+            iterator,
+            body,
+            handlers,
+        })
+    }
+}
+
+/// Process `async try concurrent(N) ...` pattern.
+pub fn process(input: TokenStream) -> Result<TokenStream> {
+    let parsed: ConcurrentInput = syn::parse2(input)?;
+    Ok(generate(parsed))
+}
+
+/// Generate code for concurrent iteration.
+fn generate(input: ConcurrentInput) -> TokenStream {
+    let mut ctx = GenContext::new().async_mode();
+    if let Some(ref with) = input.handlers.with_clause {
+        keywords::with_ctx::apply_to_context(with, &mut ctx);
+    }
+
+    let ConcurrentInput { limit, mode, binding, iterator, .. } = &input;
+    let body = transform_nested(input.body.clone());
+    let ctx_chain = keywords::with_ctx::gen_ctx_chain(&ctx);
+    let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
+
+    // Each iteration's future owns its index and item and borrows the rest
+    // of the scope. `__Owned::take` consumes the wrapper, forcing a by-value
+    // capture even for `Copy` items.
+    let spawn = quote! {
+        let __item = ::handle_this::__Owned((__next_index, __item));
+        __next_index += 1;
+        __pending.push(async {
+            let (__i, #binding) = __item.take();
+            let __result: ::core::result::Result<_, ::handle_this::__BoxedError> = async {
+                ::core::result::Result::Ok({ #body })
+            }.await;
+            (__i, __result)
+        });
+    };
+
+    let drive = match mode {
+        IterMode::FirstSuccess => quote! {
+            let mut __found = ::core::option::Option::None;
+            loop {
+                while __pending.len() < __limit {
+                    match __iter.next() {
+                        ::core::option::Option::Some(__item) => {
+                            #spawn
+                        }
+                        ::core::option::Option::None => break,
+                    }
+                }
+                match ::handle_this::__futures::StreamExt::next(&mut __pending).await {
+                    ::core::option::Option::Some((_, ::core::result::Result::Ok(__v))) => {
+                        __found = ::core::option::Option::Some(__v);
+                        break;
+                    }
+                    ::core::option::Option::Some((__i, ::core::result::Result::Err(__e))) => {
+                        __errors.push((__i, ::handle_this::__wrap_frame(__e, file!(), line!(), column!())));
+                    }
+                    ::core::option::Option::None => break,
+                }
+            }
+            // Remaining bodies are cancelled once a winner is found
+            ::core::mem::drop(__pending);
+            let __outcome = match __found {
+                ::core::option::Option::Some(__v) => ::core::result::Result::Ok(__v),
+                ::core::option::Option::None => ::core::result::Result::Err(()),
+            };
+        },
+        IterMode::CollectAll => quote! {
+            let mut __results = ::handle_this::__Vec::new();
+            loop {
+                while __pending.len() < __limit {
+                    match __iter.next() {
+                        ::core::option::Option::Some(__item) => {
+                            #spawn
+                        }
+                        ::core::option::Option::None => break,
+                    }
+                }
+                match ::handle_this::__futures::StreamExt::next(&mut __pending).await {
+                    ::core::option::Option::Some((__i, ::core::result::Result::Ok(__v))) => {
+                        __results.push((__i, __v));
+                    }
+                    ::core::option::Option::Some((__i, ::core::result::Result::Err(__e))) => {
+                        __errors.push((__i, ::handle_this::__wrap_frame(__e, file!(), line!(), column!())));
+                    }
+                    ::core::option::Option::None => break,
+                }
+            }
+            ::core::mem::drop(__pending);
+            let __outcome = if __errors.is_empty() {
+                __results.sort_by_key(|(__i, _)| *__i);
+                ::core::result::Result::Ok(
+                    __results.into_iter().map(|(_, __v)| __v).collect::<::handle_this::__Vec<_>>()
+                )
+            } else {
+                ::core::result::Result::Err(())
+            };
+        },
+    };
+
+    let empty_msg = "empty iterator in async try concurrent";
+
+    let core_logic = quote! {
+        let __limit: usize = ::core::cmp::max(#limit, 1);
+        let mut __iter = ::core::iter::IntoIterator::into_iter(#iterator);
+        let mut __pending = ::handle_this::__futures::stream::FuturesUnordered::new();
+        let mut __errors: ::handle_this::__Vec<(usize, ::handle_this::Handled)> = ::handle_this::__Vec::new();
+        let mut __next_index: usize = 0;
+
+        #drive
+
+        // Chain in input order, matching the sequential patterns
+        __errors.sort_by_key(|(__i, _)| *__i);
+        let __chained_err = __errors.into_iter().fold(
+            ::core::option::Option::None,
+            |__prev: ::core::option::Option<::handle_this::Handled>, (_, __current)| {
+                ::core::option::Option::Some(match __prev {
+                    ::core::option::Option::Some(__prev) => __current.chain_after(__prev),
+                    ::core::option::Option::None => __current,
+                })
+            },
+        );
+
+        (|| -> ::core::result::Result<_, ::handle_this::Handled> {
+            match __outcome {
+                ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
+                ::core::result::Result::Err(()) => {
+                    // __err must be mutable because throw can transform it
+                    let mut __err = __chained_err.unwrap_or_else(||
+                        ::handle_this::Handled::msg(#empty_msg)
+                            .frame(file!(), line!(), column!())
+                            #ctx_chain
+                    );
+                    #[allow(unreachable_code)]
+                    { #error_handler }
+                }
+            }
+        })()
+    };
+
+    // Wrap with finally
+    let code = if let Some(ref finally_body) = input.handlers.finally {
+        let finally_transformed = transform_nested(finally_body.clone());
+        keywords::finally::wrap(core_logic, &finally_transformed)
+    } else {
+        core_logic
+    };
+
+    quote! { { #code } }
+}
@@ -123,7 +123,8 @@ impl Parse for TryWhenInput {
 
         while !input.is_empty() {
             if peek_keyword(input, "catch") {
-                catches.push(keywords::catch::parse(input)?);
+                keywords::catch_panic::reject(input)?;
+                catches.extend(keywords::catch::parse(input)?);
             } else if peek_keyword(input, "throw") {
                 throws.push(keywords::throw::parse(input)?);
             } else if peek_keyword(input, "inspect") {
@@ -105,9 +105,14 @@ pub fn parse(input: ParseStream) -> Result<Handlers> {
 
     while !input.is_empty() {
         if peek_keyword(input, "catch") {
-            let clause = keywords::catch::parse(input)?;
-            handlers.handlers.push(Handler::Catch(clause.clone()));
-            handlers.catches.push(clause.clone());
+            keywords::catch_panic::reject(input)?;
+            let clauses = keywords::catch::parse(input)?;
+            // `catch A | B` shares one body and guard across its clauses
+            let clause = clauses[clauses.len() - 1].clone();
+            for clause in clauses {
+                handlers.handlers.push(Handler::Catch(clause.clone()));
+                handlers.catches.push(clause);
+            }
 
             // Check for `catch Type {} else {}` - creates catch-all after typed catch
             if clause.type_path.is_some() && input.peek(syn::Token![else]) {
@@ -124,6 +129,10 @@ pub fn parse(input: ParseStream) -> Result<Handlers> {
                 handlers.handlers.push(Handler::Catch(else_clause.clone()));
                 handlers.catches.push(else_clause);
             }
+        } else if keywords::throw::peek_as(input) {
+            let clause = keywords::throw::parse_as(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
         } else if peek_keyword(input, "throw") {
             let clause = keywords::throw::parse(input)?;
             handlers.handlers.push(Handler::Throw(clause.clone()));
@@ -146,7 +155,56 @@ pub fn parse(input: ParseStream) -> Result<Handlers> {
                 handlers.handlers.push(Handler::Catch(else_clause.clone()));
                 handlers.catches.push(else_clause);
             }
-        } else if peek_keyword(input, "inspect") {
+        } else if peek_keyword(input, "exit") {
+            let clause = keywords::exit::parse(input)?;
+            handlers.handlers.push(Handler::Catch(clause.clone()));
+            handlers.catches.push(clause);
+        } else if peek_keyword(input, "branch") {
+            for clause in keywords::branch::parse(input)? {
+                handlers.handlers.push(Handler::Catch(clause.clone()));
+                handlers.catches.push(clause);
+            }
+        } else if peek_keyword(input, "group") {
+            let clause = keywords::group::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "convert") {
+            let clause = keywords::convert::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "log_once") {
+            let clause = keywords::log_once::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "report_and_continue") {
+            let clause = keywords::report_and_continue::parse(input)?;
+            handlers.handlers.push(Handler::Catch(clause.clone()));
+            handlers.catches.push(clause);
+        } else if peek_keyword(input, "with_correlation") {
+            let clause = keywords::with_correlation::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "on_err") {
+            let clause = keywords::on_err::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "metric_inc") {
+            let clause = keywords::metric_inc::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "trace_error") {
+            let clause = keywords::trace_error::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "snapshot") {
+            let clause = keywords::snapshot::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if peek_keyword(input, "classify") {
+            let clause = keywords::classify::parse(input)?;
+            handlers.handlers.push(Handler::Inspect(clause.clone()));
+            handlers.inspects.push(clause);
+        } else if keywords::inspect::peek(input) {
             let clause = keywords::inspect::parse(input)?;
             handlers.handlers.push(Handler::Inspect(clause.clone()));
             handlers.inspects.push(clause);
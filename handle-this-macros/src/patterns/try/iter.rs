@@ -2,6 +2,11 @@
 //!
 //! - `try for item in iter { body }` - first success wins (alias: try any)
 //! - `try all item in iter { body }` - collect all, fail on any error
+//! - `try all item in iter { body } partial |oks, errs| { }` - keep every outcome
+//!
+//! The binding can be any pattern. With `(i, item) in iter.enumerate()`, each
+//! failure also records its index as an `attempt` attachment, so handlers can
+//! read it back with `e.get_kv("attempt")` (the most recent failure's index).
 //!
 //! # Signal Mode
 //!
@@ -19,9 +24,10 @@ use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
 use syn::ext::IdentExt;
-use syn::{Result, Ident, braced, token};
+use syn::{Result, Ident, Pat, braced, token};
 
 use crate::keywords::{self, GenContext};
+use crate::keywords::partial::PartialClause;
 use crate::nested::transform_nested;
 use super::error_handler;
 use super::handlers::{self, Handlers};
@@ -39,17 +45,28 @@ pub enum IterMode {
 
 /// Parsed iteration input (shared by for/any/all).
 struct IterInput {
-    binding: Ident,
+    binding: Pat,
     iterator: TokenStream,
     body: TokenStream,
+    partial: Option<PartialClause>,
     handlers: Handlers,
 }
 
 impl Parse for IterInput {
     fn parse(input: ParseStream) -> Result<Self> {
-        // Parse: binding in iterator { body }
-        // Use parse_any to allow `_` as a binding
-        let binding = Ident::parse_any(input)?;
+        // Parse: pattern in iterator { body }
+        // A plain binding goes through parse_any to allow `_`
+        let binding = if input.peek(Ident::peek_any) {
+            Pat::Ident(syn::PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: Ident::parse_any(input)?,
+                subpat: None,
+            })
+        } else {
+            Pat::parse_single(input)?
+        };
         input.parse::<syn::Token![in]>()?;
 
         // Collect iterator tokens until `{`
@@ -71,16 +88,35 @@ impl Parse for IterInput {
         braced!(content in input);
         let body: TokenStream = content.parse()?;
 
-        // Parse optional handlers
+        // Parse optional partial clause, then handlers
+        let partial = if keywords::peek_keyword(input, "partial") {
+            Some(keywords::partial::parse(input)?)
+        } else {
+            None
+        };
         let handlers = handlers::parse(input)?;
+        if let Some(ref partial) = partial {
+            if handlers.has_control_flow() {
+                return Err(syn::Error::new(
+                    partial.partial_span,
+                    "handlers after `partial` can't use `break`/`continue`/`return`",
+                ));
+            }
+        }
 
-        Ok(IterInput { binding, iterator, body, handlers })
+        Ok(IterInput { binding, iterator, body, partial, handlers })
     }
 }
 
 /// Process try for pattern (first success).
 pub fn process_for(input: TokenStream) -> Result<TokenStream> {
     let parsed: IterInput = syn::parse2(input)?;
+    if let Some(partial) = parsed.partial {
+        return Err(syn::Error::new(
+            partial.partial_span,
+            "`partial` only applies to `try all`; `try for`/`try any` stop at the first success",
+        ));
+    }
     Ok(generate(parsed, IterMode::FirstSuccess))
 }
 
@@ -106,6 +142,10 @@ fn generate(input: IterInput, mode: IterMode) -> TokenStream {
     let iterator = &input.iterator;
     let body = transform_nested(input.body.clone());
     let ctx_chain = keywords::with_ctx::gen_ctx_chain(&ctx);
+    let attempt_kv = match attempt_index(binding, iterator) {
+        Some(index) => quote! { .attempt_kv("attempt", #index) },
+        None => TokenStream::new(),
+    };
 
     // Check if handlers contain control flow (break/continue)
     let has_control_flow = input.handlers.has_control_flow();
@@ -113,14 +153,17 @@ fn generate(input: IterInput, mode: IterMode) -> TokenStream {
     // Check if there's an unconditional catch-all handler
     let has_catch_all = input.handlers.has_catch_all();
 
-    let core_logic = if has_control_flow {
+    let core_logic = if let Some(ref partial) = input.partial {
+        let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
+        gen_collect_partial(binding, iterator, &body, partial, &error_handler, &ctx_chain, &attempt_kv)
+    } else if has_control_flow {
         // Use SIGNAL MODE - transforms control flow to signals, allows error propagation
         match mode {
             IterMode::FirstSuccess => gen_first_success_signal(
-                binding, iterator, &body, &input.handlers, &ctx, &ctx_chain, has_catch_all,
+                binding, iterator, &body, &input.handlers, &ctx_chain, &attempt_kv, has_catch_all,
             ),
             IterMode::CollectAll => gen_collect_all_signal(
-                binding, iterator, &body, &input.handlers, &ctx, &ctx_chain, has_catch_all,
+                binding, iterator, &body, &input.handlers, &ctx_chain, &attempt_kv, has_catch_all,
             ),
         }
     } else {
@@ -128,8 +171,8 @@ fn generate(input: IterInput, mode: IterMode) -> TokenStream {
         let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
 
         match mode {
-            IterMode::FirstSuccess => gen_first_success(binding, iterator, &body, &error_handler, &ctx_chain),
-            IterMode::CollectAll => gen_collect_all(binding, iterator, &body, &error_handler),
+            IterMode::FirstSuccess => gen_first_success(binding, iterator, &body, &error_handler, &ctx_chain, &attempt_kv),
+            IterMode::CollectAll => gen_collect_all(binding, iterator, &body, &error_handler, &attempt_kv),
         }
     };
 
@@ -144,17 +187,41 @@ fn generate(input: IterInput, mode: IterMode) -> TokenStream {
     quote! { #code }
 }
 
+/// The index binding of `(i, item) in iter.enumerate()`, if that's the shape.
+pub(super) fn attempt_index<'a>(binding: &'a Pat, iterator: &TokenStream) -> Option<&'a Ident> {
+    let Pat::Tuple(tuple) = binding else { return None };
+    if tuple.elems.len() != 2 {
+        return None;
+    }
+    let Some(Pat::Ident(index)) = tuple.elems.first() else { return None };
+
+    // Must end in `.enumerate()`, so the first element really is the index
+    let tokens: Vec<TokenTree> = iterator.clone().into_iter().collect();
+    match tokens.as_slice() {
+        [.., TokenTree::Punct(dot), TokenTree::Ident(method), TokenTree::Group(args)]
+            if dot.as_char() == '.'
+                && method == "enumerate"
+                && args.delimiter() == proc_macro2::Delimiter::Parenthesis
+                && args.stream().is_empty() =>
+        {
+            Some(&index.ident)
+        }
+        _ => None,
+    }
+}
+
 // ============================================================
 // Closure Mode Generators (no control flow)
 // ============================================================
 
 /// Generate first-success iteration (try for / try any).
 fn gen_first_success(
-    binding: &Ident,
+    binding: &Pat,
     iterator: &TokenStream,
     body: &TokenStream,
     error_handler: &TokenStream,
     ctx_chain: &TokenStream,
+    attempt_kv: &TokenStream,
 ) -> TokenStream {
     quote! {
         (|| -> ::core::result::Result<_, ::handle_this::Handled> {
@@ -167,7 +234,7 @@ fn gen_first_success(
                         return ::core::result::Result::Ok(__v);
                     }
                     ::core::result::Result::Err(__e) => {
-                        let __current = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                        let __current = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #attempt_kv;
                         __chained_err = ::core::option::Option::Some(match __chained_err {
                             ::core::option::Option::Some(__prev) => __current.chain_after(__prev),
                             ::core::option::Option::None => __current,
@@ -190,14 +257,15 @@ fn gen_first_success(
 
 /// Generate collect-all iteration (try all).
 fn gen_collect_all(
-    binding: &Ident,
+    binding: &Pat,
     iterator: &TokenStream,
     body: &TokenStream,
     error_handler: &TokenStream,
+    attempt_kv: &TokenStream,
 ) -> TokenStream {
     quote! {
         (|| -> ::core::result::Result<_, ::handle_this::Handled> {
-            let mut __results = ::std::vec::Vec::new();
+            let mut __results = ::handle_this::__Vec::new();
             let mut __error: ::core::option::Option<::handle_this::Handled> = ::core::option::Option::None;
 
             for #binding in #iterator {
@@ -206,7 +274,7 @@ fn gen_collect_all(
                         __results.push(__v);
                     }
                     ::core::result::Result::Err(__e) => {
-                        let __wrapped = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                        let __wrapped = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #attempt_kv;
                         __error = ::core::option::Option::Some(match __error {
                             ::core::option::Option::Some(__prev) => __wrapped.chain_after(__prev),
                             ::core::option::Option::None => __wrapped,
@@ -229,6 +297,59 @@ fn gen_collect_all(
     }
 }
 
+/// Generate collect-all iteration that keeps every outcome (try all ... partial).
+///
+/// Each failure gets its frame and the `with` context up front, since it never
+/// reaches the error handler; only an error raised by the partial body does.
+fn gen_collect_partial(
+    binding: &Pat,
+    iterator: &TokenStream,
+    body: &TokenStream,
+    partial: &PartialClause,
+    error_handler: &TokenStream,
+    ctx_chain: &TokenStream,
+    attempt_kv: &TokenStream,
+) -> TokenStream {
+    let oks = &partial.oks;
+    let errs = &partial.errs;
+    let report = transform_nested(partial.body.clone());
+
+    quote! {
+        (|| -> ::core::result::Result<_, ::handle_this::Handled> {
+            let mut __results = ::handle_this::__Vec::new();
+            let mut __errors: ::handle_this::__Vec<::handle_this::Handled> = ::handle_this::__Vec::new();
+
+            for #binding in #iterator {
+                match ::handle_this::__try_block!(#body) {
+                    ::core::result::Result::Ok(__v) => {
+                        __results.push(__v);
+                    }
+                    ::core::result::Result::Err(__e) => {
+                        __errors.push(
+                            ::handle_this::__wrap_frame(__e, file!(), line!(), column!())
+                                #attempt_kv #ctx_chain
+                        );
+                    }
+                }
+            }
+
+            #[allow(unused_variables)]
+            let #oks = __results;
+            #[allow(unused_variables)]
+            let #errs = __errors;
+            match ::handle_this::__try_block!(#report) {
+                ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
+                // __err must be mutable because throw can transform it
+                ::core::result::Result::Err(__e) => {
+                    let mut __err = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                    #[allow(unreachable_code)]
+                    { #error_handler }
+                }
+            }
+        })()
+    }
+}
+
 // ============================================================
 // Signal Mode Generators (control flow via signals)
 // ============================================================
@@ -244,12 +365,12 @@ fn gen_collect_all(
 /// - Handler `break` becomes `return Ok(LoopSignal::Break)`
 /// - Unmatched errors: `Err(e)` propagates (typed catch) or `unreachable!()` (catch-all)
 fn gen_first_success_signal(
-    binding: &Ident,
+    binding: &Pat,
     iterator: &TokenStream,
     body: &TokenStream,
     handlers: &Handlers,
-    _ctx: &GenContext,
     ctx_chain: &TokenStream,
+    attempt_kv: &TokenStream,
     has_catch_all: bool,
 ) -> TokenStream {
     let signal = signal_type();
@@ -284,7 +405,7 @@ fn gen_first_success_signal(
                             return ::core::result::Result::Ok(#signal::Value(__v));
                         }
                         ::core::result::Result::Err(__e) => {
-                            let __current = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                            let __current = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #attempt_kv;
                             __chained_err = ::core::option::Option::Some(match __chained_err {
                                 ::core::option::Option::Some(__prev) => __current.chain_after(__prev),
                                 ::core::option::Option::None => __current,
@@ -316,12 +437,12 @@ fn gen_first_success_signal(
 
 /// Generate collect-all iteration in signal mode.
 fn gen_collect_all_signal(
-    binding: &Ident,
+    binding: &Pat,
     iterator: &TokenStream,
     body: &TokenStream,
     handlers: &Handlers,
-    _ctx: &GenContext,
     ctx_chain: &TokenStream,
+    attempt_kv: &TokenStream,
     has_catch_all: bool,
 ) -> TokenStream {
     let signal = signal_type();
@@ -347,7 +468,7 @@ fn gen_collect_all_signal(
         {
             #[allow(unreachable_code)]
             match (|| -> ::core::result::Result<#signal<_>, ::handle_this::Handled> {
-                let mut __results = ::std::vec::Vec::new();
+                let mut __results = ::handle_this::__Vec::new();
                 let mut __error: ::core::option::Option<::handle_this::Handled> = ::core::option::Option::None;
 
                 for #binding in #iterator {
@@ -357,7 +478,7 @@ fn gen_collect_all_signal(
                         }
                         ::core::result::Result::Err(__e) => {
                             let __wrapped = ::handle_this::__wrap_frame(__e, file!(), line!(), column!())
-                                #ctx_chain;
+                                #attempt_kv #ctx_chain;
                             __error = ::core::option::Option::Some(match __error {
                                 ::core::option::Option::Some(__prev) => __wrapped.chain_after(__prev),
                                 ::core::option::Option::None => __wrapped,
@@ -0,0 +1,199 @@
+//! Concurrent async join.
+//!
+//! - `async try join { a: fetch_a(), b: fetch_b() }` - labels are optional
+//!
+//! Each entry is a future resolving to a `Result`. All of them are polled
+//! together in place (no spawning, no `futures` feature) until every one has
+//! finished; the expression yields a tuple of their values in entry order.
+//!
+//! There is no short-circuit: every failure is framed, tagged with a
+//! `join` attachment (the entry's label, or its index), and chained like
+//! `try all`, so `catch all Type |errors|` sees each one. Handlers run once,
+//! on the chained error, and can't use `break`/`continue`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, token, Ident, Result};
+
+use crate::keywords::{self, GenContext};
+use crate::nested::transform_nested;
+use super::error_handler;
+use super::handlers::{self, Handlers};
+
+/// One `[label:] future` entry (shared with `race`).
+pub(super) struct JoinEntry {
+    pub(super) label: String,
+    pub(super) future: syn::Expr,
+}
+
+impl Parse for JoinEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // `label: expr`, but not a path like `module::fetch()`
+        let label = if input.peek(Ident) && input.peek2(syn::Token![:]) && !input.peek2(syn::Token![::]) {
+            let ident: Ident = input.parse()?;
+            input.parse::<syn::Token![:]>()?;
+            Some(ident.to_string())
+        } else {
+            None
+        };
+        Ok(JoinEntry {
+            label: label.unwrap_or_default(),
+            future: input.parse()?,
+        })
+    }
+}
+
+/// Parsed join input.
+struct JoinInput {
+    entries: Vec<JoinEntry>,
+    handlers: Handlers,
+}
+
+impl Parse for JoinInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Parse: join { [label:] future, ... }
+        let kw = keywords::parse_keyword(input, "join")?;
+        if !input.peek(token::Brace) {
+            return Err(syn::Error::new(
+                kw.span(),
+                "expected futures to join: `async try join { a: fetch_a(), b: fetch_b() }`",
+            ));
+        }
+        let content;
+        braced!(content in input);
+        let entries = parse_entries(&content)?;
+        if entries.is_empty() {
+            return Err(syn::Error::new(kw.span(), "`async try join` needs at least one future"));
+        }
+
+        let handlers = handlers::parse(input)?;
+        if handlers.has_control_flow() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "handlers of `async try join` can't use `break`/`continue`/`return`; \
+                 the futures don't run in a loop at this site",
+            ));
+        }
+
+        Ok(JoinInput { entries, handlers })
+    }
+}
+
+/// Parse `[label:] future, ...`, labeling unlabeled entries by index.
+pub(super) fn parse_entries(input: ParseStream) -> Result<Vec<JoinEntry>> {
+    let entries: Punctuated<JoinEntry, syn::Token![,]> = Punctuated::parse_terminated(input)?;
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut entry)| {
+            if entry.label.is_empty() {
+                entry.label = i.to_string();
+            }
+            entry
+        })
+        .collect())
+}
+
+/// Process `async try join { ... }` pattern.
+pub fn process(input: TokenStream) -> Result<TokenStream> {
+    let parsed: JoinInput = syn::parse2(input)?;
+    Ok(generate(parsed))
+}
+
+/// Generate code for a concurrent join.
+fn generate(input: JoinInput) -> TokenStream {
+    let mut ctx = GenContext::new().async_mode();
+    if let Some(ref with) = input.handlers.with_clause {
+        keywords::with_ctx::apply_to_context(with, &mut ctx);
+    }
+
+    let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
+
+    let futures: Vec<_> = (0..input.entries.len()).map(|i| format_ident!("__join_future_{}", i)).collect();
+    let outputs: Vec<_> = (0..input.entries.len()).map(|i| format_ident!("__join_output_{}", i)).collect();
+    let values: Vec<_> = (0..input.entries.len()).map(|i| format_ident!("__join_value_{}", i)).collect();
+    let exprs: Vec<TokenStream> = input
+        .entries
+        .iter()
+        .map(|e| {
+            let future = &e.future;
+            transform_nested(quote! { #future })
+        })
+        .collect();
+    let labels: Vec<&str> = input.entries.iter().map(|e| e.label.as_str()).collect();
+
+    let core_logic = quote! {
+        #( let mut #futures = ::core::pin::pin!(#exprs); )*
+        #( let mut #outputs = ::core::option::Option::None; )*
+
+        // Poll every unfinished future on each wake until all are done
+        ::core::future::poll_fn(|__cx| {
+            let mut __pending = false;
+            #(
+                if #outputs.is_none() {
+                    match ::core::future::Future::poll(#futures.as_mut(), __cx) {
+                        ::core::task::Poll::Ready(__v) => #outputs = ::core::option::Option::Some(__v),
+                        ::core::task::Poll::Pending => __pending = true,
+                    }
+                }
+            )*
+            if __pending {
+                ::core::task::Poll::Pending
+            } else {
+                ::core::task::Poll::Ready(())
+            }
+        })
+        .await;
+
+        let mut __errors: ::handle_this::__Vec<::handle_this::Handled> = ::handle_this::__Vec::new();
+        #(
+            let #values = match #outputs.expect("join output missing") {
+                ::core::result::Result::Ok(__v) => ::core::option::Option::Some(__v),
+                ::core::result::Result::Err(__e) => {
+                    let __e: ::handle_this::__BoxedError = ::core::convert::Into::into(__e);
+                    __errors.push(
+                        ::handle_this::__wrap_frame(__e, file!(), line!(), column!()).kv("join", #labels)
+                    );
+                    ::core::option::Option::None
+                }
+            };
+        )*
+
+        // Chain in entry order, matching `try all`
+        let __chained_err = __errors.into_iter().fold(
+            ::core::option::Option::None,
+            |__prev: ::core::option::Option<::handle_this::Handled>, __current| {
+                ::core::option::Option::Some(match __prev {
+                    ::core::option::Option::Some(__prev) => __current.chain_after(__prev),
+                    ::core::option::Option::None => __current,
+                })
+            },
+        );
+
+        (|| -> ::core::result::Result<_, ::handle_this::Handled> {
+            match __chained_err {
+                ::core::option::Option::None => ::core::result::Result::Ok((
+                    #( #values.expect("join value missing"), )*
+                )),
+                ::core::option::Option::Some(__chained) => {
+                    // __err must be mutable because throw can transform it
+                    let mut __err = __chained;
+                    #[allow(unreachable_code)]
+                    { #error_handler }
+                }
+            }
+        })()
+    };
+
+    // Wrap with finally
+    let code = if let Some(ref finally_body) = input.handlers.finally {
+        let finally_transformed = transform_nested(finally_body.clone());
+        keywords::finally::wrap(core_logic, &finally_transformed)
+    } else {
+        core_logic
+    };
+
+    quote! { { #code } }
+}
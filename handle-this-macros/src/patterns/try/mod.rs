@@ -16,6 +16,11 @@ pub mod signal_handler;
 
 pub mod sync;
 pub mod async_impl;
+pub mod concurrent;
+pub mod stream;
+pub mod join;
+pub mod race;
+pub mod typed;
 pub mod iter;
 pub mod retry;
 pub mod cond;
@@ -0,0 +1,155 @@
+//! Async race: first success wins.
+//!
+//! - `async try race { primary: primary(), fallback: fallback() }` - labels are optional
+//!
+//! Each entry is a future resolving to a `Result` with the same `Ok` type.
+//! All of them are polled together in place (no spawning, no `futures`
+//! feature); the first to succeed gives the expression's value, and the
+//! others are dropped (cancelled) unfinished.
+//!
+//! If every entry fails, each failure is framed, tagged with a `race`
+//! attachment (the entry's label, or its index), and chained in entry
+//! order like `try any`, so `catch all Type |errors|` sees each one.
+//! Handlers run once, on the chained error, and can't use `break`/`continue`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, token, Result};
+
+use crate::keywords::{self, GenContext};
+use crate::nested::transform_nested;
+use super::error_handler;
+use super::handlers::{self, Handlers};
+use super::join::{parse_entries, JoinEntry};
+
+/// Parsed race input.
+struct RaceInput {
+    entries: Vec<JoinEntry>,
+    handlers: Handlers,
+}
+
+impl Parse for RaceInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Parse: race { [label:] future, ... }
+        let kw = keywords::parse_keyword(input, "race")?;
+        if !input.peek(token::Brace) {
+            return Err(syn::Error::new(
+                kw.span(),
+                "expected futures to race: `async try race { primary(), fallback() }`",
+            ));
+        }
+        let content;
+        braced!(content in input);
+        let entries = parse_entries(&content)?;
+        if entries.is_empty() {
+            return Err(syn::Error::new(kw.span(), "`async try race` needs at least one future"));
+        }
+
+        let handlers = handlers::parse(input)?;
+        if handlers.has_control_flow() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "handlers of `async try race` can't use `break`/`continue`/`return`; \
+                 the futures don't run in a loop at this site",
+            ));
+        }
+
+        Ok(RaceInput { entries, handlers })
+    }
+}
+
+/// Process `async try race { ... }` pattern.
+pub fn process(input: TokenStream) -> Result<TokenStream> {
+    let parsed: RaceInput = syn::parse2(input)?;
+    Ok(generate(parsed))
+}
+
+/// Generate code for a race.
+fn generate(input: RaceInput) -> TokenStream {
+    let mut ctx = GenContext::new().async_mode();
+    if let Some(ref with) = input.handlers.with_clause {
+        keywords::with_ctx::apply_to_context(with, &mut ctx);
+    }
+
+    let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
+
+    let futures: Vec<_> = (0..input.entries.len()).map(|i| format_ident!("__race_future_{}", i)).collect();
+    let done: Vec<_> = (0..input.entries.len()).map(|i| format_ident!("__race_done_{}", i)).collect();
+    let errors: Vec<_> = (0..input.entries.len()).map(|i| format_ident!("__race_error_{}", i)).collect();
+    let exprs: Vec<TokenStream> = input
+        .entries
+        .iter()
+        .map(|e| {
+            let future = &e.future;
+            transform_nested(quote! { #future })
+        })
+        .collect();
+    let labels: Vec<&str> = input.entries.iter().map(|e| e.label.as_str()).collect();
+
+    let core_logic = quote! {
+        #( let mut #futures = ::core::pin::pin!(#exprs); )*
+        #( let mut #done = false; )*
+        #( let mut #errors: ::core::option::Option<::handle_this::Handled> = ::core::option::Option::None; )*
+
+        // Poll every unfinished future on each wake until one succeeds or all fail
+        let __race_winner = ::core::future::poll_fn(|__cx| {
+            #(
+                if !#done {
+                    if let ::core::task::Poll::Ready(__r) = ::core::future::Future::poll(#futures.as_mut(), __cx) {
+                        #done = true;
+                        match __r {
+                            ::core::result::Result::Ok(__v) => {
+                                return ::core::task::Poll::Ready(::core::option::Option::Some(__v));
+                            }
+                            ::core::result::Result::Err(__e) => {
+                                let __e: ::handle_this::__BoxedError = ::core::convert::Into::into(__e);
+                                #errors = ::core::option::Option::Some(
+                                    ::handle_this::__wrap_frame(__e, file!(), line!(), column!()).kv("race", #labels)
+                                );
+                            }
+                        }
+                    }
+                }
+            )*
+            if #( #done )&&* {
+                ::core::task::Poll::Ready(::core::option::Option::None)
+            } else {
+                ::core::task::Poll::Pending
+            }
+        })
+        .await;
+
+        (|| -> ::core::result::Result<_, ::handle_this::Handled> {
+            match __race_winner {
+                ::core::option::Option::Some(__v) => ::core::result::Result::Ok(__v),
+                ::core::option::Option::None => {
+                    // Every entry failed: chain in entry order, matching `try any`
+                    let __chained_err = [#( #errors ),*].into_iter().flatten().fold(
+                        ::core::option::Option::None,
+                        |__prev: ::core::option::Option<::handle_this::Handled>, __current| {
+                            ::core::option::Option::Some(match __prev {
+                                ::core::option::Option::Some(__prev) => __current.chain_after(__prev),
+                                ::core::option::Option::None => __current,
+                            })
+                        },
+                    );
+                    // __err must be mutable because throw can transform it
+                    let mut __err = __chained_err.expect("race error missing");
+                    #[allow(unreachable_code)]
+                    { #error_handler }
+                }
+            }
+        })()
+    };
+
+    // Wrap with finally
+    let code = if let Some(ref finally_body) = input.handlers.finally {
+        let finally_transformed = transform_nested(finally_body.clone());
+        keywords::finally::wrap(core_logic, &finally_transformed)
+    } else {
+        core_logic
+    };
+
+    quote! { { #code } }
+}
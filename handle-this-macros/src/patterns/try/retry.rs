@@ -1,6 +1,12 @@
-//! Try while pattern: `try while condition { body } [handlers...]`
+//! Try while pattern: `try while condition [limit N][, backoff KIND] { body } [handlers...]`
 //!
-//! Retry loop - keeps trying while condition is true.
+//! Retry loop - keeps trying while condition is true. With `backoff`, the
+//! loop sleeps between attempts; with `limit`, it stops after `N` failures
+//! and chains every failure into the final error. Either way the final
+//! error records an `attempts` kv.
+//!
+//! `async try while` runs the same loop in an async block: the body may
+//! `.await` and the backoff is awaited instead of blocking the thread.
 //!
 //! # Signal Mode
 //!
@@ -17,7 +23,7 @@
 use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{Result, braced, token};
+use syn::{Expr, Result, braced, token};
 
 use crate::keywords::{self, GenContext};
 use crate::nested::transform_nested;
@@ -29,15 +35,23 @@ use super::signal_handler;
 /// Parsed try while input.
 struct TryWhileInput {
     condition: TokenStream,
+    /// `backoff` schedule as a `::handle_this::Backoff` expression
+    backoff: Option<TokenStream>,
+    /// `limit N` attempt cap
+    limit: Option<Expr>,
     body: TokenStream,
     handlers: Handlers,
 }
 
 impl Parse for TryWhileInput {
     fn parse(input: ParseStream) -> Result<Self> {
-        // Collect condition tokens until `{`
-        let mut cond_tokens = Vec::new();
-        while !input.is_empty() && !input.peek(token::Brace) {
+        // Collect condition tokens until `{`, `limit N` or `, backoff`
+        let mut cond_tokens: Vec<TokenTree> = Vec::new();
+        while !input.is_empty()
+            && !input.peek(token::Brace)
+            && !keywords::backoff::peek(input)
+            && !keywords::limit::peek(input, cond_tokens.last())
+        {
             let tt: TokenTree = input.parse()?;
             cond_tokens.push(tt);
         }
@@ -46,7 +60,20 @@ impl Parse for TryWhileInput {
             return Err(syn::Error::new(input.span(), "expected condition before `{`"));
         }
 
-        let condition: TokenStream = cond_tokens.into_iter().collect();
+        let condition: TokenStream = cond_tokens.iter().cloned().collect();
+
+        // `limit N` and `, backoff KIND` in either order
+        let mut backoff = None;
+        let mut limit = None;
+        loop {
+            if limit.is_none() && keywords::limit::peek(input, cond_tokens.last()) {
+                limit = Some(keywords::limit::parse(input)?);
+            } else if backoff.is_none() && keywords::backoff::peek(input) {
+                backoff = Some(keywords::backoff::parse(input)?);
+            } else {
+                break;
+            }
+        }
 
         // Parse body
         let content;
@@ -58,6 +85,8 @@ impl Parse for TryWhileInput {
 
         Ok(TryWhileInput {
             condition,
+            backoff,
+            limit,
             body,
             handlers,
         })
@@ -67,11 +96,23 @@ impl Parse for TryWhileInput {
 /// Process try while pattern.
 pub fn process(input: TokenStream) -> Result<TokenStream> {
     let parsed: TryWhileInput = syn::parse2(input)?;
-    Ok(generate(parsed))
+    Ok(generate(parsed, false))
+}
+
+/// Process `async try while`.
+pub fn process_async(input: TokenStream) -> Result<TokenStream> {
+    let parsed: TryWhileInput = syn::parse2(input)?;
+    if parsed.handlers.has_control_flow() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`break`/`continue` in handlers isn't supported in `async try while`",
+        ));
+    }
+    Ok(generate(parsed, true))
 }
 
 /// Generate code for try while (retry loop).
-fn generate(input: TryWhileInput) -> TokenStream {
+fn generate(input: TryWhileInput, is_async: bool) -> TokenStream {
     let mut ctx = GenContext::new();
     if let Some(ref with) = input.handlers.with_clause {
         keywords::with_ctx::apply_to_context(with, &mut ctx);
@@ -87,13 +128,18 @@ fn generate(input: TryWhileInput) -> TokenStream {
     // Check if there's an unconditional catch-all handler
     let has_catch_all = input.handlers.has_catch_all();
 
-    let core_logic = if has_control_flow {
+    let attempts = gen_attempts(input.backoff.as_ref(), input.limit.as_ref(), is_async);
+
+    let core_logic = if is_async {
+        let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
+        gen_retry_async(condition, &body, &error_handler, &ctx_chain, &attempts)
+    } else if has_control_flow {
         // Use SIGNAL MODE - transforms control flow to signals, allows error propagation
-        gen_retry_signal(condition, &body, &input.handlers, &ctx_chain, has_catch_all)
+        gen_retry_signal(condition, &body, &input.handlers, &ctx_chain, has_catch_all, &attempts)
     } else {
         // Use closure mode - better type inference, no control flow
         let error_handler = error_handler::generate_for_loop(&input.handlers, &ctx);
-        gen_retry_closure(condition, &body, &error_handler, &ctx_chain)
+        gen_retry_closure(condition, &body, &error_handler, &ctx_chain, &attempts)
     };
 
     let code = if let Some(ref finally_body) = input.handlers.finally {
@@ -106,6 +152,65 @@ fn generate(input: TryWhileInput) -> TokenStream {
     quote! { #code }
 }
 
+/// Attempt hooks spliced into the retry loop (mostly empty without
+/// `backoff` or `limit`).
+struct AttemptCode {
+    /// Declares the schedule, the cap and the failed-attempt counter
+    setup: TokenStream,
+    /// Extra stop condition, or-ed with `!cond`
+    exhausted: TokenStream,
+    /// Sleeps before a retry (runs after the condition check)
+    before_attempt: TokenStream,
+    /// Counts a failed attempt
+    on_failure: TokenStream,
+    /// The new `__last_err` given the failure's `__wrapped` error
+    record_err: TokenStream,
+    /// Tags the final error with the attempt count
+    tag_err: TokenStream,
+}
+
+fn gen_attempts(backoff: Option<&TokenStream>, limit: Option<&Expr>, is_async: bool) -> AttemptCode {
+    let mut code = AttemptCode {
+        setup: TokenStream::new(),
+        exhausted: quote! { false },
+        before_attempt: TokenStream::new(),
+        on_failure: TokenStream::new(),
+        record_err: quote! { __wrapped },
+        tag_err: TokenStream::new(),
+    };
+    if backoff.is_none() && limit.is_none() {
+        return code;
+    }
+    code.setup = quote! { let mut __attempts: u32 = 0; };
+    code.on_failure = quote! { __attempts += 1; };
+    code.tag_err = quote! { __err = __err.attempt_kv("attempts", ::core::cmp::max(__attempts, 1)); };
+    if let Some(backoff) = backoff {
+        code.setup.extend(quote! { let __backoff: ::handle_this::Backoff = #backoff; });
+        let sleep = if is_async {
+            quote! { ::handle_this::__async_delay(__backoff.delay(__attempts)).await; }
+        } else {
+            quote! { ::std::thread::sleep(__backoff.delay(__attempts)); }
+        };
+        code.before_attempt = quote! {
+            if __attempts > 0 {
+                #sleep
+            }
+        };
+    }
+    if let Some(limit) = limit {
+        code.setup.extend(quote! { let __limit: u32 = #limit; });
+        code.exhausted = quote! { __attempts >= __limit };
+        // Every failure is kept, newest outermost
+        code.record_err = quote! {
+            match __last_err.take() {
+                ::core::option::Option::Some(__prev) => __wrapped.chain_after(__prev),
+                ::core::option::Option::None => __wrapped,
+            }
+        };
+    }
+    code
+}
+
 // ============================================================
 // Closure Mode Generator (no control flow)
 // ============================================================
@@ -116,16 +221,20 @@ fn gen_retry_closure(
     body: &TokenStream,
     error_handler: &TokenStream,
     ctx_chain: &TokenStream,
+    attempts: &AttemptCode,
 ) -> TokenStream {
+    let AttemptCode { setup, exhausted, before_attempt, on_failure, record_err, tag_err } = attempts;
     quote! {
         (|| -> ::core::result::Result<_, ::handle_this::Handled> {
             let mut __last_err: ::core::option::Option<::handle_this::Handled> = ::core::option::Option::None;
+            #setup
 
             loop {
-                if !(#condition) {
+                if !(#condition) || #exhausted {
                     return match __last_err {
                         // __err must be mutable because throw can transform it
                         ::core::option::Option::Some(mut __err) => {
+                            #tag_err
                             #[allow(unreachable_code)]
                             { #error_handler }
                         }
@@ -136,6 +245,7 @@ fn gen_retry_closure(
                                 ::core::result::Result::Err(__e) => {
                                     // __err must be mutable because throw can transform it
                                     let mut __err = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #ctx_chain;
+                                    #tag_err
                                     #[allow(unreachable_code)]
                                     { #error_handler }
                                 }
@@ -144,12 +254,13 @@ fn gen_retry_closure(
                     };
                 }
 
+                #before_attempt
                 match ::handle_this::__try_block!(#body) {
                     ::core::result::Result::Ok(__v) => return ::core::result::Result::Ok(__v),
                     ::core::result::Result::Err(__e) => {
-                        __last_err = ::core::option::Option::Some(
-                            ::handle_this::__wrap_frame(__e, file!(), line!(), column!())
-                        );
+                        #on_failure
+                        let __wrapped = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                        __last_err = ::core::option::Option::Some(#record_err);
                     }
                 }
             }
@@ -157,6 +268,73 @@ fn gen_retry_closure(
     }
 }
 
+// ============================================================
+// Async Generator
+// ============================================================
+
+/// Generate `async try while`: the closure-mode loop in an async block.
+///
+/// Attempts run in non-`move` async blocks so the body and condition share
+/// the caller's state across attempts, as they do in the sync loop.
+fn gen_retry_async(
+    condition: &TokenStream,
+    body: &TokenStream,
+    error_handler: &TokenStream,
+    ctx_chain: &TokenStream,
+    attempts: &AttemptCode,
+) -> TokenStream {
+    let AttemptCode { setup, exhausted, before_attempt, on_failure, record_err, tag_err } = attempts;
+    let attempt = quote! {
+        async { ::core::result::Result::Ok::<_, ::handle_this::__BoxedError>({ #body }) }.await
+    };
+    quote! {
+        {
+            let __result: ::core::result::Result<_, ::handle_this::Handled> = async {
+                let mut __last_err: ::core::option::Option<::handle_this::Handled> = ::core::option::Option::None;
+                #setup
+
+                loop {
+                    if !(#condition) || #exhausted {
+                        return match __last_err {
+                            // __err must be mutable because throw can transform it
+                            ::core::option::Option::Some(mut __err) => {
+                                #tag_err
+                                #[allow(unreachable_code)]
+                                { #error_handler }
+                            }
+                            ::core::option::Option::None => {
+                                // Condition was false on first check - run body once
+                                match #attempt {
+                                    ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
+                                    ::core::result::Result::Err(__e) => {
+                                        // __err must be mutable because throw can transform it
+                                        let mut __err = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #ctx_chain;
+                                        #tag_err
+                                        #[allow(unreachable_code)]
+                                        { #error_handler }
+                                    }
+                                }
+                            }
+                        };
+                    }
+
+                    #before_attempt
+                    match #attempt {
+                        ::core::result::Result::Ok(__v) => return ::core::result::Result::Ok(__v),
+                        ::core::result::Result::Err(__e) => {
+                            #on_failure
+                            let __wrapped = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                            __last_err = ::core::option::Option::Some(#record_err);
+                        }
+                    }
+                }
+            }
+            .await;
+            __result
+        }
+    }
+}
+
 // ============================================================
 // Signal Mode Generator (control flow via signals)
 // ============================================================
@@ -177,7 +355,9 @@ fn gen_retry_signal(
     handlers: &Handlers,
     ctx_chain: &TokenStream,
     has_catch_all: bool,
+    attempts: &AttemptCode,
 ) -> TokenStream {
+    let AttemptCode { setup, exhausted, before_attempt, on_failure, record_err, tag_err } = attempts;
     let signal = signal_type();
     let handler_code = signal_handler::gen_signal_handler(handlers, ctx_chain);
 
@@ -199,15 +379,17 @@ fn gen_retry_signal(
 
     quote! {
         {
-            #[allow(unreachable_code)]
+            #[allow(unreachable_code, clippy::diverging_sub_expression)]
             match (|| -> ::core::result::Result<#signal<_>, ::handle_this::Handled> {
                 let mut __last_err: ::core::option::Option<::handle_this::Handled> = ::core::option::Option::None;
+                #setup
 
                 loop {
-                    if !(#condition) {
+                    if !(#condition) || #exhausted {
                         return match __last_err {
                             // __err must be mutable because throw can transform it
                             ::core::option::Option::Some(mut __err) => {
+                                #tag_err
                                 // Handler returns Ok(LoopSignal::*) or Err(e) for unmatched
                                 #[allow(unreachable_code)]
                                 { #handler_code }
@@ -221,6 +403,7 @@ fn gen_retry_signal(
                                     ::core::result::Result::Err(__e) => {
                                         // __err must be mutable because throw can transform it
                                         let mut __err = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #ctx_chain;
+                                        #tag_err
                                         #[allow(unreachable_code)]
                                         { #handler_code }
                                     }
@@ -229,14 +412,15 @@ fn gen_retry_signal(
                         };
                     }
 
+                    #before_attempt
                     match ::handle_this::__try_block!(#body) {
                         ::core::result::Result::Ok(__v) => {
                             return ::core::result::Result::Ok(#signal::Value(__v));
                         }
                         ::core::result::Result::Err(__e) => {
-                            __last_err = ::core::option::Option::Some(
-                                ::handle_this::__wrap_frame(__e, file!(), line!(), column!())
-                            );
+                            #on_failure
+                            let __wrapped = ::handle_this::__wrap_frame(__e, file!(), line!(), column!());
+                            __last_err = ::core::option::Option::Some(#record_err);
                         }
                     }
                 }
@@ -268,8 +268,12 @@ pub fn gen_control_signal_handler(
         match handler {
             Handler::Catch(catch) => {
                 let binding = &catch.binding;
-                // Transform nested patterns, then convert break/continue to __ctrl_* calls
+                // Transform nested patterns, then convert break/continue to __ctrl_* calls.
+                // A body that doesn't diverge recovers: its value is stored like the try's.
                 let body = transform_control_flow_nongeneric(transform_nested(catch.body.clone()));
+                let body = quote! {
+                    return ::handle_this::__ctrl_store_value(&mut __signal_value, { #body });
+                };
 
                 let check = match (&catch.type_path, catch.variant) {
                     (None, ChainVariant::Root) => {
@@ -313,7 +317,7 @@ pub fn gen_control_signal_handler(
 
                 let check = match (&inspect.type_path, inspect.variant) {
                     (None, _) => {
-                        gen_control_inspect(&binding, &inspect.guard, &body)
+                        gen_control_inspect(binding, &inspect.guard, &body)
                     }
                     (Some(type_path), variant) => {
                         gen_control_typed_inspect(variant, type_path, binding, &inspect.guard, &body)
@@ -342,7 +346,7 @@ fn gen_control_catchall(
     guard: &Option<Guard>,
     body: &TokenStream,
 ) -> TokenStream {
-    let bind_stmt = if binding.to_string() == "_" {
+    let bind_stmt = if binding == "_" {
         quote! {}
     } else {
         quote! { let #binding = __err; }
@@ -350,7 +354,7 @@ fn gen_control_catchall(
 
     match guard {
         Some(Guard::When(cond)) => {
-            let ref_bind = if binding.to_string() == "_" {
+            let ref_bind = if binding == "_" {
                 quote! {}
             } else {
                 quote! { let #binding = &__err; }
@@ -400,7 +404,7 @@ fn gen_control_typed(
         match guard {
             Some(Guard::When(cond)) => quote! {
                 {
-                    let #binding: ::std::vec::Vec<&#type_path> = #type_check;
+                    let #binding: ::handle_this::__Vec<&#type_path> = #type_check;
                     if !#binding.is_empty() && #cond {
                         { #body }
                     }
@@ -408,7 +412,7 @@ fn gen_control_typed(
             },
             Some(Guard::Match { expr, arms }) => quote! {
                 {
-                    let #binding: ::std::vec::Vec<&#type_path> = #type_check;
+                    let #binding: ::handle_this::__Vec<&#type_path> = #type_check;
                     if !#binding.is_empty() {
                         return match #expr { #arms };
                     }
@@ -416,7 +420,7 @@ fn gen_control_typed(
             },
             None => quote! {
                 {
-                    let #binding: ::std::vec::Vec<&#type_path> = #type_check;
+                    let #binding: ::handle_this::__Vec<&#type_path> = #type_check;
                     if !#binding.is_empty() {
                         { #body }
                     }
@@ -455,7 +459,7 @@ fn gen_control_inspect(
     guard: &Option<Guard>,
     body: &TokenStream,
 ) -> TokenStream {
-    let bind_stmt = if binding.to_string() == "_" {
+    let bind_stmt = if binding == "_" {
         quote! {}
     } else {
         quote! { let #binding = &__err; }
@@ -499,7 +503,7 @@ fn gen_control_typed_inspect(
         match guard {
             Some(Guard::When(cond)) => quote! {
                 {
-                    let #binding: ::std::vec::Vec<&#type_path> = #type_check;
+                    let #binding: ::handle_this::__Vec<&#type_path> = #type_check;
                     if !#binding.is_empty() && #cond {
                         { #body }
                     }
@@ -507,7 +511,7 @@ fn gen_control_typed_inspect(
             },
             _ => quote! {
                 {
-                    let #binding: ::std::vec::Vec<&#type_path> = #type_check;
+                    let #binding: ::handle_this::__Vec<&#type_path> = #type_check;
                     if !#binding.is_empty() {
                         { #body }
                     }
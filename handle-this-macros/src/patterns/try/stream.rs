@@ -0,0 +1,148 @@
+//! Per-item async stream processing.
+//!
+//! `async try stream item in stream { body } handlers...` drives a
+//! `futures::Stream` (`futures` feature) and runs the body once per item.
+//! A failing item gets its own frame and goes through the handlers:
+//!
+//! - a `catch` that matches handles it and the stream moves on
+//! - `continue` in a handler skips to the next item, `break` stops the stream
+//! - a `throw`, or an error no handler matches, ends the stream with that error
+//!
+//! Handlers always run in signal mode, since the loop is at the expansion
+//! site. The whole expression is `Result<()>`; body and handler values are
+//! discarded.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, token, Ident, Pat, Result};
+
+use crate::keywords::{self, GenContext};
+use crate::nested::transform_nested;
+use super::handlers::{self, Handler, Handlers};
+use super::iter::attempt_index;
+use super::signal::signal_type;
+use super::signal_handler;
+
+/// Parsed stream input.
+struct StreamInput {
+    binding: Pat,
+    stream: TokenStream,
+    body: TokenStream,
+    handlers: Handlers,
+}
+
+impl Parse for StreamInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Parse: pattern in stream { body }
+        let binding = if input.peek(Ident::peek_any) {
+            Pat::Ident(syn::PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: Ident::parse_any(input)?,
+                subpat: None,
+            })
+        } else {
+            Pat::parse_single(input)?
+        };
+        input.parse::<syn::Token![in]>()?;
+
+        // Collect stream tokens until `{`
+        let mut stream_tokens = Vec::new();
+        while !input.is_empty() && !input.peek(token::Brace) {
+            let tt: TokenTree = input.parse()?;
+            stream_tokens.push(tt);
+        }
+        if stream_tokens.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "missing stream expression: `async try stream x in STREAM { ... }`",
+            ));
+        }
+        let stream: TokenStream = stream_tokens.into_iter().collect();
+
+        let content;
+        braced!(content in input);
+        let body: TokenStream = content.parse()?;
+
+        let handlers = handlers::parse(input)?;
+        if handlers.handlers.iter().any(|h| matches!(h, Handler::TryCatch(_))) {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`try catch` isn't supported in `async try stream`; use `catch` with `continue`/`break`",
+            ));
+        }
+
+        Ok(StreamInput { binding, stream, body, handlers })
+    }
+}
+
+/// Process `async try stream x in s { }` pattern.
+pub fn process(input: TokenStream) -> Result<TokenStream> {
+    let parsed: StreamInput = syn::parse2(input)?;
+    Ok(generate(parsed))
+}
+
+/// Generate code for stream processing.
+fn generate(input: StreamInput) -> TokenStream {
+    let mut ctx = GenContext::new().async_mode();
+    if let Some(ref with) = input.handlers.with_clause {
+        keywords::with_ctx::apply_to_context(with, &mut ctx);
+    }
+
+    let binding = &input.binding;
+    let stream = &input.stream;
+    let body = transform_nested(input.body.clone());
+    let ctx_chain = keywords::with_ctx::gen_ctx_chain(&ctx);
+    let attempt_kv = match attempt_index(binding, stream) {
+        Some(index) => quote! { .attempt_kv("attempt", #index) },
+        None => TokenStream::new(),
+    };
+    let signal = signal_type();
+    let handler_code = signal_handler::gen_signal_handler(&input.handlers, &ctx_chain);
+
+    // The labeled block lets an unhandled error leave the loop with a value;
+    // plain `break`/`continue` from handlers still target the `while`
+    let core_logic = quote! {
+        let __result: ::core::result::Result<(), ::handle_this::Handled> = 'handle_stream: {
+            let mut __handle_stream = ::core::pin::pin!(#stream);
+            while let ::core::option::Option::Some(#binding) =
+                ::handle_this::__futures::StreamExt::next(&mut __handle_stream).await
+            {
+                // Not `move`: the body borrows the item and the enclosing scope
+                let __body: ::core::result::Result<_, ::handle_this::__BoxedError> = async {
+                    ::core::result::Result::Ok({ #body })
+                }.await;
+                let __e = match __body {
+                    ::core::result::Result::Ok(_) => continue,
+                    ::core::result::Result::Err(__e) => __e,
+                };
+                #[allow(unreachable_code)]
+                match (|| -> ::core::result::Result<#signal<()>, ::handle_this::Handled> {
+                    // __err must be mutable because throw can transform it
+                    let mut __err = ::handle_this::__wrap_frame(__e, file!(), line!(), column!()) #attempt_kv;
+                    #[allow(unreachable_code, clippy::diverging_sub_expression)]
+                    { #handler_code }
+                })() {
+                    ::core::result::Result::Ok(#signal::Value(())) => {}
+                    ::core::result::Result::Ok(#signal::Continue) => continue,
+                    ::core::result::Result::Ok(#signal::Break) => break,
+                    ::core::result::Result::Err(__e) => break 'handle_stream ::core::result::Result::Err(__e),
+                }
+            }
+            ::core::result::Result::Ok(())
+        };
+        __result
+    };
+
+    let code = if let Some(ref finally_body) = input.handlers.finally {
+        let finally_transformed = transform_nested(finally_body.clone());
+        keywords::finally::wrap(core_logic, &finally_transformed)
+    } else {
+        core_logic
+    };
+
+    quote! { { #code } }
+}
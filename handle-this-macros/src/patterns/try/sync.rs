@@ -13,7 +13,7 @@ use quote::quote;
 use syn::parse::{Parse, ParseStream};
 use syn::{Result, Ident, braced};
 
-use crate::keywords::{self, GenContext, peek_keyword, ChainVariant, Guard};
+use crate::keywords::{self, GenContext, peek_keyword, parse_keyword, ChainVariant, Guard};
 use crate::keywords::catch::CatchClause;
 use crate::keywords::throw::ThrowClause;
 use crate::keywords::inspect::InspectClause;
@@ -37,10 +37,18 @@ struct SyncTryInput {
     inspects: Vec<InspectClause>,
     try_catches: Vec<TryCatchClause>,
     finally: Option<TokenStream>,
+    /// `catch_finally_with`: fallible finally plus a double-failure combiner
+    finally_combiner: Option<keywords::finally::FinallyCombiner>,
     with_clause: Option<WithClause>,
     /// Explicit return type for direct mode: `try -> T { ... }`
     /// When present, forces direct mode and provides type annotation.
     explicit_type: Option<syn::Type>,
+    /// `to_option`: yield `Option<T>` instead of `Result<T>`
+    to_option: bool,
+    /// `unwrap_infallible`: yield `T` instead of `Result<T>`
+    unwrap_infallible: bool,
+    /// First `rethrow`/`escalate` in a catch body; those catches run as `try catch`
+    rethrow: Option<Ident>,
 }
 
 impl Parse for SyncTryInput {
@@ -74,6 +82,14 @@ impl Parse for SyncTryInput {
         let mut try_catches = Vec::new();
         let mut finally = None;
         let mut with_clause = None;
+        let mut on_ok = Vec::new();
+        let mut to_option = false;
+        let mut unwrap_infallible = false;
+        let mut throttle = None;
+        let mut ratelimit = None;
+        let mut finally_combiner = None;
+        let mut catch_panic = None;
+        let mut rethrow = None;
 
         while !input.is_empty() {
             // Check for `try catch` (result-returning catch)
@@ -83,7 +99,20 @@ impl Parse for SyncTryInput {
                 handlers.push(Handler::TryCatch(clause.clone()));
                 try_catches.push(clause);
             } else if peek_keyword(input, "catch") {
-                let clause = keywords::catch::parse(input)?;
+                if keywords::catch_panic::peek(input) {
+                    catch_panic = Some(input.span());
+                }
+                let mut clauses = keywords::catch::parse_rethrowable(input)?;
+                // `catch e { } escalate` ends the body with `escalate`
+                if peek_keyword(input, "escalate") {
+                    let keyword = parse_keyword(input, "escalate")?;
+                    clauses = clauses
+                        .into_iter()
+                        .map(|clause| keywords::rethrow::append_escalate(clause, keyword.clone()))
+                        .collect::<Result<_>>()?;
+                }
+                // `catch A | B` shares one body and guard across its clauses
+                let clause = clauses[clauses.len() - 1].clone();
                 // Catch bodies must be infallible - reject `?` operator
                 let has_question_mark = contains_question_mark(&clause.body)
                     || matches!(&clause.guard, Some(Guard::Match { arms, .. }) if contains_question_mark(arms));
@@ -93,8 +122,47 @@ impl Parse for SyncTryInput {
                         "catch handlers must be infallible; use `try catch { ... }` to return Result",
                     ));
                 }
-                handlers.push(Handler::Catch(clause.clone()));
-                catches.push(clause.clone());
+                if peek_keyword(input, "assert_no_panic") {
+                    keywords::assert_no_panic::parse(input)?;
+                    if let Some(keyword) = keywords::rethrow::find(&clause) {
+                        return Err(syn::Error::new(
+                            keyword.span(),
+                            format!("`{}` can't be combined with `assert_no_panic`", keyword),
+                        ));
+                    }
+                    if matches!(clause.guard, Some(Guard::Match { .. })) {
+                        return Err(syn::Error::new(
+                            clause.catch_span,
+                            "`assert_no_panic` can't be combined with a `match` guard; use `when` instead",
+                        ));
+                    }
+                    for clause in clauses {
+                        let clause = keywords::assert_no_panic::wrap(clause);
+                        handlers.push(Handler::TryCatch(clause.clone()));
+                        try_catches.push(clause);
+                    }
+                    continue;
+                }
+                // A body that can `rethrow` is fallible: it runs as `try catch`
+                if let Some(keyword) = keywords::rethrow::find(&clause) {
+                    if explicit_type.is_some() {
+                        return Err(syn::Error::new(
+                            keyword.span(),
+                            format!("`{}` can't be used with `try -> T`, which must not fail", keyword),
+                        ));
+                    }
+                    rethrow.get_or_insert(keyword);
+                    for clause in clauses {
+                        let clause = keywords::rethrow::wrap(clause)?;
+                        handlers.push(Handler::TryCatch(clause.clone()));
+                        try_catches.push(clause);
+                    }
+                    continue;
+                }
+                for clause in clauses {
+                    handlers.push(Handler::Catch(clause.clone()));
+                    catches.push(clause);
+                }
 
                 // Check for `catch Type {} else {}` - creates catch-all after typed catch
                 if clause.type_path.is_some() && input.peek(syn::Token![else]) {
@@ -114,9 +182,20 @@ impl Parse for SyncTryInput {
                         guard: None,
                         body: else_body,
                     };
-                    handlers.push(Handler::Catch(else_clause.clone()));
-                    catches.push(else_clause);
+                    if let Some(keyword) = keywords::rethrow::find(&else_clause).filter(|_| explicit_type.is_none()) {
+                        rethrow.get_or_insert(keyword);
+                        let else_clause = keywords::rethrow::wrap(else_clause)?;
+                        handlers.push(Handler::TryCatch(else_clause.clone()));
+                        try_catches.push(else_clause);
+                    } else {
+                        handlers.push(Handler::Catch(else_clause.clone()));
+                        catches.push(else_clause);
+                    }
                 }
+            } else if keywords::throw::peek_as(input) {
+                let clause = keywords::throw::parse_as(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
             } else if peek_keyword(input, "throw") {
                 let clause = keywords::throw::parse(input)?;
                 handlers.push(Handler::Throw(clause.clone()));
@@ -144,7 +223,109 @@ impl Parse for SyncTryInput {
                     handlers.push(Handler::Catch(else_clause.clone()));
                     catches.push(else_clause);
                 }
-            } else if peek_keyword(input, "inspect") {
+            } else if peek_keyword(input, "exit") {
+                let clause = keywords::exit::parse(input)?;
+                handlers.push(Handler::Catch(clause.clone()));
+                catches.push(clause);
+            } else if peek_keyword(input, "branch") {
+                for clause in keywords::branch::parse(input)? {
+                    if contains_question_mark(&clause.body) {
+                        return Err(syn::Error::new(
+                            clause.catch_span,
+                            "branch arms must be infallible; use `try catch { ... }` to return Result",
+                        ));
+                    }
+                    handlers.push(Handler::Catch(clause.clone()));
+                    catches.push(clause);
+                }
+            } else if peek_keyword(input, "group") {
+                let clause = keywords::group::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "throttle") {
+                let clause = keywords::throttle::parse(input)?;
+                if throttle.is_some() {
+                    return Err(syn::Error::new(
+                        clause.throttle_span,
+                        "multiple `throttle` clauses are not allowed; combine into a single key",
+                    ));
+                }
+                throttle = Some(clause);
+            } else if peek_keyword(input, "ratelimit_propagate") {
+                let clause = keywords::ratelimit_propagate::parse(input)?;
+                if ratelimit.is_some() {
+                    return Err(syn::Error::new(
+                        clause.span,
+                        "multiple `ratelimit_propagate` clauses are not allowed",
+                    ));
+                }
+                if contains_control_flow(&body) {
+                    return Err(syn::Error::new(
+                        clause.span,
+                        "`ratelimit_propagate` can't guard a try body containing break/continue",
+                    ));
+                }
+                if contains_question_mark(&clause.fallback) {
+                    return Err(syn::Error::new(
+                        clause.span,
+                        "ratelimit_propagate fallbacks must be infallible",
+                    ));
+                }
+                ratelimit = Some(clause);
+            } else if peek_keyword(input, "convert") {
+                let clause = keywords::convert::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "log_once") {
+                let clause = keywords::log_once::parse(input)?;
+                if contains_question_mark(&clause.body) {
+                    return Err(syn::Error::new(
+                        clause.inspect_span,
+                        "log_once handlers must be infallible; use `try catch { ... }` for fallible error handling",
+                    ));
+                }
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "with_correlation") {
+                let clause = keywords::with_correlation::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "metric_inc") {
+                let clause = keywords::metric_inc::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "trace_error") {
+                let clause = keywords::trace_error::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "on_ok") {
+                let clause = keywords::on_ok::parse(input)?;
+                if contains_question_mark(&clause.body) {
+                    return Err(syn::Error::new(
+                        clause.on_ok_span,
+                        "on_ok handlers must be infallible; they can't change the result",
+                    ));
+                }
+                on_ok.push(clause);
+            } else if peek_keyword(input, "on_err") {
+                let clause = keywords::on_err::parse(input)?;
+                if contains_question_mark(&clause.body) {
+                    return Err(syn::Error::new(
+                        clause.inspect_span,
+                        "on_err handlers must be infallible; use `try catch { ... }` for fallible error handling",
+                    ));
+                }
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "snapshot") {
+                let clause = keywords::snapshot::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if peek_keyword(input, "classify") {
+                let clause = keywords::classify::parse(input)?;
+                handlers.push(Handler::Inspect(clause.clone()));
+                inspects.push(clause);
+            } else if keywords::inspect::peek(input) {
                 let clause = keywords::inspect::parse(input)?;
                 // Inspect bodies must be infallible - reject `?` operator
                 let has_question_mark = contains_question_mark(&clause.body)
@@ -157,6 +338,26 @@ impl Parse for SyncTryInput {
                 }
                 handlers.push(Handler::Inspect(clause.clone()));
                 inspects.push(clause);
+            } else if peek_keyword(input, "continue_with") {
+                let clause = keywords::continue_with::parse(input)?;
+                if contains_question_mark(&clause.body) {
+                    return Err(syn::Error::new(
+                        clause.catch_span,
+                        "continue_with handlers must be infallible; use `try catch { ... }` to return Result",
+                    ));
+                }
+                handlers.push(Handler::Catch(clause.clone()));
+                catches.push(clause);
+            } else if peek_keyword(input, "report_and_continue") {
+                let clause = keywords::report_and_continue::parse(input)?;
+                if contains_question_mark(&clause.body) {
+                    return Err(syn::Error::new(
+                        clause.catch_span,
+                        "report_and_continue handlers must be infallible; use `try catch { ... }` to return Result",
+                    ));
+                }
+                handlers.push(Handler::Catch(clause.clone()));
+                catches.push(clause);
             } else if peek_keyword(input, "finally") {
                 let finally_span = input.span();
                 if finally.is_some() {
@@ -166,6 +367,21 @@ impl Parse for SyncTryInput {
                     ));
                 }
                 finally = Some(keywords::finally::parse(input)?);
+            } else if peek_keyword(input, "catch_finally_with") {
+                let combiner = keywords::finally::parse_combiner(input)?;
+                if finally_combiner.is_some() {
+                    return Err(syn::Error::new(
+                        combiner.span,
+                        "multiple `catch_finally_with` clauses are not allowed",
+                    ));
+                }
+                if contains_question_mark(&combiner.body) {
+                    return Err(syn::Error::new(
+                        combiner.span,
+                        "catch_finally_with must be infallible; it produces the final error",
+                    ));
+                }
+                finally_combiner = Some(combiner);
             } else if peek_keyword(input, "with") {
                 if with_clause.is_some() {
                     return Err(syn::Error::new(
@@ -174,6 +390,24 @@ impl Parse for SyncTryInput {
                     ));
                 }
                 with_clause = Some(keywords::with_ctx::parse(input)?);
+            } else if peek_keyword(input, "to_option") {
+                let span = keywords::to_option::parse(input)?;
+                if explicit_type.is_some() {
+                    return Err(syn::Error::new(
+                        span,
+                        "`to_option` can't be combined with direct mode (`try -> T { }`)",
+                    ));
+                }
+                to_option = true;
+            } else if peek_keyword(input, "unwrap_infallible") {
+                let span = keywords::unwrap_infallible::parse(input)?;
+                if explicit_type.is_some() {
+                    return Err(syn::Error::new(
+                        span,
+                        "`unwrap_infallible` is redundant in direct mode (`try -> T { }`), which already yields `T`",
+                    ));
+                }
+                unwrap_infallible = true;
             } else if input.peek(syn::Token![else]) {
                 // `else { }` is syntactic sugar for catch-all in direct mode (try -> T)
                 let else_token = input.parse::<syn::Token![else]>()?;
@@ -207,7 +441,7 @@ impl Parse for SyncTryInput {
                 let msg = if ident == "scope" {
                     "`scope` must appear before `try`, not after handlers. Use: `scope \"name\", try { } catch { }`".to_string()
                 } else {
-                    format!("unexpected keyword `{}`, expected catch/throw/inspect/finally/with/else", ident)
+                    format!("unexpected keyword `{}`, expected catch/throw/inspect/continue_with/finally/with/else", ident)
                 };
                 return Err(syn::Error::new(ident.span(), msg));
             }
@@ -217,6 +451,35 @@ impl Parse for SyncTryInput {
         // (handlers after them are unreachable)
         validate_handler_order(&handlers)?;
 
+        if let Some(combiner) = &finally_combiner {
+            if finally.is_none() {
+                return Err(syn::Error::new(
+                    combiner.span,
+                    "`catch_finally_with` needs a `finally` block: `try { } finally { } catch_finally_with |b, f| { }`",
+                ));
+            }
+            if explicit_type.is_some() {
+                return Err(syn::Error::new(
+                    combiner.span,
+                    "`catch_finally_with` can't be combined with direct mode (`try -> T { }`)",
+                ));
+            }
+        }
+
+        if let Some(span) = catch_panic {
+            if contains_control_flow(&body) {
+                return Err(syn::Error::new(
+                    span,
+                    "`catch panic` can't guard a try body containing break/continue/return",
+                ));
+            }
+        }
+
+        let body = keywords::catch_panic::wrap_body(body, catch_panic.is_some());
+        let body = keywords::on_ok::wrap_body(body, &on_ok);
+        let body = keywords::ratelimit_propagate::wrap_body(body, ratelimit.as_ref());
+        let body = keywords::throttle::wrap_body(body, throttle.as_ref());
+
         Ok(SyncTryInput {
             body,
             handlers,
@@ -225,14 +488,19 @@ impl Parse for SyncTryInput {
             inspects,
             try_catches,
             finally,
+            finally_combiner,
             with_clause,
             explicit_type,
+            to_option,
+            unwrap_infallible,
+            rethrow,
         })
     }
 }
 
 /// Validate that no handlers follow an untyped catch or try catch.
 /// Untyped catch/try_catch catches ALL errors, making subsequent handlers unreachable.
+/// Also rejects dead handlers (see `check_dead_handlers`).
 fn validate_handler_order(handlers: &[Handler]) -> Result<()> {
     let mut untyped_catch_span: Option<proc_macro2::Span> = None;
     let mut untyped_catch_is_try: bool = false;
@@ -275,12 +543,21 @@ fn validate_handler_order(handlers: &[Handler]) -> Result<()> {
         }
     }
 
-    Ok(())
+    super::common::check_dead_handlers(handlers)
 }
 
 /// Process sync try pattern.
 pub fn process(input: TokenStream) -> Result<TokenStream> {
     let parsed: SyncTryInput = syn::parse2(input)?;
+    // `rethrow` leaves the handler closure with `?`; control flow expands without one
+    if let Some(keyword) = &parsed.rethrow {
+        if handlers_have_control_flow(&parsed) {
+            return Err(syn::Error::new(
+                keyword.span(),
+                format!("`{}` can't be used when the try body or a handler uses break/continue/return", keyword),
+            ));
+        }
+    }
     Ok(generate(parsed))
 }
 
@@ -444,7 +721,24 @@ fn generate(input: SyncTryInput) -> TokenStream {
     // Wrap with finally if present
     let code = if let Some(ref finally_body) = input.finally {
         let finally_transformed = transform_nested(finally_body.clone());
-        keywords::finally::wrap(code, &finally_transformed)
+        match &input.finally_combiner {
+            Some(combiner) => {
+                let combiner = keywords::finally::FinallyCombiner {
+                    body: transform_nested(combiner.body.clone()),
+                    ..combiner.clone()
+                };
+                keywords::finally::wrap_fallible(code, &finally_transformed, &combiner)
+            }
+            None => keywords::finally::wrap(code, &finally_transformed),
+        }
+    } else {
+        code
+    };
+
+    let code = if input.to_option {
+        keywords::to_option::wrap(code)
+    } else if input.unwrap_infallible {
+        keywords::unwrap_infallible::wrap(code)
     } else {
         code
     };
@@ -511,14 +805,15 @@ fn can_skip_frame_wrapping(input: &SyncTryInput) -> bool {
         return false;
     }
     // Additional check: binding must be `_`
-    input.catches[0].binding.to_string() == "_"
+    input.catches[0].binding == "_"
 }
 
 /// Check if a catch can be checked early on raw Box<dyn Error>.
 /// Returns true for Root variant typed catches (any binding, any guard).
 /// These can use downcast_ref directly on Box<dyn Error> without Handled wrapper.
+/// Trait objects can't: `Box::downcast_ref` only takes concrete types.
 fn can_check_early(catch: &CatchClause) -> bool {
-    catch.type_path.is_some()  // Must be typed (not catch-all)
+    catch.type_path.as_ref().is_some_and(|tp| !keywords::parsing::is_dyn_type(tp))  // Typed, not dyn
         && matches!(catch.variant, ChainVariant::Root)  // Only root variant (downcast_ref)
 }
 
@@ -526,7 +821,7 @@ fn can_check_early(catch: &CatchClause) -> bool {
 /// Returns true for typed catches with `_` binding, no guard, and Root variant.
 fn is_early_exit_catch(catch: &CatchClause) -> bool {
     can_check_early(catch)
-        && catch.binding.to_string() == "_"  // Binding unused
+        && catch.binding == "_"  // Binding unused
         && catch.guard.is_none()  // No guard
 }
 
@@ -550,7 +845,7 @@ fn can_use_early_type_check(input: &SyncTryInput) -> Option<(Vec<&CatchClause>,
             early_exits.push(catch);
         } else if catch.type_path.is_none() && catch.guard.is_none() {
             // Catch-all - must also have `_` binding for this optimization
-            if catch.binding.to_string() == "_" {
+            if catch.binding == "_" {
                 catchall = Some(catch);
             } else {
                 // Catch-all needs binding, can't use optimization
@@ -649,7 +944,7 @@ fn generate_signal_mode(
 
         return quote! {
             {
-                #[allow(unreachable_code)]
+                #[allow(unreachable_code, clippy::diverging_sub_expression)]
                 match ::handle_this::__force_result_type::<_, ::handle_this::Handled>({ #body }) {
                     ::core::result::Result::Ok(__v) => ::core::result::Result::Ok(__v),
                     ::core::result::Result::Err(__handled_err) => {
@@ -719,7 +1014,7 @@ fn generate_signal_mode(
         // Use __ctrl_none_like to create Option<T> where T is inferred from the body Result.
         return quote! {
             {
-                #[allow(unreachable_code)]
+                #[allow(unreachable_code, clippy::diverging_sub_expression)]
                 {
                     let __body_result = ::handle_this::__try_block!(#body);
                     let mut __signal_value = ::handle_this::__ctrl_none_like(&__body_result);
@@ -775,7 +1070,7 @@ fn generate_signal_mode(
 
         return quote! {
             {
-                #[allow(unreachable_code)]
+                #[allow(unreachable_code, clippy::diverging_sub_expression)]
                 match ::handle_this::__force_result_type::<_, ::handle_this::Handled>({
                     // Evaluate body inline - control flow escapes here
                     match { #body } {
@@ -1361,7 +1656,7 @@ fn gen_throw_nested_direct(
             let inner = match guard {
                 Some(Guard::When(cond)) => quote! {
                     {
-                        let #binding_ident: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                        let #binding_ident: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                         if !#binding_ident.is_empty() && #cond {
                             #transform_body
                         }
@@ -1369,7 +1664,7 @@ fn gen_throw_nested_direct(
                 },
                 Some(Guard::Match { expr, arms }) => quote! {
                     {
-                        let #binding_ident: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                        let #binding_ident: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                         if !#binding_ident.is_empty() {
                             #[allow(unused_imports)]
                             use ::handle_this::__Thrown;
@@ -1381,7 +1676,7 @@ fn gen_throw_nested_direct(
                 },
                 None => quote! {
                     {
-                        let #binding_ident: ::std::vec::Vec<&#type_path> = __err.chain_all::<#type_path>();
+                        let #binding_ident: ::handle_this::__Vec<&#type_path> = __err.chain_all::<#type_path>();
                         if !#binding_ident.is_empty() {
                             #transform_body
                         }
@@ -1698,7 +1993,7 @@ fn gen_inline_catch(
                 match guard {
                     Some(Guard::When(cond)) => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() && #cond {
                                 #ok_body
                             } else {
@@ -1708,7 +2003,7 @@ fn gen_inline_catch(
                     },
                     Some(Guard::Match { expr, arms }) => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() {
                                 ::core::result::Result::Ok(match #expr { #arms })
                             } else {
@@ -1718,7 +2013,7 @@ fn gen_inline_catch(
                     },
                     None => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() {
                                 #ok_body
                             } else {
@@ -1790,7 +2085,7 @@ fn gen_inline_throw(
     match type_path {
         None => {
             // Untyped throw
-            let bind_stmt = if binding_ident.to_string() == "_" {
+            let bind_stmt = if binding_ident == "_" {
                 quote! {}
             } else {
                 quote! { let #binding_ident = &__err; }
@@ -1829,7 +2124,7 @@ fn gen_inline_throw(
                 match guard {
                     Some(Guard::When(cond)) => quote! {
                         {
-                            let #binding_ident: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding_ident: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding_ident.is_empty() && #cond {
                                 #transform_body
                             }
@@ -1838,7 +2133,7 @@ fn gen_inline_throw(
                     },
                     _ => quote! {
                         {
-                            let #binding_ident: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding_ident: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding_ident.is_empty() {
                                 #transform_body
                             }
@@ -1919,7 +2214,7 @@ fn gen_inline_inspect(
                 match guard {
                     Some(Guard::When(cond)) => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() && #cond {
                                 { #body }
                             }
@@ -1928,7 +2223,7 @@ fn gen_inline_inspect(
                     },
                     _ => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() {
                                 { #body }
                             }
@@ -2024,7 +2319,7 @@ fn gen_inline_try_catch(
                 match guard {
                     Some(Guard::When(cond)) => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() && #cond {
                                 #result_body
                             } else {
@@ -2034,7 +2329,7 @@ fn gen_inline_try_catch(
                     },
                     Some(Guard::Match { expr, arms }) => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() {
                                 match #expr { #arms }
                             } else {
@@ -2044,7 +2339,7 @@ fn gen_inline_try_catch(
                     },
                     None => quote! {
                         {
-                            let #binding: ::std::vec::Vec<&#tp> = #type_check;
+                            let #binding: ::handle_this::__Vec<&#tp> = #type_check;
                             if !#binding.is_empty() {
                                 #result_body
                             } else {
@@ -0,0 +1,82 @@
+//! Typed try: `try<E> { body } [with ...]`
+//!
+//! Yields `Result<T, Handled<E>>` instead of the erased `Handled`, so
+//! callers reach the concrete error with `source_ref()`, no downcast.
+//!
+//! `?` in the body converts into `E`, as in a function returning
+//! `Result<T, E>` (so `#[from]` conversions apply). The error then gets a
+//! frame at the `try` and the `with` context, if any. Handlers work on the
+//! erased error, so only `with` may follow the body.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, token, Result, Token, Type};
+
+use crate::keywords::{self, peek_keyword, GenContext};
+use crate::keywords::with_ctx::WithClause;
+use crate::nested::transform_nested;
+
+/// Parsed typed try input (after `try<`).
+struct TypedTryInput {
+    error_type: Type,
+    body: TokenStream,
+    with_clause: Option<WithClause>,
+}
+
+impl Parse for TypedTryInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let error_type: Type = input.parse()?;
+        input.parse::<Token![>]>()?;
+
+        if !input.peek(token::Brace) {
+            return Err(syn::Error::new(input.span(), "expected body: `try<E> { ... }`"));
+        }
+        let content;
+        braced!(content in input);
+        let body: TokenStream = content.parse()?;
+        if body.is_empty() {
+            return Err(syn::Error::new(input.span(), "try body cannot be empty: `try<E> { EXPR }`"));
+        }
+
+        let with_clause = if peek_keyword(input, "with") {
+            Some(keywords::with_ctx::parse(input)?)
+        } else {
+            None
+        };
+
+        if !input.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "`try<E>` takes no handlers (they see the erased error); \
+                 use `try { }` to handle it, or only `with` here",
+            ));
+        }
+
+        Ok(TypedTryInput { error_type, body, with_clause })
+    }
+}
+
+/// Process typed try pattern.
+pub fn process(input: TokenStream) -> Result<TokenStream> {
+    let parsed: TypedTryInput = syn::parse2(input)?;
+    Ok(generate(parsed))
+}
+
+/// Generate code for typed try.
+fn generate(input: TypedTryInput) -> TokenStream {
+    let mut ctx = GenContext::new();
+    if let Some(ref with) = input.with_clause {
+        keywords::with_ctx::apply_to_context(with, &mut ctx);
+    }
+    let ctx_chain = keywords::with_ctx::gen_ctx_chain(&ctx);
+
+    let error_type = &input.error_type;
+    let body = transform_nested(input.body);
+
+    quote! {
+        ::handle_this::__typed_try_block!(#error_type; #body).map_err(|__e| {
+            ::handle_this::Handled::<#error_type>::new(__e).frame(file!(), line!(), column!()) #ctx_chain
+        })
+    }
+}
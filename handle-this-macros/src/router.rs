@@ -29,10 +29,16 @@ pub fn route(input: TokenStream) -> Result<TokenStream> {
     match marker.to_string().as_str() {
         "SYNC" => r#try::sync::process(rest),
         "ASYNC" => r#try::async_impl::process(rest),
+        "CONCURRENT" => r#try::concurrent::process(rest),
+        "STREAM" => r#try::stream::process(rest),
+        "JOIN" => r#try::join::process(rest),
+        "RACE" => r#try::race::process(rest),
+        "TYPED" => r#try::typed::process(rest),
         "FOR" => r#try::iter::process_for(rest),
         "ANY" => r#try::iter::process_any(rest),
         "ALL" => r#try::iter::process_all(rest),
         "WHILE" => r#try::retry::process(rest),
+        "ASYNC_WHILE" => r#try::retry::process_async(rest),
         "REQUIRE" => crate::patterns::require::process(rest),
         "SCOPE" => crate::patterns::scope::process(rest),
         "WHEN" => r#try::cond::process(rest),
@@ -0,0 +1,44 @@
+//! `#[traced]`: name the enclosing function in frames.
+//!
+//! A sync function gets a guard as its first statement; an `async fn` has
+//! its body wrapped in a future that is current only while polled. Either
+//! way the name is `module_path!()` plus the function's identifier.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, ItemFn, Result};
+
+/// Expand `#[traced]` on a function.
+pub fn expand(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "`#[traced]` takes no arguments"));
+    }
+    let mut func: ItemFn = syn::parse2(item)?;
+
+    // A `#[context]` below expands first, so its frame lands inside the guard
+    let context = func.attrs.iter().position(|attr| {
+        attr.path().segments.last().is_some_and(|segment| segment.ident == "context")
+    });
+    if let Some(pos) = context {
+        let context = func.attrs.remove(pos);
+        return Ok(quote! { #context #[::handle_this::traced] #func });
+    }
+
+    let ident = func.sig.ident.to_string();
+    let name = ident.strip_prefix("r#").unwrap_or(&ident);
+    let path = quote! { ::core::concat!(::core::module_path!(), "::", #name) };
+
+    let body = &func.block;
+    func.block = if func.sig.asyncness.is_some() {
+        parse_quote! {{
+            ::handle_this::__traced_async(#path, async move #body).await
+        }}
+    } else {
+        parse_quote! {{
+            let __handle_this_traced = ::handle_this::__TracedGuard::enter(#path);
+            #body
+        }}
+    };
+    Ok(quote! { #func })
+}
+
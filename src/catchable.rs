@@ -0,0 +1,76 @@
+//! Matching errors by trait as well as by concrete type.
+//!
+//! Typed handlers (`catch T(e)`, `chain_any::<T>()`, ...) accept any
+//! [`Catchable`] type. Every concrete error type is one; a trait object
+//! becomes one when [`catchable!`](crate::catchable) lists the concrete
+//! types that implement the trait, so `catch dyn Retryable (e)` can try each.
+
+use crate::core_error::CoreError as StdError;
+
+/// A type typed handlers can look for in an error.
+///
+/// Implemented for every `'static` error type, and for trait objects via
+/// [`catchable!`](crate::catchable).
+pub trait Catchable: 'static {
+    /// View `err` as `Self`, if it is one.
+    fn cast<'a>(err: &'a (dyn StdError + 'static)) -> Option<&'a Self>;
+}
+
+impl<T: StdError + 'static> Catchable for T {
+    #[inline]
+    fn cast<'a>(err: &'a (dyn StdError + 'static)) -> Option<&'a T> {
+        err.downcast_ref::<T>()
+    }
+}
+
+/// Let handlers match a trait object: `catch dyn Trait (e) { ... }`.
+///
+/// Rust can't ask an error whether it implements a trait, so list the
+/// concrete types that do; they are tried in order. `catch impl Trait (e)`
+/// is the same match. Use it once per trait, where the trait is defined.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{catchable, handle, Result};
+/// use std::fmt;
+///
+/// trait Retryable {
+///     fn retry_after(&self) -> u64;
+/// }
+///
+/// #[derive(Debug)]
+/// struct Busy;
+/// impl fmt::Display for Busy {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str("busy") }
+/// }
+/// impl std::error::Error for Busy {}
+/// impl Retryable for Busy {
+///     fn retry_after(&self) -> u64 { 5 }
+/// }
+///
+/// catchable!(dyn Retryable: Busy);
+///
+/// let result: Result<u64> = handle! {
+///     try { Err(Busy)? }
+///     catch dyn Retryable (e) { e.retry_after() }
+/// };
+/// assert_eq!(result.unwrap(), 5);
+/// ```
+#[macro_export]
+macro_rules! catchable {
+    (dyn $trait_:path : $($ty:ty),+ $(,)?) => {
+        impl $crate::Catchable for dyn $trait_ {
+            fn cast<'a>(
+                err: &'a (dyn $crate::__StdError + 'static),
+            ) -> ::core::option::Option<&'a Self> {
+                $(
+                    if let ::core::option::Option::Some(e) = err.downcast_ref::<$ty>() {
+                        return ::core::option::Option::Some(e as &Self);
+                    }
+                )+
+                ::core::option::Option::None
+            }
+        }
+    };
+}
@@ -0,0 +1,156 @@
+//! Per-call-site circuit breaking - the `ratelimit_propagate` clause.
+//!
+//! Each call site keeps a small state machine in a global registry:
+//!
+//! - **Closed** - the body runs; failures within the window are counted and a
+//!   success clears them. Reaching the threshold opens the circuit.
+//! - **Open** - the body is skipped and the `else` fallback is returned until
+//!   the cooldown (one window) has passed.
+//! - **Half-open** - one trial run is let through. Success closes the
+//!   circuit, failure opens it again.
+//!
+//! ```
+//! use handle_this::{handle, Result};
+//!
+//! fn fetch(runs: &mut u32) -> Result<&'static str> {
+//!     handle! {
+//!         try { *runs += 1; Err("upstream down")? }
+//!         ratelimit_propagate 2/min else { "cached" }
+//!     }
+//! }
+//!
+//! let mut runs = 0;
+//! assert!(fetch(&mut runs).is_err());
+//! assert!(fetch(&mut runs).is_err());
+//! assert_eq!(fetch(&mut runs).unwrap(), "cached");
+//! assert_eq!(runs, 2);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// State of a call site's circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls run normally.
+    Closed,
+    /// Calls are skipped in favour of the fallback.
+    Open,
+    /// The cooldown passed and one trial call is in flight.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: CircuitState,
+    failures: VecDeque<Instant>,
+    opened_at: Instant,
+}
+
+impl Default for Breaker {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            failures: VecDeque::new(),
+            opened_at: Instant::now(),
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<&'static str, Breaker>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<&'static str, Breaker>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Whether a call at `site` may run its body.
+///
+/// An open circuit whose cooldown (`window`) has passed moves to half-open
+/// and admits this one call as the trial.
+pub fn admit(site: &'static str, window: Duration) -> bool {
+    let mut sites = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let breaker = sites.entry(site).or_default();
+    match breaker.state {
+        CircuitState::Closed => true,
+        CircuitState::Open if breaker.opened_at.elapsed() >= window => {
+            breaker.state = CircuitState::HalfOpen;
+            true
+        }
+        CircuitState::Open | CircuitState::HalfOpen => false,
+    }
+}
+
+/// Record a successful call at `site`, closing its circuit.
+pub fn record_success(site: &'static str) {
+    let mut sites = registry().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(breaker) = sites.get_mut(site) {
+        breaker.state = CircuitState::Closed;
+        breaker.failures.clear();
+    }
+}
+
+/// Record a failed call at `site`.
+///
+/// Opens the circuit once `threshold` failures fall within `window`, or
+/// immediately when the failure was a half-open trial.
+pub fn record_failure(site: &'static str, threshold: u32, window: Duration) {
+    let now = Instant::now();
+    let mut sites = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let breaker = sites.entry(site).or_default();
+
+    while breaker.failures.front().is_some_and(|t| now.duration_since(*t) >= window) {
+        breaker.failures.pop_front();
+    }
+    breaker.failures.push_back(now);
+
+    if breaker.state == CircuitState::HalfOpen || breaker.failures.len() >= threshold as usize {
+        breaker.state = CircuitState::Open;
+        breaker.opened_at = now;
+        breaker.failures.clear();
+    }
+}
+
+/// Current state of the circuit at `site` (`Closed` if never seen).
+pub fn state(site: &str) -> CircuitState {
+    let sites = registry().lock().unwrap_or_else(|e| e.into_inner());
+    sites.get(site).map_or(CircuitState::Closed, |b| b.state)
+}
+
+/// Forget all state for `site`, closing its circuit.
+pub fn reset(site: &str) {
+    registry().lock().unwrap_or_else(|e| e.into_inner()).remove(site);
+}
+
+/// One admitted call. Records a failure when dropped unless
+/// [`succeed`](Self::succeed) was called, so `?` and panics both count.
+#[doc(hidden)]
+#[must_use]
+pub struct Attempt {
+    site: &'static str,
+    threshold: u32,
+    window: Duration,
+    done: bool,
+}
+
+impl Attempt {
+    /// Start a call at `site`, or `None` if its circuit is open.
+    #[inline]
+    pub fn begin(site: &'static str, threshold: u32, window: Duration) -> Option<Self> {
+        admit(site, window).then_some(Self { site, threshold, window, done: false })
+    }
+
+    /// Record the call as successful.
+    #[inline]
+    pub fn succeed(mut self) {
+        self.done = true;
+        record_success(self.site);
+    }
+}
+
+impl Drop for Attempt {
+    fn drop(&mut self) {
+        if !self.done {
+            record_failure(self.site, self.threshold, self.window);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! The error trait behind typed catches, whichever the build provides.
+//!
+//! [`CoreError`] is `std::error::Error` with `std`, and
+//! `core::error::Error` with `core_error` (Rust 1.81+). A `no_std` build
+//! without `core_error` gets a small trait of its own with the same
+//! shape: `Debug + Display`, an optional `source()`, and `is`/
+//! `downcast_ref`/`downcast` on the trait object. Error types implement
+//! it with an empty `impl CoreError for MyError {}`, as they would
+//! `std::error::Error`.
+
+#[cfg(feature = "std")]
+pub use std::error::Error as CoreError;
+
+#[cfg(all(not(feature = "std"), feature = "core_error"))]
+pub use core::error::Error as CoreError;
+
+#[cfg(all(not(feature = "std"), not(feature = "core_error")))]
+pub use fallback::CoreError;
+
+#[cfg(all(not(feature = "std"), not(feature = "core_error")))]
+mod fallback {
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use core::any::TypeId;
+    use core::fmt;
+
+    use crate::StringError;
+
+    mod private {
+        /// Keeps `type_id` from being overridden, as in `std::error::Error`.
+        #[derive(Debug)]
+        pub struct Internal;
+    }
+
+    /// An error: `Debug + Display`, with an optional lower-level cause.
+    ///
+    /// The stand-in for `std::error::Error` in `no_std` builds without
+    /// the `core_error` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::fmt;
+    /// use handle_this::CoreError;
+    ///
+    /// #[derive(Debug)]
+    /// struct SensorFault;
+    /// impl fmt::Display for SensorFault {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("sensor fault")
+    ///     }
+    /// }
+    /// impl CoreError for SensorFault {}
+    /// ```
+    pub trait CoreError: fmt::Debug + fmt::Display {
+        /// The lower-level error that caused this one, if any.
+        fn source(&self) -> Option<&(dyn CoreError + 'static)> {
+            None
+        }
+
+        #[doc(hidden)]
+        fn type_id(&self, _: private::Internal) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<Self>()
+        }
+    }
+
+    macro_rules! impl_downcast {
+        ($($bounds:tt)*) => {
+            impl dyn CoreError $($bounds)* {
+                /// Whether the error is a `T`.
+                #[inline]
+                pub fn is<T: CoreError + 'static>(&self) -> bool {
+                    self.type_id(private::Internal) == TypeId::of::<T>()
+                }
+
+                /// The error as a `T`, if it is one.
+                #[inline]
+                pub fn downcast_ref<T: CoreError + 'static>(&self) -> Option<&T> {
+                    if self.is::<T>() {
+                        // SAFETY: the sealed `type_id` just confirmed the concrete type
+                        unsafe { Some(&*(self as *const Self as *const T)) }
+                    } else {
+                        None
+                    }
+                }
+
+                /// The error as a mutable `T`, if it is one.
+                #[inline]
+                pub fn downcast_mut<T: CoreError + 'static>(&mut self) -> Option<&mut T> {
+                    if self.is::<T>() {
+                        // SAFETY: as in `downcast_ref`
+                        unsafe { Some(&mut *(self as *mut Self as *mut T)) }
+                    } else {
+                        None
+                    }
+                }
+
+                /// Take the boxed error as a `T`, or give the box back.
+                #[inline]
+                pub fn downcast<T: CoreError + 'static>(self: Box<Self>) -> Result<Box<T>, Box<Self>> {
+                    if self.is::<T>() {
+                        // SAFETY: as in `downcast_ref`; the allocation is a `T`
+                        unsafe { Ok(Box::from_raw(Box::into_raw(self) as *mut T)) }
+                    } else {
+                        Err(self)
+                    }
+                }
+            }
+        };
+    }
+
+    impl_downcast!(+ 'static);
+    impl_downcast!(+ Send + 'static);
+    impl_downcast!(+ Send + Sync + 'static);
+
+    // The boxed conversions std gives `Box<dyn Error>`, so `?` works in
+    // `try` bodies the same with or without `std`
+    impl<'a, E: CoreError + Send + Sync + 'a> From<E> for Box<dyn CoreError + Send + Sync + 'a> {
+        #[inline]
+        fn from(e: E) -> Self {
+            Box::new(e)
+        }
+    }
+
+    impl<'a> From<String> for Box<dyn CoreError + Send + Sync + 'a> {
+        #[inline]
+        fn from(message: String) -> Self {
+            Box::new(StringError(message))
+        }
+    }
+
+    impl<'a> From<&str> for Box<dyn CoreError + Send + Sync + 'a> {
+        #[inline]
+        fn from(message: &str) -> Self {
+            Box::new(StringError(message.into()))
+        }
+    }
+
+    impl CoreError for fmt::Error {}
+    impl CoreError for core::num::ParseIntError {}
+    impl CoreError for core::num::ParseFloatError {}
+    impl CoreError for core::num::TryFromIntError {}
+    impl CoreError for core::str::ParseBoolError {}
+    impl CoreError for core::str::Utf8Error {}
+    impl CoreError for core::char::ParseCharError {}
+    impl CoreError for core::array::TryFromSliceError {}
+    impl CoreError for alloc::string::FromUtf8Error {}
+}
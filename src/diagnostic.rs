@@ -0,0 +1,91 @@
+//! `miette::Diagnostic` for [`Handled`] (`miette` feature).
+//!
+//! - `code` and `severity` come from [`Handled::code`] and [`Handled::severity`]
+//! - `help` lists the key-value attachments, newest first
+//! - `related` holds one advice per frame, oldest first, then the chained
+//!   error (from `try all`, `join`, ...) as a full diagnostic of its own
+//!
+//! miette needs the source text to draw labeled spans, which an error
+//! doesn't carry, so frames are reported as related diagnostics instead.
+
+use core::fmt;
+use std::error::Error as StdError;
+
+use miette::Diagnostic;
+
+use crate::handled::Location;
+use crate::{Error, Handled, Severity};
+
+impl Diagnostic for Handled<Error> {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.code().map(|c| Box::new(c) as Box<dyn fmt::Display + 'a>)
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.severity().map(|s| match s {
+            Severity::Debug | Severity::Info => miette::Severity::Advice,
+            Severity::Warn => miette::Severity::Warning,
+            Severity::Error | Severity::Fatal => miette::Severity::Error,
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.all_kv().next()?;
+        Some(Box::new(Help(self)))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        if self.locations.is_empty() && self.chained.is_none() {
+            return None;
+        }
+        let frames = self.locations.iter().map(|loc| FrameDiagnostic::from_location(loc) as &dyn Diagnostic);
+        let chained = self.chained.as_deref().map(|c| c as &dyn Diagnostic);
+        Some(Box::new(frames.chain(chained)))
+    }
+}
+
+/// Attachments as `key: value`, one per line.
+struct Help<'a>(&'a Handled<Error>);
+
+impl fmt::Display for Help<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.all_kv().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// One frame of the trace, reported as a related advice.
+#[repr(transparent)]
+struct FrameDiagnostic(Location);
+
+impl FrameDiagnostic {
+    fn from_location(loc: &Location) -> &Self {
+        // SAFETY: `FrameDiagnostic` is `repr(transparent)` over `Location`
+        unsafe { &*(loc as *const Location as *const Self) }
+    }
+}
+
+impl fmt::Debug for FrameDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for FrameDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at {}:{}:{}", self.0.file, self.0.line, self.0.col)
+    }
+}
+
+impl StdError for FrameDiagnostic {}
+
+impl Diagnostic for FrameDiagnostic {
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(miette::Severity::Advice)
+    }
+}
@@ -1,9 +1,50 @@
-//! Extension trait for Result types.
+//! Extension traits for Result and Option types.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 
 use crate::handled::IntoValue;
-use crate::Result;
+use crate::{Handled, Result};
+
+/// Errors a [`HandleExt`] result can carry.
+///
+/// Any [`CoreError`](crate::CoreError) that is `Send + Sync + 'static`. A [`Handled`] passes through as is; anything
+/// else is wrapped the first time a builder touches it.
+pub trait IntoHandled {
+    /// Convert into a type-erased [`Handled`].
+    fn into_handled(self) -> Handled;
+}
+
+impl<E: crate::CoreError + Send + Sync + 'static> IntoHandled for E {
+    #[inline]
+    fn into_handled(self) -> Handled {
+        Handled::wrap(self)
+    }
+}
 
-/// Extension trait for adding context to `Result<T, Handled>`.
+/// Extension trait for adding context to a `Result`.
+///
+/// Implemented for `Result<T, Handled>` and for any `Result<T, E>` whose
+/// error converts with [`IntoHandled`], so code that never uses `handle!`
+/// can still build up a trace.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{HandleExt, Result};
+/// use std::fs;
+///
+/// fn load(path: &str) -> Result<String> {
+///     fs::read_to_string(path).ctx("loading config").kv("path", path)
+/// }
+///
+/// let err = load("/no/such/file.toml").unwrap_err();
+/// assert!(err.downcast_ref::<std::io::Error>().is_some());
+/// assert_eq!(err.depth(), 2);
+/// assert_eq!(err.frames().next().unwrap().context, Some("loading config"));
+/// assert!(err.get_kv("path").is_some());
+/// ```
+#[allow(clippy::result_large_err)]
 pub trait HandleExt<T> {
     /// Chain another operation, adding a frame on error.
     fn then<U, F>(self, f: F) -> Result<U>
@@ -16,13 +57,28 @@ pub trait HandleExt<T> {
         F: FnOnce(T) -> Result<U>;
 
     /// Add context message to error.
-    fn context(self, ctx: impl Into<String>) -> Self;
+    fn context(self, ctx: impl Into<String>) -> Result<T>;
 
     /// Add key-value attachment to error with typed value.
-    fn attach(self, key: &'static str, val: impl IntoValue) -> Self;
+    fn attach(self, key: &'static str, val: impl IntoValue) -> Result<T>;
+
+    /// Add a frame at the caller with context `msg`, like [`Handled::ctx`].
+    fn ctx(self, msg: impl Into<String>) -> Result<T>;
+
+    /// Add a frame at the caller with `key: val` attached, like [`Handled::kv`].
+    fn kv(self, key: &'static str, val: impl IntoValue) -> Result<T>;
+
+    /// Add a frame at the caller, converting the error if needed.
+    fn here(self) -> Result<T>;
+
+    /// Unwrap the value, or panic with `msg` and the error's full trace.
+    ///
+    /// Unlike `Result::expect`, which shows the `Debug` form, the panic
+    /// message is the error's `Display` output: message, frames, and context.
+    fn expect_handled(self, msg: &str) -> T;
 }
 
-impl<T> HandleExt<T> for Result<T> {
+impl<T, E: IntoHandled> HandleExt<T> for core::result::Result<T, E> {
     #[track_caller]
     fn then<U, F>(self, f: F) -> Result<U>
     where
@@ -31,7 +87,7 @@ impl<T> HandleExt<T> for Result<T> {
         let loc = core::panic::Location::caller();
         match self {
             Ok(v) => f(v).map_err(|e| e.frame(loc.file(), loc.line(), loc.column())),
-            Err(e) => Err(e),
+            Err(e) => Err(e.into_handled()),
         }
     }
 
@@ -44,19 +100,89 @@ impl<T> HandleExt<T> for Result<T> {
         let ctx = ctx.into();
         match self {
             Ok(v) => f(v).map_err(|e| e.frame(loc.file(), loc.line(), loc.column()).ctx(ctx)),
-            Err(e) => Err(e),
+            Err(e) => Err(e.into_handled()),
         }
     }
 
     #[track_caller]
-    fn context(self, ctx: impl Into<String>) -> Self {
+    fn context(self, ctx: impl Into<String>) -> Result<T> {
+        self.ctx(ctx)
+    }
+
+    #[track_caller]
+    fn attach(self, key: &'static str, val: impl IntoValue) -> Result<T> {
+        self.kv(key, val)
+    }
+
+    #[track_caller]
+    fn ctx(self, msg: impl Into<String>) -> Result<T> {
         let loc = core::panic::Location::caller();
-        self.map_err(|e| e.frame(loc.file(), loc.line(), loc.column()).ctx(ctx))
+        self.map_err(|e| e.into_handled().frame(loc.file(), loc.line(), loc.column()).ctx(msg))
     }
 
     #[track_caller]
-    fn attach(self, key: &'static str, val: impl IntoValue) -> Self {
+    fn kv(self, key: &'static str, val: impl IntoValue) -> Result<T> {
         let loc = core::panic::Location::caller();
-        self.map_err(|e| e.frame(loc.file(), loc.line(), loc.column()).kv(key, val))
+        self.map_err(|e| e.into_handled().frame(loc.file(), loc.line(), loc.column()).kv(key, val))
+    }
+
+    #[track_caller]
+    fn here(self) -> Result<T> {
+        let loc = core::panic::Location::caller();
+        self.map_err(|e| e.into_handled().frame(loc.file(), loc.line(), loc.column()))
+    }
+
+    #[track_caller]
+    fn expect_handled(self, msg: &str) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => panic!("{}: {}", msg, e.into_handled()),
+        }
+    }
+}
+
+/// Extension trait for turning `None` into a `Handled` error.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{OptionExt, Result};
+///
+/// fn find(id: u32) -> Result<&'static str> {
+///     let user = [(1, "ada")].iter().find(|(k, _)| *k == id).map(|(_, v)| *v);
+///     user.or_err_kv("missing user", "id", id)
+/// }
+///
+/// assert_eq!(find(1).unwrap(), "ada");
+/// let err = find(7).unwrap_err();
+/// assert_eq!(err.message(), "missing user");
+/// assert_eq!(err.depth(), 1);
+/// ```
+#[allow(clippy::result_large_err)]
+pub trait OptionExt<T> {
+    /// Convert `None` into an error with `msg`, framed at the caller.
+    fn or_err(self, msg: impl Into<String>) -> Result<T>;
+
+    /// Like [`or_err`](OptionExt::or_err), also attaching `key: val` to the frame.
+    fn or_err_kv(self, msg: impl Into<String>, key: &'static str, val: impl IntoValue) -> Result<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[track_caller]
+    fn or_err(self, msg: impl Into<String>) -> Result<T> {
+        let loc = core::panic::Location::caller();
+        match self {
+            Some(v) => Ok(v),
+            None => Err(Handled::msg(msg).frame(loc.file(), loc.line(), loc.column())),
+        }
+    }
+
+    #[track_caller]
+    fn or_err_kv(self, msg: impl Into<String>, key: &'static str, val: impl IntoValue) -> Result<T> {
+        let loc = core::panic::Location::caller();
+        match self {
+            Some(v) => Ok(v),
+            None => Err(Handled::msg(msg).frame(loc.file(), loc.line(), loc.column()).kv(key, val)),
+        }
     }
 }
@@ -0,0 +1,155 @@
+//! Conversion into thiserror-style error enums.
+//!
+//! An application enum implements [`FromHandled`] once, usually with a
+//! [`Classifier`], and [`Handled::classify`] turns any error into it.
+//! `throw as Enum::Variant` in `handle!` does the same for one variant.
+
+use crate::core_error::CoreError as StdError;
+
+use crate::{Error, Handled};
+
+/// An error enum that can be built from a [`Handled`] by its root type.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{Classifier, FromHandled, Handled};
+/// use std::{fmt, io, num::ParseIntError};
+///
+/// #[derive(Debug)]
+/// enum AppError {
+///     Io(Handled<io::Error>),
+///     Parse(ParseIntError),
+///     Other(Handled),
+/// }
+///
+/// impl fmt::Display for AppError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             AppError::Io(e) => write!(f, "io: {}", e),
+///             AppError::Parse(e) => write!(f, "parse: {}", e),
+///             AppError::Other(e) => write!(f, "{}", e),
+///         }
+///     }
+/// }
+///
+/// impl FromHandled for AppError {
+///     fn from_handled(err: Handled) -> Result<Self, Handled> {
+///         Ok(Classifier::new(err)
+///             .traced(AppError::Io)
+///             .variant(AppError::Parse)
+///             .or(AppError::Other))
+///     }
+/// }
+///
+/// let err = Handled::wrap(io::Error::new(io::ErrorKind::NotFound, "gone")).frame("fs.rs", 4, 1);
+/// let app: AppError = err.classify().unwrap();
+/// // The frames survive in the variant's Display
+/// assert!(app.to_string().contains("fs.rs:4:1"));
+/// ```
+pub trait FromHandled: Sized {
+    /// Build `Self` from `err`, or hand it back if no variant fits.
+    #[allow(clippy::result_large_err)]
+    fn from_handled(err: Handled) -> core::result::Result<Self, Handled>;
+}
+
+impl Handled<Error> {
+    /// Convert into an error enum via its [`FromHandled`] impl.
+    #[allow(clippy::result_large_err)]
+    pub fn classify<T: FromHandled>(self) -> core::result::Result<T, Self> {
+        T::from_handled(self)
+    }
+
+    /// Wrap the root in an enum variant, keeping the trace, contexts,
+    /// chain, and fields.
+    ///
+    /// `variant` is usually a tuple variant (`AppError::Io`); its payload
+    /// type picks the root type to match. The error comes back unchanged
+    /// if the root isn't that type. This is what `throw as` runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::{fmt, num::ParseIntError};
+    ///
+    /// #[derive(Debug)]
+    /// enum AppError {
+    ///     Parse(ParseIntError),
+    /// }
+    /// # impl fmt::Display for AppError {
+    /// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// #         write!(f, "bad number")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for AppError {}
+    ///
+    /// let err = Handled::wrap("x".parse::<i32>().unwrap_err()).frame("cfg.rs", 9, 1);
+    /// let err = err.into_variant(AppError::Parse).unwrap();
+    /// assert!(matches!(err.downcast_ref::<AppError>(), Some(AppError::Parse(_))));
+    /// assert_eq!(err.frames().next().unwrap().file, "cfg.rs");
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn into_variant<T, V, F>(self, variant: F) -> core::result::Result<Self, Self>
+    where
+        T: StdError + 'static,
+        V: StdError + Send + Sync + 'static,
+        F: FnOnce(T) -> V,
+    {
+        let typed = self.downcast_handled::<T>()?;
+        Ok(typed.map_err(variant).erase())
+    }
+}
+
+/// Builder for [`FromHandled`] impls: tries variants in order until the
+/// root error matches one.
+pub struct Classifier<T> {
+    // `Err` until a variant matches
+    state: core::result::Result<T, Handled>,
+}
+
+impl<T> Classifier<T> {
+    /// Start classifying `err`.
+    pub fn new(err: Handled) -> Self {
+        Classifier { state: Err(err) }
+    }
+
+    /// Match a variant whose payload is the plain root error:
+    /// `Parse(ParseIntError)`. The trace is dropped.
+    #[allow(clippy::result_large_err)]
+    pub fn variant<E, F>(self, variant: F) -> Self
+    where
+        E: StdError + 'static,
+        F: FnOnce(E) -> T,
+    {
+        self.then(|err| err.downcast::<E>().map(variant))
+    }
+
+    /// Match a variant whose payload keeps the trace: `Io(Handled<io::Error>)`.
+    #[allow(clippy::result_large_err)]
+    pub fn traced<E, F>(self, variant: F) -> Self
+    where
+        E: StdError + 'static,
+        F: FnOnce(Handled<E>) -> T,
+    {
+        self.then(|err| err.downcast_handled::<E>().map(variant))
+    }
+
+    /// The matched value, or the error if no variant matched.
+    #[allow(clippy::result_large_err)]
+    pub fn finish(self) -> core::result::Result<T, Handled> {
+        self.state
+    }
+
+    /// The matched value, or `fallback` applied to the unmatched error.
+    pub fn or<F: FnOnce(Handled) -> T>(self, fallback: F) -> T {
+        self.finish().unwrap_or_else(fallback)
+    }
+
+    fn then<F>(self, f: F) -> Self
+    where
+        F: FnOnce(Handled) -> core::result::Result<T, Handled>,
+    {
+        Classifier { state: self.state.or_else(f) }
+    }
+}
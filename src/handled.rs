@@ -10,15 +10,26 @@ use alloc::{
     vec::Vec,
 };
 
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+
 #[cfg(feature = "std")]
 use std::borrow::Cow;
 #[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
 use std::sync::OnceLock;
+#[cfg(not(feature = "std"))]
+use crate::once::OnceLock;
 
 use core::fmt;
 
-#[cfg(feature = "std")]
-use std::error::Error as StdError;
+use crate::limits::{capture_enabled, trace_limits, DropPolicy};
+#[cfg(not(feature = "no-trace"))]
+use crate::limits::MAX_LOCATION_LIMIT;
+use crate::catchable::Catchable;
+
+use crate::core_error::CoreError as StdError;
 
 // ============================================================
 // Core types
@@ -61,24 +72,75 @@ pub struct Handled<E = Error> {
     pub(crate) contexts: Option<Vec<ContextEntry>>,
     /// Previous error in chain - used by `chain_after` for `catch any/all`.
     /// Stored separately to preserve the root error's type for `catch Type`.
-    #[cfg(feature = "std")]
     pub(crate) chained: Option<Box<Handled<Error>>>,
+    /// Typed fields - only allocated when `set_field()` used.
+    pub(crate) fields: Option<FieldMap>,
+    /// Thread the error was created on (only with `thread-info` feature).
+    #[cfg(feature = "thread-info")]
+    pub(crate) thread: ThreadInfo,
+    /// Native stack at creation (only with `backtrace` feature).
+    /// Shared so `clone()` doesn't need a `Clone` backtrace.
+    #[cfg(feature = "backtrace")]
+    pub(crate) backtrace: std::sync::Arc<std::backtrace::Backtrace>,
+    /// `tracing` span active at creation (only with `tracing` feature).
+    #[cfg(feature = "tracing")]
+    pub(crate) span_id: Option<tracing::Id>,
+    /// Wall-clock time the error was created (only with `timestamps` feature).
+    /// `None` for deserialized errors that carried no timestamp.
+    #[cfg(feature = "timestamps")]
+    pub(crate) created_at: Option<std::time::SystemTime>,
 }
 
 /// Type-erased error wrapper for when you don't need to preserve the concrete type.
 ///
-/// This is a newtype wrapper around `Box<dyn StdError>` that enables proper trait
+/// This is a newtype wrapper around `Box<dyn CoreError>` that enables proper trait
 /// resolution for typed catches. It does NOT implement `Error` itself (to avoid
 /// trait impl conflicts), but provides access to the inner error.
-#[cfg(feature = "std")]
-#[derive(Debug)]
-pub struct Error(Box<dyn StdError + Send + Sync + 'static>);
+///
+/// [`CoreError`](crate::CoreError) is whichever error trait the build has, so
+/// typed downcasting works the same in `no_std`.
+pub struct Error(Box<dyn AnyError>);
+
+/// The boxed error behind [`Error`], still reachable as `Any` when its type
+/// was known at construction (for `Handled::get_any`).
+trait AnyError: Send + Sync + 'static {
+    fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static);
+    fn as_any(&self) -> Option<&dyn core::any::Any>;
+    fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static>;
+}
 
-#[cfg(not(feature = "std"))]
-#[derive(Debug)]
-pub struct Error(Box<dyn fmt::Debug + fmt::Display + Send + Sync + 'static>);
+impl<E: StdError + Send + Sync + 'static> AnyError for E {
+    fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        Some(self)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+        self
+    }
+}
+
+/// An error that arrived already boxed; its concrete type is only reachable
+/// through `downcast_ref`.
+struct BoxedError(Box<dyn StdError + Send + Sync + 'static>);
+
+impl AnyError for BoxedError {
+    fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        None
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+        self.0
+    }
+}
 
-#[cfg(feature = "std")]
 impl Error {
     /// Create from any error type.
     #[inline]
@@ -89,66 +151,111 @@ impl Error {
     /// Create from a boxed error.
     #[inline]
     pub fn from_box(e: Box<dyn StdError + Send + Sync + 'static>) -> Self {
-        Self(e)
+        Self(Box::new(BoxedError(e)))
     }
 
     /// Get the inner error as a trait object reference.
     #[inline]
     pub fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
-        self.0.as_ref()
+        self.0.as_error()
     }
 
     /// Get the inner error as a trait object reference (non-Send/Sync for Error trait compat).
     #[inline]
     pub fn as_dyn_error(&self) -> &(dyn StdError + 'static) {
-        self.0.as_ref()
+        self.0.as_error()
     }
 
     /// Try to downcast to a specific error type.
     #[inline]
     pub fn downcast_ref<T: StdError + 'static>(&self) -> Option<&T> {
-        self.0.downcast_ref::<T>()
+        self.0.as_error().downcast_ref::<T>()
     }
 
     /// Try to downcast and consume the error.
     #[inline]
     pub fn downcast<T: StdError + 'static>(self) -> core::result::Result<T, Self> {
-        match self.0.downcast::<T>() {
+        if !self.0.as_error().is::<T>() {
+            return Err(self);
+        }
+        match self.0.into_error().downcast::<T>() {
             Ok(e) => Ok(*e),
-            Err(e) => Err(Self(e)),
+            Err(_) => unreachable!("type checked above"),
         }
     }
 
     /// Get the inner boxed error.
     pub fn into_inner(self) -> Box<dyn StdError + Send + Sync + 'static> {
-        self.0
+        self.0.into_error()
+    }
+
+    /// The inner error as `Any`.
+    ///
+    /// Errors passed in already boxed (as `?` does inside `handle!`) lost
+    /// their type on the way in; the common `std` error types among them
+    /// are recognized by downcasting.
+    pub(crate) fn as_any(&self) -> Option<&dyn core::any::Any> {
+        if let Some(any) = self.0.as_any() {
+            return Some(any);
+        }
+        #[cfg(feature = "std")]
+        {
+            let err = self.0.as_error();
+            macro_rules! probe {
+                ($($ty:ty),+) => {
+                    $(
+                        if let Some(e) = err.downcast_ref::<$ty>() {
+                            return Some(e);
+                        }
+                    )+
+                };
+            }
+            probe!(
+                std::io::Error,
+                core::fmt::Error,
+                core::num::ParseIntError,
+                core::num::ParseFloatError,
+                core::num::TryFromIntError,
+                core::str::ParseBoolError,
+                core::str::Utf8Error,
+                core::char::ParseCharError,
+                std::string::FromUtf8Error,
+                std::net::AddrParseError,
+                std::time::SystemTimeError
+            );
+        }
+        None
     }
 }
 
-#[cfg(not(feature = "std"))]
 impl Error {
-    /// Create from any error type.
+    /// Create from a display-only value, keeping it downcastable.
     #[inline]
-    pub fn new<E: fmt::Debug + fmt::Display + Send + Sync + 'static>(e: E) -> Self {
-        Self(Box::new(e))
+    pub fn from_display<T: fmt::Display + Send + Sync + 'static>(value: T) -> Self {
+        Self(Box::new(DisplayError::new(value)))
     }
 
-    /// Create from a boxed error.
+    /// Try to downcast a display-only value stored via [`Error::from_display`].
     #[inline]
-    pub fn from_box(e: Box<dyn fmt::Debug + fmt::Display + Send + Sync + 'static>) -> Self {
-        Self(e)
+    pub fn downcast_display<T: fmt::Display + 'static>(&self) -> Option<&T> {
+        self.downcast_ref::<DisplayError>()?.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Error").field(&self.0.as_error()).finish()
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
+        fmt::Display::fmt(self.0.as_error(), f)
     }
 }
 
 // From impl for Error - enables ? operator in try blocks
 // This doesn't conflict with From<T> for T because Error doesn't implement Error
-#[cfg(feature = "std")]
 impl<E: StdError + Send + Sync + 'static> From<E> for Error {
     fn from(e: E) -> Self {
         Error::new(e)
@@ -156,57 +263,171 @@ impl<E: StdError + Send + Sync + 'static> From<E> for Error {
 }
 
 /// Location in source code - cheap, no allocation.
+///
+/// Kept at 24 bytes (without `timestamps`), since every `Handled` stores
+/// four inline. The `#[traced]` function name lives in `LocationVec`'s
+/// side table instead.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct Location {
-    pub(crate) file: &'static str,  // Always from file!() macro
+    pub(crate) file: &'static str,  // From file!(), or interned when deserialized
     pub(crate) line: u32,
-    pub(crate) col: u32,
+    /// Saturates at `u16::MAX`
+    pub(crate) col: u16,
+    /// Times this frame was pushed in a row; repeats are collapsed
+    pub(crate) repeat: u16,
+    /// When this frame was pushed (only with `timestamps` feature)
+    #[cfg(feature = "timestamps")]
+    pub(crate) at: std::time::Instant,
+}
+
+#[cfg(all(not(feature = "timestamps"), target_pointer_width = "64"))]
+const _: () = assert!(core::mem::size_of::<Location>() == 24);
+
+impl Location {
+    #[inline]
+    pub(crate) fn new(file: &'static str, line: u32, col: u32) -> Self {
+        Self {
+            file,
+            line,
+            col: u16::try_from(col).unwrap_or(u16::MAX),
+            repeat: 1,
+            #[cfg(feature = "timestamps")]
+            at: std::time::Instant::now(),
+        }
+    }
+
+    #[inline]
+    fn same_place(&self, other: &Location) -> bool {
+        self.line == other.line && self.col == other.col && self.file == other.file
+    }
+
+    fn format_location(&self) -> String {
+        format!("{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
+/// Originating thread of an error (only with `thread-info` feature).
+#[cfg(feature = "thread-info")]
+#[derive(Debug, Clone)]
+pub(crate) struct ThreadInfo {
+    pub(crate) name: Option<Box<str>>,
+    /// `None` for deserialized errors - thread ids don't cross processes.
+    pub(crate) id: Option<std::thread::ThreadId>,
+}
+
+#[cfg(feature = "thread-info")]
+impl ThreadInfo {
+    #[inline]
+    pub(crate) fn current() -> Self {
+        let thread = std::thread::current();
+        Self {
+            name: thread.name().map(Into::into),
+            id: Some(thread.id()),
+        }
+    }
+}
+
+/// Capture the native stack for a new error (only with `backtrace` feature).
+///
+/// Follows `Backtrace::capture`: disabled unless `RUST_LIB_BACKTRACE` or
+/// `RUST_BACKTRACE` is set, so the feature costs little when switched off.
+#[cfg(feature = "backtrace")]
+#[inline]
+fn capture_backtrace() -> std::sync::Arc<std::backtrace::Backtrace> {
+    std::sync::Arc::new(std::backtrace::Backtrace::capture())
 }
 
 /// Inline storage for locations - avoids heap allocation for common case.
-/// Stores up to 4 frames inline (covers most error traces); overflows to Vec for deeper traces.
+/// Stores up to 4 frames inline (covers most error traces); overflows to the heap for deeper traces.
+#[cfg(not(feature = "no-trace"))]
 const INLINE_CAPACITY: usize = 4;
 
-#[derive(Debug)]
+#[cfg(not(feature = "no-trace"))]
+#[derive(Debug, Clone)]
 pub(crate) struct LocationVec {
     len: u8,
     inline: [core::mem::MaybeUninit<Location>; INLINE_CAPACITY],
-    overflow: Option<Vec<Location>>,
+    /// Heap part, only allocated for deep traces or `#[traced]` frames.
+    spill: Option<Box<Spill>>,
 }
 
-impl Clone for LocationVec {
-    fn clone(&self) -> Self {
-        let mut new = Self::new();
-        for loc in self.iter() {
-            new.push(*loc);
-        }
-        new
-    }
+#[cfg(not(feature = "no-trace"))]
+#[derive(Debug, Clone, Default)]
+struct Spill {
+    /// Locations past `INLINE_CAPACITY`
+    overflow: Vec<Location>,
+    /// Enclosing `#[traced]` function by location index; most frames have none
+    functions: Vec<(u8, &'static str)>,
 }
 
+#[cfg(not(feature = "no-trace"))]
 impl LocationVec {
     #[inline]
     pub const fn new() -> Self {
         Self {
             len: 0,
             inline: [core::mem::MaybeUninit::uninit(); INLINE_CAPACITY],
-            overflow: None,
+            spill: None,
         }
     }
 
+    /// Append a location, recording the `#[traced]` function it ran in.
     #[inline]
-    pub fn push(&mut self, loc: Location) {
+    pub fn push(&mut self, loc: Location, function: Option<&'static str>) {
         let idx = self.len as usize;
         if idx < INLINE_CAPACITY {
             self.inline[idx] = core::mem::MaybeUninit::new(loc);
-            self.len += 1;
-        } else if idx < DEFAULT_LOCATION_LIMIT {
+        } else if idx < MAX_LOCATION_LIMIT {
             // Spill to overflow
-            let overflow = self.overflow.get_or_insert_with(Vec::new);
-            overflow.push(loc);
-            self.len += 1;
+            self.spill.get_or_insert_with(Default::default).overflow.push(loc);
+        } else {
+            // Silently drop if at limit
+            return;
+        }
+        if let Some(function) = function {
+            self.spill.get_or_insert_with(Default::default).functions.push((idx as u8, function));
+        }
+        self.len += 1;
+    }
+
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut Location> {
+        let len = self.len as usize;
+        if len > INLINE_CAPACITY {
+            self.spill.as_mut().and_then(|s| s.overflow.last_mut())
+        } else if len > 0 {
+            // SAFETY: elements below len are initialized
+            Some(unsafe { self.inline[len - 1].assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// The `#[traced]` function location `idx` was pushed in, if any.
+    #[inline]
+    pub fn function(&self, idx: usize) -> Option<&'static str> {
+        let spill = self.spill.as_ref()?;
+        spill.functions.iter().find(|(i, _)| *i as usize == idx).map(|(_, f)| *f)
+    }
+
+    /// Remove the oldest location, shifting the rest down.
+    pub fn remove_first(&mut self) {
+        let len = self.len as usize;
+        if len == 0 {
+            return;
+        }
+        let inline_count = core::cmp::min(len, INLINE_CAPACITY);
+        self.inline.copy_within(1..inline_count, 0);
+        if let Some(spill) = self.spill.as_mut() {
+            if !spill.overflow.is_empty() {
+                self.inline[INLINE_CAPACITY - 1] = core::mem::MaybeUninit::new(spill.overflow.remove(0));
+            }
+            spill.functions.retain(|(i, _)| *i != 0);
+            for (i, _) in spill.functions.iter_mut() {
+                *i -= 1;
+            }
         }
-        // Silently drop if at limit
+        self.len -= 1;
     }
 
     #[inline]
@@ -219,24 +440,196 @@ impl LocationVec {
         self.len == 0
     }
 
+    /// Keep the first `len` locations.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len as usize {
+            return;
+        }
+        if let Some(spill) = self.spill.as_mut() {
+            spill.overflow.truncate(len.saturating_sub(INLINE_CAPACITY));
+            spill.functions.retain(|(i, _)| (*i as usize) < len);
+        }
+        // Inline locations are `Copy`; nothing to drop
+        self.len = len as u8;
+    }
+
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &Location> + '_ {
+    pub fn iter(&self) -> LocationIter<'_> {
         let inline_count = core::cmp::min(self.len as usize, INLINE_CAPACITY);
-        let inline_iter = (0..inline_count).map(move |i| {
-            // SAFETY: We only read initialized elements (i < inline_count)
-            unsafe { self.inline[i].assume_init_ref() }
-        });
-        let overflow_iter = self.overflow.iter().flat_map(|v| v.iter());
-        inline_iter.chain(overflow_iter)
+        // SAFETY: We only read initialized elements (below inline_count)
+        let inline = unsafe { core::slice::from_raw_parts(self.inline.as_ptr().cast::<Location>(), inline_count) };
+        let overflow = self.spill.as_ref().map_or(&[][..], |s| s.overflow.as_slice());
+        LocationIter { inline: inline.iter(), overflow: overflow.iter() }
+    }
+}
+
+/// With `no-trace` no location is ever recorded, so the storage is zero-sized.
+#[cfg(feature = "no-trace")]
+#[derive(Debug, Clone)]
+pub(crate) struct LocationVec;
+
+#[cfg(feature = "no-trace")]
+const _: () = assert!(core::mem::size_of::<LocationVec>() == 0);
+
+#[cfg(feature = "no-trace")]
+impl LocationVec {
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    #[inline]
+    pub fn push(&mut self, _loc: Location, _function: Option<&'static str>) {}
+
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut Location> {
+        None
+    }
+
+    #[inline]
+    pub fn function(&self, _idx: usize) -> Option<&'static str> {
+        None
+    }
+
+    #[inline]
+    pub fn remove_first(&mut self) {}
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    pub fn truncate(&mut self, _len: usize) {}
+
+    #[inline]
+    pub fn iter(&self) -> LocationIter<'_> {
+        LocationIter { inline: [].iter(), overflow: [].iter() }
+    }
+}
+
+/// Inline locations, then overflow; walks both ways, for `frames_rev()`.
+pub(crate) struct LocationIter<'a> {
+    inline: core::slice::Iter<'a, Location>,
+    overflow: core::slice::Iter<'a, Location>,
+}
+
+impl<'a> Iterator for LocationIter<'a> {
+    type Item = &'a Location;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inline.next().or_else(|| self.overflow.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inline.len() + self.overflow.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for LocationIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.overflow.next_back().or_else(|| self.inline.next_back())
     }
 }
 
+impl ExactSizeIterator for LocationIter<'_> {}
+
 /// Context entry attached to a specific location - expensive, has allocations.
 #[derive(Debug, Clone)]
 pub(crate) struct ContextEntry {
     pub(crate) location_idx: u16,  // Which location this attaches to
     pub(crate) message: Option<String>,
     pub(crate) attachments: Vec<(Cow<'static, str>, Value)>,
+    /// Pushed by `scope()`/`scope_kv()` rather than `ctx()`/`kv()`
+    pub(crate) is_scope: bool,
+    /// Pushed by `note_at()` - an annotated location, not a propagation step
+    pub(crate) is_note: bool,
+}
+
+/// Typed field storage keyed by `TypeId` - at most one value per type.
+#[derive(Default)]
+pub(crate) struct FieldMap(Vec<(core::any::TypeId, Box<dyn core::any::Any + Send + Sync>)>);
+
+impl FieldMap {
+    pub(crate) fn insert<T: core::any::Any + Send + Sync>(&mut self, value: T) {
+        let id = core::any::TypeId::of::<T>();
+        if let Some(slot) = self.0.iter_mut().find(|(k, _)| *k == id) {
+            slot.1 = Box::new(value);
+        } else {
+            self.0.push((id, Box::new(value)));
+        }
+    }
+
+    pub(crate) fn get<T: core::any::Any>(&self) -> Option<&T> {
+        let id = core::any::TypeId::of::<T>();
+        self.0.iter().find(|(k, _)| *k == id).and_then(|(_, v)| v.downcast_ref::<T>())
+    }
+
+    pub(crate) fn get_mut<T: core::any::Any>(&mut self) -> Option<&mut T> {
+        let id = core::any::TypeId::of::<T>();
+        self.0.iter_mut().find(|(k, _)| *k == id).and_then(|(_, v)| v.downcast_mut::<T>())
+    }
+
+    /// Copy the fields this crate sets; other values may not be `Clone`.
+    #[cfg(feature = "clone")]
+    pub(crate) fn clone_known(&self) -> Option<Self> {
+        let mut copy = FieldMap::default();
+        self.copy_into::<Code>(&mut copy);
+        self.copy_into::<Severity>(&mut copy);
+        self.copy_into::<Correlation>(&mut copy);
+        self.copy_into::<crate::ErrorKind>(&mut copy);
+        self.copy_into::<Tags>(&mut copy);
+        self.copy_into::<Group>(&mut copy);
+        self.copy_into::<Attempts>(&mut copy);
+        (!copy.0.is_empty()).then_some(copy)
+    }
+
+    #[cfg(feature = "clone")]
+    fn copy_into<T: core::any::Any + Send + Sync + Clone>(&self, to: &mut FieldMap) {
+        if let Some(value) = self.get::<T>() {
+            to.insert(value.clone());
+        }
+    }
+}
+
+/// Tag list stored in the field map by `Handled::tag`.
+#[derive(Clone)]
+struct Tags(Vec<Cow<'static, str>>);
+
+/// Group name stored in the field map by `Handled::in_group`.
+#[derive(Clone)]
+struct Group(Cow<'static, str>);
+
+/// Correlation id stored in the field map by `Handled::with_correlation`.
+#[derive(Clone)]
+struct Correlation(Cow<'static, str>);
+
+/// Error code stored in the field map by `Handled::with_code`.
+#[derive(Clone)]
+struct Code(Cow<'static, str>);
+
+/// `attempt`/`attempts` recorded by `try for`/`try all` over `.enumerate()`
+/// and by `try while` retries. Kept as a typed field so `get_kv` still
+/// finds them when trace capture is off.
+#[derive(Clone, Default)]
+struct Attempts {
+    attempt: Option<Value>,
+    attempts: Option<Value>,
+}
+
+impl fmt::Debug for FieldMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldMap").field("len", &self.0.len()).finish()
+    }
 }
 
 /// A typed value for structured logging attachments.
@@ -256,6 +649,16 @@ pub enum Value {
     Bool(bool),
     /// Null/None value
     Null,
+    /// Ordered list (`Vec<T>`)
+    List(Vec<Value>),
+    /// String-keyed map (`BTreeMap`, `HashMap`), kept sorted by key
+    Map(BTreeMap<String, Value>),
+    /// Raw bytes (`&[u8]`)
+    Bytes(Vec<u8>),
+    /// Time span (`Duration`)
+    Duration(core::time::Duration),
+    /// A secret, kept only as a hash of its text (see [`Value::redact`])
+    Redacted(u64),
 }
 
 impl Value {
@@ -263,6 +666,32 @@ impl Value {
     pub fn from<T: IntoValue>(v: T) -> Self {
         v.into_value()
     }
+
+    /// Keep only a hash of a sensitive value.
+    ///
+    /// The result displays as `***` and serializes as
+    /// `{"$redacted":"<hash>"}`, so the same secret can be correlated
+    /// across errors without ever being recorded. The hash is SipHash of
+    /// the value's text under a per-process random key (or one set with
+    /// [`set_redaction_key`](crate::set_redaction_key)), so it can't be
+    /// reversed by hashing guesses offline. Without `std` there is no
+    /// random key, so until one is set the hash is replaced by the constant
+    /// [`UNKEYED_REDACTION`](crate::UNKEYED_REDACTION).
+    ///
+    /// ```
+    /// use handle_this::Value;
+    ///
+    /// let pw = Value::redact("hunter2");
+    /// assert_eq!(pw.to_string(), "***");
+    /// assert_eq!(pw, Value::redact(String::from("hunter2")));
+    /// assert_ne!(pw, Value::redact("hunter3"));
+    /// ```
+    pub fn redact<T: IntoValue>(v: T) -> Self {
+        match v.into_value() {
+            Value::Redacted(hash) => Value::Redacted(hash),
+            value => Value::Redacted(crate::redact::hash(&value)),
+        }
+    }
 }
 
 impl fmt::Display for Value {
@@ -274,6 +703,35 @@ impl fmt::Display for Value {
             Value::Float(n) => write!(f, "{}", n),
             Value::Bool(b) => write!(f, "{}", b),
             Value::Null => write!(f, "null"),
+            Value::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            Value::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                f.write_str("}")
+            }
+            Value::Bytes(bytes) => {
+                f.write_str("0x")?;
+                for b in bytes {
+                    write!(f, "{:02x}", b)?;
+                }
+                Ok(())
+            }
+            Value::Duration(d) => write!(f, "{:?}", d),
+            Value::Redacted(_) => f.write_str("***"),
         }
     }
 }
@@ -452,6 +910,38 @@ impl<T: IntoValue> IntoValue for Option<T> {
     }
 }
 
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::List(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl<K: Into<String>, V: IntoValue> IntoValue for BTreeMap<K, V> {
+    fn into_value(self) -> Value {
+        Value::Map(self.into_iter().map(|(k, v)| (k.into(), v.into_value())).collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Into<String>, V: IntoValue, S> IntoValue for std::collections::HashMap<K, V, S> {
+    fn into_value(self) -> Value {
+        Value::Map(self.into_iter().map(|(k, v)| (k.into(), v.into_value())).collect())
+    }
+}
+
+// Byte slices attach as bytes; a `Vec<u8>` is a list like any other `Vec`
+impl IntoValue for &[u8] {
+    fn into_value(self) -> Value {
+        Value::Bytes(self.to_vec())
+    }
+}
+
+impl IntoValue for core::time::Duration {
+    fn into_value(self) -> Value {
+        Value::Duration(self)
+    }
+}
+
 // Reference implementation - deref and convert
 impl<T: IntoValue + Clone> IntoValue for &T {
     fn into_value(self) -> Value {
@@ -459,10 +949,6 @@ impl<T: IntoValue + Clone> IntoValue for &T {
     }
 }
 
-/// Default limits for trace depth
-pub const DEFAULT_LOCATION_LIMIT: usize = 32;
-pub const DEFAULT_CONTEXT_LIMIT: usize = 8;
-
 /// View into a single frame of the error trace.
 #[derive(Debug, Clone)]
 pub struct FrameView<'a> {
@@ -474,8 +960,22 @@ pub struct FrameView<'a> {
     pub col: u32,
     /// Optional context message
     pub context: Option<&'a str>,
+    /// `module::function` path of the enclosing `#[traced]` function, if any
+    pub function: Option<&'a str>,
     /// Key-value attachments (internal)
     attachments_inner: &'a [(Cow<'static, str>, Value)],
+    /// Pushed by a `scope` (internal)
+    is_scope: bool,
+    /// Pushed by `note_at` (internal)
+    is_note: bool,
+    /// Consecutive pushes collapsed into this frame (internal)
+    repeat_count: u32,
+    /// When this frame was pushed (internal)
+    #[cfg(feature = "timestamps")]
+    at: std::time::Instant,
+    /// When the first frame was pushed (internal)
+    #[cfg(feature = "timestamps")]
+    origin: std::time::Instant,
 }
 
 impl<'a> FrameView<'a> {
@@ -488,6 +988,52 @@ impl<'a> FrameView<'a> {
     pub fn attachments_str(&self) -> impl Iterator<Item = (&'a str, String)> + 'a {
         self.attachments_inner.iter().map(|(k, v)| (k.as_ref(), v.to_string()))
     }
+
+    /// Whether this frame was pushed by a `scope` rather than a plain frame.
+    pub fn is_scope(&self) -> bool {
+        self.is_scope
+    }
+
+    /// Whether this frame is an annotated location added by
+    /// [`Handled::note_at`] rather than a place the error passed through.
+    pub fn is_note(&self) -> bool {
+        self.is_note
+    }
+
+    /// How many times in a row the error passed through this frame.
+    ///
+    /// A frame pushed again at the same place (a loop or recursion
+    /// re-wrapping the error) is counted here instead of stored twice, as
+    /// long as the first push got no context. `1` for an ordinary frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("deep").push_frames([("walk.rs", 8, 5); 240]).frame("main.rs", 3, 1);
+    /// let counts: Vec<_> = err.frames().map(|f| f.repeat_count()).collect();
+    /// assert_eq!(counts, [240, 1]);
+    /// assert!(err.to_string().contains("... repeated 240 times"));
+    /// ```
+    pub fn repeat_count(&self) -> u32 {
+        self.repeat_count
+    }
+
+    /// When this frame was pushed.
+    #[cfg(feature = "timestamps")]
+    pub fn timestamp(&self) -> std::time::Instant {
+        self.at
+    }
+
+    /// Time between the first frame being pushed and this one.
+    ///
+    /// Shows how long the error took to bubble up to this frame.
+    /// Always zero for the first frame.
+    #[cfg(feature = "timestamps")]
+    pub fn elapsed_since_origin(&self) -> std::time::Duration {
+        self.at.saturating_duration_since(self.origin)
+    }
 }
 
 // ============================================================
@@ -513,7 +1059,6 @@ impl<T> TryCatch<T> for T {
 }
 
 // Downcast match - for type-erased errors (newtype, doesn't impl Error)
-#[cfg(feature = "std")]
 impl<T: StdError + 'static> TryCatch<T> for Error {
     #[inline]
     fn try_catch(&self) -> Option<&T> {
@@ -521,6 +1066,51 @@ impl<T: StdError + 'static> TryCatch<T> for Error {
     }
 }
 
+/// Hash one link's message and frames for `Handled::hash_trace`.
+fn hash_link<E: fmt::Display, H: core::hash::Hasher>(err: &Handled<E>, state: &mut H) {
+    use core::hash::Hash;
+    err.message().hash(state);
+    err.locations.len().hash(state);
+    for frame in err.frames() {
+        (frame.file, frame.line, frame.col, frame.context).hash(state);
+        (frame.is_scope, frame.is_note).hash(state);
+        frame.attachments_inner.len().hash(state);
+        for (key, value) in frame.attachments_inner {
+            key.hash(state);
+            hash_value(value, state);
+        }
+    }
+}
+
+/// `Value` can't derive `Hash` (floats); hash consistently with its `PartialEq`.
+fn hash_value<H: core::hash::Hasher>(value: &Value, state: &mut H) {
+    use core::hash::Hash;
+    core::mem::discriminant(value).hash(state);
+    match value {
+        Value::String(s) => s.hash(state),
+        Value::Int(i) => i.hash(state),
+        Value::Uint(u) => u.hash(state),
+        // 0.0 == -0.0, so they must hash alike
+        Value::Float(f) => (if *f == 0.0 { 0.0f64 } else { *f }).to_bits().hash(state),
+        Value::Bool(b) => b.hash(state),
+        Value::Null => {}
+        Value::List(items) => {
+            items.len().hash(state);
+            items.iter().for_each(|v| hash_value(v, state));
+        }
+        Value::Map(map) => {
+            map.len().hash(state);
+            for (k, v) in map {
+                k.hash(state);
+                hash_value(v, state);
+            }
+        }
+        Value::Bytes(bytes) => bytes.hash(state),
+        Value::Duration(d) => d.hash(state),
+        Value::Redacted(hash) => hash.hash(state),
+    }
+}
+
 // ============================================================
 // StringError helper
 // ============================================================
@@ -534,32 +1124,161 @@ impl fmt::Display for StringError {
     }
 }
 
-#[cfg(feature = "std")]
 impl StdError for StringError {}
 
-// ============================================================
-// Handled<E> implementation - generic over error type
-// ============================================================
+/// How serious an error is, set with [`Handled::with_severity`].
+///
+/// Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Diagnostic detail, expected in normal operation.
+    Debug,
+    /// Noteworthy but harmless.
+    Info,
+    /// Degraded, but the operation could carry on.
+    Warn,
+    /// The operation failed.
+    Error,
+    /// The process or service can't continue.
+    Fatal,
+}
 
-impl<E> Handled<E> {
-    /// Create a new Handled wrapper around an error.
-    /// Message is computed lazily on first access.
-    #[cfg(feature = "std")]
-    #[inline]
-    pub fn new(source: E) -> Self
-    where
-        E: fmt::Display,
-    {
-        Self {
-            source,
-            message: OnceLock::new(),
-            locations: LocationVec::new(),
-            contexts: None,
-            chained: None,
+impl Severity {
+    /// The next level up; `Fatal` stays `Fatal`.
+    pub fn raised(self) -> Self {
+        match self {
+            Severity::Debug => Severity::Info,
+            Severity::Info => Severity::Warn,
+            Severity::Warn => Severity::Error,
+            Severity::Error | Severity::Fatal => Severity::Fatal,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Debug => "debug",
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        })
+    }
+}
+
+/// How [`Handled::merge`] combines two errors into one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the first error and drop the second.
+    KeepFirst,
+    /// Keep the second error and drop the first.
+    KeepLast,
+    /// New message joining both with `"; "`; frames and attachments of
+    /// both are kept, first error's frames first.
+    CombineMessages,
+    /// Keep the first error with the second linked behind it via
+    /// [`Handled::chain_after`].
+    KeepFirstChainSecond,
+}
+
+/// Several errors combined into one by [`Handled::combine`].
+///
+/// Displays a count followed by each error's message, sectioned by
+/// group name in order of first appearance. Ungrouped errors come first.
+#[derive(Debug)]
+pub struct CombinedError(Vec<Handled<Error>>);
+
+impl CombinedError {
+    /// The combined errors, in the order they were given.
+    pub fn errors(&self) -> &[Handled<Error>] {
+        &self.0
+    }
+
+    /// Group names in order of first appearance (ungrouped errors excluded).
+    pub fn groups(&self) -> Vec<&str> {
+        let mut groups: Vec<&str> = Vec::new();
+        for name in self.0.iter().filter_map(|e| e.group()) {
+            if !groups.contains(&name) {
+                groups.push(name);
+            }
+        }
+        groups
+    }
+
+    /// Errors belonging to `group`.
+    pub fn in_group<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a Handled<Error>> + 'a {
+        self.0.iter().filter(move |e| e.group() == Some(group))
+    }
+}
+
+impl fmt::Display for CombinedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} error{}", self.0.len(), if self.0.len() == 1 { "" } else { "s" })?;
+        for e in self.0.iter().filter(|e| e.group().is_none()) {
+            write!(f, "\n  - {}", e.message())?;
         }
+        for group in self.groups() {
+            write!(f, "\n[{}]", group)?;
+            for e in self.in_group(group) {
+                write!(f, "\n  - {}", e.message())?;
+            }
+        }
+        Ok(())
     }
+}
+
+impl StdError for CombinedError {}
+
+/// Display-only value stored as an error, downcastable via `Any`.
+///
+/// Created by [`Handled::from_display`] for types that implement `Display`
+/// but not `Error`. Recover the original with [`Handled::downcast_display`].
+pub struct DisplayError(Box<dyn AnyDisplay>);
 
-    #[cfg(not(feature = "std"))]
+trait AnyDisplay: fmt::Display + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn core::any::Any;
+}
+
+impl<T: fmt::Display + Send + Sync + 'static> AnyDisplay for T {
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+impl DisplayError {
+    /// Wrap a display-only value.
+    pub fn new<T: fmt::Display + Send + Sync + 'static>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// Try to downcast to the original display type.
+    pub fn downcast_ref<T: fmt::Display + 'static>(&self) -> Option<&T> {
+        (*self.0).as_any().downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for DisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DisplayError").field(&format_args!("{}", self.0)).finish()
+    }
+}
+
+impl fmt::Display for DisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StdError for DisplayError {}
+
+// ============================================================
+// Handled<E> implementation - generic over error type
+// ============================================================
+
+impl<E> Handled<E> {
+    /// Create a new Handled wrapper around an error.
+    /// Message is computed lazily on first access.
     #[inline]
     pub fn new(source: E) -> Self
     where
@@ -570,7 +1289,18 @@ impl<E> Handled<E> {
             message: OnceLock::new(),
             locations: LocationVec::new(),
             contexts: None,
+            chained: None,
+            fields: None,
+            #[cfg(feature = "thread-info")]
+            thread: ThreadInfo::current(),
+            #[cfg(feature = "backtrace")]
+            backtrace: capture_backtrace(),
+            #[cfg(feature = "tracing")]
+            span_id: crate::trace::current_span_id(),
+            #[cfg(feature = "timestamps")]
+            created_at: Some(std::time::SystemTime::now()),
         }
+        .created()
     }
 
     /// Add a frame with location information.
@@ -578,8 +1308,74 @@ impl<E> Handled<E> {
     #[doc(hidden)]
     #[inline]
     pub fn frame(mut self, file: &'static str, line: u32, col: u32) -> Self {
-        if self.locations.len() < DEFAULT_LOCATION_LIMIT {
-            self.locations.push(Location { file, line, col });
+        self.push_frame(Location::new(file, line, col));
+        self
+    }
+
+    /// Like [`frame`](Self::frame), but never collapsed into the newest
+    /// frame: for a step that changes the error at the place it already
+    /// propagated from (`throw as`).
+    pub(crate) fn step_frame(mut self, file: &'static str, line: u32, col: u32) -> Self {
+        self.push_location(Location::new(file, line, col));
+        self
+    }
+
+    /// Append many `(file, line, col)` frames at once, oldest first.
+    ///
+    /// For replaying a recorded trace. Frames past the location limit (32 by
+    /// default, see [`set_trace_limits`](crate::set_trace_limits)) are
+    /// handled as with repeated `frame()` calls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("replayed").push_frames([("db.rs", 10, 1), ("api.rs", 22, 5)]);
+    /// assert_eq!(err.depth(), 2);
+    /// assert_eq!(err.location_string().as_deref(), Some("api.rs:22:5"));
+    /// ```
+    pub fn push_frames(
+        mut self,
+        frames: impl IntoIterator<Item = (&'static str, u32, u32)>,
+    ) -> Self {
+        for (file, line, col) in frames {
+            self.push_frame(Location::new(file, line, col));
+        }
+        self
+    }
+
+    /// Annotate a source position other than where the error propagated.
+    ///
+    /// Adds a frame marked as a note (see [`FrameView::is_note`]), so
+    /// formatters can tell "relevant location" from "passed through here",
+    /// e.g. the config line that caused the error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("timeout too short")
+    ///     .frame("src/client.rs", 40, 9)
+    ///     .note_at("config/app.toml", 12, 1, "timeout set here");
+    /// assert!(err.to_string().contains("config/app.toml:12:1 (note)"));
+    /// ```
+    pub fn note_at(
+        mut self,
+        file: &'static str,
+        line: u32,
+        col: u32,
+        note: impl Into<String>,
+    ) -> Self {
+        if let Some(location_idx) = self.push_location(Location::new(file, line, col)) {
+            self.push_context(ContextEntry {
+                location_idx,
+                message: Some(note.into()),
+                attachments: Vec::new(),
+                is_scope: false,
+                is_note: true,
+            });
         }
         self
     }
@@ -589,24 +1385,39 @@ impl<E> Handled<E> {
     #[doc(hidden)]
     #[inline]
     pub fn ctx(mut self, msg: impl Into<String>) -> Self {
+        if !capture_enabled() {
+            return self;
+        }
         let location_idx = self.locations.len().saturating_sub(1) as u16;
         let contexts = self.contexts.get_or_insert_with(Vec::new);
 
-        if contexts.len() < DEFAULT_CONTEXT_LIMIT {
-            // Check if we already have a context for this location
-            if let Some(entry) = contexts.iter_mut().find(|e| e.location_idx == location_idx) {
-                entry.message = Some(msg.into());
-            } else {
-                contexts.push(ContextEntry {
-                    location_idx,
-                    message: Some(msg.into()),
-                    attachments: Vec::new(),
-                });
-            }
+        // Check if we already have a context for this location
+        if let Some(entry) = contexts.iter_mut().find(|e| e.location_idx == location_idx) {
+            entry.message = Some(msg.into());
+        } else {
+            self.push_context(ContextEntry {
+                location_idx,
+                message: Some(msg.into()),
+                attachments: Vec::new(),
+                is_scope: false,
+                is_note: false,
+            });
         }
         self
     }
 
+    /// Add a context message built from the error itself, as in
+    /// `with |e| format!("failed at depth {}", e.depth())`.
+    #[doc(hidden)]
+    #[inline]
+    pub fn ctx_with<M: Into<String>>(self, f: impl FnOnce(&Self) -> M) -> Self {
+        if !capture_enabled() {
+            return self;
+        }
+        let msg = f(&self);
+        self.ctx(msg)
+    }
+
     /// Add a scope frame - creates a new frame for hierarchical context.
     /// Unlike `.ctx()` which modifies the current frame, this adds a new frame.
     #[doc(hidden)]
@@ -618,18 +1429,14 @@ impl<E> Handled<E> {
         col: u32,
         msg: impl Into<String>,
     ) -> Self {
-        if self.locations.len() < DEFAULT_LOCATION_LIMIT {
-            self.locations.push(Location { file, line, col });
-            let location_idx = (self.locations.len() - 1) as u16;
-
-            let contexts = self.contexts.get_or_insert_with(Vec::new);
-            if contexts.len() < DEFAULT_CONTEXT_LIMIT {
-                contexts.push(ContextEntry {
-                    location_idx,
-                    message: Some(msg.into()),
-                    attachments: Vec::new(),
-                });
-            }
+        if let Some(location_idx) = self.push_location(Location::new(file, line, col)) {
+            self.push_context(ContextEntry {
+                location_idx,
+                message: Some(msg.into()),
+                attachments: Vec::new(),
+                is_scope: true,
+                is_note: false,
+            });
         }
         self
     }
@@ -645,18 +1452,14 @@ impl<E> Handled<E> {
         msg: impl Into<String>,
         attachments: Vec<(Cow<'static, str>, Value)>,
     ) -> Self {
-        if self.locations.len() < DEFAULT_LOCATION_LIMIT {
-            self.locations.push(Location { file, line, col });
-            let location_idx = (self.locations.len() - 1) as u16;
-
-            let contexts = self.contexts.get_or_insert_with(Vec::new);
-            if contexts.len() < DEFAULT_CONTEXT_LIMIT {
-                contexts.push(ContextEntry {
-                    location_idx,
-                    message: Some(msg.into()),
-                    attachments,
-                });
-            }
+        if let Some(location_idx) = self.push_location(Location::new(file, line, col)) {
+            self.push_context(ContextEntry {
+                location_idx,
+                message: Some(msg.into()),
+                attachments,
+                is_scope: true,
+                is_note: false,
+            });
         }
         self
     }
@@ -664,68 +1467,859 @@ impl<E> Handled<E> {
     /// Add key-value attachment to the most recent frame with typed value.
     #[doc(hidden)]
     #[inline]
-    pub fn kv(mut self, key: &'static str, val: impl IntoValue) -> Self {
+    pub fn kv(self, key: &'static str, val: impl IntoValue) -> Self {
+        if !capture_enabled() {
+            return self;
+        }
+        self.push_kv(Cow::Borrowed(key), val.into_value())
+    }
+
+    /// Attach a sensitive value as a [`Value::Redacted`] hash.
+    ///
+    /// The raw value is never stored; `Display`, JSON and serde output show
+    /// `***` or the hash instead. In `handle!`, `with { password: secret pw }`
+    /// does the same.
+    ///
+    /// ```
+    /// use handle_this::{Handled, Value};
+    ///
+    /// let err = Handled::msg("login failed").kv_secret("password", "hunter2");
+    /// assert_eq!(err.get_kv("password"), Some(&Value::redact("hunter2")));
+    /// assert!(!err.to_string().contains("hunter2"));
+    /// ```
+    #[inline]
+    pub fn kv_secret(self, key: &'static str, val: impl IntoValue) -> Self {
+        if !capture_enabled() {
+            return self;
+        }
+        self.push_kv(Cow::Borrowed(key), Value::redact(val))
+    }
+
+    /// Add key-value attachment with a non-static key (e.g. `"x-request-id"`
+    /// or a runtime `String`) to the most recent frame.
+    #[doc(hidden)]
+    #[inline]
+    pub fn kv_owned(self, key: impl Into<Cow<'static, str>>, val: impl IntoValue) -> Self {
+        if !capture_enabled() {
+            return self;
+        }
+        self.push_kv(key.into(), val.into_value())
+    }
+
+    /// Record `attempt` or `attempts` from iteration and retries: an
+    /// attachment on the most recent frame, plus a typed field that
+    /// [`get_kv`](Self::get_kv) falls back to when capture is off.
+    #[doc(hidden)]
+    #[inline]
+    pub fn attempt_kv(mut self, key: &'static str, val: impl IntoValue) -> Self {
+        let val = val.into_value();
+        let fields = self.fields.get_or_insert_with(FieldMap::default);
+        let mut meta = fields.get::<Attempts>().cloned().unwrap_or_default();
+        match key {
+            "attempts" => meta.attempts = Some(val.clone()),
+            _ => meta.attempt = Some(val.clone()),
+        }
+        fields.insert(meta);
+        if !capture_enabled() {
+            return self;
+        }
+        self.push_kv(Cow::Borrowed(key), val)
+    }
+
+    /// Attach `key: val` to the most recent frame; callers check capture.
+    fn push_kv(mut self, key: Cow<'static, str>, val: Value) -> Self {
         let location_idx = self.locations.len().saturating_sub(1) as u16;
         let contexts = self.contexts.get_or_insert_with(Vec::new);
 
         // Find or create context entry for this location
         if let Some(entry) = contexts.iter_mut().find(|e| e.location_idx == location_idx) {
-            entry.attachments.push((Cow::Borrowed(key), val.into_value()));
-        } else if contexts.len() < DEFAULT_CONTEXT_LIMIT {
-            contexts.push(ContextEntry {
+            entry.attachments.push((key, val));
+        } else {
+            self.push_context(ContextEntry {
                 location_idx,
                 message: None,
-                attachments: vec![(Cow::Borrowed(key), val.into_value())],
+                attachments: vec![(key, val)],
+                is_scope: false,
+                is_note: false,
             });
         }
         self
     }
 
-    /// Get the error message, computing it lazily on first access.
-    pub fn message(&self) -> &str
-    where
-        E: fmt::Display,
-    {
-        self.message.get_or_init(|| self.source.to_string())
+    /// Append a location under the configured limit and drop policy.
+    ///
+    /// Returns the new location's index, or `None` if it was dropped.
+    fn push_location(&mut self, loc: Location) -> Option<u16> {
+        if !capture_enabled() {
+            return None;
+        }
+        let limits = trace_limits();
+        if self.locations.len() >= limits.location_limit() {
+            if limits.drop_policy() == DropPolicy::Newest || self.locations.is_empty() {
+                return None;
+            }
+            // Evict the oldest frame along with its context
+            self.locations.remove_first();
+            if let Some(contexts) = self.contexts.as_mut() {
+                contexts.retain(|c| c.location_idx != 0);
+                for entry in contexts.iter_mut() {
+                    entry.location_idx -= 1;
+                }
+            }
+        }
+        #[cfg(feature = "std")]
+        let function = crate::traced::current();
+        #[cfg(not(feature = "std"))]
+        let function = None;
+        self.locations.push(loc, function);
+        Some((self.locations.len() - 1) as u16)
+    }
+
+    /// Append a plain frame, collapsing it into the newest frame if that is
+    /// the same place and carries no context (an error re-wrapped in a loop
+    /// or by recursion).
+    fn push_frame(&mut self, loc: Location) {
+        let last_idx = self.locations.len().wrapping_sub(1) as u16;
+        let has_context = self
+            .contexts
+            .as_ref()
+            .is_some_and(|c| c.iter().any(|e| e.location_idx == last_idx));
+        if !has_context {
+            if let Some(last) = self.locations.last_mut().filter(|last| last.same_place(&loc)) {
+                last.repeat = last.repeat.saturating_add(1);
+                return;
+            }
+        }
+        self.push_location(loc);
+    }
+
+    /// Append a context entry under the configured limit and drop policy.
+    fn push_context(&mut self, entry: ContextEntry) {
+        if !capture_enabled() {
+            return;
+        }
+        let limits = trace_limits();
+        let contexts = self.contexts.get_or_insert_with(Vec::new);
+        if contexts.len() >= limits.context_limit() {
+            if limits.drop_policy() == DropPolicy::Newest || contexts.is_empty() {
+                return;
+            }
+            // Evict the context on the oldest frame
+            if let Some(oldest) = (0..contexts.len()).min_by_key(|&i| contexts[i].location_idx) {
+                contexts.remove(oldest);
+            }
+        }
+        contexts.push(entry);
+    }
+
+    /// Remove duplicate key-value attachments across all frames.
+    ///
+    /// A pair is a duplicate if the same key with an equal value appears on
+    /// this or any earlier (older) frame; the first occurrence is kept.
+    /// Same key with a different value is not a duplicate. Frames left with
+    /// neither a message nor attachments lose their context entry.
+    pub fn dedup_attachments(&mut self) {
+        let Some(contexts) = self.contexts.as_mut() else { return };
+        contexts.sort_by_key(|c| c.location_idx);
+
+        let mut seen: Vec<(Cow<'static, str>, Value)> = Vec::new();
+        for entry in contexts.iter_mut() {
+            entry.attachments.retain(|pair| {
+                if seen.contains(pair) {
+                    false
+                } else {
+                    seen.push(pair.clone());
+                    true
+                }
+            });
+        }
+        contexts.retain(|c| c.message.is_some() || !c.attachments.is_empty());
+    }
+
+    /// Remove and return every key-value attachment, oldest frame first.
+    ///
+    /// The trace and context messages are left in place. Useful for lifting
+    /// context into a structured event before passing the error on.
+    pub fn take_attachments(&mut self) -> Vec<(String, Value)> {
+        let Some(contexts) = self.contexts.as_mut() else { return Vec::new() };
+        contexts.sort_by_key(|c| c.location_idx);
+
+        let taken = contexts
+            .iter_mut()
+            .flat_map(|c| core::mem::take(&mut c.attachments))
+            .map(|(k, v)| (k.into_owned(), v))
+            .collect();
+        contexts.retain(|c| c.message.is_some() || c.is_scope || c.is_note);
+        if contexts.is_empty() {
+            self.contexts = None;
+        }
+        taken
+    }
+
+    /// Exact structural equality: same message, frames, contexts, and attachments.
+    ///
+    /// Unlike [`fingerprint`](Self::fingerprint), nothing is hashed away.
+    /// Sources aren't compared (they needn't be `PartialEq`), so two errors
+    /// of different types with the same message and trace are "the same".
+    pub fn is_same_as<F: fmt::Display>(&self, other: &Handled<F>) -> bool
+    where
+        E: fmt::Display,
+    {
+        self.message() == other.message()
+            && self.locations.len() == other.locations.len()
+            && self.frames().zip(other.frames()).all(|(a, b)| {
+                a.file == b.file
+                    && a.line == b.line
+                    && a.col == b.col
+                    && a.context == b.context
+                    && a.is_scope == b.is_scope
+                    && a.is_note == b.is_note
+                    && a.attachments_inner == b.attachments_inner
+            })
+    }
+
+    /// [`is_same_as`](Self::is_same_as) over the whole chain: every link has
+    /// the same message, frames, contexts, and attachments, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let make = || Handled::msg("timeout").frame("db.rs", 4, 1).chain_after(Handled::msg("reset"));
+    /// assert!(make().trace_eq(&make()));
+    /// assert!(!make().trace_eq(&make().ctx("retrying")));
+    /// ```
+    pub fn trace_eq<F: fmt::Display>(&self, other: &Handled<F>) -> bool
+    where
+        E: fmt::Display,
+    {
+        if !self.is_same_as(other) {
+            return false;
+        }
+        {
+            let (mut a, mut b) = (self.chained.as_deref(), other.chained.as_deref());
+            loop {
+                match (a, b) {
+                    (None, None) => break,
+                    (Some(x), Some(y)) if x.is_same_as(y) => {
+                        a = x.chained.as_deref();
+                        b = y.chained.as_deref();
+                    }
+                    _ => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Feed everything [`trace_eq`](Self::trace_eq) compares into `state`.
+    ///
+    /// Errors that are `trace_eq` hash the same, so a newtype can implement
+    /// `Hash` and `Eq` with these two and deduplicate errors in a `HashSet`
+    /// or cache key.
+    pub fn hash_trace<H: core::hash::Hasher>(&self, state: &mut H)
+    where
+        E: fmt::Display,
+    {
+        hash_link(self, state);
+        {
+            let mut link = self.chained.as_deref();
+            while let Some(l) = link {
+                hash_link(l, state);
+                link = l.chained.as_deref();
+            }
+        }
+    }
+
+    /// Remove synthetic frames and their contexts from the trace.
+    ///
+    /// Frames whose file starts with `<` (like the `<anyhow>` and `<eyre>`
+    /// frames added when importing interop errors) are dropped; contexts on
+    /// the remaining frames stay attached to the same frames.
+    pub fn strip_synthetic_frames(&mut self) {
+        if !self.locations.iter().any(|l| l.file.starts_with('<')) {
+            return;
+        }
+
+        let mut kept = LocationVec::new();
+        let mut remap: Vec<Option<u16>> = Vec::with_capacity(self.locations.len());
+        for (idx, loc) in self.locations.iter().enumerate() {
+            if loc.file.starts_with('<') {
+                remap.push(None);
+            } else {
+                remap.push(Some(kept.len() as u16));
+                kept.push(*loc, self.locations.function(idx));
+            }
+        }
+        self.locations = kept;
+
+        if let Some(contexts) = self.contexts.as_mut() {
+            contexts.retain_mut(|c| match remap.get(c.location_idx as usize) {
+                Some(Some(idx)) => {
+                    c.location_idx = *idx;
+                    true
+                }
+                _ => false,
+            });
+            if contexts.is_empty() {
+                self.contexts = None;
+            }
+        }
+    }
+
+    /// Attach a typed field, replacing any existing field of the same type.
+    ///
+    /// Fields carry arbitrary data (categories, ids, flags) alongside the
+    /// error without affecting its message or trace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Category { Transient, Fatal }
+    ///
+    /// let err = Handled::msg("timed out").set_field(Category::Transient);
+    /// assert_eq!(err.field::<Category>(), Some(&Category::Transient));
+    /// ```
+    pub fn set_field<T: core::any::Any + Send + Sync>(mut self, value: T) -> Self {
+        self.fields.get_or_insert_with(FieldMap::default).insert(value);
+        self
+    }
+
+    /// Get a typed field previously attached with [`Handled::set_field`].
+    pub fn field<T: core::any::Any>(&self) -> Option<&T> {
+        self.fields.as_ref()?.get::<T>()
+    }
+
+    /// Look up a `T` anywhere on this error: typed fields first, then the
+    /// source itself (or, for an erased `Handled<Error>`, the error inside
+    /// it), then a display-only payload from [`Handled::from_display`].
+    ///
+    /// Unlike [`Handled::downcast_ref`], `T` needn't implement `Error`.
+    /// `?` inside `handle!` boxes the error before this crate sees it, so
+    /// of those only the common `std` error types (`io::Error`,
+    /// `ParseIntError`, `Utf8Error`, ...) are found here; reach your own
+    /// error types through `downcast_ref`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// struct RequestId(u64);
+    ///
+    /// let err = Handled::wrap(std::io::Error::new(std::io::ErrorKind::Other, "io"))
+    ///     .set_field(RequestId(7));
+    /// assert!(err.get_any::<std::io::Error>().is_some());
+    /// assert_eq!(err.get_any::<RequestId>().map(|r| r.0), Some(7));
+    /// ```
+    pub fn get_any<T: core::any::Any>(&self) -> Option<&T>
+    where
+        E: 'static,
+    {
+        if let Some(value) = self.field::<T>() {
+            return Some(value);
+        }
+        let source = &self.source as &dyn core::any::Any;
+        if let Some(value) = source.downcast_ref::<T>() {
+            return Some(value);
+        }
+        let erased = source.downcast_ref::<Error>()?;
+        if let Some(value) = erased.as_any().and_then(|any| any.downcast_ref::<T>()) {
+            return Some(value);
+        }
+        let display = erased.downcast_ref::<DisplayError>()?;
+        (*display.0).as_any().downcast_ref::<T>()
+    }
+
+    /// Attach a tag, e.g. `"retryable"` or `"fatal"`.
+    ///
+    /// Tags are plain labels stored as a typed field; adding the same
+    /// tag twice has no effect.
+    pub fn tag(mut self, tag: impl Into<Cow<'static, str>>) -> Self {
+        let tag = tag.into();
+        let fields = self.fields.get_or_insert_with(FieldMap::default);
+        match fields.get_mut::<Tags>() {
+            Some(tags) => {
+                if !tags.0.contains(&tag) {
+                    tags.0.push(tag);
+                }
+            }
+            None => fields.insert(Tags(vec![tag])),
+        }
+        self
+    }
+
+    /// Check whether a tag was attached with [`Handled::tag`].
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().any(|t| t == tag)
+    }
+
+    /// Iterate over attached tags in insertion order.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.field::<Tags>().into_iter().flat_map(|t| t.0.iter().map(|s| s.as_ref()))
+    }
+
+    /// Place this error in a named group, replacing any previous group.
+    ///
+    /// [`Handled::combine`] sections its report by group name.
+    pub fn in_group(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.set_field(Group(name.into()))
+    }
+
+    /// Group name set with [`Handled::in_group`] or the `group` clause.
+    pub fn group(&self) -> Option<&str> {
+        self.field::<Group>().map(|g| g.0.as_ref())
+    }
+
+    /// Attach a request correlation id, replacing any previous one.
+    pub fn with_correlation(self, id: impl Into<Cow<'static, str>>) -> Self {
+        self.set_field(Correlation(id.into()))
+    }
+
+    /// Correlation id set with [`Handled::with_correlation`] or the
+    /// `with_correlation` clause.
+    ///
+    /// Falls back to chained errors, so an id attached before a `throw`
+    /// is still found on the transformed error.
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let first = Handled::msg("timeout").with_correlation("req-7");
+    /// let err = Handled::msg("upstream failed").chain_after(first);
+    /// assert_eq!(err.correlation_id(), Some("req-7"));
+    /// ```
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.field_or_chained::<Correlation>().map(|c| c.0.as_ref())
+    }
+
+    /// Attach a stable error code (e.g. `"E1042"`), replacing any previous one.
+    ///
+    /// Typed alternative to a `code` kv: read it back with [`Handled::code`].
+    pub fn with_code(self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.set_field(Code(code.into()))
+    }
+
+    /// Error code set with [`Handled::with_code`] or `with code "..."`.
+    ///
+    /// Falls back to chained errors, like [`Handled::correlation_id`].
+    ///
+    /// ```
+    /// use handle_this::{Handled, Severity};
+    ///
+    /// let err = Handled::msg("quota exceeded").with_code("E1042").with_severity(Severity::Warn);
+    /// assert_eq!(err.code(), Some("E1042"));
+    /// assert_eq!(err.severity(), Some(Severity::Warn));
+    /// ```
+    pub fn code(&self) -> Option<&str> {
+        self.field_or_chained::<Code>().map(|c| c.0.as_ref())
+    }
+
+    /// Set how serious this error is, replacing any previous severity.
+    pub fn with_severity(self, severity: Severity) -> Self {
+        self.set_field(severity)
+    }
+
+    /// Severity set with [`Handled::with_severity`] or `with severity Level`.
+    ///
+    /// Falls back to chained errors, like [`Handled::code`].
+    pub fn severity(&self) -> Option<Severity> {
+        self.field_or_chained::<Severity>().copied()
+    }
+
+    /// Raise the severity by one level (see [`Severity::raised`]).
+    ///
+    /// An error with no severity counts as [`Severity::Error`], so it
+    /// becomes `Fatal`. Used by the `escalate` catch keyword.
+    ///
+    /// ```
+    /// use handle_this::{Handled, Severity};
+    ///
+    /// let err = Handled::msg("retry budget spent").with_severity(Severity::Warn);
+    /// assert_eq!(err.escalate().severity(), Some(Severity::Error));
+    /// ```
+    pub fn escalate(self) -> Self {
+        let severity = self.severity().unwrap_or(Severity::Error).raised();
+        self.set_field(severity)
+    }
+
+    /// A field on this error, or else the nearest chained error that has one.
+    pub(crate) fn field_or_chained<T: core::any::Any>(&self) -> Option<&T> {
+        if let Some(value) = self.field::<T>() {
+            return Some(value);
+        }
+        let mut current = self.chained.as_deref();
+        while let Some(link) = current {
+            if let Some(value) = link.field::<T>() {
+                return Some(value);
+            }
+            current = link.chained.as_deref();
+        }
+        None
+    }
+
+    /// Get the error message, computing it lazily on first access.
+    pub fn message(&self) -> &str
+    where
+        E: fmt::Display,
+    {
+        self.message.get_or_init(|| self.source.to_string())
+    }
+
+    /// Try to get a reference to a specific error type.
+    ///
+    /// This works with both concrete error types (direct access) and
+    /// type-erased errors (runtime downcasting).
+    #[doc(hidden)]
+    #[inline]
+    pub fn try_catch<Target>(&self) -> Option<&Target>
+    where
+        E: TryCatch<Target>,
+    {
+        self.source.try_catch()
+    }
+
+    /// Get the underlying error source.
+    pub fn source_ref(&self) -> &E {
+        &self.source
+    }
+
+    /// Consume and return the underlying error.
+    pub fn into_source(self) -> E {
+        self.source
+    }
+
+    /// Name of the thread this error was created on, if it was named.
+    #[cfg(feature = "thread-info")]
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread.name.as_deref()
+    }
+
+    /// Id of the thread this error was created on.
+    ///
+    /// `None` for errors restored through serde.
+    #[cfg(feature = "thread-info")]
+    pub fn thread_id(&self) -> Option<std::thread::ThreadId> {
+        self.thread.id
+    }
+
+    /// Record the current thread as this error's origin.
+    ///
+    /// Errors capture their thread on creation; use this to re-attribute
+    /// one handed across threads (e.g. received over a channel).
+    #[cfg(feature = "thread-info")]
+    pub fn with_thread_name(mut self) -> Self {
+        self.thread = ThreadInfo::current();
+        self
+    }
+
+    /// Hook run on every newly created error.
+    #[inline]
+    fn created(self) -> Self
+    where
+        E: fmt::Display,
+    {
+        #[cfg(feature = "tracing")]
+        crate::trace::on_create(&self);
+        self
+    }
+
+    /// Id of the `tracing` span that was current when this error was
+    /// created, for correlating it with the span's other events.
+    ///
+    /// `None` outside any span and for errors restored through serde.
+    #[cfg(feature = "tracing")]
+    pub fn span_id(&self) -> Option<&tracing::Id> {
+        self.span_id.as_ref()
+    }
+
+    /// Native stack captured when this error was first created.
+    ///
+    /// Captured via [`Backtrace::capture`](std::backtrace::Backtrace::capture),
+    /// so it's only resolved when `RUST_LIB_BACKTRACE=1` or `RUST_BACKTRACE=1`
+    /// is set; check [`status`](std::backtrace::Backtrace::status). Wrapping
+    /// an existing `Handled` keeps its original backtrace. Disabled for errors
+    /// restored through serde.
+    ///
+    /// ```
+    /// use handle_this::{handle, Result};
+    /// use std::backtrace::BacktraceStatus;
+    ///
+    /// let result: Result<()> = handle! { try { Err("deep in a dependency")? } };
+    /// let trace = result.unwrap_err().backtrace().status();
+    /// assert!(matches!(trace, BacktraceStatus::Captured | BacktraceStatus::Disabled));
+    /// ```
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> &std::backtrace::Backtrace {
+        &self.backtrace
+    }
+
+    /// Wall-clock time this error was created.
+    ///
+    /// `None` for errors restored through serde without a timestamp.
+    #[cfg(feature = "timestamps")]
+    pub fn timestamp(&self) -> Option<std::time::SystemTime> {
+        self.created_at
+    }
+
+    /// Record the current time as this error's creation time.
+    ///
+    /// Errors are stamped on creation; use this to re-stamp one that was
+    /// built ahead of time or restored from elsewhere.
+    #[cfg(feature = "timestamps")]
+    pub fn with_now(mut self) -> Self {
+        self.created_at = Some(std::time::SystemTime::now());
+        self
+    }
+
+    /// Iterate over frames in the trace, oldest first.
+    /// Combines locations with their optional contexts.
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = FrameView<'_>> + ExactSizeIterator {
+        let contexts = self.contexts.as_ref();
+        #[cfg(feature = "timestamps")]
+        let origin = self.locations.iter().next().map(|loc| loc.at);
+        self.locations.iter().enumerate().map(move |(idx, loc)| {
+            let idx = idx as u16;
+            let ctx = contexts.and_then(|c| c.iter().find(|e| e.location_idx == idx));
+            FrameView {
+                file: loc.file,
+                line: loc.line,
+                col: loc.col.into(),
+                context: ctx.and_then(|c| c.message.as_deref()),
+                function: self.locations.function(idx as usize),
+                attachments_inner: ctx.map(|c| c.attachments.as_slice()).unwrap_or(&[]),
+                is_scope: ctx.is_some_and(|c| c.is_scope),
+                is_note: ctx.is_some_and(|c| c.is_note),
+                repeat_count: loc.repeat.into(),
+                #[cfg(feature = "timestamps")]
+                at: loc.at,
+                #[cfg(feature = "timestamps")]
+                origin: origin.unwrap_or(loc.at),
+            }
+        })
+    }
+
+    /// Iterate over frames most recent first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom").frame("db.rs", 1, 1).frame("api.rs", 2, 1);
+    /// let files: Vec<_> = err.frames_rev().map(|f| f.file).collect();
+    /// assert_eq!(files, ["api.rs", "db.rs"]);
+    /// ```
+    pub fn frames_rev(&self) -> impl Iterator<Item = FrameView<'_>> {
+        self.frames().rev()
+    }
+
+    /// The oldest frame, where the error was first wrapped.
+    pub fn first_frame(&self) -> Option<FrameView<'_>> {
+        self.frames().next()
+    }
+
+    /// The most recent frame.
+    pub fn last_frame(&self) -> Option<FrameView<'_>> {
+        self.frames().next_back()
+    }
+
+    /// Frames whose file path starts with `prefix`, oldest first.
+    ///
+    /// A directory like `"src/db"` matches `src/db.rs` and everything under
+    /// `src/db/`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom")
+    ///     .frame("vendor/pg/conn.rs", 88, 1)
+    ///     .frame("src/db/query.rs", 12, 5)
+    ///     .frame("src/api.rs", 40, 9);
+    /// // Where the error entered our code
+    /// let entry = err.frames_in_file("src/").next().unwrap();
+    /// assert_eq!(entry.file, "src/db/query.rs");
+    /// ```
+    pub fn frames_in_file<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = FrameView<'a>> + 'a {
+        self.frames().filter(move |f| f.file.starts_with(prefix))
+    }
+
+    /// Keep only the `n` oldest frames, dropping later frames and their
+    /// contexts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let mut err = Handled::msg("boom")
+    ///     .frame("db.rs", 1, 1).ctx("querying")
+    ///     .frame("api.rs", 2, 1).ctx("handling request");
+    /// err.truncate_trace(1);
+    /// assert_eq!(err.depth(), 1);
+    /// assert_eq!(err.last_frame().unwrap().context, Some("querying"));
+    /// ```
+    pub fn truncate_trace(&mut self, n: usize) {
+        if n >= self.locations.len() {
+            return;
+        }
+        self.locations.truncate(n);
+        if let Some(contexts) = self.contexts.as_mut() {
+            contexts.retain(|c| (c.location_idx as usize) < n);
+            if contexts.is_empty() {
+                self.contexts = None;
+            }
+        }
+    }
+
+    /// When each frame was pushed, oldest first.
+    #[cfg(feature = "timestamps")]
+    pub fn frame_timestamps(&self) -> impl Iterator<Item = std::time::Instant> + '_ {
+        self.locations.iter().map(|loc| loc.at)
+    }
+
+    /// Find the context message inherited by the frame at `frame_idx`.
+    ///
+    /// Returns the frame's own context if it has one, otherwise searches
+    /// outward through enclosing frames (higher indices, toward the most
+    /// recent frame) for the first one with a context message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::{handle, Result};
+    ///
+    /// fn leaf() -> Result<()> {
+    ///     handle! { try { Err("boom")? } }
+    /// }
+    ///
+    /// let err = handle! { scope "loading config", try { leaf()? } }.unwrap_err();
+    /// assert_eq!(err.frames().next().unwrap().context, None);
+    /// assert_eq!(err.nearest_context(0), Some("loading config"));
+    /// ```
+    pub fn nearest_context(&self, frame_idx: usize) -> Option<&str> {
+        self.frames().skip(frame_idx).find_map(|f| f.context)
+    }
+
+    /// Find the first frame whose context message contains `needle`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom")
+    ///     .frame("db.rs", 12, 5).ctx("running query")
+    ///     .frame("api.rs", 40, 9).ctx("handling request");
+    /// let frame = err.find_context_frame("query").unwrap();
+    /// assert_eq!((frame.file, frame.line), ("db.rs", 12));
+    /// ```
+    pub fn find_context_frame(&self, needle: &str) -> Option<FrameView<'_>> {
+        self.frames().find(|f| f.context.is_some_and(|c| c.contains(needle)))
+    }
+
+    /// Every key-value attachment in the trace, most recent first.
+    ///
+    /// Frames are visited from the newest back to the origin; within a
+    /// frame, later attachments come first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom")
+    ///     .frame("db.rs", 12, 5).kv("table", "users").kv("request_id", 7)
+    ///     .frame("api.rs", 40, 9).kv("route", "/users");
+    /// let keys: Vec<_> = err.all_kv().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, ["route", "request_id", "table"]);
+    /// ```
+    pub fn all_kv(&self) -> impl Iterator<Item = (&str, &Value)> {
+        let mut entries: Vec<&ContextEntry> = self.contexts.iter().flatten().collect();
+        // Newest frame first; among entries on one frame, the later one first
+        entries.reverse();
+        entries.sort_by_key(|c| core::cmp::Reverse(c.location_idx));
+        entries
+            .into_iter()
+            .flat_map(|c| c.attachments.iter().rev().map(|(k, v)| (k.as_ref(), v)))
     }
 
-    /// Try to get a reference to a specific error type.
+    /// The most recent attachment named `key`, searching every frame.
     ///
-    /// This works with both concrete error types (direct access) and
-    /// type-erased errors (runtime downcasting).
-    #[doc(hidden)]
-    #[inline]
-    pub fn try_catch<Target>(&self) -> Option<&Target>
-    where
-        E: TryCatch<Target>,
-    {
-        self.source.try_catch()
+    /// `attempt` and `attempts` from `try for`/`try all` over `.enumerate()`
+    /// and from `try while` retries are found even with capture off
+    /// (`set_capture(false)` or the `no-trace` feature).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom")
+    ///     .frame("db.rs", 12, 5).kv("request_id", "r-1")
+    ///     .frame("api.rs", 40, 9).ctx("handling request");
+    /// assert_eq!(err.get_kv("request_id").unwrap(), "r-1");
+    /// assert!(err.get_kv("user").is_none());
+    /// ```
+    pub fn get_kv(&self, key: &str) -> Option<&Value> {
+        self.all_kv().find(|(k, _)| *k == key).map(|(_, v)| v).or_else(|| {
+            let meta = self.field::<Attempts>()?;
+            match key {
+                "attempt" => meta.attempt.as_ref(),
+                "attempts" => meta.attempts.as_ref(),
+                _ => None,
+            }
+        })
     }
 
-    /// Get the underlying error source.
-    pub fn source_ref(&self) -> &E {
-        &self.source
+    /// The oldest frame (where the error was first wrapped) as `file:line:col`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom").frame("src/db.rs", 42, 5).frame("src/api.rs", 7, 9);
+    /// assert_eq!(err.origin_string().as_deref(), Some("src/db.rs:42:5"));
+    /// assert_eq!(err.location_string().as_deref(), Some("src/api.rs:7:9"));
+    /// ```
+    pub fn origin_string(&self) -> Option<String> {
+        self.locations.iter().next().map(Location::format_location)
     }
 
-    /// Consume and return the underlying error.
-    pub fn into_source(self) -> E {
-        self.source
+    /// The most recent frame as `file:line:col`.
+    pub fn location_string(&self) -> Option<String> {
+        self.locations.iter().next_back().map(Location::format_location)
     }
 
-    /// Iterate over frames in the trace.
-    /// Combines locations with their optional contexts.
-    pub fn frames(&self) -> impl Iterator<Item = FrameView<'_>> {
-        let contexts = self.contexts.as_ref();
-        self.locations.iter().enumerate().map(move |(idx, loc)| {
-            let idx = idx as u16;
-            let ctx = contexts.and_then(|c| c.iter().find(|e| e.location_idx == idx));
-            FrameView {
-                file: loc.file,
-                line: loc.line,
-                col: loc.col,
-                context: ctx.and_then(|c| c.message.as_deref()),
-                attachments_inner: ctx.map(|c| c.attachments.as_slice()).unwrap_or(&[]),
-            }
+    /// Number of `scope` frames enclosing frame `frame_idx`: the scope
+    /// frames at or after it (frames run innermost first, as in
+    /// [`frames`](Self::frames), so outer scopes come later), a scope frame
+    /// counting itself.
+    /// Useful for indenting nested scopes in custom renderers.
+    /// # Example
+    /// ```
+    /// use handle_this::Handled;
+    /// let err = Handled::msg("boom")
+    ///     .frame("db.rs", 12, 1)
+    ///     .scope("db.rs", 5, 1, "querying")
+    ///     .scope("api.rs", 9, 1, "handling request");
+    /// assert_eq!(err.scope_depth_at(0), 2);
+    /// assert_eq!(err.scope_depth_at(1), 2);
+    /// assert_eq!(err.scope_depth_at(2), 1);
+    /// ```
+    pub fn scope_depth_at(&self, frame_idx: usize) -> usize {
+        self.contexts.as_ref().map_or(0, |contexts| {
+            contexts
+                .iter()
+                .filter(|c| c.is_scope && c.location_idx as usize >= frame_idx)
+                .count()
         })
     }
 
@@ -752,7 +2346,6 @@ impl<E> Handled<E> {
     }
 
     /// Convert to a type-erased Handled.
-    #[cfg(feature = "std")]
     pub fn erase(self) -> Handled<Error>
     where
         E: StdError + Send + Sync + 'static,
@@ -763,6 +2356,59 @@ impl<E> Handled<E> {
             locations: self.locations,
             contexts: self.contexts,
             chained: self.chained,
+            fields: self.fields,
+            #[cfg(feature = "thread-info")]
+            thread: self.thread,
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+            #[cfg(feature = "tracing")]
+            span_id: self.span_id,
+            #[cfg(feature = "timestamps")]
+            created_at: self.created_at,
+        }
+    }
+
+    /// Convert to a type-erased Handled whose source is the message text,
+    /// keeping the trace, contexts, chain, and fields.
+    ///
+    /// Unlike [`erase`](Self::erase), `E` needn't be `'static`: a
+    /// `Handled` around a borrowing error can carry its frames and `kv`
+    /// attachments past the end of the borrow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let input = String::from("x1");
+    /// let err = Handled::new(format_args!("bad token {}", &input[..1]))
+    ///     .frame("lexer.rs", 3, 1)
+    ///     .kv("offset", 0)
+    ///     .into_snapshot();
+    /// drop(input);
+    /// assert_eq!(err.message(), "bad token x");
+    /// assert_eq!(err.get_kv("offset").unwrap(), &0i64);
+    /// ```
+    pub fn into_snapshot(self) -> Handled<Error>
+    where
+        E: fmt::Display,
+    {
+        let message = self.message().to_string();
+        Handled {
+            source: Error::new(StringError(message.clone())),
+            message: OnceLock::from(message),
+            locations: self.locations,
+            contexts: self.contexts,
+            chained: self.chained,
+            fields: self.fields,
+            #[cfg(feature = "thread-info")]
+            thread: self.thread,
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+            #[cfg(feature = "tracing")]
+            span_id: self.span_id,
+            #[cfg(feature = "timestamps")]
+            created_at: self.created_at,
         }
     }
 
@@ -778,12 +2424,159 @@ impl<E> Handled<E> {
             message: OnceLock::new(),  // Lazy - will compute from new source
             locations: self.locations,
             contexts: self.contexts,
-            #[cfg(feature = "std")]
             chained: self.chained,
+            fields: self.fields,
+            #[cfg(feature = "thread-info")]
+            thread: self.thread,
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+            #[cfg(feature = "tracing")]
+            span_id: self.span_id,
+            #[cfg(feature = "timestamps")]
+            created_at: self.created_at,
+        }
+    }
+
+    /// Swap in a new source error, keeping the trace, contexts, chain, and fields.
+    ///
+    /// Unlike `throw`, which chains the old error as a cause, the old source
+    /// is dropped. The message is recomputed from the new source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::io;
+    ///
+    /// let err = Handled::msg("raw failure").frame("db.rs", 7, 1).ctx("querying");
+    /// let err = err.replace_source(io::Error::new(io::ErrorKind::TimedOut, "query timed out"));
+    /// assert_eq!(err.message(), "query timed out");
+    /// assert_eq!(err.frames().next().unwrap().context, Some("querying"));
+    /// assert!(err.downcast_ref::<io::Error>().is_some());
+    /// ```
+    pub fn replace_source<N>(self, new: N) -> Handled<Error>
+    where
+        N: StdError + Send + Sync + 'static,
+    {
+        Handled {
+            source: Error::new(new),
+            message: OnceLock::new(),
+            locations: self.locations,
+            contexts: self.contexts,
+            chained: self.chained,
+            fields: self.fields,
+            #[cfg(feature = "thread-info")]
+            thread: self.thread,
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+            #[cfg(feature = "tracing")]
+            span_id: self.span_id,
+            #[cfg(feature = "timestamps")]
+            created_at: self.created_at,
+        }
+    }
+
+    /// Clone this error, including its source, trace, contexts, and chain.
+    ///
+    /// Chained errors have erased sources, so each link is cloned as a
+    /// [`StringError`] with the same message, trace, and contexts. The
+    /// typed fields the crate sets (code, severity, correlation id, kind,
+    /// tags, group) are copied; other `set_field` values may not be `Clone`
+    /// and are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::num::ParseIntError;
+    ///
+    /// let parse_err: ParseIntError = "x".parse::<i32>().unwrap_err();
+    /// let err = Handled::new(parse_err).frame("cfg.rs", 3, 1).ctx("reading port");
+    /// let copy = err.deep_clone();
+    /// assert_eq!(copy.source_ref(), err.source_ref());
+    /// assert_eq!(copy.frames().next().unwrap().context, Some("reading port"));
+    /// ```
+    #[cfg(feature = "clone")]
+    pub fn deep_clone(&self) -> Self
+    where
+        E: Clone,
+    {
+        Self {
+            source: self.source.clone(),
+            message: self.message.clone(),
+            locations: self.locations.clone(),
+            contexts: self.contexts.clone(),
+            chained: self.chained.as_deref().map(|link| Box::new(link.clone_link())),
+            fields: self.fields.as_ref().and_then(FieldMap::clone_known),
+            #[cfg(feature = "thread-info")]
+            thread: self.thread.clone(),
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace.clone(),
+            #[cfg(feature = "tracing")]
+            span_id: self.span_id.clone(),
+            #[cfg(feature = "timestamps")]
+            created_at: self.created_at,
+        }
+    }
+
+    /// Clone as a message-only link, recursing through the chain.
+    ///
+    /// Built field by field rather than through `Handled::msg`, so the copy
+    /// keeps the original's thread, backtrace and timestamps and doesn't
+    /// count as a newly created error.
+    #[cfg(feature = "clone")]
+    fn clone_link(&self) -> Handled<Error>
+    where
+        E: fmt::Display,
+    {
+        let message = self.message().to_string();
+        Handled {
+            source: Error::new(StringError(message.clone())),
+            message: OnceLock::from(message),
+            locations: self.locations.clone(),
+            contexts: self.contexts.clone(),
+            chained: self.chained.as_deref().map(|next| Box::new(next.clone_link())),
+            fields: self.fields.as_ref().and_then(FieldMap::clone_known),
+            #[cfg(feature = "thread-info")]
+            thread: self.thread.clone(),
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace.clone(),
+            #[cfg(feature = "tracing")]
+            span_id: self.span_id.clone(),
+            #[cfg(feature = "timestamps")]
+            created_at: self.created_at,
         }
     }
 }
 
+/// Cloning a type-erased error re-boxes its source as a [`StringError`]
+/// with the same message, since the original type may not be `Clone`.
+/// The trace, contexts, and chain are copied, and so are the typed fields
+/// the crate sets: code, severity, correlation id, kind, tags and group.
+/// Other [`set_field`](Handled::set_field) values are dropped, as with
+/// [`deep_clone`](Handled::deep_clone).
+///
+/// Handy for shipping an error to a reporter thread:
+///
+/// ```
+/// use handle_this::{handle, Result};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// let result: Result<u16> = handle! {
+///     try { "x".parse::<u16>()? }
+///     inspect e { let _ = tx.send(e.clone()); }
+/// };
+/// let reported = rx.recv().unwrap();
+/// assert_eq!(reported.message(), result.unwrap_err().message());
+/// ```
+#[cfg(feature = "clone")]
+impl Clone for Handled<Error> {
+    fn clone(&self) -> Self {
+        self.clone_link()
+    }
+}
+
 // ============================================================
 // Handled<Error> specific methods (type-erased)
 // ============================================================
@@ -791,7 +2584,6 @@ impl<E> Handled<E> {
 impl Handled<Error> {
     /// Wrap any error, avoiding double-boxing if already Handled.
     /// Message is computed lazily on first access.
-    #[cfg(feature = "std")]
     #[inline]
     pub fn wrap<E>(e: E) -> Self
     where
@@ -813,14 +2605,23 @@ impl Handled<Error> {
                 locations: LocationVec::new(),
                 contexts: None,
                 chained: None,
+                fields: None,
+                #[cfg(feature = "thread-info")]
+                thread: ThreadInfo::current(),
+                #[cfg(feature = "backtrace")]
+                backtrace: capture_backtrace(),
+                #[cfg(feature = "tracing")]
+                span_id: crate::trace::current_span_id(),
+                #[cfg(feature = "timestamps")]
+                created_at: Some(std::time::SystemTime::now()),
             }
+            .created()
         }
     }
 
     /// Wrap a boxed error into a type-erased Handled.
     /// If the boxed error is already a Handled, unwrap it to avoid double-wrapping.
     /// Message is computed lazily on first access.
-    #[cfg(feature = "std")]
     #[inline]
     pub fn wrap_box(e: Box<dyn StdError + Send + Sync + 'static>) -> Self {
         // Check if it's already a Handled<Error>
@@ -833,14 +2634,114 @@ impl Handled<Error> {
                     locations: LocationVec::new(),
                     contexts: None,
                     chained: None,
+                    fields: None,
+                    #[cfg(feature = "thread-info")]
+                    thread: ThreadInfo::current(),
+                    #[cfg(feature = "backtrace")]
+                    backtrace: capture_backtrace(),
+                    #[cfg(feature = "tracing")]
+                    span_id: crate::trace::current_span_id(),
+                    #[cfg(feature = "timestamps")]
+                    created_at: Some(std::time::SystemTime::now()),
                 }
+                .created()
             }
         }
     }
 
+    /// Combine several errors into one.
+    ///
+    /// The result wraps a [`CombinedError`] whose report sections the
+    /// errors by their group (see [`Handled::in_group`]). Individual errors
+    /// are available via `downcast_ref::<CombinedError>()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::{CombinedError, Handled};
+    ///
+    /// let err = Handled::combine([
+    ///     Handled::msg("refused").in_group("db"),
+    ///     Handled::msg("miss").in_group("cache"),
+    /// ]);
+    /// assert_eq!(err.message(), "2 errors\n[db]\n  - refused\n[cache]\n  - miss");
+    /// assert_eq!(err.downcast_ref::<CombinedError>().unwrap().groups(), ["db", "cache"]);
+    /// ```
+    pub fn combine(errors: impl IntoIterator<Item = Handled<Error>>) -> Self {
+        Self::wrap(CombinedError(errors.into_iter().collect()))
+    }
+
+    /// Wrap a value that implements `Display` but not `Error`.
+    ///
+    /// Unlike the `Box<dyn Display>` conversion, the concrete type is kept
+    /// and can be recovered with [`Handled::downcast_display`].
+    #[inline]
+    pub fn from_display<T: fmt::Display + Send + Sync + 'static>(value: T) -> Self {
+        Self::wrap_erased(Error::from_display(value))
+    }
+
+    /// Capture an error's `Display` text now, so a borrowing error can
+    /// become a `Handled`.
+    ///
+    /// `Handled` needs a `'static` source; an error that borrows its input
+    /// (a parser error holding `&str`, say) can't be stored. The snapshot
+    /// keeps only the message, as a [`StringError`], and the borrow ends
+    /// with this call. (Named `snapshot_of` because [`Handled::snapshot`]
+    /// already builds an [`ErrorRecord`](crate::ErrorRecord).)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::{handle, Handled, Result};
+    /// use std::fmt;
+    ///
+    /// struct Unexpected<'a>(&'a str);
+    /// impl fmt::Display for Unexpected<'_> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "unexpected {:?}", self.0)
+    ///     }
+    /// }
+    ///
+    /// fn parse(input: &str) -> std::result::Result<u32, Unexpected<'_>> {
+    ///     input.parse().map_err(|_| Unexpected(input))
+    /// }
+    ///
+    /// let line = String::from("x1");
+    /// let result: Result<u32> = handle! {
+    ///     try { parse(&line).map_err(|e| Handled::snapshot_of(&e))? }
+    /// };
+    /// assert_eq!(result.unwrap_err().message(), "unexpected \"x1\"");
+    /// ```
+    pub fn snapshot_of<T: fmt::Display + ?Sized>(err: &T) -> Self {
+        Self::msg(err.to_string())
+    }
+
+    /// Try to downcast a display-only value stored via [`Handled::from_display`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::fmt;
+    ///
+    /// struct Code(u16);
+    /// impl fmt::Display for Code {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "code {}", self.0)
+    ///     }
+    /// }
+    ///
+    /// let err = Handled::from_display(Code(404));
+    /// assert_eq!(err.downcast_display::<Code>().map(|c| c.0), Some(404));
+    /// assert_eq!(err.message(), "code 404");
+    /// ```
+    #[inline]
+    pub fn downcast_display<T: fmt::Display + 'static>(&self) -> Option<&T> {
+        self.source.downcast_display::<T>()
+    }
+
     /// Wrap an Error directly.
     /// Message is computed lazily on first access.
-    #[cfg(feature = "std")]
     #[inline]
     pub fn wrap_erased(e: Error) -> Self {
         Self {
@@ -849,13 +2750,22 @@ impl Handled<Error> {
             locations: LocationVec::new(),
             contexts: None,
             chained: None,
+            fields: None,
+            #[cfg(feature = "thread-info")]
+            thread: ThreadInfo::current(),
+            #[cfg(feature = "backtrace")]
+            backtrace: capture_backtrace(),
+            #[cfg(feature = "tracing")]
+            span_id: crate::trace::current_span_id(),
+            #[cfg(feature = "timestamps")]
+            created_at: Some(std::time::SystemTime::now()),
         }
+        .created()
     }
 
     /// Wrap a boxed error and add a frame in one operation.
     /// Equivalent to wrap_box().frame() but as a single call.
     #[doc(hidden)]
-    #[cfg(feature = "std")]
     #[inline]
     pub fn wrap_box_with_frame(
         e: Box<dyn StdError + Send + Sync + 'static>,
@@ -866,32 +2776,8 @@ impl Handled<Error> {
         Self::wrap_box(e).frame(file, line, col)
     }
 
-    #[cfg(not(feature = "std"))]
-    #[inline]
-    pub fn wrap<E>(e: E) -> Self
-    where
-        E: fmt::Debug + fmt::Display + Send + Sync + 'static,
-    {
-        use core::any::TypeId;
-        if TypeId::of::<E>() == TypeId::of::<Self>() {
-            unsafe {
-                let handled = core::ptr::read(&e as *const E as *const Self);
-                core::mem::forget(e);
-                handled
-            }
-        } else {
-            Self {
-                source: Error::new(e),
-                message: OnceLock::new(),
-                locations: LocationVec::new(),
-                contexts: None,
-            }
-        }
-    }
-
     /// Create from a message string.
     /// Message is pre-initialized since we already have it.
-    #[cfg(feature = "std")]
     #[inline]
     pub fn msg(message: impl Into<String>) -> Self {
         let message = message.into();
@@ -903,21 +2789,17 @@ impl Handled<Error> {
             locations: LocationVec::new(),
             contexts: None,
             chained: None,
+            fields: None,
+            #[cfg(feature = "thread-info")]
+            thread: ThreadInfo::current(),
+            #[cfg(feature = "backtrace")]
+            backtrace: capture_backtrace(),
+            #[cfg(feature = "tracing")]
+            span_id: crate::trace::current_span_id(),
+            #[cfg(feature = "timestamps")]
+            created_at: Some(std::time::SystemTime::now()),
         }
-    }
-
-    #[cfg(not(feature = "std"))]
-    #[inline]
-    pub fn msg(message: impl Into<String>) -> Self {
-        let message = message.into();
-        let msg_lock = OnceLock::new();
-        let _ = msg_lock.set(message.clone());
-        Self {
-            source: Error::new(StringError(message)),
-            message: msg_lock,
-            locations: LocationVec::new(),
-            contexts: None,
-        }
+        .created()
     }
 
     /// Chain this error after a previous error.
@@ -928,8 +2810,6 @@ impl Handled<Error> {
     ///
     /// The previous error becomes accessible via `chain_any`/`chain_all`.
     /// The root error type is preserved for `catch Type` matching.
-    #[doc(hidden)]
-    #[cfg(feature = "std")]
     pub fn chain_after(mut self, previous: Self) -> Self {
         // Flatten any existing chain from self
         let existing_chain = self.chained.take();
@@ -948,22 +2828,181 @@ impl Handled<Error> {
         self
     }
 
+    /// Merge two errors into one using `strategy`.
+    ///
+    /// The two-error counterpart of [`Handled::combine`]. With
+    /// [`MergeStrategy::CombineMessages`] the root becomes a
+    /// [`StringError`]; chain and typed fields are taken from `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::{Handled, MergeStrategy};
+    ///
+    /// let read = Handled::msg("read failed").frame("a.rs", 1, 1).kv("path", "x.txt");
+    /// let close = Handled::msg("close failed").frame("b.rs", 2, 1).kv("fd", 3);
+    /// let err = read.merge(close, MergeStrategy::CombineMessages);
+    /// assert_eq!(err.message(), "read failed; close failed");
+    /// assert_eq!(err.depth(), 2);
+    /// ```
+    pub fn merge(self, other: Self, strategy: MergeStrategy) -> Self {
+        match strategy {
+            MergeStrategy::KeepFirst => self,
+            MergeStrategy::KeepLast => other,
+            MergeStrategy::KeepFirstChainSecond => self.chain_after(other),
+            MergeStrategy::CombineMessages => {
+                let mut merged = Self::msg(format!("{}; {}", self.message(), other.message()));
+                merged.chained = self.chained;
+                merged.fields = self.fields;
+                #[cfg(feature = "thread-info")]
+                {
+                    merged.thread = self.thread;
+                }
+                #[cfg(feature = "backtrace")]
+                {
+                    merged.backtrace = self.backtrace;
+                }
+                #[cfg(feature = "tracing")]
+                {
+                    merged.span_id = self.span_id;
+                }
+                #[cfg(feature = "timestamps")]
+                {
+                    merged.created_at = self.created_at;
+                }
+
+                let offset = self.locations.len();
+                for locations in [&self.locations, &other.locations] {
+                    for (idx, loc) in locations.iter().enumerate() {
+                        merged.locations.push(*loc, locations.function(idx));
+                    }
+                }
+                let kept = merged.locations.len();
+
+                let mut contexts = self.contexts.unwrap_or_default();
+                for mut entry in other.contexts.into_iter().flatten() {
+                    let idx = entry.location_idx as usize + offset;
+                    // Frames past the location limit were dropped
+                    if idx < kept {
+                        entry.location_idx = idx as u16;
+                        contexts.push(entry);
+                    }
+                }
+                if !contexts.is_empty() {
+                    merged.contexts = Some(contexts);
+                }
+                merged
+            }
+        }
+    }
+
+    /// Wrap an external error, eagerly importing its `source()` chain.
+    ///
+    /// `wrap` keeps the causes only behind the trait object. Here each
+    /// level below `e` is snapshotted as a [`StringError`] link (see
+    /// [`Handled::chain_after`]), so the chain survives serialization and
+    /// shows up in [`Handled::chain_all`] and [`Handled::context_messages`].
+    /// The root is still `e` itself, so typed `catch`/`downcast_ref` work.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::{error::Error, fmt};
+    ///
+    /// #[derive(Debug)]
+    /// struct Outer(std::io::Error);
+    /// impl fmt::Display for Outer {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str("load failed") }
+    /// }
+    /// impl Error for Outer {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> { Some(&self.0) }
+    /// }
+    ///
+    /// let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+    /// let err = Handled::import_chain(Outer(io));
+    /// assert_eq!(err.context_messages(), ["load failed", "no such file"]);
+    /// ```
+    pub fn import_chain<E: StdError + Send + Sync + 'static>(e: E) -> Self {
+        // A Handled already carries its chain eagerly
+        if (&e as &dyn core::any::Any).is::<Self>() {
+            return Self::wrap(e);
+        }
+
+        let mut sources = Vec::new();
+        let mut current = e.source();
+        while let Some(err) = current {
+            sources.push(err.to_string());
+            current = err.source();
+        }
+
+        let mut chained: Option<Box<Self>> = None;
+        for message in sources.into_iter().rev() {
+            let mut link = Self::msg(message);
+            link.chained = chained;
+            chained = Some(Box::new(link));
+        }
+
+        let mut handled = Self::wrap(e);
+        handled.chained = chained;
+        handled
+    }
+
+    /// Messages of this error and each chained link, outermost first.
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("second").chain_after(Handled::msg("first"));
+    /// assert_eq!(err.context_messages(), ["second", "first"]);
+    /// ```
+    pub fn context_messages(&self) -> Vec<&str> {
+        let mut messages = vec![self.message()];
+        let mut current = self.chained.as_deref();
+        while let Some(link) = current {
+            messages.push(link.message());
+            current = link.chained.as_deref();
+        }
+        messages
+    }
+
+    /// Detach and return the chained (previous) errors, leaving `self`
+    /// without a chain.
+    ///
+    /// The returned error keeps its own chain, so it can be inspected or
+    /// modified and then reattached with [`Handled::chain_after`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let mut err = Handled::msg("second").chain_after(Handled::msg("first"));
+    /// let prev = err.take_chain().unwrap();
+    /// assert_eq!(prev.message(), "first");
+    /// assert!(err.take_chain().is_none());
+    ///
+    /// let err = err.chain_after(*prev);
+    /// assert_eq!(err.chain_all::<handle_this::StringError>().len(), 2);
+    /// ```
+    pub fn take_chain(&mut self) -> Option<Box<Handled<Error>>> {
+        self.chained.take()
+    }
+
     /// Get the root error as a trait object.
-    #[cfg(feature = "std")]
     pub fn root(&self) -> &(dyn StdError + 'static) {
         self.source.as_dyn_error()
     }
 
     /// Try to downcast to a specific error type.
-    #[cfg(feature = "std")]
     #[inline]
-    pub fn downcast_ref<T: StdError + 'static>(&self) -> Option<&T> {
-        self.source.downcast_ref::<T>()
+    pub fn downcast_ref<T: ?Sized + Catchable>(&self) -> Option<&T> {
+        T::cast(self.source.as_dyn_error())
     }
 
     /// Try to downcast and consume the error.
-    #[cfg(feature = "std")]
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub fn downcast<T: StdError + 'static>(self) -> core::result::Result<T, Self> {
         if self.source.downcast_ref::<T>().is_some() {
             let Self {
@@ -972,6 +3011,15 @@ impl Handled<Error> {
                 contexts,
                 message,
                 chained,
+                fields,
+                #[cfg(feature = "thread-info")]
+                thread,
+                #[cfg(feature = "backtrace")]
+                backtrace,
+                #[cfg(feature = "tracing")]
+                span_id,
+                #[cfg(feature = "timestamps")]
+                created_at,
             } = self;
             match source.downcast::<T>() {
                 Ok(e) => Ok(e),
@@ -981,6 +3029,15 @@ impl Handled<Error> {
                     contexts,
                     message,
                     chained,
+                    fields,
+                    #[cfg(feature = "thread-info")]
+                    thread,
+                    #[cfg(feature = "backtrace")]
+                    backtrace,
+                    #[cfg(feature = "tracing")]
+                    span_id,
+                    #[cfg(feature = "timestamps")]
+                    created_at,
                 }),
             }
         } else {
@@ -988,6 +3045,107 @@ impl Handled<Error> {
         }
     }
 
+    /// Downcast the root to `T`, keeping the trace, contexts, chain, and fields.
+    ///
+    /// Unlike [`downcast`](Self::downcast), the result is still a `Handled`,
+    /// so its `Display` lists the frames. Handy as the payload of an error
+    /// enum variant: `Io(Handled<io::Error>)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::io;
+    ///
+    /// let err = Handled::wrap(io::Error::new(io::ErrorKind::NotFound, "gone")).frame("fs.rs", 4, 1);
+    /// let io_err: Handled<io::Error> = err.downcast_handled().unwrap();
+    /// assert_eq!(io_err.source_ref().kind(), io::ErrorKind::NotFound);
+    /// assert!(io_err.to_string().contains("fs.rs:4:1"));
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn downcast_handled<T: StdError + 'static>(self) -> core::result::Result<Handled<T>, Self> {
+        if self.source.downcast_ref::<T>().is_none() {
+            return Err(self);
+        }
+        let Self {
+            source,
+            locations,
+            contexts,
+            message,
+            chained,
+            fields,
+            #[cfg(feature = "thread-info")]
+            thread,
+            #[cfg(feature = "backtrace")]
+            backtrace,
+            #[cfg(feature = "tracing")]
+            span_id,
+            #[cfg(feature = "timestamps")]
+            created_at,
+        } = self;
+        let source = match source.downcast::<T>() {
+            Ok(e) => e,
+            Err(_) => unreachable!("root type checked above"),
+        };
+        Ok(Handled {
+            source,
+            message,
+            locations,
+            contexts,
+            chained,
+            fields,
+            #[cfg(feature = "thread-info")]
+            thread,
+            #[cfg(feature = "backtrace")]
+            backtrace,
+            #[cfg(feature = "tracing")]
+            span_id,
+            #[cfg(feature = "timestamps")]
+            created_at,
+        })
+    }
+
+    /// Transform the root if it is a `T`, keeping the trace, contexts,
+    /// chain, and fields; any other error comes back unchanged.
+    ///
+    /// The method form of `throw Type(e) { ... }`, minus the cause link:
+    /// the old root is consumed by `f` rather than chained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    /// use std::{fmt, io};
+    ///
+    /// #[derive(Debug)]
+    /// struct StoreError(io::ErrorKind);
+    /// # impl fmt::Display for StoreError {
+    /// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// #         write!(f, "store failed: {:?}", self.0)
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for StoreError {}
+    ///
+    /// let err = Handled::wrap(io::Error::new(io::ErrorKind::NotFound, "gone")).frame("db.rs", 3, 1);
+    /// let err = err.map_if::<io::Error, _>(|e| StoreError(e.kind()));
+    /// assert_eq!(err.message(), "store failed: NotFound");
+    /// assert_eq!(err.frames().next().unwrap().file, "db.rs");
+    ///
+    /// // Not an io::Error: untouched
+    /// let other = Handled::msg("plain").map_if::<io::Error, _>(|e| StoreError(e.kind()));
+    /// assert_eq!(other.message(), "plain");
+    /// ```
+    pub fn map_if<T, O>(self, f: impl FnOnce(T) -> O) -> Self
+    where
+        T: StdError + 'static,
+        O: StdError + Send + Sync + 'static,
+    {
+        match self.downcast_handled::<T>() {
+            Ok(typed) => typed.map_err(f).erase(),
+            Err(err) => err,
+        }
+    }
+
     /// Find the first error of type `T` in the cause chain.
     ///
     /// Walks the error chain via `std::error::Error::source()` and returns
@@ -1005,10 +3163,9 @@ impl Handled<Error> {
     ///     }
     /// }
     /// ```
-    #[cfg(feature = "std")]
-    pub fn chain_any<T: StdError + 'static>(&self) -> Option<&T> {
+    pub fn chain_any<T: ?Sized + Catchable>(&self) -> Option<&T> {
         // First check the root error
-        if let Some(e) = self.source.downcast_ref::<T>() {
+        if let Some(e) = T::cast(self.source.as_dyn_error()) {
             return Some(e);
         }
 
@@ -1016,7 +3173,7 @@ impl Handled<Error> {
         let mut current: Option<&(dyn StdError + 'static)> = self.source.as_dyn_error().source();
         while let Some(err) = current {
             // Direct type match
-            if let Some(e) = err.downcast_ref::<T>() {
+            if let Some(e) = T::cast(err) {
                 return Some(e);
             }
 
@@ -1058,12 +3215,11 @@ impl Handled<Error> {
     ///     }
     /// }
     /// ```
-    #[cfg(feature = "std")]
-    pub fn chain_all<T: StdError + 'static>(&self) -> Vec<&T> {
+    pub fn chain_all<T: ?Sized + Catchable>(&self) -> Vec<&T> {
         let mut matches = Vec::new();
 
         // First check the root error
-        if let Some(e) = self.source.downcast_ref::<T>() {
+        if let Some(e) = T::cast(self.source.as_dyn_error()) {
             matches.push(e);
         }
 
@@ -1077,7 +3233,7 @@ impl Handled<Error> {
             }
 
             // Direct type match
-            if let Some(e) = err.downcast_ref::<T>() {
+            if let Some(e) = T::cast(err) {
                 matches.push(e);
             }
 
@@ -1107,14 +3263,25 @@ impl<E: fmt::Display> fmt::Display for Handled<E> {
         let msg = self.message.get_or_init(|| self.source.to_string());
         writeln!(f, "{}", msg)?;
 
+        #[cfg(feature = "thread-info")]
+        if let Some(name) = self.thread_name() {
+            writeln!(f, "Thread: {}", name)?;
+        }
+
         if !self.locations.is_empty() {
             writeln!(f, "\nTrace (most recent last):")?;
             for (idx, loc) in self.locations.iter().enumerate() {
                 write!(f, "  {}:{}:{}", loc.file, loc.line, loc.col)?;
+                if let Some(function) = self.locations.function(idx) {
+                    write!(f, " in {}", function)?;
+                }
 
                 // Find context for this location if any
                 if let Some(contexts) = &self.contexts {
                     if let Some(ctx) = contexts.iter().find(|c| c.location_idx == idx as u16) {
+                        if ctx.is_note {
+                            write!(f, " (note)")?;
+                        }
                         if let Some(msg) = &ctx.message {
                             write!(f, "\n    \u{2192} {}", msg)?;
                         }
@@ -1123,6 +3290,9 @@ impl<E: fmt::Display> fmt::Display for Handled<E> {
                         }
                     }
                 }
+                if loc.repeat > 1 {
+                    write!(f, "\n    ... repeated {} times", loc.repeat)?;
+                }
                 writeln!(f)?;
             }
         }
@@ -1136,7 +3306,6 @@ impl<E: fmt::Display> fmt::Display for Handled<E> {
 // which allows us to have non-conflicting trait impls for wrapping.
 
 // StdError impl ONLY for type-erased Handled (needed for ? operator in functions returning Result<_, Handled>)
-#[cfg(feature = "std")]
 impl StdError for Handled<Error> {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         Some(self.source.as_dyn_error())
@@ -1150,7 +3319,6 @@ impl StdError for Handled<Error> {
 /// Wrap any error in Handled, avoiding double-wrapping.
 /// Uses TypeId to detect if the input is already a Handled<Error>.
 #[doc(hidden)]
-#[cfg(feature = "std")]
 pub fn __wrap_any<E: StdError + Send + Sync + 'static>(e: E) -> Handled<Error> {
     use core::any::TypeId;
     // Check if E is Handled<Error>
@@ -1205,14 +3373,12 @@ impl From<()> for Handled<Error> {
 }
 
 // Box<dyn Display> - allows any Display type to convert to Handled
-#[cfg(feature = "std")]
 impl From<Box<dyn core::fmt::Display + Send + Sync>> for Handled<Error> {
     fn from(e: Box<dyn core::fmt::Display + Send + Sync>) -> Self {
         Self::msg(e.to_string())
     }
 }
 
-#[cfg(feature = "std")]
 impl From<Box<dyn StdError + Send + Sync + 'static>> for Handled<Error> {
     fn from(e: Box<dyn StdError + Send + Sync + 'static>) -> Self {
         Self::wrap_box(e)
@@ -1230,30 +3396,26 @@ impl From<std::io::Error> for Handled<Error> {
     }
 }
 
-#[cfg(feature = "std")]
-impl From<std::num::ParseIntError> for Handled<Error> {
-    fn from(e: std::num::ParseIntError) -> Self {
+impl From<core::num::ParseIntError> for Handled<Error> {
+    fn from(e: core::num::ParseIntError) -> Self {
         Self::wrap(e)
     }
 }
 
-#[cfg(feature = "std")]
-impl From<std::num::ParseFloatError> for Handled<Error> {
-    fn from(e: std::num::ParseFloatError) -> Self {
+impl From<core::num::ParseFloatError> for Handled<Error> {
+    fn from(e: core::num::ParseFloatError) -> Self {
         Self::wrap(e)
     }
 }
 
-#[cfg(feature = "std")]
-impl From<std::str::Utf8Error> for Handled<Error> {
-    fn from(e: std::str::Utf8Error) -> Self {
+impl From<core::str::Utf8Error> for Handled<Error> {
+    fn from(e: core::str::Utf8Error) -> Self {
         Self::wrap(e)
     }
 }
 
-#[cfg(feature = "std")]
-impl From<std::string::FromUtf8Error> for Handled<Error> {
-    fn from(e: std::string::FromUtf8Error) -> Self {
+impl From<alloc::string::FromUtf8Error> for Handled<Error> {
+    fn from(e: alloc::string::FromUtf8Error) -> Self {
         Self::wrap(e)
     }
 }
@@ -1285,6 +3447,45 @@ impl From<anyhow::Error> for Handled<Error> {
     }
 }
 
+#[cfg(feature = "anyhow")]
+impl Handled<Error> {
+    /// Convert into an `anyhow::Error`, keeping context messages as layers.
+    ///
+    /// The root error becomes the innermost error and each frame's context
+    /// message is applied with `anyhow::Context::context`, oldest first, so
+    /// `{:#}` and `chain()` show them. Plain `.into()` keeps the whole
+    /// `Handled` as one opaque error instead.
+    ///
+    /// The root arrives type-erased, so anyhow's `downcast_ref` can't see its
+    /// concrete type; downcast before converting if you need it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("connection refused")
+    ///     .frame("db.rs", 10, 5).ctx("connecting to db")
+    ///     .frame("app.rs", 3, 1).ctx("loading user");
+    /// let err = err.into_anyhow();
+    /// assert_eq!(format!("{:#}", err), "loading user: connecting to db: connection refused");
+    /// ```
+    pub fn into_anyhow(self) -> anyhow::Error {
+        let mut messages: Vec<String> = Vec::new();
+        if let Some(contexts) = self.contexts {
+            let mut contexts = contexts;
+            contexts.sort_by_key(|c| c.location_idx);
+            messages.extend(contexts.into_iter().filter_map(|c| c.message));
+        }
+
+        let mut err = anyhow::Error::from_boxed(self.source.into_inner());
+        for message in messages {
+            err = err.context(message);
+        }
+        err
+    }
+}
+
 // ============================================================
 // eyre interop
 // ============================================================
@@ -1308,18 +3509,337 @@ impl From<eyre::Report> for Handled<Error> {
     }
 }
 
+// ============================================================
+// JSON output
+// ============================================================
+
+impl FrameView<'_> {
+    /// Render this frame as a JSON object.
+    ///
+    /// Same shape as the frame's serde form: `file`, `line` and `col`, then
+    /// `message`, `attachments` (sorted by key, values keep their type),
+    /// `scope` and `note` only when present. Needs no `serde` feature.
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("boom").frame("src/db.rs", 4, 9).ctx("querying").kv("rows", 3);
+    /// let frame = err.frames().next().unwrap();
+    /// assert_eq!(
+    ///     frame.format_json(),
+    ///     r#"{"file":"src/db.rs","line":4,"col":9,"message":"querying","attachments":{"rows":3}}"#,
+    /// );
+    /// ```
+    pub fn format_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    fn write_json(&self, out: &mut String) {
+        use fmt::Write;
+        out.push_str("{\"file\":");
+        write_json_str(out, self.file);
+        let _ = write!(out, ",\"line\":{},\"col\":{}", self.line, self.col);
+        if let Some(function) = self.function {
+            out.push_str(",\"function\":");
+            write_json_str(out, function);
+        }
+        if let Some(message) = self.context {
+            out.push_str(",\"message\":");
+            write_json_str(out, message);
+        }
+        if !self.attachments_inner.is_empty() {
+            // Later duplicates win, as in the serde form
+            let map: BTreeMap<&str, &Value> = self.attachments().collect();
+            out.push_str(",\"attachments\":{");
+            for (i, (key, value)) in map.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_str(out, key);
+                out.push(':');
+                write_json_value(out, value);
+            }
+            out.push('}');
+        }
+        if self.is_scope {
+            out.push_str(",\"scope\":true");
+        }
+        if self.is_note {
+            out.push_str(",\"note\":true");
+        }
+        if self.repeat_count > 1 {
+            let _ = write!(out, ",\"repeat\":{}", self.repeat_count);
+        }
+        out.push('}');
+    }
+}
+
+impl<E: fmt::Display> Handled<E> {
+    /// Render this error as a JSON string for logs and other tools.
+    ///
+    /// Contains the `message` and the `trace` (see
+    /// [`FrameView::format_json`]), plus the messages of chained links as
+    /// `chain`, and `thread` and `timestamp` when those features are on.
+    /// Matches what the `serde` feature produces through `serde_json`, but
+    /// needs no serializer in the caller; only serde writes each chained
+    /// link whole, with its own trace, so that it round-trips.
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("disk full").frame("src/io.rs", 10, 5).kv("free", 0u64);
+    /// assert!(err.to_json().starts_with(
+    ///     r#"{"message":"disk full","trace":[{"file":"src/io.rs","line":10,"col":5,"attachments":{"free":0}}]"#,
+    /// ));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"message\":");
+        write_json_str(&mut out, self.message());
+
+        out.push_str(",\"trace\":[");
+        for (i, frame) in self.frames().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            frame.write_json(&mut out);
+        }
+        out.push(']');
+
+        if self.chained.is_some() {
+            out.push_str(",\"chain\":[");
+            let mut current = self.chained.as_deref();
+            while let Some(link) = current {
+                write_json_str(&mut out, link.message());
+                current = link.chained.as_deref();
+                if current.is_some() {
+                    out.push(',');
+                }
+            }
+            out.push(']');
+        }
+
+        #[cfg(feature = "thread-info")]
+        if let Some(name) = self.thread_name() {
+            out.push_str(",\"thread\":");
+            write_json_str(&mut out, name);
+        }
+
+        #[cfg(feature = "timestamps")]
+        if let Some(stamp) = self.created_at.and_then(format_rfc3339) {
+            out.push_str(",\"timestamp\":");
+            write_json_str(&mut out, &stamp);
+        }
+
+        out.push('}');
+        out
+    }
+}
+
+/// Append `s` as a quoted JSON string.
+pub(crate) fn write_json_str(out: &mut String, s: &str) {
+    use fmt::Write;
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Append a typed attachment value. Non-finite floats become `null`,
+/// since JSON has no representation for them. Bytes become an array of
+/// numbers, durations `{"secs":..,"nanos":..}` and secrets `{"$redacted":"<hash>"}`,
+/// matching the serde output.
+pub(crate) fn write_json_value(out: &mut String, value: &Value) {
+    use fmt::Write;
+    let _ = match value {
+        Value::String(s) => {
+            write_json_str(out, s);
+            Ok(())
+        }
+        Value::Int(n) => write!(out, "{}", n),
+        Value::Uint(n) => write!(out, "{}", n),
+        Value::Float(n) if n.is_finite() => write!(out, "{:?}", n),
+        Value::Float(_) | Value::Null => write!(out, "null"),
+        Value::Bool(b) => write!(out, "{}", b),
+        Value::List(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_value(out, item);
+            }
+            out.push(']');
+            Ok(())
+        }
+        Value::Map(entries) => {
+            out.push('{');
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_str(out, key);
+                out.push(':');
+                write_json_value(out, value);
+            }
+            out.push('}');
+            Ok(())
+        }
+        Value::Bytes(bytes) => {
+            out.push('[');
+            for (i, b) in bytes.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                let _ = write!(out, "{}", b);
+            }
+            out.push(']');
+            Ok(())
+        }
+        Value::Duration(d) => write!(out, "{{\"secs\":{},\"nanos\":{}}}", d.as_secs(), d.subsec_nanos()),
+        Value::Redacted(hash) => write!(out, "{{\"$redacted\":\"{:016x}\"}}", hash),
+    };
+}
+
+/// Format as RFC 3339 in UTC with microsecond precision.
+/// Times before the Unix epoch are not representable and yield `None`.
+#[cfg(feature = "timestamps")]
+fn format_rfc3339(time: std::time::SystemTime) -> Option<String> {
+    let since = time.duration_since(std::time::UNIX_EPOCH).ok()?;
+    let secs = since.as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year, month, day,
+        rem / 3600, rem % 3600 / 60, rem % 60,
+        since.subsec_micros(),
+    ))
+}
+
+// ============================================================
+// Pretty output (color feature)
+// ============================================================
+
+#[cfg(feature = "color")]
+impl<E: fmt::Display> Handled<E> {
+    /// Render this error for reading in a terminal.
+    ///
+    /// Same content as `Display`, laid out for scanning: the message in bold
+    /// red, numbered frames aligned under it with dimmed file paths, and
+    /// context messages highlighted. ANSI colors are used only when stderr
+    /// is a terminal and `NO_COLOR` is unset; see [`render_pretty_with`]
+    /// to choose explicitly.
+    ///
+    /// [`render_pretty_with`]: Handled::render_pretty_with
+    pub fn render_pretty(&self) -> String {
+        use std::io::IsTerminal;
+        let color = std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        self.render_pretty_with(color)
+    }
+
+    /// [`render_pretty`](Handled::render_pretty) with ANSI colors forced on or off.
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("disk full").frame("src/io.rs", 10, 5).ctx("saving");
+    /// let plain = err.render_pretty_with(false);
+    /// assert!(plain.starts_with("error: disk full\n"));
+    /// assert!(plain.ends_with("\n  1  src/io.rs:10:5\n     \u{2192} saving\n"));
+    /// assert!(err.render_pretty_with(true).contains("\x1b[2msrc/io.rs\x1b[0m"));
+    /// ```
+    pub fn render_pretty_with(&self, color: bool) -> String {
+        use fmt::Write;
+        let paint = |code: &str, text: &dyn fmt::Display| {
+            if color {
+                format!("\x1b[{}m{}\x1b[0m", code, text)
+            } else {
+                text.to_string()
+            }
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{} {}", paint("1;31", &"error:"), paint("1", &self.message()));
+
+        #[cfg(feature = "thread-info")]
+        if let Some(name) = self.thread_name() {
+            let _ = writeln!(out, "{}", paint("2", &format_args!("thread: {}", name)));
+        }
+
+        let width = self.depth().to_string().len();
+        // Continuation lines start under the file path
+        let indent = " ".repeat(width + 4);
+        for (i, frame) in self.frames().enumerate() {
+            if i == 0 {
+                out.push('\n');
+            }
+            let _ = write!(
+                out,
+                "  {}  {}:{}:{}",
+                paint("2", &format_args!("{:>width$}", i + 1, width = width)),
+                paint("2", &frame.file),
+                frame.line,
+                frame.col,
+            );
+            if let Some(function) = frame.function {
+                let _ = write!(out, " in {}", paint("1", &function));
+            }
+            if frame.is_note() {
+                let _ = write!(out, " {}", paint("35", &"(note)"));
+            }
+            out.push('\n');
+            if let Some(message) = frame.context {
+                let _ = writeln!(out, "{}{}", indent, paint("1;36", &format_args!("\u{2192} {}", message)));
+            }
+            for (key, value) in frame.attachments() {
+                let _ = writeln!(out, "{}{}: {}", indent, paint("33", &key), value);
+            }
+            if frame.repeat_count() > 1 {
+                let _ = writeln!(out, "{}{}", indent, paint("2", &format_args!("... repeated {} times", frame.repeat_count())));
+            }
+        }
+        out
+    }
+}
+
 // ============================================================
 // Serde support
 // ============================================================
 
+#[cfg(feature = "serde")]
+pub use serde_impl::{FrameRepr, HandledRepr};
+
 #[cfg(feature = "serde")]
 mod serde_impl {
     use super::*;
-    #[cfg(feature = "std")]
-    use std::collections::BTreeMap;
-    #[cfg(not(feature = "std"))]
-    use alloc::collections::BTreeMap;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use crate::intern::intern;
 
     // Serialize Value to preserve type information
     impl Serialize for Value {
@@ -1331,6 +3851,16 @@ mod serde_impl {
                 Value::Float(n) => serializer.serialize_f64(*n),
                 Value::Bool(b) => serializer.serialize_bool(*b),
                 Value::Null => serializer.serialize_none(),
+                Value::List(items) => serializer.collect_seq(items),
+                Value::Map(entries) => serializer.collect_map(entries),
+                Value::Bytes(bytes) => serializer.serialize_bytes(bytes),
+                Value::Duration(d) => d.serialize(serializer),
+                Value::Redacted(hash) => {
+                    use serde::ser::SerializeMap;
+                    let mut map = serializer.serialize_map(Some(1))?;
+                    map.serialize_entry(REDACTED_TAG, &format_args!("{:016x}", hash))?;
+                    map.end()
+                }
             }
         }
     }
@@ -1345,7 +3875,7 @@ mod serde_impl {
                 type Value = Value;
 
                 fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                    formatter.write_str("a string, number, boolean, or null")
+                    formatter.write_str("a string, number, boolean, null, list, map, or bytes")
                 }
 
                 fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
@@ -1379,77 +3909,345 @@ mod serde_impl {
                 fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
                     Ok(Value::Null)
                 }
+
+                fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Value, E> {
+                    Ok(Value::Bytes(v.to_vec()))
+                }
+
+                fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Value, E> {
+                    Ok(Value::Bytes(v))
+                }
+
+                fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+                    let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                    while let Some(item) = seq.next_element()? {
+                        items.push(item);
+                    }
+                    Ok(Value::List(items))
+                }
+
+                // A map of exactly `secs` and `nanos` is how a duration
+                // serializes, and one `$redacted` entry is how a secret
+                // does, so both come back as such
+                fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+                    let mut entries = BTreeMap::new();
+                    while let Some((key, value)) = map.next_entry::<String, Value>()? {
+                        entries.insert(key, value);
+                    }
+                    if entries.len() == 1 {
+                        if let Some(Value::String(hex)) = entries.get(REDACTED_TAG) {
+                            if let Some(hash) = redacted_from_hex(hex) {
+                                return Ok(hash);
+                            }
+                        }
+                    }
+                    if entries.len() == 2 {
+                        if let (Some(Value::Uint(secs)), Some(Value::Uint(nanos))) =
+                            (entries.get("secs"), entries.get("nanos"))
+                        {
+                            if *nanos < 1_000_000_000 {
+                                return Ok(Value::Duration(core::time::Duration::new(*secs, *nanos as u32)));
+                            }
+                        }
+                    }
+                    Ok(Value::Map(entries))
+                }
             }
 
             deserializer.deserialize_any(ValueVisitor)
         }
     }
 
-    #[derive(Serialize, Deserialize)]
-    struct SerializedFrame {
-        file: String,
-        line: u32,
-        col: u32,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        message: Option<String>,
-        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-        attachments: BTreeMap<String, Value>,
+    /// The key of the one-entry map a [`Value::Redacted`] serializes as.
+    const REDACTED_TAG: &str = "$redacted";
+
+    fn redacted_from_hex(hex: &str) -> Option<Value> {
+        if hex.len() != 16 {
+            return None;
+        }
+        u64::from_str_radix(hex, 16).ok().map(Value::Redacted)
+    }
+
+    /// One frame of a [`HandledRepr`].
+    ///
+    /// Owned counterpart of [`FrameView`].
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct FrameRepr {
+        /// Source file path
+        pub file: String,
+        /// Line number
+        pub line: u32,
+        /// Column number
+        pub col: u32,
+        /// Enclosing `#[traced]` function, if any
+        #[serde(default)]
+        pub function: Option<String>,
+        /// Context message, if any
+        #[serde(default)]
+        pub message: Option<String>,
+        /// Key-value attachments
+        #[serde(default)]
+        pub attachments: BTreeMap<String, Value>,
+        /// Pushed by a `scope`
+        #[serde(default)]
+        pub scope: bool,
+        /// Pushed by `note_at`
+        #[serde(default)]
+        pub note: bool,
+        /// Consecutive pushes collapsed into this frame
+        #[serde(default = "one")]
+        pub repeat: u32,
     }
 
-    #[derive(Serialize, Deserialize)]
-    struct SerializedHandled {
-        message: String,
-        trace: Vec<SerializedFrame>,
+    fn one() -> u32 {
+        1
     }
 
-    // Only implement for Error variant (type-erased)
-    impl Serialize for Handled<Error> {
-        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            let contexts = self.contexts.as_ref();
-            let serialized = SerializedHandled {
-                message: self.message().to_string(),
-                trace: self
-                    .locations
+    impl From<&FrameView<'_>> for FrameRepr {
+        fn from(f: &FrameView<'_>) -> Self {
+            FrameRepr {
+                file: f.file.to_string(),
+                line: f.line,
+                col: f.col,
+                function: f.function.map(str::to_string),
+                message: f.context.map(str::to_string),
+                attachments: f
+                    .attachments_inner
                     .iter()
-                    .enumerate()
-                    .map(|(idx, loc)| {
-                        let ctx = contexts.and_then(|c| c.iter().find(|e| e.location_idx == idx as u16));
-                        SerializedFrame {
-                            file: loc.file.to_string(),
-                            line: loc.line,
-                            col: loc.col,
-                            message: ctx.and_then(|c| c.message.clone()),
-                            attachments: ctx
-                                .map(|c| c.attachments.iter()
-                                    .map(|(k, v)| (k.to_string(), v.clone()))
-                                    .collect::<BTreeMap<_, _>>())
-                                .unwrap_or_default(),
-                        }
-                    })
+                    .map(|(k, v)| (k.to_string(), v.clone()))
                     .collect(),
-            };
-            serialized.serialize(serializer)
+                scope: f.is_scope,
+                note: f.is_note,
+                repeat: f.repeat_count,
+            }
         }
     }
 
-    impl<'de> Deserialize<'de> for Handled<Error> {
-        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-            let serialized = SerializedHandled::deserialize(deserializer)?;
+    /// Plain-data form of a [`Handled`], for any serde format.
+    ///
+    /// Built by [`Handled::to_serializable`]; converts back with
+    /// `Handled::from`. In human-readable formats like JSON, empty and
+    /// default fields are left out; binary formats like CBOR or
+    /// MessagePack always get every field in the same order, so
+    /// positional (array-encoded) structs decode too.
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    pub struct HandledRepr {
+        /// The error message
+        pub message: String,
+        /// Frames, oldest first
+        pub trace: Vec<FrameRepr>,
+        /// Chained links (from `chain_after`/`import_chain`), outermost first.
+        /// Links carry their own trace. `to_serializable` lists them flat;
+        /// a link's own `chain` converts back as the links right after it.
+        #[serde(default)]
+        pub chain: Vec<HandledRepr>,
+        /// Name of the thread the error was created on
+        #[cfg(feature = "thread-info")]
+        #[serde(default)]
+        pub thread: Option<String>,
+        /// Creation time as an RFC 3339 UTC timestamp
+        #[cfg(feature = "timestamps")]
+        #[serde(default)]
+        pub timestamp: Option<String>,
+    }
+
+    impl Serialize for FrameRepr {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeStruct;
+            let full = !serializer.is_human_readable();
+            // Same order as `FrameView::format_json`
+            let optional = [
+                full || self.function.is_some(),
+                full || self.message.is_some(),
+                full || !self.attachments.is_empty(),
+                full || self.scope,
+                full || self.note,
+                full || self.repeat != 1,
+            ];
+            // Sized formats like CBOR write the field count up front
+            let len = 3 + optional.iter().filter(|&&b| b).count();
+            let mut state = serializer.serialize_struct("FrameRepr", len)?;
+            state.serialize_field("file", &self.file)?;
+            state.serialize_field("line", &self.line)?;
+            state.serialize_field("col", &self.col)?;
+            if optional[0] {
+                state.serialize_field("function", &self.function)?;
+            }
+            if optional[1] {
+                state.serialize_field("message", &self.message)?;
+            }
+            if optional[2] {
+                state.serialize_field("attachments", &self.attachments)?;
+            }
+            if optional[3] {
+                state.serialize_field("scope", &self.scope)?;
+            }
+            if optional[4] {
+                state.serialize_field("note", &self.note)?;
+            }
+            if optional[5] {
+                state.serialize_field("repeat", &self.repeat)?;
+            }
+            state.end()
+        }
+    }
+
+    impl Serialize for HandledRepr {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeStruct;
+            let full = !serializer.is_human_readable();
+            let chain = full || !self.chain.is_empty();
+            #[cfg(feature = "thread-info")]
+            let thread = full || self.thread.is_some();
+            #[cfg(not(feature = "thread-info"))]
+            let thread = false;
+            #[cfg(feature = "timestamps")]
+            let timestamp = full || self.timestamp.is_some();
+            #[cfg(not(feature = "timestamps"))]
+            let timestamp = false;
+
+            let len = 2 + [chain, thread, timestamp].iter().filter(|&&b| b).count();
+            let mut state = serializer.serialize_struct("HandledRepr", len)?;
+            state.serialize_field("message", &self.message)?;
+            state.serialize_field("trace", &self.trace)?;
+            if chain {
+                state.serialize_field("chain", &self.chain)?;
+            }
+            #[cfg(feature = "thread-info")]
+            if thread {
+                state.serialize_field("thread", &self.thread)?;
+            }
+            #[cfg(feature = "timestamps")]
+            if timestamp {
+                state.serialize_field("timestamp", &self.timestamp)?;
+            }
+            state.end()
+        }
+    }
+
+    /// Parse an RFC 3339 timestamp (any offset, optional fraction).
+    #[cfg(feature = "timestamps")]
+    fn parse_rfc3339(s: &str) -> Option<std::time::SystemTime> {
+        let num = |range: core::ops::Range<usize>| -> Option<i64> {
+            let part = s.get(range)?;
+            if !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            part.parse().ok()
+        };
+        let bytes = s.as_bytes();
+        if bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-'
+            || !matches!(bytes[10], b'T' | b't' | b' ') || bytes[13] != b':' || bytes[16] != b':'
+        {
+            return None;
+        }
+        let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+        let (hour, min, sec) = (num(11..13)?, num(14..16)?, num(17..19)?);
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || min > 59 || sec > 60 {
+            return None;
+        }
+
+        let mut rest = &s[19..];
+        let mut nanos = 0u32;
+        if let Some(frac) = rest.strip_prefix('.') {
+            let digits = frac.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            for (i, b) in frac.bytes().take(digits).enumerate() {
+                if i < 9 {
+                    nanos += u32::from(b - b'0') * 10u32.pow(8 - i as u32);
+                }
+            }
+            rest = &frac[digits..];
+        }
+        let offset = match rest.as_bytes() {
+            [b'Z' | b'z'] => 0,
+            [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2]
+                if [h1, h2, m1, m2].iter().all(|b| b.is_ascii_digit()) =>
+            {
+                let minutes = i64::from((h1 - b'0') * 10 + (h2 - b'0')) * 60
+                    + i64::from((m1 - b'0') * 10 + (m2 - b'0'));
+                if *sign == b'+' { minutes * 60 } else { -minutes * 60 }
+            }
+            _ => return None,
+        };
+
+        // Days-from-civil (Howard Hinnant's algorithm)
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let mp = if month > 2 { month - 3 } else { month + 9 };
+        let doy = (153 * mp + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+
+        let secs = days * 86_400 + hour * 3600 + min * 60 + sec - offset;
+        let secs = u64::try_from(secs).ok()?;
+        std::time::UNIX_EPOCH.checked_add(std::time::Duration::new(secs, nanos))
+    }
+
+    impl<E: fmt::Display> Handled<E> {
+        /// Copy this error into a [`HandledRepr`]: plain owned data that
+        /// any serde format can write.
+        ///
+        /// Chained links keep their own frames and contexts, not just
+        /// their messages.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use handle_this::{Handled, HandledRepr};
+        ///
+        /// let err = Handled::msg("outer").frame("a.rs", 1, 1)
+        ///     .chain_after(Handled::msg("inner").frame("b.rs", 2, 1).ctx("reading"));
+        /// let repr: HandledRepr = err.to_serializable();
+        /// assert_eq!(repr.chain[0].trace[0].message.as_deref(), Some("reading"));
+        ///
+        /// let back = Handled::from(repr);
+        /// assert_eq!(back.context_messages(), ["outer", "inner"]);
+        /// ```
+        pub fn to_serializable(&self) -> HandledRepr {
+            let mut repr = self.repr_link();
+            {
+                let mut current = self.chained.as_deref();
+                while let Some(link) = current {
+                    repr.chain.push(link.repr_link());
+                    current = link.chained.as_deref();
+                }
+            }
+            repr
+        }
+
+        /// This error without its chain.
+        fn repr_link(&self) -> HandledRepr {
+            HandledRepr {
+                message: self.message().to_string(),
+                trace: self.frames().map(|f| FrameRepr::from(&f)).collect(),
+                chain: Vec::new(),
+                #[cfg(feature = "thread-info")]
+                thread: self.thread_name().map(str::to_string),
+                #[cfg(feature = "timestamps")]
+                timestamp: self.created_at.and_then(format_rfc3339),
+            }
+        }
+    }
 
+    impl From<HandledRepr> for Handled<Error> {
+        fn from(repr: HandledRepr) -> Self {
             let mut locations = LocationVec::new();
             let mut contexts = Vec::new();
 
-            for (idx, f) in serialized.trace.into_iter().enumerate() {
-                // Note: deserialized files are owned strings, we leak them to get 'static
-                // This is acceptable for deserialized errors which are typically short-lived
-                let file: &'static str = Box::leak(f.file.into_boxed_str());
-                locations.push(Location {
-                    file,
-                    line: f.line,
-                    col: f.col,
-                });
-
-                if f.message.is_some() || !f.attachments.is_empty() {
+            for (idx, f) in repr.trace.into_iter().enumerate() {
+                locations.push(
+                    Location {
+                        repeat: u16::try_from(f.repeat.max(1)).unwrap_or(u16::MAX),
+                        ..Location::new(intern(f.file), f.line, f.col)
+                    },
+                    f.function.map(intern),
+                );
+
+                if f.message.is_some() || !f.attachments.is_empty() || f.scope || f.note {
                     contexts.push(ContextEntry {
                         location_idx: idx as u16,
                         message: f.message,
@@ -1457,58 +4255,89 @@ mod serde_impl {
                             .into_iter()
                             .map(|(k, v)| (Cow::Owned(k), v))
                             .collect(),
+                        is_scope: f.scope,
+                        is_note: f.note,
                     });
                 }
             }
 
-            Ok(Self {
+            let mut links = Vec::new();
+            flatten_links(repr.chain, &mut links);
+            let chained = links.into_iter().rev().fold(None, |next, link| {
+                let mut link = Handled::from(link);
+                link.chained = next;
+                Some(Box::new(link))
+            });
+
+            Self {
                 message: {
                     let lock = OnceLock::new();
-                    let _ = lock.set(serialized.message.clone());
+                    let _ = lock.set(repr.message.clone());
                     lock
                 },
-                source: Error::new(StringError(serialized.message)),
+                source: Error::new(StringError(repr.message)),
                 locations,
                 contexts: if contexts.is_empty() { None } else { Some(contexts) },
-                #[cfg(feature = "std")]
-                chained: None,
-            })
+                chained,
+                fields: None,
+                #[cfg(feature = "thread-info")]
+                thread: ThreadInfo {
+                    name: repr.thread.map(Into::into),
+                    id: None,
+                },
+                #[cfg(feature = "backtrace")]
+                backtrace: std::sync::Arc::new(std::backtrace::Backtrace::disabled()),
+                #[cfg(feature = "tracing")]
+                span_id: None,
+                #[cfg(feature = "timestamps")]
+                created_at: repr.timestamp.as_deref().and_then(parse_rfc3339),
+            }
+        }
+    }
+
+    /// List links flat, each followed by the links of its own `chain`.
+    fn flatten_links(chain: Vec<HandledRepr>, out: &mut Vec<HandledRepr>) {
+        for mut link in chain {
+            let nested = core::mem::take(&mut link.chain);
+            out.push(link);
+            flatten_links(nested, out);
+        }
+    }
+
+    // Only implement for Error variant (type-erased)
+    impl Serialize for Handled<Error> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.to_serializable().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Handled<Error> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            HandledRepr::deserialize(deserializer).map(Handled::from)
         }
     }
 
     impl Serialize for Location {
         fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            SerializedFrame {
+            FrameRepr {
                 file: self.file.to_string(),
                 line: self.line,
-                col: self.col,
+                col: self.col.into(),
+                function: None,
                 message: None,
                 attachments: BTreeMap::new(),
+                scope: false,
+                note: false,
+                repeat: self.repeat.into(),
             }
             .serialize(serializer)
         }
     }
 
-    // Custom serializer for FrameView that serializes attachments as a map
+    // Same shape and field count as the frames of a serialized `Handled`
     impl Serialize for FrameView<'_> {
         fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            use serde::ser::SerializeStruct;
-            let mut state = serializer.serialize_struct("FrameView", 5)?;
-            state.serialize_field("file", self.file)?;
-            state.serialize_field("line", &self.line)?;
-            state.serialize_field("col", &self.col)?;
-            if self.context.is_some() {
-                state.serialize_field("message", &self.context)?;
-            }
-            if !self.attachments_inner.is_empty() {
-                // Serialize as a map
-                let map: BTreeMap<&str, &Value> = self.attachments_inner
-                    .iter()
-                    .map(|(k, v)| (k.as_ref(), v))
-                    .collect();
-                state.serialize_field("attachments", &map)?;
-            }
-            state.end()
+            FrameRepr::from(self).serialize(serializer)
         }
     }
 }
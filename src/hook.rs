@@ -0,0 +1,97 @@
+//! Process-wide error hook.
+//!
+//! One observer sees every error `handle!` wraps, so a telemetry sink
+//! (Sentry, OTLP, a log shipper) doesn't need an `inspect` at each site.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::Handled;
+
+type Hook = Arc<dyn Fn(&Handled) + Send + Sync + 'static>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+// Skips the lock on the hot path while no hook is installed
+static HOOK_SET: AtomicBool = AtomicBool::new(false);
+
+std::thread_local! {
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install `hook`, replacing any previous one.
+///
+/// The hook runs when `handle!` wraps a new error: the first time an error
+/// passes through a `try` body (or a `then` step) it gets its first frame,
+/// and the hook sees it right after, before any `with` context is added.
+/// Errors that already carry frames are re-framed silently as they
+/// propagate, so each failure is reported once.
+///
+/// A `catch` that neither binds nor inspects the error skips wrapping it
+/// entirely, so the hook doesn't see errors swallowed that way. Errors
+/// raised inside the hook itself don't re-enter it.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{clear_hook, handle, set_hook, Result};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static SEEN: AtomicUsize = AtomicUsize::new(0);
+///
+/// set_hook(|err| {
+///     assert_eq!(err.depth(), 1);
+///     SEEN.fetch_add(1, Ordering::Relaxed);
+/// });
+///
+/// let result: Result<i32> = handle! { try { "x".parse::<i32>()? } };
+/// assert!(result.is_err());
+/// assert_eq!(SEEN.load(Ordering::Relaxed), 1);
+/// clear_hook();
+/// ```
+pub fn set_hook<F>(hook: F)
+where
+    F: Fn(&Handled) + Send + Sync + 'static,
+{
+    let mut slot = HOOK.write().unwrap_or_else(|e| e.into_inner());
+    *slot = Some(Arc::new(hook));
+    HOOK_SET.store(true, Ordering::Release);
+}
+
+/// Remove the installed hook, if any.
+pub fn clear_hook() {
+    let mut slot = HOOK.write().unwrap_or_else(|e| e.into_inner());
+    *slot = None;
+    HOOK_SET.store(false, Ordering::Release);
+}
+
+/// Run the hook on a freshly wrapped error.
+#[inline]
+pub(crate) fn on_wrap(err: &Handled) {
+    if HOOK_SET.load(Ordering::Acquire) {
+        run(err);
+    }
+}
+
+#[cold]
+fn run(err: &Handled) {
+    // Clone out of the lock so the hook may call `set_hook` itself
+    let hook = match HOOK.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(hook) => Arc::clone(hook),
+        None => return,
+    };
+    if IN_HOOK.with(|h| h.replace(true)) {
+        return;
+    }
+
+    /// Clears the re-entry flag even if the hook panics.
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IN_HOOK.with(|h| h.set(false));
+        }
+    }
+
+    let _reset = Reset;
+    hook(err);
+}
@@ -0,0 +1,64 @@
+//! Interned names for deserialized frames.
+//!
+//! Frames keep `&'static str` file and function names so `Location` stays
+//! `Copy` and `file!()` paths cost nothing. A deserialized frame's names
+//! are owned strings, so each distinct one is stored here once for the
+//! life of the process. Deserializing the same errors again reuses the
+//! stored names instead of allocating new ones.
+//!
+//! The table is a lock-free list (it also works without `std`) capped at
+//! [`INTERN_LIMIT`] names, so input with endless distinct paths can't grow
+//! it without bound.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+/// Most distinct names kept; past it, new names read [`UNKNOWN`].
+pub(crate) const INTERN_LIMIT: usize = 4096;
+
+/// Stand-in for a name that didn't fit in the table.
+pub(crate) const UNKNOWN: &str = "(unknown)";
+
+struct Node {
+    name: &'static str,
+    next: *const Node,
+}
+
+static HEAD: AtomicPtr<Node> = AtomicPtr::new(ptr::null_mut());
+static LEN: AtomicUsize = AtomicUsize::new(0);
+
+/// The stored copy of `name`, adding it if it's new.
+pub(crate) fn intern(name: String) -> &'static str {
+    let mut head = HEAD.load(Ordering::Acquire);
+    if let Some(found) = find(head, &name) {
+        return found;
+    }
+    if LEN.fetch_add(1, Ordering::Relaxed) >= INTERN_LIMIT {
+        LEN.fetch_sub(1, Ordering::Relaxed);
+        return UNKNOWN;
+    }
+
+    let name: &'static str = Box::leak(name.into_boxed_str());
+    let node = Box::into_raw(Box::new(Node { name, next: head }));
+    // A racing thread may push the same name first; the duplicate is
+    // harmless and counts toward the limit
+    while let Err(newer) = HEAD.compare_exchange_weak(head, node, Ordering::AcqRel, Ordering::Acquire) {
+        head = newer;
+        // SAFETY: `node` isn't shared until the exchange succeeds
+        unsafe { (*node).next = head };
+    }
+    name
+}
+
+fn find(mut node: *const Node, name: &str) -> Option<&'static str> {
+    // SAFETY: nodes are leaked once published and never freed or changed
+    while let Some(current) = unsafe { node.as_ref() } {
+        if current.name == name {
+            return Some(current.name);
+        }
+        node = current.next;
+    }
+    None
+}
@@ -0,0 +1,219 @@
+//! Coarse error kinds.
+//!
+//! [`Handled::kind`] sorts any error into a small [`ErrorKind`] so one
+//! table can map it to an HTTP status, exit code, or retry decision.
+//! Common std errors are classified out of the box; application errors
+//! are taught with [`register_kind_rule`].
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::{Error, Elapsed, Handled};
+
+/// A coarse category of error, from [`Handled::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The thing asked for doesn't exist.
+    NotFound,
+    /// An operation or a peer took too long.
+    Timeout,
+    /// The caller isn't allowed to do this.
+    Permission,
+    /// The input was malformed or out of range.
+    Invalid,
+    /// The request clashes with current state (already exists, edited concurrently).
+    Conflict,
+    /// A dependency is down or refused the connection; worth retrying later.
+    Unavailable,
+    /// Anything else: a bug or an unexpected failure.
+    Internal,
+}
+
+impl ErrorKind {
+    /// The HTTP status code conventionally used for this kind.
+    ///
+    /// ```
+    /// use handle_this::ErrorKind;
+    ///
+    /// assert_eq!(ErrorKind::NotFound.http_status(), 404);
+    /// assert_eq!(ErrorKind::Internal.http_status(), 500);
+    /// ```
+    pub fn http_status(self) -> u16 {
+        match self {
+            ErrorKind::NotFound => 404,
+            ErrorKind::Timeout => 504,
+            ErrorKind::Permission => 403,
+            ErrorKind::Invalid => 400,
+            ErrorKind::Conflict => 409,
+            ErrorKind::Unavailable => 503,
+            ErrorKind::Internal => 500,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::NotFound => "not found",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Permission => "permission",
+            ErrorKind::Invalid => "invalid",
+            ErrorKind::Conflict => "conflict",
+            ErrorKind::Unavailable => "unavailable",
+            ErrorKind::Internal => "internal",
+        })
+    }
+}
+
+type KindRule = Arc<dyn Fn(&(dyn StdError + 'static)) -> Option<ErrorKind> + Send + Sync + 'static>;
+
+/// Registered rules, replaced wholesale on registration so a lookup only
+/// bumps a refcount and a rule may register another without deadlocking.
+fn kind_rules() -> &'static RwLock<Arc<[KindRule]>> {
+    static KIND_RULES: OnceLock<RwLock<Arc<[KindRule]>>> = OnceLock::new();
+    KIND_RULES.get_or_init(|| RwLock::new(Arc::from(Vec::new())))
+}
+
+/// Teach [`Handled::kind`] about an error type.
+///
+/// `rule` sees the root error and returns `None` for errors it doesn't
+/// know. Rules run in registration order before the built-in rules;
+/// the first `Some` wins.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{register_kind_rule, ErrorKind, Handled};
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct QuotaExceeded;
+/// impl fmt::Display for QuotaExceeded {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "quota exceeded")
+///     }
+/// }
+/// impl std::error::Error for QuotaExceeded {}
+///
+/// register_kind_rule(|e| e.is::<QuotaExceeded>().then_some(ErrorKind::Unavailable));
+///
+/// assert_eq!(Handled::wrap(QuotaExceeded).kind(), ErrorKind::Unavailable);
+/// ```
+pub fn register_kind_rule<F>(rule: F)
+where
+    F: Fn(&(dyn StdError + 'static)) -> Option<ErrorKind> + Send + Sync + 'static,
+{
+    let mut rules = kind_rules().write().unwrap_or_else(|e| e.into_inner());
+    let mut updated = rules.to_vec();
+    updated.push(Arc::new(rule));
+    *rules = updated.into();
+}
+
+/// Remove every rule added with [`register_kind_rule`].
+pub fn clear_kind_rules() {
+    *kind_rules().write().unwrap_or_else(|e| e.into_inner()) = Arc::from(Vec::new());
+}
+
+/// Kind of a plain error: registered rules, then the built-in rules.
+fn kind_of(err: &(dyn StdError + 'static)) -> ErrorKind {
+    if let Some(handled) = err.downcast_ref::<Handled>() {
+        return handled.kind();
+    }
+
+    let rules = Arc::clone(&kind_rules().read().unwrap_or_else(|e| e.into_inner()));
+    if let Some(kind) = rules.iter().find_map(|rule| rule(err)) {
+        return kind;
+    }
+
+    if let Some(io) = err.downcast_ref::<io::Error>() {
+        return match io.kind() {
+            io::ErrorKind::NotFound => ErrorKind::NotFound,
+            io::ErrorKind::TimedOut => ErrorKind::Timeout,
+            io::ErrorKind::PermissionDenied => ErrorKind::Permission,
+            io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof => {
+                ErrorKind::Invalid
+            }
+            io::ErrorKind::AlreadyExists => ErrorKind::Conflict,
+            io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::AddrNotAvailable => ErrorKind::Unavailable,
+            _ => ErrorKind::Internal,
+        };
+    }
+    if err.is::<Elapsed>() {
+        return ErrorKind::Timeout;
+    }
+    if err.is::<std::num::ParseIntError>()
+        || err.is::<std::num::ParseFloatError>()
+        || err.is::<std::str::ParseBoolError>()
+        || err.is::<std::str::Utf8Error>()
+        || err.is::<std::string::FromUtf8Error>()
+        || err.is::<std::net::AddrParseError>()
+    {
+        return ErrorKind::Invalid;
+    }
+    ErrorKind::Internal
+}
+
+impl Handled<Error> {
+    /// The coarse kind of this error.
+    ///
+    /// A kind set with [`with_kind`](Self::with_kind) (here or on a chained
+    /// error) wins; otherwise the root error goes through the rules from
+    /// [`register_kind_rule`], then the built-in rules for std errors
+    /// (`io::Error` kinds, `Elapsed`, parse errors). Anything unknown is
+    /// [`ErrorKind::Internal`].
+    ///
+    /// In `handle!`, `catch e when kind Timeout { }` is shorthand for
+    /// `catch e when e.kind() == ErrorKind::Timeout { }`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::{ErrorKind, Handled};
+    /// use std::io;
+    ///
+    /// let err = Handled::wrap(io::Error::new(io::ErrorKind::NotFound, "no such user"));
+    /// assert_eq!(err.kind(), ErrorKind::NotFound);
+    /// assert_eq!(err.kind().http_status(), 404);
+    ///
+    /// let err = Handled::msg("nope").with_kind(ErrorKind::Permission);
+    /// assert_eq!(err.kind(), ErrorKind::Permission);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self.field_or_chained::<ErrorKind>() {
+            Some(kind) => *kind,
+            None => kind_of(self.root()),
+        }
+    }
+
+    /// Set the kind reported by [`kind`](Self::kind), skipping classification.
+    pub fn with_kind(self, kind: ErrorKind) -> Self {
+        self.set_field(kind)
+    }
+}
+
+/// Kind of a handler binding, used by the `when kind ...` guard.
+#[doc(hidden)]
+pub trait __KindOf {
+    fn __kind(&self) -> ErrorKind;
+}
+
+impl<T: StdError + 'static> __KindOf for T {
+    #[inline]
+    fn __kind(&self) -> ErrorKind {
+        kind_of(self)
+    }
+}
+
+impl __KindOf for dyn StdError + 'static {
+    #[inline]
+    fn __kind(&self) -> ErrorKind {
+        kind_of(self)
+    }
+}
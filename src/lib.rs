@@ -22,14 +22,45 @@
 //! | Pattern | Description |
 //! |---------|-------------|
 //! | `try { }` | Execute, wrap error with trace |
+//! | `try<E> { }` | `Result<T, Handled<E>>`: `?` converts into `E`, no downcast needed |
 //! | `try { } catch e { }` | Recover from error |
 //! | `try { } catch Type(e) { }` | Recover only specific type |
 //! | `try { } catch Type(e) { } else { }` | Typed catch with fallback |
+//! | `try { } catch A \| B (e) { }` | First matching type, `e` as `&dyn Error` |
+//! | `try { } catch dyn Trait (e) { }` | Any type registered with `catchable!`, `e` as `&dyn Trait` |
+//! | `try { } branch { Type(e) => .., _ => .. }` | Match-like type switch (one typed catch per arm) |
+//! | `try { } catch e delay d { }` | Sleep for `d` before recovering (crude backpressure) |
 //! | `try { } try catch e { }` | Fallible recovery (body returns Result) |
+//! | `try { } catch panic p { }` | Catch panics from the body as `Panicked` (also `async try`) |
+//! | `try { } catch e { } assert_no_panic` | A panicking catch body becomes an error (yields Result) |
+//! | `try { } catch e { if x { rethrow } }` | Give up from a catch body; `rethrow expr` chains a new error |
+//! | `try { } catch e { } escalate` | Run the catch body, then propagate with severity raised a level |
 //! | `try { } throw e { }` | Transform error |
 //! | `try { } throw Type(e) { }` | Transform only specific type |
+//! | `try { } throw snapshot { }` | Transform into a borrowing error, captured by message |
+//! | `try { } throw as Enum::Variant` | Wrap the error in an enum variant, keeping the trace |
 //! | `try { } inspect e { }` | Side effect, then propagate |
+//! | `try { } convert MyError` | Replace error with `MyError::from(err)` as the new root |
+//! | `try { } on_ok \|v\| { }` | Tap the try body's success value (`&T`) |
+//! | `try { } to_option` | `Option<T>`: `None` on any unhandled error |
+//! | `try { } catch Type(e) { } unwrap_infallible` | `T`: handlers cover every error; panics if one escapes |
+//! | `try { } on_err \|e\| { }` | Tap the error path (like `inspect`) |
+//! | `try { } only_in_tests e { }` | Inspect compiled only under `cfg(test)` |
+//! | `try { } classify \|e\| -> T { }` | Attach computed value, read with `field::<T>()` |
+//! | `try { } snapshot \|rec\| { }` | Pass an owned `ErrorRecord` (fingerprint, message, origin) |
+//! | `try { } with_correlation(id)` | Attach a correlation id, read with `correlation_id()` |
+//! | `try { } log_once` | Print to stderr only the first time this error is seen here |
+//! | `try { } trace_error` | Emit a `tracing::error!` event with frames and attachments (`tracing` feature) |
+//! | `try { } inspect log error` | Emit a one-line record through the `log` facade (`log` feature) |
+//! | `try { } metric_inc "name"` | Increment a built-in counter (`metrics` feature) |
+//! | `try { } throttle key, N/sec` | Fail with `Throttled` past `N` attempts per window (`throttle` feature) |
+//! | `try { } ratelimit_propagate N/min else { }` | Skip the body and yield the fallback after `N` failures per window (`circuit-breaker` feature) |
+//! | `try { } continue_with \|e\| { }` | Side effect, then `continue` enclosing loop; an `Option` body recovers on `Some` |
+//! | `try { } report_and_continue` | Log the error (`log` feature, else stderr), then `continue` enclosing loop |
+//! | `try { } exit(code)` | Print error to stderr and exit the process (never returns) |
 //! | `try { } finally { }` | Cleanup always runs |
+//! | `try { } finally { } catch_finally_with \|b, f\| { }` | Fallible cleanup; combine errors when both fail |
+//! | `try { let x = a()?; defer { close(&x) }; ... }` | Cleanup registered mid-body; runs in reverse order at block exit |
 //! | `try -> T { } else { }` | Infallible (returns T, not Result) |
 //!
 //! ## Guards
@@ -39,7 +70,9 @@
 //! | `catch e when cond { }` | Conditional catch |
 //! | `catch Type(e) when cond { }` | Typed with guard |
 //! | `throw e when cond { }` | Conditional transform |
+//! | `catch e when kind Timeout \| NotFound { }` | Match by `ErrorKind` (`Handled::kind`) |
 //! | `catch Type(e) match expr { arms }` | Match on error value |
+//! | `catch Enum::Variant { x } { }` | Match an enum variant, binding its fields |
 //!
 //! ## Chain Search
 //!
@@ -54,15 +87,23 @@
 //! |---------|-------------|
 //! | `try { } with "message"` | Add context message |
 //! | `try { } with { key: val }` | Add structured data |
+//! | `try { } with { "x-key" => val }` | Data with non-identifier key |
+//! | `try { } with { key: secret val }` | Data kept only as a redacted hash |
+//! | `try { } with "msg", code "E1042", severity Warn` | Typed error code and `Severity` (`code()`, `severity()`) |
 //! | `try { } with "msg", { key: val }` | Both message and data |
 //! | `scope "name", try { }` | Hierarchical scope |
+//! | `scope lazy \|\| expr, try { }` / `with lazy \|\| expr` | Context built only on error |
+//! | `try { } with \|e\| expr` | Context computed from the wrapped error |
 //! | `require cond else "msg", try { }` | Precondition check |
+//! | `require let Some(x) = opt else "msg", try { }` | Bind a value or fail; `x` is in scope after |
+//! | `require cond else MyError::Bad, try { }` | Fail with an error value instead of a message |
 //!
 //! ## Chaining
 //!
 //! | Pattern | Description |
 //! |---------|-------------|
 //! | `try { a()? }, then \|x\| { b(x)? }` | Chain operations |
+//! | `try { a()? }, then async \|x\| { b(x).await? }` | Chain an async step |
 //!
 //! ## Iteration
 //!
@@ -71,39 +112,236 @@
 //! | `try for x in iter { }` | First success |
 //! | `try any x in iter { }` | Alias for try for |
 //! | `try all x in iter { }` | Collect all results |
+//! | `try all x in iter { } group "name"` | Tag errors for `Handled::combine` sections |
+//! | `try all x in iter { } partial \|oks, errs\| { }` | Keep every success and every failure (`Vec<Handled>`) |
 //! | `try while cond { }` | Retry loop |
+//! | `try while cond, backoff exponential(100ms, max 5s) { }` | Sleep between retries; final error gets an `attempts` kv |
+//! | `try while cond limit N { }` | Stop after `N` failures; final error chains every attempt |
 //!
 //! ## Async
 //!
 //! | Pattern | Description |
 //! |---------|-------------|
 //! | `async try { }` | Async version (all patterns supported) |
+//! | `async try { } finally async { }` | Awaited async cleanup |
+//! | `async try { } finally { } cancel_safe` | `finally` also runs if the future is dropped |
+//! | `async try timeout d { }` | Fail with `Elapsed` if the body takes longer than `d` |
+//! | `async try while cond, backoff fixed(d) { }` | Retry loop whose body and backoff are awaited |
+//! | `async try spawn { }` | Run the body as a `tokio::spawn`ed task; panics become `Panicked` (`tokio` feature) |
+//! | `async try concurrent(N) for x in iter { }` | Up to `N` bodies at once, first success wins (`futures` feature) |
+//! | `async try concurrent(N) all x in iter { }` | Up to `N` bodies at once, collect all in input order (`futures` feature) |
+//! | `async try stream x in s { }` | Drive a `Stream`, handlers per item with `continue`/`break` (`futures` feature) |
+//! | `async try join { a: fut_a(), b: fut_b() }` | Await all at once, tuple of values; every failure chained |
+//! | `async try race { primary(), fallback() }` | First success wins, the rest are cancelled; all failures chained |
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(not(feature = "std"))]
 extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 // ============================================================
 // Modules
 // ============================================================
 
 mod handled;
+#[cfg(feature = "serde")]
+mod intern;
+mod limits;
+mod redact;
+mod core_error;
+#[cfg(not(feature = "std"))]
+mod once;
+mod catchable;
 mod ext;
 mod macros;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "std")]
+mod snapshot;
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "std")]
+mod timer;
+#[cfg(feature = "std")]
+mod panicked;
+#[cfg(feature = "std")]
+mod hook;
+mod from_handled;
+#[cfg(feature = "std")]
+mod kind;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "throttle")]
+pub mod throttle;
+#[cfg(feature = "circuit-breaker")]
+pub mod circuit;
+#[cfg(feature = "tracing")]
+pub mod trace;
+#[cfg(feature = "miette")]
+mod diagnostic;
+#[cfg(feature = "axum")]
+mod response;
+#[cfg(feature = "tokio")]
+mod spawn;
+#[cfg(feature = "std")]
+mod traced;
+#[cfg(feature = "log")]
+mod logging;
 
 // ============================================================
 // Re-exports
 // ============================================================
 
 pub use handled::{Handled, FrameView, Error, StringError, TryCatch, Value, IntoValue};
-pub use ext::HandleExt;
+pub use core_error::CoreError;
+pub use catchable::Catchable;
+pub use limits::{capture_enabled, set_capture, set_trace_limits, trace_limits, DropPolicy, TraceLimits};
+pub use limits::{DEFAULT_CONTEXT_LIMIT, DEFAULT_LOCATION_LIMIT, MAX_CONTEXT_LIMIT, MAX_LOCATION_LIMIT};
+pub use redact::{set_redaction_key, UNKEYED_REDACTION};
+pub use handled::{CombinedError, DisplayError, MergeStrategy, Severity};
+#[cfg(feature = "std")]
+pub use retry::{RetryPolicy, DefaultRetryPolicy, Backoff};
+#[cfg(feature = "std")]
+pub use timeout::Elapsed;
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use timeout::__timeout;
+#[cfg(feature = "std")]
+pub use panicked::Panicked;
+#[doc(hidden)]
+#[cfg(feature = "tokio")]
+pub use spawn::__spawn;
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use traced::{__traced_async, __TracedGuard};
+#[cfg(feature = "std")]
+pub use hook::{set_hook, clear_hook};
+pub use from_handled::{Classifier, FromHandled};
+#[cfg(feature = "std")]
+pub use kind::{clear_kind_rules, register_kind_rule, ErrorKind};
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use kind::__KindOf;
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use panicked::{__catch_panic, __catch_panic_async};
+#[cfg(feature = "std")]
+pub use snapshot::{ErrorRecord, ErrorOrigin};
+#[cfg(feature = "serde")]
+pub use handled::{FrameRepr, HandledRepr};
+pub use ext::{HandleExt, IntoHandled, OptionExt};
+
+/// Derive `From<T> for Handled` and `IntoValue` for a domain error type.
+///
+/// `?` then converts the error outside `handle!` without a hand-written
+/// `From` impl, keeping `T` as the source for `catch T(e)` and
+/// `downcast_ref`. As an attachment value, a unit variant is its name and
+/// anything else its `Display` text. `#[handle_this(error)]` also adds an
+/// empty `std::error::Error` impl for types with only `Debug` + `Display`.
+///
+/// ```
+/// use handle_this::{HandleThis, Handled, Result};
+/// use std::fmt;
+///
+/// #[derive(Debug, HandleThis)]
+/// #[handle_this(error)]
+/// enum StoreError {
+///     NotFound,
+///     Corrupt { block: u32 },
+/// }
+///
+/// impl fmt::Display for StoreError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             StoreError::NotFound => write!(f, "not found"),
+///             StoreError::Corrupt { block } => write!(f, "block {} is corrupt", block),
+///         }
+///     }
+/// }
+///
+/// fn read(block: u32) -> Result<Vec<u8>> {
+///     Err(StoreError::Corrupt { block })?
+/// }
+///
+/// let err = read(7).unwrap_err().kv("kind", StoreError::NotFound);
+/// assert_eq!(err.message(), "block 7 is corrupt");
+/// assert!(err.downcast_ref::<StoreError>().is_some());
+/// ```
+#[cfg(feature = "std")]
+pub use handle_this_macros::HandleThis;
+
+/// Record the function's `module::function` path in the frames pushed
+/// while it runs.
+///
+/// File and line numbers go stale as code moves around; the function
+/// path keeps a trace readable. It shows after the location in `Display`,
+/// as [`FrameView::function`], and as `function` in JSON and serde.
+///
+/// The function is current on its thread from entry to return, so frames
+/// pushed by untraced functions it calls name it too; trace those as well
+/// for finer paths. An `async fn` is current only while it is being
+/// polled. Methods are named by module, not by type.
+///
+/// ```
+/// use handle_this::{handle, traced, Result};
+///
+/// #[traced]
+/// fn load(path: &str) -> Result<String> {
+///     handle! { try { std::fs::read_to_string(path)? } }
+/// }
+///
+/// let err = load("/no/such/file").unwrap_err();
+/// let function = err.frames().next().unwrap().function.unwrap();
+/// assert!(function.ends_with("::load"));
+/// ```
+#[cfg(feature = "std")]
+pub use handle_this_macros::traced;
+
+/// Add context to every error leaving a function, like wrapping its whole
+/// body in `handle! { try { ... } with "message" }`.
+///
+/// The arguments are a `with` clause: a message, `{ key: value }`
+/// attachments, or both. The function must return [`Result`]; `return`
+/// and `?` in the body work as before, and `async fn`s are supported.
+/// The frame points at the attribute.
+///
+/// ```
+/// use handle_this::{context, Result};
+///
+/// #[context("loading config")]
+/// fn load(path: &str) -> Result<String> {
+///     if path.is_empty() {
+///         return Err("no path".into());
+///     }
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// let err = load("/no/such/file").unwrap_err();
+/// assert_eq!(err.frames().last().unwrap().context, Some("loading config"));
+/// assert!(load("").is_err());
+/// ```
+pub use handle_this_macros::context;
 
 // Internal helper for macros
 #[doc(hidden)]
-#[cfg(feature = "std")]
 pub use handled::__wrap_any;
 
+// `Vec` for generated code, which can't assume `std` or `extern crate alloc`
+#[doc(hidden)]
+pub use alloc::vec::Vec as __Vec;
+
+// Driver for `async try concurrent(N) ...`
+#[doc(hidden)]
+#[cfg(feature = "futures")]
+pub use futures_util as __futures;
+#[doc(hidden)]
+#[cfg(feature = "log")]
+pub use log as __log;
+#[doc(hidden)]
+#[cfg(feature = "futures")]
+pub use macros::__Owned;
+
 // Re-export proc-macro crate for nested pattern expansion
 #[doc(hidden)]
 pub use handle_this_macros;
@@ -128,12 +366,14 @@ pub mod result {
 
     /// Create an `Ok` result.
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub fn Ok<T>(v: T) -> Result<T> {
         core::result::Result::Ok(v)
     }
 
     /// Create an `Err` result with automatic conversion to `Handled`.
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub fn Err<T>(e: impl Into<Handled>) -> Result<T> {
         core::result::Result::Err(e.into())
     }
@@ -141,18 +381,25 @@ pub mod result {
 
 /// Type alias for errors in chain closures.
 #[doc(hidden)]
-#[cfg(feature = "std")]
-pub type __BoxedError = Box<dyn std::error::Error + Send + Sync + 'static>;
+pub type __BoxedError = Box<dyn CoreError + Send + Sync + 'static>;
+
+/// Error trait named by `catchable!`.
+#[doc(hidden)]
+pub use core_error::CoreError as __StdError;
 
 // Re-export helper functions for macros
 #[doc(hidden)]
 pub use macros::{
-    __map_try_erased, __with_finally, __wrap_frame,
-    __ThrowExpr, __Thrown,
+    __map_try_erased, __with_finally, __wrap_frame, __FinallyGuard,
+    __convert_err, __combine_finally, __rethrow,
+    __ThrowExpr, __Thrown, __Snapshot, __throw_as,
     __convert_try_catch_result, __convert_try_catch_result_str,
-    __ErrWrap, __IntoHandled,
+    __ErrWrap, __IntoHandled, __context_body, __context_async, __ContinueWith,
     TryCatchConvert, TryCatchResult,
 };
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use macros::{__LogOnce, __catch_no_panic, __async_delay, __AsyncDelay, __report};
 
 // ============================================================
 // Loop Signal - Control Flow as Data
@@ -221,6 +468,7 @@ impl<T> __LoopSignal<T> {
 /// Used by transformed control flow in signal mode handlers.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __signal_continue<T>() -> core::result::Result<__LoopSignal<T>, Handled> {
     core::result::Result::Ok(__LoopSignal::Continue)
 }
@@ -245,6 +493,7 @@ pub type __ControlResult = core::result::Result<__ControlSignal, Handled>;
 /// Helper to return Continue signal.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __ctrl_continue() -> __ControlResult {
     core::result::Result::Ok(__ControlSignal::Continue)
 }
@@ -252,6 +501,7 @@ pub fn __ctrl_continue() -> __ControlResult {
 /// Helper to return Break signal.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __ctrl_break() -> __ControlResult {
     core::result::Result::Ok(__ControlSignal::Break)
 }
@@ -260,6 +510,7 @@ pub fn __ctrl_break() -> __ControlResult {
 /// Uses Option for better type inference than MaybeUninit.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __ctrl_store_value<T>(slot: &mut Option<T>, value: T) -> __ControlResult {
     *slot = Some(value);
     core::result::Result::Ok(__ControlSignal::Value)
@@ -285,6 +536,7 @@ pub fn __force_result_type<T, E>(result: core::result::Result<T, E>) -> core::re
 /// Used by transformed control flow in signal mode handlers.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __signal_break<T>() -> core::result::Result<__LoopSignal<T>, Handled> {
     core::result::Result::Ok(__LoopSignal::Break)
 }
@@ -305,6 +557,17 @@ macro_rules! __try_block {
     };
 }
 
+/// Internal macro for typed try blocks: `?` converts into `$err`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __typed_try_block {
+    ($err:ty; $($body:tt)*) => {
+        (|| -> ::core::result::Result<_, $err> {
+            ::core::result::Result::Ok({ $($body)* })
+        })()
+    };
+}
+
 /// Internal macro for async try blocks.
 #[doc(hidden)]
 #[macro_export]
@@ -321,13 +584,14 @@ macro_rules! __async_try_block {
 /// Async finally helper.
 #[doc(hidden)]
 #[inline]
-pub async fn __with_finally_async<T, F, Fut, G>(f: F, finally: G) -> T
+pub async fn __with_finally_async<T, F, Fut, G, GFut>(f: F, finally: G) -> T
 where
     F: FnOnce() -> Fut,
-    Fut: std::future::Future<Output = T>,
-    G: FnOnce(),
+    Fut: core::future::Future<Output = T>,
+    G: FnOnce() -> GFut,
+    GFut: core::future::Future<Output = ()>,
 {
     let result = f().await;
-    finally();
+    finally().await;
     result
 }
@@ -0,0 +1,178 @@
+//! Process-wide limits on how much trace an error keeps.
+
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Default maximum number of frames per error.
+pub const DEFAULT_LOCATION_LIMIT: usize = 32;
+/// Default maximum number of context entries per error.
+pub const DEFAULT_CONTEXT_LIMIT: usize = 8;
+/// Hard cap on frames per error; larger location limits are clamped to it.
+pub const MAX_LOCATION_LIMIT: usize = u8::MAX as usize;
+
+/// Largest context limit; larger limits are clamped to it.
+pub const MAX_CONTEXT_LIMIT: usize = usize::MAX >> CONTEXT_SHIFT;
+
+/// The limits in one word - frame limit in the low 8 bits, then the drop
+/// policy, then the context limit - so a push reads them with one load.
+static LIMITS: AtomicUsize = AtomicUsize::new(TraceLimits::new().pack());
+const DROP_OLDEST_BIT: usize = 1 << 8;
+const CONTEXT_SHIFT: u32 = 9;
+static CAPTURE: AtomicBool = AtomicBool::new(true);
+
+/// What to drop when an error is already at a limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Ignore the new frame or context; the origin of the error is kept.
+    #[default]
+    Newest,
+    /// Evict the oldest frame or context to make room; the most recent
+    /// propagation steps are kept. Dropping a frame drops its context too.
+    Oldest,
+}
+
+/// Frame and context limits, set process-wide with [`set_trace_limits`].
+///
+/// `TraceLimits::new()` holds the defaults: 32 frames, 8 contexts,
+/// [`DropPolicy::Newest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceLimits {
+    locations: usize,
+    contexts: usize,
+    policy: DropPolicy,
+}
+
+impl TraceLimits {
+    /// The default limits.
+    pub const fn new() -> Self {
+        Self {
+            locations: DEFAULT_LOCATION_LIMIT,
+            contexts: DEFAULT_CONTEXT_LIMIT,
+            policy: DropPolicy::Newest,
+        }
+    }
+
+    /// Maximum frames per error, clamped to [`MAX_LOCATION_LIMIT`].
+    pub const fn locations(mut self, limit: usize) -> Self {
+        self.locations = if limit > MAX_LOCATION_LIMIT { MAX_LOCATION_LIMIT } else { limit };
+        self
+    }
+
+    /// Maximum context entries per error, clamped to [`MAX_CONTEXT_LIMIT`].
+    pub const fn contexts(mut self, limit: usize) -> Self {
+        self.contexts = if limit > MAX_CONTEXT_LIMIT { MAX_CONTEXT_LIMIT } else { limit };
+        self
+    }
+
+    /// What to drop at a limit.
+    pub const fn policy(mut self, policy: DropPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The frame limit.
+    pub const fn location_limit(&self) -> usize {
+        self.locations
+    }
+
+    /// The context limit.
+    pub const fn context_limit(&self) -> usize {
+        self.contexts
+    }
+
+    /// The drop policy.
+    pub const fn drop_policy(&self) -> DropPolicy {
+        self.policy
+    }
+}
+
+impl TraceLimits {
+    const fn pack(self) -> usize {
+        let policy = match self.policy {
+            DropPolicy::Newest => 0,
+            DropPolicy::Oldest => DROP_OLDEST_BIT,
+        };
+        self.locations | policy | (self.contexts << CONTEXT_SHIFT)
+    }
+
+    const fn unpack(word: usize) -> Self {
+        Self {
+            locations: word & MAX_LOCATION_LIMIT,
+            contexts: word >> CONTEXT_SHIFT,
+            policy: if word & DROP_OLDEST_BIT != 0 { DropPolicy::Oldest } else { DropPolicy::Newest },
+        }
+    }
+}
+
+impl Default for TraceLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Set the frame and context limits for every error from now on.
+///
+/// Long-running services can lower them to bound memory, or raise them for
+/// more verbose traces. Limits apply when a frame or context is added, so
+/// existing errors keep what they already have.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{set_trace_limits, trace_limits, DropPolicy, Handled, TraceLimits};
+///
+/// set_trace_limits(TraceLimits::new().locations(3).policy(DropPolicy::Oldest));
+/// let err = Handled::msg("deep").push_frames([("a.rs", 1, 1), ("b.rs", 2, 1), ("c.rs", 3, 1), ("d.rs", 4, 1)]);
+/// set_trace_limits(TraceLimits::new());
+///
+/// // The oldest frame made room for the newest
+/// let files: Vec<_> = err.frames().map(|f| f.file).collect();
+/// assert_eq!(files, ["b.rs", "c.rs", "d.rs"]);
+/// assert_eq!(trace_limits(), TraceLimits::new());
+/// ```
+pub fn set_trace_limits(limits: TraceLimits) {
+    LIMITS.store(limits.pack(), Ordering::Relaxed);
+}
+
+/// The limits currently in effect.
+#[inline]
+pub fn trace_limits() -> TraceLimits {
+    TraceLimits::unpack(LIMITS.load(Ordering::Relaxed))
+}
+
+/// Turn trace capture on or off for every error from now on.
+///
+/// With capture off, frames, context messages and key-value attachments
+/// are not recorded: `frame()`, `ctx()`, `kv()` and the frames `handle!`
+/// adds on `?` return the error unchanged. Errors still propagate, match
+/// typed handlers and keep their message, so hot paths and embedded builds
+/// get plain propagation with the same syntax. Errors created while capture
+/// was on keep the trace they already have. The `attempt`/`attempts` values
+/// from iteration and retries are still readable through
+/// [`Handled::get_kv`](crate::Handled::get_kv).
+///
+/// The `no-trace` feature turns capture off at compile time instead: this
+/// call has no effect and errors carry no frame storage at all.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{set_capture, Handled};
+///
+/// set_capture(false);
+/// let err = Handled::msg("disk full").frame("store.rs", 10, 5).ctx("saving");
+/// set_capture(true);
+///
+/// assert_eq!(err.message(), "disk full");
+/// assert_eq!(err.frames().count(), 0);
+/// ```
+pub fn set_capture(enabled: bool) {
+    CAPTURE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether trace capture is on (the default); see [`set_capture`].
+///
+/// Always `false` with the `no-trace` feature.
+#[inline]
+pub fn capture_enabled() -> bool {
+    !cfg!(feature = "no-trace") && CAPTURE.load(Ordering::Relaxed)
+}
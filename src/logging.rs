@@ -0,0 +1,101 @@
+//! `log` crate bridge (`log` feature).
+//!
+//! [`Handled::log`] emits one record through the `log` facade, with the
+//! trace squeezed onto a single line by [`Handled::log_line`]. In
+//! `handle!`, `inspect log error` (or `warn`, `info`, `debug`, `trace`) does
+//! the same on the error path and lets the error propagate:
+//!
+//! ```
+//! use handle_this::{handle, Result};
+//!
+//! fn load() -> Result<i32> {
+//!     handle! {
+//!         try { "x".parse::<i32>()? }
+//!         with "loading", { file: "app.toml" }
+//!         inspect log error
+//!     }
+//! }
+//!
+//! assert!(load().is_err());
+//! ```
+
+use core::fmt::{self, Write};
+
+use crate::{Handled, Value};
+
+impl<E: fmt::Display> Handled<E> {
+    /// Emit this error as one `log` record at `level`, target `handle_this`.
+    ///
+    /// The record's file and line are the caller's; its message is
+    /// [`log_line`](Self::log_line). A [correlation id](Self::correlation_id)
+    /// is also set as the record's `correlation_id` key-value.
+    #[track_caller]
+    pub fn log(&self, level: log::Level) {
+        if level > log::max_level() {
+            return;
+        }
+        let caller = core::panic::Location::caller();
+        let line = self.log_line();
+        let correlation = self.correlation_id().map(|id| ("correlation_id", id));
+        log::logger().log(
+            &log::Record::builder()
+                .level(level)
+                .target("handle_this")
+                .file(Some(caller.file()))
+                .line(Some(caller.line()))
+                .args(format_args!("{}", line))
+                .key_values(&correlation)
+                .build(),
+        );
+    }
+
+    /// The message and trace on one line, for line-oriented logs.
+    ///
+    /// A correlation id follows the message as `correlation_id="..."`.
+    /// Frames come oldest first, joined by `" -> "`; each is `file:line:col`
+    /// (plus `in module::function` under [`traced`](crate::traced)), then its
+    /// quoted context and `key=value` attachments (string values
+    /// quoted). Newlines are escaped, so the result is always one line.
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let err = Handled::msg("refused")
+    ///     .frame("db.rs", 12, 5).ctx("running query").kv("table", "users")
+    ///     .frame("api.rs", 40, 9).kv("attempt", 2);
+    /// assert_eq!(
+    ///     err.log_line(),
+    ///     r#"refused @ db.rs:12:5 "running query" table="users" -> api.rs:40:9 attempt=2"#,
+    /// );
+    /// ```
+    pub fn log_line(&self) -> String {
+        let mut out = String::new();
+        out.push_str(self.message());
+        if let Some(id) = self.correlation_id() {
+            let _ = write!(out, " correlation_id={:?}", id);
+        }
+        for (i, frame) in self.frames().enumerate() {
+            out.push_str(if i == 0 { " @ " } else { " -> " });
+            let _ = write!(out, "{}:{}:{}", frame.file, frame.line, frame.col);
+            if let Some(function) = frame.function {
+                let _ = write!(out, " in {}", function);
+            }
+            if frame.repeat_count() > 1 {
+                let _ = write!(out, " x{}", frame.repeat_count());
+            }
+            if let Some(context) = frame.context {
+                let _ = write!(out, " {:?}", context);
+            }
+            for (key, value) in frame.attachments() {
+                let _ = match value {
+                    Value::String(s) => write!(out, " {}={:?}", key, s),
+                    other => write!(out, " {}={}", key, other),
+                };
+            }
+        }
+        if out.contains(['\n', '\r']) {
+            out = out.replace('\n', "\\n").replace('\r', "\\r");
+        }
+        out
+    }
+}
@@ -89,11 +89,51 @@ macro_rules! handle {
         $crate::handle_this_macros::__handle_proc!(WHEN $($rest)+)
     };
 
+    // try<E> { } [with ...] - typed `Result<T, Handled<E>>`
+    (try < $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(TYPED $($rest)+)
+    };
+
     // ========================================
     // Then chains, iteration patterns, and async
     // More specific patterns (with `, then`) must come first
     // ========================================
 
+    // async try concurrent(N) for/any/all x in iter { } handlers...
+    (async try concurrent $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(CONCURRENT concurrent $($rest)+)
+    };
+
+    // async try stream x in stream { } handlers...
+    (async try stream $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(STREAM $($rest)+)
+    };
+
+    // async try join { a: fut_a, b: fut_b } handlers...
+    (async try join $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(JOIN join $($rest)+)
+    };
+
+    // async try race { primary(), fallback() } handlers...
+    (async try race $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(RACE race $($rest)+)
+    };
+
+    // async try spawn [timeout DURATION] { } handlers...
+    (async try spawn $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(ASYNC spawn $($rest)+)
+    };
+
+    // async try while cond [limit N][, backoff KIND] { } handlers...
+    (async try while $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(ASYNC_WHILE $($rest)+)
+    };
+
+    // async try timeout DURATION { } handlers...
+    (async try timeout $($rest:tt)+) => {
+        $crate::handle_this_macros::__handle_proc!(ASYNC timeout $($rest)+)
+    };
+
     // async try { } , then ... (must come before general async)
     (async try { $($body:tt)* } , then $($rest:tt)+) => {
         $crate::handle_this_macros::__handle_proc!(THEN ASYNC { $($body)* } , then $($rest)+)
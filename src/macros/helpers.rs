@@ -1,14 +1,16 @@
 //! Helper functions and types for macros.
 
-use crate::{Handled, Error};
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::{String, ToString}};
+use crate::{CoreError, Handled, Error};
 use core::fmt;
 
 /// Map error in a Result with boxed error, used by chain macros.
 /// Uses map_err to eliminate identity Ok arm.
 #[doc(hidden)]
-#[cfg(feature = "std")]
 #[inline]
-pub fn __map_try_erased<T, F>(r: core::result::Result<T, Box<dyn std::error::Error + Send + Sync + 'static>>, on_err: F) -> core::result::Result<T, Handled<Error>>
+#[allow(clippy::result_large_err)]
+pub fn __map_try_erased<T, F>(r: core::result::Result<T, Box<dyn CoreError + Send + Sync + 'static>>, on_err: F) -> core::result::Result<T, Handled<Error>>
 where
     F: FnOnce(Handled<Error>) -> Handled<Error>,
 {
@@ -16,12 +18,20 @@ where
 }
 
 /// Wrap a boxed error and add a frame in one call.
-/// Uses optimized wrap_box_with_frame to avoid empty Vec + push pattern.
+/// Errors getting their first frame here are passed to the global hook.
 #[doc(hidden)]
-#[cfg(feature = "std")]
 #[inline]
-pub fn __wrap_frame(e: Box<dyn std::error::Error + Send + Sync + 'static>, file: &'static str, line: u32, col: u32) -> Handled<Error> {
-    Handled::wrap_box_with_frame(e, file, line, col)
+pub fn __wrap_frame(e: Box<dyn CoreError + Send + Sync + 'static>, file: &'static str, line: u32, col: u32) -> Handled<Error> {
+    let handled = Handled::wrap_box(e);
+    let fresh = handled.is_empty();
+    let handled = handled.frame(file, line, col);
+    #[cfg(feature = "std")]
+    if fresh {
+        crate::hook::on_wrap(&handled);
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = fresh;
+    handled
 }
 
 
@@ -47,7 +57,7 @@ impl<T, E: fmt::Display> __ThrowExpr<core::result::Result<T, Handled<E>>> {
 }
 
 // Inherent impl for Result<T, E> where E is a concrete error
-impl<T, E: std::error::Error + Send + Sync + 'static> __ThrowExpr<core::result::Result<T, E>> {
+impl<T, E: CoreError + Send + Sync + 'static> __ThrowExpr<core::result::Result<T, E>> {
     #[inline]
     pub fn __thrown_erased(self) -> Handled<Error> {
         match self.0 {
@@ -97,13 +107,52 @@ pub trait __Thrown {
 }
 
 // Trait impl for any Error type - used when not a Result
-impl<E: std::error::Error + Send + Sync + 'static> __Thrown for __ThrowExpr<E> {
+impl<E: CoreError + Send + Sync + 'static> __Thrown for __ThrowExpr<E> {
     #[inline]
     fn __thrown(self) -> Handled<Error> {
         Handled::wrap(self.0)
     }
 }
 
+// `throw snapshot { expr }`: a Handled keeps its trace and kv, anything
+// else that implements Display is reduced to its message
+impl<E: fmt::Display> __ThrowExpr<Handled<E>> {
+    #[inline]
+    pub fn __snapshot(self) -> Handled<Error> {
+        self.0.into_snapshot()
+    }
+}
+
+/// Fallback for `throw snapshot` on a plain `Display` value.
+#[doc(hidden)]
+pub trait __Snapshot {
+    fn __snapshot(self) -> Handled<Error>;
+}
+
+impl<T: fmt::Display> __Snapshot for __ThrowExpr<T> {
+    #[inline]
+    fn __snapshot(self) -> Handled<Error> {
+        Handled::snapshot_of(&self.0)
+    }
+}
+
+/// `throw as Enum::Variant`: wrap the root in the variant if its type fits,
+/// framing the conversion as its own step; any other error passes through
+/// untouched.
+#[doc(hidden)]
+#[inline]
+pub fn __throw_as<T, V, F>(err: Handled<Error>, variant: F, file: &'static str, line: u32, col: u32) -> Handled<Error>
+where
+    T: CoreError + 'static,
+    V: CoreError + Send + Sync + 'static,
+    F: FnOnce(T) -> V,
+{
+    match err.into_variant(variant) {
+        Ok(converted) => converted.step_frame(file, line, col),
+        Err(err) => err,
+    }
+}
+
 // ============================================================
 // Result helpers - reduce generated code for common patterns
 // ============================================================
@@ -122,10 +171,223 @@ where
     result
 }
 
+/// Run a catch body for `assert_no_panic`, turning a panic into an error.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+#[allow(clippy::result_large_err)]
+pub fn __catch_no_panic<T, F: FnOnce() -> T>(f: F) -> core::result::Result<T, Handled<Error>> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|payload| {
+        let reason = crate::panicked::payload_message(&*payload);
+        Handled::msg(std::format!("catch handler panicked: {}", reason))
+    })
+}
+
+/// Error side of `rethrow`: `?` on it leaves the catch handler with `err`.
+#[doc(hidden)]
+#[inline]
+#[allow(clippy::result_large_err)]
+pub fn __rethrow(err: Handled<Error>) -> core::result::Result<core::convert::Infallible, Handled<Error>> {
+    Err(err)
+}
+
+/// Merge the try body's result with a fallible finally's status for
+/// `catch_finally_with`. The combiner runs only when both failed.
+#[doc(hidden)]
+#[inline]
+#[allow(clippy::result_large_err)]
+pub fn __combine_finally<T, C>(
+    body: core::result::Result<T, Handled<Error>>,
+    finally: core::result::Result<(), Handled<Error>>,
+    combine: C,
+) -> core::result::Result<T, Handled<Error>>
+where
+    C: FnOnce(Handled<Error>, Handled<Error>) -> Handled<Error>,
+{
+    match (body, finally) {
+        (body, Ok(())) => body,
+        (Ok(_), Err(finally_err)) => Err(finally_err),
+        (Err(body_err), Err(finally_err)) => Err(combine(body_err, finally_err)),
+    }
+}
+
+/// Runs a `#[context]` function's original body as a closure returning
+/// `Result<T>`.
+#[doc(hidden)]
+#[inline]
+#[allow(clippy::result_large_err)]
+pub fn __context_body<T, F: FnOnce() -> Result<T, Handled>>(body: F) -> Result<T, Handled> {
+    body()
+}
+
+/// The async form of [`__context_body`]: fixes the body's output type.
+#[doc(hidden)]
+#[inline]
+pub fn __context_async<T, F: core::future::Future<Output = Result<T, Handled>>>(body: F) -> F {
+    body
+}
+
+/// Runtime-agnostic sleep for `catch e delay d { }` in `async try`.
+/// The crate's shared timer thread wakes the task once the deadline passes.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn __async_delay(duration: std::time::Duration) -> __AsyncDelay {
+    __AsyncDelay {
+        deadline: std::time::Instant::now() + duration,
+        timer: None,
+    }
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub struct __AsyncDelay {
+    deadline: std::time::Instant,
+    timer: Option<crate::timer::Key>,
+}
+
+#[cfg(feature = "std")]
+impl core::future::Future for __AsyncDelay {
+    type Output = ();
+
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<()> {
+        if std::time::Instant::now() >= self.deadline {
+            if let Some(key) = self.timer.take() {
+                crate::timer::cancel(key);
+            }
+            return core::task::Poll::Ready(());
+        }
+        match self.timer {
+            // Keep the timer pointed at the latest waker
+            Some(key) => crate::timer::update(key, cx.waker()),
+            None => self.timer = Some(crate::timer::register(self.deadline, cx.waker())),
+        }
+        core::task::Poll::Pending
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for __AsyncDelay {
+    fn drop(&mut self) {
+        if let Some(key) = self.timer.take() {
+            crate::timer::cancel(key);
+        }
+    }
+}
+
+/// Moves a value into an `async try concurrent` body.
+/// Taking it consumes the wrapper, so the body's future captures it by value
+/// (even when the item is `Copy`) while borrowing everything else.
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+pub struct __Owned<T>(pub T);
+
+#[cfg(feature = "futures")]
+impl<T> __Owned<T> {
+    #[inline]
+    pub fn take(self) -> T {
+        self.0
+    }
+}
+
+/// Drop guard for `async try { } finally { } cancel_safe`.
+/// Runs the finally closure on normal completion via `run()`, or from `Drop`
+/// if the enclosing future is cancelled mid-await.
+#[doc(hidden)]
+pub struct __FinallyGuard<F: FnOnce()>(Option<F>);
+
+impl<F: FnOnce()> __FinallyGuard<F> {
+    #[inline]
+    pub fn new(finally: F) -> Self {
+        Self(Some(finally))
+    }
+
+    /// Run the finally closure now and disarm the guard.
+    #[inline]
+    pub fn run(mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
+
+impl<F: FnOnce()> Drop for __FinallyGuard<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
+
+/// Convert an error into the user's type for `convert Type` and wrap it
+/// as the new root.
+#[doc(hidden)]
+#[inline]
+pub fn __convert_err<T>(err: Handled<Error>) -> Handled<Error>
+where
+    T: From<Handled<Error>> + CoreError + Send + Sync + 'static,
+{
+    Handled::wrap(T::from(err))
+}
+
+/// Per-call-site fingerprint set for `log_once`.
+/// Each expansion declares its own `static`, so deduplication is per site.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[derive(Default)]
+pub struct __LogOnce(std::sync::OnceLock<std::sync::Mutex<std::collections::HashSet<u64>>>);
+
+#[cfg(feature = "std")]
+impl __LogOnce {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(std::sync::OnceLock::new())
+    }
+
+    /// Record the error's fingerprint; `true` the first time this site sees it.
+    pub fn first_seen(&self, err: &Handled<Error>) -> bool {
+        let seen = self.0.get_or_init(Default::default);
+        let mut seen = seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.insert(err.fingerprint())
+    }
+}
+
+/// Bare `report_and_continue`: an `error` record through the `log` facade
+/// under the `log` feature (see [`Handled::log`]), stderr otherwise.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[track_caller]
+pub fn __report(err: &Handled<Error>) {
+    #[cfg(feature = "log")]
+    err.log(log::Level::Error);
+    #[cfg(not(feature = "log"))]
+    std::eprintln!("error: {}", err);
+}
+
+/// What a `continue_with` body may evaluate to: `()` continues the enclosing
+/// loop, `Option<T>` recovers with `Some(v)` and continues on `None`.
+#[doc(hidden)]
+pub trait __ContinueWith<T> {
+    fn __recover(self) -> Option<T>;
+}
+
+impl<T> __ContinueWith<T> for () {
+    #[inline]
+    fn __recover(self) -> Option<T> {
+        None
+    }
+}
+
+impl<T> __ContinueWith<T> for Option<T> {
+    #[inline]
+    fn __recover(self) -> Option<T> {
+        self
+    }
+}
+
 /// Convert a user's Result<T, E> to Result<T, Handled> for try catch blocks.
 /// Uses Into<Handled> trait for error conversion.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __convert_try_catch_result<T, E: Into<Handled<Error>>>(
     result: core::result::Result<T, E>,
     file: &'static str,
@@ -139,6 +401,7 @@ pub fn __convert_try_catch_result<T, E: Into<Handled<Error>>>(
 /// Specialized version for string literal errors.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __convert_try_catch_result_str<T>(
     result: core::result::Result<T, &str>,
     file: &'static str,
@@ -151,6 +414,7 @@ pub fn __convert_try_catch_result_str<T>(
 /// Trait for converting try catch body results to Result<T, Handled>.
 #[doc(hidden)]
 pub trait TryCatchResult<T> {
+    #[allow(clippy::result_large_err)]
     fn into_handled_result(self, file: &'static str, line: u32, col: u32) -> core::result::Result<T, Handled<Error>>;
 }
 
@@ -169,6 +433,7 @@ pub struct TryCatchConvert;
 impl TryCatchConvert {
     /// Convert a try catch body. Error type is inferred from the body.
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub fn run<T, E: Into<Handled<Error>>>(
         result: core::result::Result<T, E>,
         file: &'static str,
@@ -183,6 +448,7 @@ impl TryCatchConvert {
 /// Uses Box<dyn Display> as the error type to accept any Display type.
 #[doc(hidden)]
 #[inline]
+#[allow(clippy::result_large_err)]
 pub fn __try_catch_any<T, E: core::fmt::Display + Send + Sync + 'static, F>(
     f: F,
     file: &'static str,
@@ -223,7 +489,7 @@ impl __ErrWrap<String> {
 }
 
 // Inherent impl for Box<dyn Error>
-impl __ErrWrap<Box<dyn std::error::Error + Send + Sync + 'static>> {
+impl __ErrWrap<Box<dyn CoreError + Send + Sync + 'static>> {
     #[inline]
     pub fn __into_handled(self) -> Handled<Error> {
         Handled::wrap_box(self.0)
@@ -244,7 +510,7 @@ pub trait __IntoHandled {
     fn __into_handled(self) -> Handled<Error>;
 }
 
-impl<E: std::error::Error + Send + Sync + 'static> __IntoHandled for __ErrWrap<E> {
+impl<E: CoreError + Send + Sync + 'static> __IntoHandled for __ErrWrap<E> {
     #[inline]
     fn __into_handled(self) -> Handled<Error> {
         Handled::wrap(self.0)
@@ -0,0 +1,75 @@
+//! Built-in error counters - lightweight observability without a metrics backend.
+//!
+//! Counters are created on first use and live for the rest of the process.
+//! The `metric_inc "name"` clause increments one on the error path:
+//!
+//! ```
+//! use handle_this::{handle, metrics, Result};
+//!
+//! fn load() -> Result<i32> {
+//!     handle! {
+//!         try { "x".parse::<i32>()? }
+//!         metric_inc "load.parse_error"
+//!     }
+//! }
+//!
+//! let _ = load();
+//! assert_eq!(metrics::counter("load.parse_error").get(), 1);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+fn registry() -> &'static Mutex<HashMap<String, Arc<AtomicU64>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, Arc<AtomicU64>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Handle to a named counter in the built-in registry.
+///
+/// Cheap to clone; all handles for the same name share one value.
+#[derive(Debug, Clone)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    /// Increment by one.
+    #[inline]
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Increment by `n`.
+    #[inline]
+    pub fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Current value.
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Get the counter registered under `name`, creating it at zero if needed.
+pub fn counter(name: &str) -> Counter {
+    let mut counters = registry().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(c) = counters.get(name) {
+        return Counter(c.clone());
+    }
+    let c = Arc::new(AtomicU64::new(0));
+    counters.insert(name.to_string(), c.clone());
+    Counter(c)
+}
+
+/// Current value of every counter, sorted by name.
+pub fn snapshot() -> Vec<(String, u64)> {
+    let counters = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let mut values: Vec<_> = counters
+        .iter()
+        .map(|(name, c)| (name.clone(), c.load(Ordering::Relaxed)))
+        .collect();
+    values.sort();
+    values
+}
@@ -0,0 +1,87 @@
+//! A write-once cell for `no_std`, standing in for `std::sync::OnceLock`.
+//!
+//! Only what `Handled`'s lazy message needs. There's no lock to wait on:
+//! threads that race to initialize each compute a value, one wins, and
+//! the others drop theirs.
+
+use alloc::boxed::Box;
+use core::fmt;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+pub(crate) struct OnceLock<T>(AtomicPtr<T>);
+
+// The value is shared by reference once set, like `std::sync::OnceLock`
+unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+unsafe impl<T: Send> Send for OnceLock<T> {}
+
+impl<T> OnceLock<T> {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self(AtomicPtr::new(ptr::null_mut()))
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> Option<&T> {
+        // SAFETY: a non-null pointer came from `Box::into_raw` and is only
+        // freed in `drop`, which needs `&mut self`
+        unsafe { self.0.load(Ordering::Acquire).as_ref() }
+    }
+
+    /// Store `value` unless the cell is already set.
+    pub(crate) fn set(&self, value: T) -> Result<(), T> {
+        let new = Box::into_raw(Box::new(value));
+        match self.0.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => Ok(()),
+            // SAFETY: `new` was never shared
+            Err(_) => Err(*unsafe { Box::from_raw(new) }),
+        }
+    }
+
+    pub(crate) fn get_or_init(&self, init: impl FnOnce() -> T) -> &T {
+        if let Some(value) = self.get() {
+            return value;
+        }
+        let _ = self.set(init());
+        self.get().expect("cell was just set")
+    }
+}
+
+impl<T> From<T> for OnceLock<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(AtomicPtr::new(Box::into_raw(Box::new(value))))
+    }
+}
+
+impl<T> Default for OnceLock<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for OnceLock<T> {
+    fn clone(&self) -> Self {
+        match self.get() {
+            Some(value) => Self::from(value.clone()),
+            None => Self::new(),
+        }
+    }
+}
+
+impl<T> Drop for OnceLock<T> {
+    fn drop(&mut self) {
+        let value = *self.0.get_mut();
+        if !value.is_null() {
+            // SAFETY: see `get`; nothing else can reach the value now
+            drop(unsafe { Box::from_raw(value) });
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OnceLock").field(&self.get()).finish()
+    }
+}
@@ -0,0 +1,72 @@
+//! Panic capture for `try { } catch panic p { }`.
+
+use core::future::Future;
+use core::task::Poll;
+use std::any::Any;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// A try body panicked and the panic was caught by `catch panic`.
+///
+/// ```
+/// use handle_this::{handle, Result};
+///
+/// fn risky(items: &[u32]) -> Result<u32> {
+///     handle! {
+///         try { items[3] }
+///         catch panic p { if p.message().contains("out of bounds") { 0 } else { 1 } }
+///     }
+/// }
+///
+/// assert_eq!(risky(&[1]).unwrap(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panicked {
+    message: String,
+}
+
+impl Panicked {
+    /// The panic message, or `"unknown panic"` for a non-string payload.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub(crate) fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        Self { message: payload_message(&*payload).to_string() }
+    }
+}
+
+impl fmt::Display for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "panicked: {}", self.message)
+    }
+}
+
+impl std::error::Error for Panicked {}
+
+/// Message of a `panic!` payload (`&str` or `String`).
+pub(crate) fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&'static str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Run a `catch panic` try body, turning a panic into `Panicked`.
+#[doc(hidden)]
+pub fn __catch_panic<T, F: FnOnce() -> T>(f: F) -> Result<T, Panicked> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(Panicked::from_payload)
+}
+
+/// Async [`__catch_panic`]: each poll of `fut` runs under `catch_unwind`.
+#[doc(hidden)]
+pub async fn __catch_panic_async<F: Future>(fut: F) -> Result<F::Output, Panicked> {
+    let mut fut = core::pin::pin!(fut);
+    core::future::poll_fn(|cx| match catch_unwind(AssertUnwindSafe(|| fut.as_mut().poll(cx))) {
+        Ok(Poll::Ready(v)) => Poll::Ready(Ok(v)),
+        Ok(Poll::Pending) => Poll::Pending,
+        Err(payload) => Poll::Ready(Err(Panicked::from_payload(payload))),
+    })
+    .await
+}
@@ -0,0 +1,87 @@
+//! The key secrets are hashed with by [`Value::redact`](crate::Value::redact).
+
+use core::fmt::{self, Write};
+use core::hash::Hasher;
+
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(not(feature = "std"))]
+use crate::once::OnceLock;
+
+static KEY: OnceLock<(u64, u64)> = OnceLock::new();
+
+/// Set the key [`Value::redact`](crate::Value::redact) hashes secrets with.
+///
+/// By default each process picks a random key, so a secret hashes the
+/// same everywhere within one run but can't be matched across runs,
+/// services, or against a table of guesses. Share a key (loaded from
+/// config, never from source) to correlate redacted values between
+/// processes. Without `std` there is no random source: until a key is
+/// set, every secret redacts to the same [`UNKEYED_REDACTION`]
+/// placeholder, which records nothing about it.
+///
+/// The key is fixed by whichever comes first, this call or (with `std`)
+/// the first redaction; returns `false` if it was already fixed.
+///
+/// ```
+/// use handle_this::{set_redaction_key, Value};
+///
+/// assert!(set_redaction_key(*b"per-deploy key!!"));
+/// assert!(!set_redaction_key([0; 16]));
+/// assert_eq!(Value::redact("hunter2"), Value::redact("hunter2"));
+/// ```
+pub fn set_redaction_key(key: [u8; 16]) -> bool {
+    let (k0, k1) = key.split_at(8);
+    let k0 = u64::from_le_bytes(k0.try_into().unwrap());
+    let k1 = u64::from_le_bytes(k1.try_into().unwrap());
+    KEY.set((k0, k1)).is_ok()
+}
+
+/// What [`Value::redact`](crate::Value::redact) stores without `std`
+/// until [`set_redaction_key`] is called: a constant, not a hash.
+pub const UNKEYED_REDACTION: u64 = 0;
+
+/// SipHash-2-4 of `value`'s text under the redaction key, or [`UNKEYED_REDACTION`]
+/// if there is no key yet.
+pub(crate) fn hash(value: &impl fmt::Display) -> u64 {
+    let Some(&(k0, k1)) = key() else {
+        return UNKEYED_REDACTION;
+    };
+    #[allow(deprecated)] // `SipHasher` is the only keyed hash in `core`
+    let mut hasher = Keyed(core::hash::SipHasher::new_with_keys(k0, k1));
+    let _ = write!(hasher, "{}", value);
+    hasher.0.finish()
+}
+
+#[cfg(feature = "std")]
+fn key() -> Option<&'static (u64, u64)> {
+    Some(KEY.get_or_init(random_key))
+}
+
+// No random source, and a fixed fallback key would let anyone hash guesses
+// offline, so leave the key unset rather than fixing one
+#[cfg(not(feature = "std"))]
+fn key() -> Option<&'static (u64, u64)> {
+    KEY.get()
+}
+
+#[cfg(feature = "std")]
+fn random_key() -> (u64, u64) {
+    use std::hash::{BuildHasher, Hash};
+    let random = std::collections::hash_map::RandomState::new();
+    let half = |n: u8| {
+        let mut hasher = random.build_hasher();
+        n.hash(&mut hasher);
+        hasher.finish()
+    };
+    (half(0), half(1))
+}
+
+struct Keyed<H>(H);
+
+impl<H: Hasher> fmt::Write for Keyed<H> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! `axum::response::IntoResponse` for [`Handled`] (`axum` feature).
+//!
+//! - the status is a [`StatusCode`] field if one was set with
+//!   [`Handled::set_field`], otherwise [`Handled::kind`]'s HTTP status
+//! - the body is JSON: the message as `error`, plus `kind` and `code`
+//! - debug builds add the key-value attachments as `attachments`, newest
+//!   value per key; release builds leave them out so internal context
+//!   doesn't reach clients
+
+use axum_core::response::{IntoResponse, Response};
+use http::{header, HeaderValue, StatusCode};
+
+use crate::handled::write_json_str;
+#[cfg(debug_assertions)]
+use crate::handled::write_json_value;
+use crate::{Error, Handled};
+
+impl IntoResponse for Handled<Error> {
+    fn into_response(self) -> Response {
+        let kind = self.kind();
+        let status = match self.field_or_chained::<StatusCode>() {
+            Some(status) => *status,
+            None => StatusCode::from_u16(kind.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        let mut body = String::from("{\"error\":");
+        write_json_str(&mut body, self.message());
+        body.push_str(",\"kind\":");
+        write_json_str(&mut body, &kind.to_string());
+        if let Some(code) = self.code() {
+            body.push_str(",\"code\":");
+            write_json_str(&mut body, code);
+        }
+        #[cfg(debug_assertions)]
+        {
+            let mut seen: Vec<&str> = Vec::new();
+            for (key, value) in self.all_kv() {
+                if seen.contains(&key) {
+                    continue;
+                }
+                body.push_str(if seen.is_empty() { ",\"attachments\":{" } else { "," });
+                write_json_str(&mut body, key);
+                body.push(':');
+                write_json_value(&mut body, value);
+                seen.push(key);
+            }
+            if !seen.is_empty() {
+                body.push('}');
+            }
+        }
+        body.push('}');
+
+        let mut response = (status, body).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response
+    }
+}
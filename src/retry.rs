@@ -0,0 +1,120 @@
+//! Retry policies - decide from an error whether an operation is worth retrying.
+
+use std::io;
+use std::time::Duration;
+
+use crate::handled::{Error, Handled};
+
+/// Decides whether an error should be retried.
+///
+/// Implement this to plug custom rules into retry loops via
+/// [`Handled::is_retryable`].
+pub trait RetryPolicy {
+    /// Return `true` if the operation that produced `err` should be retried.
+    fn is_retryable(&self, err: &Handled<Error>) -> bool;
+}
+
+impl<F: Fn(&Handled<Error>) -> bool> RetryPolicy for F {
+    fn is_retryable(&self, err: &Handled<Error>) -> bool {
+        self(err)
+    }
+}
+
+/// Default policy based on tags and transient I/O errors.
+///
+/// In order of precedence:
+/// - tagged `"fatal"` - never retried
+/// - tagged `"retryable"` - always retried
+/// - `io::Error` anywhere in the cause chain with kind `TimedOut`,
+///   `Interrupted`, `WouldBlock`, `ConnectionReset`, or `ConnectionAborted`
+///   - retried
+/// - anything else - not retried
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRetryPolicy;
+
+impl RetryPolicy for DefaultRetryPolicy {
+    fn is_retryable(&self, err: &Handled<Error>) -> bool {
+        if err.has_tag("fatal") {
+            return false;
+        }
+        if err.has_tag("retryable") {
+            return true;
+        }
+        err.chain_any::<io::Error>().is_some_and(|e| {
+            matches!(
+                e.kind(),
+                io::ErrorKind::TimedOut
+                    | io::ErrorKind::Interrupted
+                    | io::ErrorKind::WouldBlock
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+            )
+        })
+    }
+}
+
+impl Handled<Error> {
+    /// Check whether this error should be retried under `policy`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::{Handled, DefaultRetryPolicy};
+    ///
+    /// let transient = Handled::msg("busy").tag("retryable");
+    /// let fatal = Handled::msg("bad config").tag("fatal");
+    ///
+    /// assert!(transient.is_retryable(&DefaultRetryPolicy));
+    /// assert!(!fatal.is_retryable(&DefaultRetryPolicy));
+    /// ```
+    pub fn is_retryable(&self, policy: &impl RetryPolicy) -> bool {
+        policy.is_retryable(self)
+    }
+}
+
+/// Delay schedule between `try while` attempts, built by its `backoff` clause.
+///
+/// # Example
+///
+/// ```
+/// use handle_this::{handle, Result, Value};
+///
+/// fn connect(attempts: &mut u32) -> Result<&'static str> {
+///     handle! {
+///         try while *attempts < 3, backoff exponential(1ms, max 10ms) {
+///             *attempts += 1;
+///             Err("refused")?
+///         }
+///     }
+/// }
+///
+/// let mut attempts = 0;
+/// let err = connect(&mut attempts).unwrap_err();
+/// assert_eq!(attempts, 3);
+/// let attempts_kv = err.frames().flat_map(|f| f.attachments()).find(|(k, _)| *k == "attempts");
+/// assert_eq!(attempts_kv, Some(("attempts", &Value::Uint(3))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// The same delay before every retry.
+    Fixed(Duration),
+    /// `step`, `2 * step`, `3 * step`, ... capped at `max`.
+    Linear { step: Duration, max: Duration },
+    /// `base`, `2 * base`, `4 * base`, ... capped at `max`.
+    Exponential { base: Duration, max: Duration },
+}
+
+impl Backoff {
+    /// Delay before retry number `retry` (`1` for the first retry).
+    pub fn delay(&self, retry: u32) -> Duration {
+        let retry = retry.max(1);
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Linear { step, max } => step.saturating_mul(retry).min(max),
+            Backoff::Exponential { base, max } => 2u32
+                .checked_pow(retry - 1)
+                .map_or(max, |factor| base.saturating_mul(factor))
+                .min(max),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Error snapshots - small owned records for dedup and rate-limited reporting.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::handled::Handled;
+
+/// Source position where an error was first wrapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorOrigin {
+    /// Source file path
+    pub file: &'static str,
+    /// Line number
+    pub line: u32,
+    /// Column number
+    pub col: u32,
+}
+
+impl fmt::Display for ErrorOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
+/// Owned, hashable summary of an error.
+///
+/// Cheap to clone and keep around - it holds only the message and origin,
+/// never the source error. Built by [`Handled::snapshot`] or the
+/// `snapshot |rec| { }` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorRecord {
+    /// See [`Handled::fingerprint`].
+    pub fingerprint: u64,
+    /// The error message.
+    pub message: String,
+    /// See [`Handled::origin`].
+    pub origin: Option<ErrorOrigin>,
+}
+
+impl<E: fmt::Display> Handled<E> {
+    /// Where this error was first wrapped (the oldest frame), if traced.
+    pub fn origin(&self) -> Option<ErrorOrigin> {
+        self.locations.iter().next().map(|loc| ErrorOrigin {
+            file: loc.file,
+            line: loc.line,
+            col: loc.col.into(),
+        })
+    }
+
+    /// Hash of the message and origin, identifying "the same error".
+    ///
+    /// Two errors with the same message raised at the same place share a
+    /// fingerprint, regardless of context or frames added on the way up.
+    /// Stable within a process; don't persist it across builds.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.message().hash(&mut hasher);
+        self.origin().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Build an [`ErrorRecord`] for this error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use handle_this::Handled;
+    ///
+    /// let a = Handled::msg("disk full").frame("store.rs", 10, 5);
+    /// let b = Handled::msg("disk full").frame("store.rs", 10, 5).ctx("retrying");
+    /// assert_eq!(a.snapshot(), b.snapshot());
+    /// ```
+    pub fn snapshot(&self) -> ErrorRecord {
+        ErrorRecord {
+            fingerprint: self.fingerprint(),
+            message: self.message().to_string(),
+            origin: self.origin(),
+        }
+    }
+}
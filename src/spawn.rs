@@ -0,0 +1,24 @@
+//! Task spawning for `async try spawn { }` (`tokio` feature).
+
+use core::future::Future;
+
+use crate::{Panicked, __BoxedError};
+
+/// Spawn `fut` on the current tokio runtime and wait for it.
+///
+/// A panic in the task comes back as [`Panicked`]; a cancelled task as
+/// `tokio::task::JoinError`.
+#[doc(hidden)]
+pub async fn __spawn<F>(fut: F) -> Result<F::Output, __BoxedError>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::task::spawn(fut).await.map_err(|e| -> __BoxedError {
+        if e.is_panic() {
+            Box::new(Panicked::from_payload(e.into_panic()))
+        } else {
+            Box::new(e)
+        }
+    })
+}
@@ -0,0 +1,128 @@
+//! Client-side rate limiting by key - the `throttle` clause.
+//!
+//! Each key keeps the timestamps of its recent attempts in a global registry.
+//! Keys with no attempt left in their window are evicted as the registry grows.
+//! `throttle key, N/per` lets at most `N` attempts through per window; further
+//! attempts fail with [`Throttled`] before the try body runs, so a `catch`
+//! can route them:
+//!
+//! ```
+//! use handle_this::{handle, throttle::Throttled, Result};
+//!
+//! fn ping() -> Result<&'static str> {
+//!     handle! {
+//!         try { "sent" }
+//!         throttle "doc.ping", 1/sec
+//!         catch Throttled(_) { "throttled" }
+//!     }
+//! }
+//!
+//! assert_eq!(ping().unwrap(), "sent");
+//! assert_eq!(ping().unwrap(), "throttled");
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Recent attempts for one key, and the window they were checked against.
+struct Window {
+    per: Duration,
+    attempts: VecDeque<Instant>,
+}
+
+/// Keys are swept once the map reaches `sweep_at` entries, so keys that stop
+/// being used don't accumulate.
+#[derive(Default)]
+struct Registry {
+    keys: HashMap<String, Window>,
+    sweep_at: usize,
+}
+
+/// Smallest map size that triggers a sweep.
+const MIN_SWEEP: usize = 64;
+
+impl Registry {
+    /// Drop keys whose attempts have all left their window, then wait until
+    /// the map doubles before sweeping again.
+    fn sweep(&mut self, now: Instant) {
+        self.keys.retain(|_, w| w.attempts.back().is_some_and(|t| now.duration_since(*t) < w.per));
+        self.sweep_at = (self.keys.len() * 2).max(MIN_SWEEP);
+    }
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// An attempt was rejected because its key exceeded the rate limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Throttled {
+    key: String,
+    retry_after: Duration,
+}
+
+impl Throttled {
+    /// The throttled key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// How long until the key admits another attempt.
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+}
+
+impl fmt::Display for Throttled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "throttled: `{}` retry in {:?}", self.key, self.retry_after)
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+/// Record an attempt for `key`, allowing at most `limit` attempts per `per`.
+///
+/// Rejected attempts are not recorded, so they don't extend the wait.
+pub fn check(key: impl AsRef<str>, limit: u32, per: Duration) -> Result<(), Throttled> {
+    let key = key.as_ref();
+    let now = Instant::now();
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    if registry.keys.len() >= registry.sweep_at.max(MIN_SWEEP) {
+        registry.sweep(now);
+    }
+    let window = match registry.keys.get_mut(key) {
+        Some(window) => window,
+        None => registry.keys.entry(key.to_string()).or_insert_with(|| Window {
+            per,
+            attempts: VecDeque::new(),
+        }),
+    };
+    window.per = per;
+    let attempts = &mut window.attempts;
+
+    while attempts.front().is_some_and(|t| now.duration_since(*t) >= per) {
+        attempts.pop_front();
+    }
+
+    if attempts.len() >= limit as usize {
+        let oldest = attempts.front().copied().unwrap_or(now);
+        return Err(Throttled {
+            key: key.to_string(),
+            retry_after: per.saturating_sub(now.duration_since(oldest)),
+        });
+    }
+
+    attempts.push_back(now);
+    Ok(())
+}
+
+/// Number of keys the registry currently tracks.
+///
+/// Keys whose attempts have all expired are dropped on a later [`check`].
+pub fn tracked() -> usize {
+    registry().lock().unwrap_or_else(|e| e.into_inner()).keys.len()
+}
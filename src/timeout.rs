@@ -0,0 +1,66 @@
+//! Deadlines for `async try timeout DURATION { }`.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::Poll;
+use std::fmt;
+use std::time::Duration;
+
+/// An `async try timeout` body didn't finish within its deadline.
+///
+/// ```
+/// use handle_this::{handle, Elapsed, Result};
+/// use std::time::Duration;
+///
+/// async fn fetch() -> Result<&'static str> {
+///     handle! {
+///         async try timeout Duration::from_millis(10) {
+///             std::future::pending::<()>().await;
+///             "fresh"
+///         }
+///         catch Elapsed(_) { "cached" }
+///     }
+/// }
+/// # let _ = fetch();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed {
+    after: Duration,
+}
+
+impl Elapsed {
+    /// The deadline that passed.
+    pub fn after(&self) -> Duration {
+        self.after
+    }
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.after)
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+/// Run `fut` until it completes or `after` passes, whichever is first.
+///
+/// Runtime-agnostic: the deadline sits on the crate's shared timer thread,
+/// like `catch e delay d`, and is only registered once `fut` first returns
+/// `Pending`. Finishing in time removes it, so no thread or timer outlives
+/// the call.
+#[doc(hidden)]
+pub async fn __timeout<F: Future>(after: Duration, fut: F) -> Result<F::Output, Elapsed> {
+    let mut fut = core::pin::pin!(fut);
+    let mut deadline = crate::macros::__async_delay(after);
+    core::future::poll_fn(|cx| {
+        if let Poll::Ready(v) = fut.as_mut().poll(cx) {
+            return Poll::Ready(Ok(v));
+        }
+        match Pin::new(&mut deadline).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed { after })),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
@@ -0,0 +1,98 @@
+//! One timer thread for every async delay and deadline.
+//!
+//! `catch e delay d` and `async try timeout` don't assume a runtime, so
+//! something outside the executor has to wake them. Pending timers sit in
+//! a map ordered by deadline; a single thread, started on first use, sleeps
+//! until the earliest one and wakes it. A timer dropped before its deadline
+//! (a body that beat its timeout) is removed right away, so nothing lingers.
+
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock};
+use std::task::Waker;
+use std::time::Instant;
+
+/// Pending timers, keyed by deadline and then a unique id.
+pub(crate) type Key = (Instant, u64);
+
+#[derive(Default)]
+struct State {
+    timers: BTreeMap<Key, Waker>,
+    next_id: u64,
+}
+
+struct Timer {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        std::thread::Builder::new()
+            .name("handle-this-timer".into())
+            .spawn(run)
+            .expect("failed to spawn the handle-this timer thread");
+        Timer { state: Mutex::new(State::default()), changed: Condvar::new() }
+    })
+}
+
+fn lock(timer: &Timer) -> MutexGuard<'_, State> {
+    timer.state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Wake `waker` once `deadline` passes.
+pub(crate) fn register(deadline: Instant, waker: &Waker) -> Key {
+    let timer = timer();
+    let mut state = lock(timer);
+    let key = (deadline, state.next_id);
+    state.next_id += 1;
+    let earliest = state.timers.keys().next().map_or(true, |first| key < *first);
+    state.timers.insert(key, waker.clone());
+    drop(state);
+    if earliest {
+        timer.changed.notify_one();
+    }
+    key
+}
+
+/// Point a pending timer at the task's latest waker.
+pub(crate) fn update(key: Key, waker: &Waker) {
+    if let Some(current) = lock(timer()).timers.get_mut(&key) {
+        if !current.will_wake(waker) {
+            current.clone_from(waker);
+        }
+    }
+}
+
+/// Forget a timer that is no longer awaited.
+pub(crate) fn cancel(key: Key) {
+    lock(timer()).timers.remove(&key);
+}
+
+fn run() {
+    let timer = timer();
+    let mut due = Vec::new();
+    let mut state = lock(timer);
+    loop {
+        let now = Instant::now();
+        while let Some(entry) = state.timers.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            due.push(entry.remove());
+        }
+        if !due.is_empty() {
+            // Wake outside the lock; a waker may poll and register again
+            drop(state);
+            due.drain(..).for_each(Waker::wake);
+            state = lock(timer);
+            continue;
+        }
+        state = match state.timers.keys().next() {
+            Some(&(deadline, _)) => {
+                timer.changed.wait_timeout(state, deadline - now).unwrap_or_else(|e| e.into_inner()).0
+            }
+            None => timer.changed.wait(state).unwrap_or_else(|e| e.into_inner()),
+        };
+    }
+}
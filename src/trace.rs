@@ -0,0 +1,83 @@
+//! `tracing` integration - error events and span correlation.
+//!
+//! Every `Handled` records the id of the span that was current when it was
+//! created ([`Handled::span_id`]). The `trace_error` clause emits a
+//! `tracing::error!` event on the error path, carrying the message, frames
+//! and attachments as fields, then lets the error propagate:
+//!
+//! ```
+//! use handle_this::{handle, Result};
+//!
+//! fn load() -> Result<i32> {
+//!     handle! {
+//!         try { "x".parse::<i32>()? }
+//!         with "loading", { file: "app.toml" }
+//!         trace_error
+//!     }
+//! }
+//!
+//! assert!(load().is_err());
+//! ```
+//!
+//! [`set_emit_on_create`] additionally emits an event for every new error,
+//! wherever it's created.
+
+use core::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::Handled;
+
+static EMIT_ON_CREATE: AtomicBool = AtomicBool::new(false);
+
+/// Emit a `tracing::error!` event whenever a `Handled` is created.
+///
+/// Off by default. The event carries only the message: the error has no
+/// frames or attachments yet.
+pub fn set_emit_on_create(enabled: bool) {
+    EMIT_ON_CREATE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether [`set_emit_on_create`] is on.
+pub fn emit_on_create() -> bool {
+    EMIT_ON_CREATE.load(Ordering::Relaxed)
+}
+
+/// Emit a `tracing::error!` event for `err`.
+///
+/// Fields: `error` (the message), `frames` (`file:line:col` oldest first,
+/// joined by `" -> "`), `attachments` (`key=value`, comma separated),
+/// `correlation_id` (from [`Handled::correlation_id`], if any) and
+/// `origin_span` (the id from [`Handled::span_id`], if any).
+pub fn emit<E: fmt::Display>(err: &Handled<E>) {
+    let frames = err
+        .frames()
+        .map(|f| format!("{}:{}:{}", f.file, f.line, f.col))
+        .collect::<Vec<_>>()
+        .join(" -> ");
+    let attachments = err
+        .frames()
+        .flat_map(|f| f.attachments())
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(", ");
+    tracing::error!(
+        error = %err.message(),
+        frames = %frames,
+        attachments = %attachments,
+        correlation_id = err.correlation_id(),
+        origin_span = ?err.span_id().map(|id| id.into_u64()),
+        "error handled",
+    );
+}
+
+/// Id of the current span, recorded on each new error.
+pub(crate) fn current_span_id() -> Option<tracing::Id> {
+    tracing::Span::current().id()
+}
+
+/// Creation hook for [`set_emit_on_create`].
+pub(crate) fn on_create<E: fmt::Display>(err: &Handled<E>) {
+    if emit_on_create() {
+        tracing::error!(error = %err.message(), "error created");
+    }
+}
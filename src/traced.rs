@@ -0,0 +1,56 @@
+//! Function names for frames, set by `#[handle_this::traced]`.
+//!
+//! A traced function marks itself as the current function on its thread
+//! for as long as it runs (for an `async fn`, during each poll). Frames
+//! pushed meanwhile record its `module::function` path.
+
+use core::future::Future;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+std::thread_local! {
+    static CURRENT: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+// Skips the thread-local on the hot path until something is traced
+static TRACED: AtomicBool = AtomicBool::new(false);
+
+/// Path of the innermost traced function running on this thread.
+#[inline]
+pub(crate) fn current() -> Option<&'static str> {
+    if !TRACED.load(Ordering::Relaxed) {
+        return None;
+    }
+    CURRENT.with(Cell::get)
+}
+
+/// Marks a traced function as current until dropped.
+#[doc(hidden)]
+pub struct __TracedGuard(Option<&'static str>);
+
+impl __TracedGuard {
+    #[inline]
+    pub fn enter(function: &'static str) -> Self {
+        TRACED.store(true, Ordering::Relaxed);
+        Self(CURRENT.with(|current| current.replace(Some(function))))
+    }
+}
+
+impl Drop for __TracedGuard {
+    #[inline]
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.0));
+    }
+}
+
+/// Body of a traced `async fn`: current only while being polled, so other
+/// tasks on the thread don't pick up its name across an `.await`.
+#[doc(hidden)]
+pub async fn __traced_async<F: Future>(function: &'static str, fut: F) -> F::Output {
+    let mut fut = core::pin::pin!(fut);
+    core::future::poll_fn(|cx| {
+        let _guard = __TracedGuard::enter(function);
+        fut.as_mut().poll(cx)
+    })
+    .await
+}
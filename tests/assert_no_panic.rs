@@ -0,0 +1,59 @@
+//! `assert_no_panic` contains panics raised by a catch handler.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::io;
+
+fn fails() -> Result<i32> {
+    handle! { try { Err("primary failure")? } }
+}
+
+fn log_and_panic(_: &str) -> i32 {
+    panic!("logger exploded")
+}
+
+#[test]
+fn panicking_catch_yields_fallback_error() {
+    let result: Result<i32> = handle! {
+        try { fails()? }
+        catch e { log_and_panic(e.message()) }
+        assert_no_panic
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "catch handler panicked: logger exploded");
+}
+
+#[test]
+fn formatted_panic_message_is_kept() {
+    let result: Result<i32> = handle! {
+        try { fails()? }
+        catch e { panic!("while handling {}", e.message()) }
+        assert_no_panic
+    };
+    assert_eq!(
+        result.unwrap_err().message(),
+        "catch handler panicked: while handling primary failure"
+    );
+}
+
+#[test]
+fn non_panicking_catch_recovers() {
+    let result: Result<i32> = handle! {
+        try { fails()? }
+        catch { 7 }
+        assert_no_panic
+    };
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[test]
+fn typed_catch_with_fallback() {
+    let result: Result<i32> = handle! {
+        try { Err(io::Error::new(io::ErrorKind::Other, "io"))? }
+        catch io::Error(_) { panic!("typed handler") }
+        assert_no_panic
+        catch { 0 }
+    };
+    assert_eq!(result.unwrap_err().message(), "catch handler panicked: typed handler");
+}
@@ -0,0 +1,87 @@
+//! Tests for `try for (i, x) in iter.enumerate()` recording `attempt` on failures.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result, Value};
+
+fn connect(server: &str) -> Result<&str> {
+    if server.starts_with("up") {
+        Ok(server)
+    } else {
+        Err(Handled::msg(format!("{} unreachable", server)))
+    }
+}
+
+/// `attempt` of the error and each chained link, most recent first.
+fn attempts(err: Handled) -> Vec<u64> {
+    let mut out = Vec::new();
+    let mut current = Some(Box::new(err));
+    while let Some(mut e) = current {
+        if let Some(Value::Uint(n)) = e.get_kv("attempt") {
+            out.push(*n);
+        }
+        current = e.take_chain();
+    }
+    out
+}
+
+#[test]
+fn each_failure_records_its_index() {
+    let servers = ["down-a", "down-b", "down-c"];
+    let err = handle! {
+        try for (i, server) in servers.iter().enumerate() { connect(server)? }
+    }
+    .unwrap_err();
+    assert_eq!(attempts(err), [2, 1, 0]);
+}
+
+#[test]
+fn handlers_read_the_last_attempt() {
+    let servers = ["down-a", "down-b"];
+    let last: Result<Value> = handle! {
+        try for (i, server) in servers.iter().enumerate() { Value::from(connect(server)?) }
+        catch e { e.get_kv("attempt").cloned().unwrap() }
+    };
+    assert_eq!(last.unwrap(), Value::Uint(1));
+}
+
+#[test]
+fn success_still_wins() {
+    let servers = ["down-a", "up-b"];
+    let picked = handle! {
+        try for (i, server) in servers.iter().enumerate() { (i, connect(server)?) }
+    }
+    .unwrap();
+    assert_eq!(picked, (1, "up-b"));
+}
+
+#[test]
+fn try_all_records_failed_indices() {
+    let servers = ["up-a", "down-b", "up-c", "down-d"];
+    let err = handle! {
+        try all (i, server) in servers.iter().enumerate() { connect(server)? } with "connecting"
+    }
+    .unwrap_err();
+    assert_eq!(attempts(err), [3, 1]);
+}
+
+#[test]
+fn plain_tuples_are_not_indices() {
+    let pairs = [(7u32, "down-a")];
+    let err = handle! {
+        try for (id, server) in pairs.iter().copied() { (id, connect(server)?) }
+    }
+    .unwrap_err();
+    assert!(err.get_kv("attempt").is_none());
+}
+
+#[test]
+fn partial_keeps_indices_on_errors() {
+    let servers = ["down-a", "up-b"];
+    let failed: Vec<u64> = handle! {
+        try all (i, server) in servers.iter().enumerate() { connect(server)? }
+        partial |_oks, errs| { errs.into_iter().flat_map(attempts).collect() }
+    }
+    .unwrap();
+    assert_eq!(failed, [0]);
+}
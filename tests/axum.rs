@@ -0,0 +1,71 @@
+//! `axum::response::IntoResponse` for `Handled` (`axum` feature).
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "axum")]
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use handle_this::{handle, ErrorKind, Handled, Result};
+use std::io;
+
+async fn json(response: Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn get_user(id: u32) -> Result<String> {
+    handle! {
+        try { Err(io::Error::new(io::ErrorKind::NotFound, format!("no user {}", id)))? }
+        with "loading user", { user_id: id }, code "USER_MISSING"
+    }
+}
+
+#[tokio::test]
+async fn kind_selects_the_status() {
+    let response = get_user(7).into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["content-type"], "application/json");
+
+    let body = json(response).await;
+    assert_eq!(body["error"], "no user 7");
+    assert_eq!(body["kind"], "not found");
+    assert_eq!(body["code"], "USER_MISSING");
+}
+
+#[tokio::test]
+async fn unclassified_errors_are_500() {
+    let response = Handled::msg("boom").into_response();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body = json(response).await;
+    assert_eq!(body["kind"], "internal");
+    assert!(body.get("code").is_none());
+}
+
+#[test]
+fn explicit_status_field_wins() {
+    let err = Handled::msg("slow down").with_kind(ErrorKind::Unavailable).set_field(StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(err.into_response().status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let outer = Handled::msg("gateway").chain_after(Handled::msg("denied").with_kind(ErrorKind::Permission));
+    assert_eq!(outer.into_response().status(), StatusCode::FORBIDDEN);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn attachments_only_in_debug_builds() {
+    let err = Handled::msg("bad").frame("a.rs", 1, 1).kv("field", "email").frame("b.rs", 2, 1).kv("field", "name");
+    let body = json(err.into_response()).await;
+    if cfg!(debug_assertions) {
+        assert_eq!(body["attachments"], serde_json::json!({ "field": "name" }));
+    } else {
+        assert!(body.get("attachments").is_none());
+    }
+    assert!(json(Handled::msg("plain").into_response()).await.get("attachments").is_none());
+}
+
+#[test]
+fn handlers_can_return_result() {
+    let ok: Result<&str> = Ok("hi");
+    assert_eq!(ok.into_response().status(), StatusCode::OK);
+    assert_eq!(get_user(1).map_err(|e| e.with_kind(ErrorKind::Conflict)).into_response().status(), StatusCode::CONFLICT);
+}
@@ -0,0 +1,182 @@
+//! Tests for `try while cond, backoff KIND { }`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Backoff, Handled, Result, Value};
+use std::time::{Duration, Instant};
+
+fn attempts_kv(err: &Handled) -> Option<Value> {
+    err.frames()
+        .flat_map(|f| f.attachments())
+        .find(|(k, _)| *k == "attempts")
+        .map(|(_, v)| v.clone())
+}
+
+#[test]
+fn exponential_doubles_up_to_the_cap() {
+    let b = Backoff::Exponential { base: Duration::from_millis(100), max: Duration::from_secs(1) };
+    let delays: Vec<_> = (1..=6).map(|n| b.delay(n).as_millis()).collect();
+    assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+    assert_eq!(b.delay(200), Duration::from_secs(1));
+}
+
+#[test]
+fn linear_and_fixed_delays() {
+    let linear = Backoff::Linear { step: Duration::from_millis(50), max: Duration::from_millis(120) };
+    assert_eq!(linear.delay(1), Duration::from_millis(50));
+    assert_eq!(linear.delay(2), Duration::from_millis(100));
+    assert_eq!(linear.delay(3), Duration::from_millis(120));
+    assert_eq!(Backoff::Fixed(Duration::from_millis(7)).delay(9), Duration::from_millis(7));
+}
+
+#[test]
+fn sleeps_between_attempts_but_not_before_the_first() {
+    let mut attempts = 0;
+    let start = Instant::now();
+    let result: Result<i32> = handle! {
+        try while attempts < 3, backoff fixed(20ms) {
+            attempts += 1;
+            Err("busy")?
+        }
+    };
+    let elapsed = start.elapsed();
+    assert!(result.is_err());
+    assert_eq!(attempts, 3);
+    // Two retries, two sleeps
+    assert!(elapsed >= Duration::from_millis(40), "{:?}", elapsed);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn final_error_records_attempt_count() {
+    let mut attempts = 0;
+    let result: Result<i32> = handle! {
+        try while attempts < 4, backoff exponential(1ms, max 2ms) {
+            attempts += 1;
+            Err("refused")?
+        }
+    };
+    assert_eq!(attempts_kv(&result.unwrap_err()), Some(Value::Uint(4)));
+}
+
+#[test]
+fn success_stops_retrying() {
+    let mut attempts = 0;
+    let result: Result<&str> = handle! {
+        try while attempts < 10, backoff linear(1ms) {
+            attempts += 1;
+            if attempts < 3 { Err("not yet")? }
+            "connected"
+        }
+    };
+    assert_eq!(result.unwrap(), "connected");
+    assert_eq!(attempts, 3);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn false_condition_runs_once_and_counts_one_attempt() {
+    let result: Result<i32> = handle! {
+        try while false, backoff fixed(1s) { Err("once")? }
+    };
+    assert_eq!(attempts_kv(&result.unwrap_err()), Some(Value::Uint(1)));
+}
+
+#[test]
+fn accepts_duration_expressions() {
+    let base = Duration::from_millis(1);
+    let mut attempts = 0;
+    let result: Result<i32> = handle! {
+        try while attempts < 2, backoff exponential(base, max base * 4) {
+            attempts += 1;
+            Err("down")?
+        }
+        catch { attempts * 10 }
+    };
+    assert_eq!(result.unwrap(), 20);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn handlers_see_the_attempts_kv() {
+    let mut attempts = 0;
+    let result: Result<u64> = handle! {
+        try while attempts < 2, backoff fixed(1ms) {
+            attempts += 1;
+            Err("down")?
+        }
+        catch e {
+            match attempts_kv(&e) {
+                Some(Value::Uint(n)) => n,
+                _ => 0,
+            }
+        }
+    };
+    assert_eq!(result.unwrap(), 2);
+}
+
+#[test]
+fn works_with_control_flow_handlers() {
+    let mut seen = Vec::new();
+    for job in 0..3 {
+        let mut attempts = 0;
+        let value: i32 = handle! {
+            try while attempts < 2, backoff fixed(1ms) {
+                attempts += 1;
+                if job == 1 { Err("flaky")? }
+                job
+            }
+            catch { continue }
+        };
+        seen.push(value);
+    }
+    assert_eq!(seen, vec![0, 2]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn async_backoff_is_awaited() {
+    let mut attempts = 0;
+    let log = std::cell::RefCell::new(Vec::new());
+    let retry = async {
+        let result: Result<i32> = handle! {
+            async try while attempts < 3, backoff fixed(20ms) {
+                attempts += 1;
+                tokio::task::yield_now().await;
+                Err("busy")?
+            }
+        };
+        log.borrow_mut().push("retry done");
+        result
+    };
+    let other = async {
+        tokio::task::yield_now().await;
+        log.borrow_mut().push("other ran");
+    };
+    let (result, ()) = tokio::join!(retry, other);
+    assert_eq!(attempts_kv(&result.unwrap_err()), Some(Value::Uint(3)));
+    assert_eq!(attempts, 3);
+    // The backoff didn't block the thread, so `other` finished first
+    assert_eq!(*log.borrow(), ["other ran", "retry done"]);
+}
+
+#[tokio::test]
+async fn async_retry_with_limit_and_handler() {
+    let mut calls = 0;
+    let result: Result<i32> = handle! {
+        async try while true limit 3, backoff fixed(1ms) {
+            calls += 1;
+            if calls < 3 { Err("flaky")? }
+            calls
+        }
+        catch { -1 }
+    };
+    assert_eq!(result.unwrap(), 3);
+
+    let result: Result<usize> = handle! {
+        async try while true limit 2 { Err(std::io::Error::new(std::io::ErrorKind::Other, "down"))? }
+        catch e { e.chain_all::<std::io::Error>().len() }
+    };
+    assert_eq!(result.unwrap(), 2);
+}
@@ -0,0 +1,59 @@
+//! Native stack capture under the `backtrace` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "backtrace")]
+
+use handle_this::{handle, Handled, Result};
+use std::backtrace::BacktraceStatus;
+use std::sync::Once;
+
+/// `Backtrace::capture` reads the environment once, so every test turns
+/// capture on before creating its first error.
+fn enable() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| std::env::set_var("RUST_LIB_BACKTRACE", "1"));
+}
+
+fn deep_helper() -> std::result::Result<(), std::io::Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::Other, "deep"))
+}
+
+fn fail() -> Result<()> {
+    handle! { try { deep_helper()? } }
+}
+
+#[test]
+fn captured_on_creation() {
+    enable();
+    let err = fail().unwrap_err();
+    assert_eq!(err.backtrace().status(), BacktraceStatus::Captured);
+    assert!(err.backtrace().to_string().contains("fail"));
+}
+
+#[test]
+fn rewrapping_keeps_the_first_backtrace() {
+    enable();
+    let err = fail().unwrap_err();
+    let first: *const _ = err.backtrace();
+    let outer: Result<()> = handle! { try { Err(err)? } with "outer" };
+    let outer = outer.unwrap_err();
+    assert!(std::ptr::eq(first, outer.backtrace()));
+}
+
+#[test]
+fn transforms_keep_the_backtrace() {
+    enable();
+    let err = Handled::new(std::io::Error::new(std::io::ErrorKind::Other, "x"));
+    let first: *const _ = err.backtrace();
+    let erased = err.frame("src/a.rs", 1, 1).ctx("step").erase();
+    assert!(std::ptr::eq(first, erased.backtrace()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialized_errors_have_none() {
+    enable();
+    let json = serde_json::to_string(&fail().unwrap_err()).unwrap();
+    let restored: Handled = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.backtrace().status(), BacktraceStatus::Disabled);
+}
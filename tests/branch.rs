@@ -0,0 +1,119 @@
+//! Tests for the `branch { }` type switch.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::io;
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl std::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+fn fail(kind: u8) -> Result<String> {
+    handle! {
+        try {
+            match kind {
+                0 => Err(io::Error::new(io::ErrorKind::NotFound, "missing"))?,
+                1 => "x".parse::<i32>().map(|n| n.to_string())?,
+                2 => Err(Timeout)?,
+                _ => Err(Handled::msg("other"))?,
+            }
+        }
+    }
+}
+
+fn classify(kind: u8) -> String {
+    handle! {
+        try -> String { fail(kind)? }
+        branch {
+            io::Error(e) => { format!("io: {:?}", e.kind()) },
+            ParseIntError(e) => format!("parse: {}", e),
+            Timeout => "timeout".to_string(),
+            e => format!("other: {}", e.message()),
+        }
+    }
+}
+
+#[test]
+fn three_types_with_catch_all() {
+    assert_eq!(classify(0), "io: NotFound");
+    assert_eq!(classify(1), "parse: invalid digit found in string");
+    assert_eq!(classify(2), "timeout");
+    assert_eq!(classify(3), "other: other");
+}
+
+#[test]
+fn without_catch_all_unmatched_propagates() {
+    let run = |kind| -> Result<&'static str> {
+        handle! {
+            try { fail(kind).map(|_| "ok")? }
+            branch {
+                io::Error(_) => "io",
+                Timeout(_) => "timeout",
+            }
+        }
+    };
+    assert_eq!(run(0).unwrap(), "io");
+    assert_eq!(run(2).unwrap(), "timeout");
+    assert_eq!(run(1).unwrap_err().message(), "invalid digit found in string");
+}
+
+#[test]
+fn underscore_arm_and_later_handlers() {
+    let result: Result<&str> = handle! {
+        try { fail(1).map(|_| "ok")? }
+        branch {
+            io::Error(_) => "io",
+        }
+        catch _ { "fallback" }
+    };
+    assert_eq!(result.unwrap(), "fallback");
+
+    let result = handle! {
+        try -> &str { fail(0).map(|_| "ok")? }
+        branch { _ => "any" }
+    };
+    assert_eq!(result, "any");
+}
+
+#[test]
+fn arms_can_continue_loops() {
+    let mut seen = Vec::new();
+    for kind in 0..4u8 {
+        let value = handle! {
+            try -> String { fail(kind)? }
+            branch {
+                Timeout => { continue },
+                e => e.message().to_string(),
+            }
+        };
+        seen.push(value);
+    }
+    assert_eq!(seen.len(), 3);
+}
+
+#[test]
+fn nested_inside_outer_try() {
+    let result: Result<String> = handle! {
+        try {
+            let inner = handle! {
+                try { fail(2)? }
+                branch {
+                    Timeout => "retry later".to_string(),
+                    io::Error(e) => e.to_string(),
+                }
+            };
+            inner?
+        }
+        with "outer"
+    };
+    assert_eq!(result.unwrap(), "retry later");
+}
@@ -0,0 +1,85 @@
+//! Tests for `async try { } finally { } cancel_safe`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, RawWaker, RawWakerVTable, Waker};
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+/// Poll once, then drop the future mid-await.
+fn poll_once_then_drop<F: Future>(fut: F) {
+    let mut fut = Box::pin(fut);
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert!(Pin::as_mut(&mut fut).poll(&mut cx).is_pending());
+}
+
+async fn never() -> std::result::Result<i32, std::io::Error> {
+    std::future::pending().await
+}
+
+async fn guarded(runs: Arc<AtomicUsize>) -> Result<i32> {
+    handle! {
+        async try { never().await? }
+        catch { -1 }
+        finally { runs.fetch_add(1, Ordering::SeqCst); }
+        cancel_safe
+    }
+}
+
+#[test]
+fn cancel_safe_runs_finally_when_dropped() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    poll_once_then_drop(guarded(runs.clone()));
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn cancel_safe_runs_finally_once_on_success() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let result: Result<i32> = handle! {
+        async try { 42 }
+        finally { runs.fetch_add(1, Ordering::SeqCst); }
+        cancel_safe
+    };
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn cancel_safe_runs_finally_once_on_error() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let result: Result<i32> = handle! {
+        async try { Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))? }
+        catch { -1 }
+        finally { runs.fetch_add(1, Ordering::SeqCst); }
+        cancel_safe
+    };
+    assert_eq!(result.unwrap(), -1);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn plain_finally_skipped_when_dropped() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let r = runs.clone();
+    poll_once_then_drop(async move {
+        let _: Result<i32> = handle! {
+            async try { never().await? }
+            finally { r.fetch_add(1, Ordering::SeqCst); }
+        };
+    });
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+}
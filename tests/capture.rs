@@ -0,0 +1,116 @@
+//! Tests for `set_capture`: turning trace capture off process-wide.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{capture_enabled, handle, set_capture, Handled, Result, Value};
+use std::sync::{Mutex, MutexGuard};
+
+/// Capture is process-wide; tests that turn it off take turns.
+static CAPTURE: Mutex<()> = Mutex::new(());
+
+/// Capture stays off until the guard is dropped.
+struct Uncaptured {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Uncaptured {
+    fn new() -> Self {
+        let guard = CAPTURE.lock().unwrap_or_else(|e| e.into_inner());
+        set_capture(false);
+        Uncaptured { _guard: guard }
+    }
+}
+
+impl Drop for Uncaptured {
+    fn drop(&mut self) {
+        set_capture(true);
+    }
+}
+
+fn parse(s: &str) -> Result<i32> {
+    handle! { try { s.parse::<i32>()? } with "parsing", { input: s } }
+}
+
+#[test]
+fn builders_are_no_ops() {
+    let _off = Uncaptured::new();
+    assert!(!capture_enabled());
+    let err = Handled::msg("busy")
+        .frame("a.rs", 1, 1)
+        .ctx("retrying")
+        .kv("attempt", 3)
+        .scope("w.rs", 3, 1, "worker")
+        .note_at("cfg.toml", 2, 1, "limit set here");
+    assert_eq!(err.message(), "busy");
+    assert_eq!(err.frames().count(), 0);
+    assert_eq!(err.get_kv("attempt"), None);
+    assert_eq!(err.to_string(), Handled::msg("busy").to_string());
+}
+
+#[test]
+fn handle_still_propagates_and_matches() {
+    let _off = Uncaptured::new();
+    let err = parse("x").unwrap_err();
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+    assert_eq!(err.frames().count(), 0);
+    assert_eq!(err.get_kv("input"), None);
+
+    let recovered: Result<i32> = handle! {
+        try { parse("x")? }
+        catch std::num::ParseIntError(_) { -1 }
+    };
+    assert_eq!(recovered.unwrap(), -1);
+}
+
+#[test]
+fn attempts_are_kept_without_capture() {
+    let _off = Uncaptured::new();
+    let last: Result<Option<Value>> = handle! {
+        try for (i, s) in ["x", "y"].iter().enumerate() { Some(Value::from(parse(s)?)) }
+        catch e { e.get_kv("attempt").cloned() }
+    };
+    assert_eq!(last.unwrap(), Some(Value::Uint(1)));
+
+    let err: Handled = handle! {
+        try while true limit 2 { parse("x")? }
+    }
+    .unwrap_err();
+    assert_eq!(err.frames().count(), 0);
+    assert_eq!(err.get_kv("attempts"), Some(&Value::Uint(2)));
+}
+
+#[test]
+#[cfg(not(feature = "no-trace"))]
+fn existing_traces_are_kept() {
+    let err = {
+        let _guard = CAPTURE.lock().unwrap_or_else(|e| e.into_inner());
+        parse("x").unwrap_err()
+    };
+    assert_eq!(err.frames().count(), 1);
+
+    let _off = Uncaptured::new();
+    let err = err.frame("b.rs", 2, 1).ctx("later");
+    assert_eq!(err.frames().count(), 1);
+    assert_eq!(err.frames().next().unwrap().context, Some("parsing"));
+}
+
+#[test]
+#[cfg(not(feature = "no-trace"))]
+fn turning_capture_back_on() {
+    drop(Uncaptured::new());
+    let _guard = CAPTURE.lock().unwrap_or_else(|e| e.into_inner());
+    assert!(capture_enabled());
+    assert_eq!(Handled::msg("x").frame("a.rs", 1, 1).frames().count(), 1);
+}
+
+#[test]
+#[cfg(feature = "no-trace")]
+fn no_trace_ignores_set_capture() {
+    let _guard = CAPTURE.lock().unwrap_or_else(|e| e.into_inner());
+    set_capture(true);
+    assert!(!capture_enabled());
+    let err = parse("x").unwrap_err().frame("a.rs", 1, 1).kv_secret("token", "t0k3n");
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+    assert_eq!(err.frames().count(), 0);
+    assert_eq!(err.get_kv("token"), None);
+}
@@ -0,0 +1,172 @@
+//! Tests for `catch dyn Trait (e)` / `catch impl Trait (e)` via `catchable!`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{catchable, handle, Handled, Result};
+use std::fmt;
+
+trait Retryable {
+    fn retry_after(&self) -> u64;
+}
+
+macro_rules! error_type {
+    ($name:ident, $msg:literal) => {
+        #[derive(Debug)]
+        struct $name;
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str($msg)
+            }
+        }
+        impl std::error::Error for $name {}
+    };
+}
+
+error_type!(Busy, "busy");
+error_type!(RateLimited, "rate limited");
+error_type!(Fatal, "fatal");
+
+impl Retryable for Busy {
+    fn retry_after(&self) -> u64 {
+        1
+    }
+}
+
+impl Retryable for RateLimited {
+    fn retry_after(&self) -> u64 {
+        30
+    }
+}
+
+catchable!(dyn Retryable: Busy, RateLimited);
+
+mod domain {
+    pub trait Reportable {
+        fn code(&self) -> u16;
+    }
+}
+
+impl domain::Reportable for Fatal {
+    fn code(&self) -> u16 {
+        500
+    }
+}
+
+catchable!(dyn domain::Reportable: Fatal);
+
+fn fail(which: u8) -> Result<u64> {
+    match which {
+        0 => Err(Handled::wrap(Busy)),
+        1 => Err(Handled::wrap(RateLimited)),
+        _ => Err(Handled::wrap(Fatal)),
+    }
+}
+
+fn wait_for(which: u8) -> Result<u64> {
+    handle! {
+        try { fail(which)? }
+        catch dyn Retryable (e) { e.retry_after() }
+    }
+}
+
+#[test]
+fn matches_every_registered_type() {
+    assert_eq!(wait_for(0).unwrap(), 1);
+    assert_eq!(wait_for(1).unwrap(), 30);
+}
+
+#[test]
+fn unregistered_types_propagate() {
+    let err = wait_for(2).unwrap_err();
+    assert!(err.downcast_ref::<Fatal>().is_some());
+}
+
+#[test]
+fn impl_syntax_and_paths() {
+    let result: Result<u64> = handle! {
+        try { fail(2)? }
+        catch impl Retryable (_) { 0 }
+        catch dyn domain::Reportable (e) { e.code().into() }
+    };
+    assert_eq!(result.unwrap(), 500);
+}
+
+#[test]
+fn unused_binding_with_fallback() {
+    let result: Result<&str> = handle! {
+        try { fail(1)?; "ok" }
+        catch dyn Retryable (_) { "retry" }
+        catch _ { "give up" }
+    };
+    assert_eq!(result.unwrap(), "retry");
+}
+
+#[test]
+fn guards_see_the_trait_object() {
+    let result: Result<&str> = handle! {
+        try { fail(1)?; "ok" }
+        catch dyn Retryable (e) when e.retry_after() < 10 { "soon" }
+        catch dyn Retryable (_) { "later" }
+    };
+    assert_eq!(result.unwrap(), "later");
+}
+
+#[test]
+fn chain_search() {
+    let err = Handled::wrap(Busy).chain_after(Handled::wrap(RateLimited));
+    assert_eq!(err.chain_any::<dyn Retryable>().map(|e| e.retry_after()), Some(1));
+    let all: Vec<u64> = err.chain_all::<dyn Retryable>().iter().map(|e| e.retry_after()).collect();
+    assert_eq!(all, [1, 30]);
+
+    let result: Result<u64> = handle! {
+        try { Err(err)? }
+        catch all dyn Retryable |errors| { errors.iter().map(|e| e.retry_after()).max().unwrap() }
+    };
+    assert_eq!(result.unwrap(), 30);
+}
+
+#[test]
+fn throw_and_inspect() {
+    let seen = std::cell::Cell::new(0);
+    let result: Result<u64> = handle! {
+        try { fail(0)? }
+        inspect dyn Retryable (e) { seen.set(e.retry_after()); }
+        throw dyn Retryable (e) { format!("retry in {}s", e.retry_after()) }
+    };
+    assert_eq!(seen.get(), 1);
+    assert_eq!(result.unwrap_err().message(), "retry in 1s");
+}
+
+#[test]
+fn nested() {
+    let result: Result<u64> = handle! {
+        try {
+            let inner = try { fail(1)? } catch dyn Retryable (e) { e.retry_after() };
+            inner + 1
+        }
+    };
+    assert_eq!(result.unwrap(), 31);
+}
+
+#[tokio::test]
+async fn async_try() {
+    let result: Result<u64> = handle! {
+        async try { fail(0)? }
+        catch dyn Retryable (e) { e.retry_after() }
+    };
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[test]
+fn control_flow_in_loops() {
+    let mut done = Vec::new();
+    for which in 0..3u8 {
+        let value = handle! {
+            try -> u64 { fail(which)? }
+            catch dyn Retryable (_) { continue }
+            catch { 0 }
+        };
+        done.push(value);
+    }
+    assert_eq!(done, [0]);
+}
@@ -0,0 +1,57 @@
+//! `catch_finally_with` combining body and finally failures.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+
+fn run(body_fails: bool, finally_fails: bool) -> Result<i32> {
+    handle! {
+        try {
+            if body_fails { Err("body failed")?; }
+            1
+        }
+        finally {
+            if finally_fails { Err("cleanup failed")?; }
+        }
+        catch_finally_with |body_err, finally_err| {
+            format!("{} / {}", body_err.message(), finally_err.message())
+        }
+    }
+}
+
+#[test]
+fn combiner_receives_both_errors() {
+    let err = run(true, true).unwrap_err();
+    assert_eq!(err.message(), "body failed / cleanup failed");
+}
+
+#[test]
+fn body_error_alone_propagates() {
+    let err = run(true, false).unwrap_err();
+    assert_eq!(err.message(), "body failed");
+}
+
+#[test]
+fn finally_error_alone_propagates() {
+    let err = run(false, true).unwrap_err();
+    assert_eq!(err.message(), "cleanup failed");
+}
+
+#[test]
+fn success_when_neither_fails() {
+    assert_eq!(run(false, false).unwrap(), 1);
+}
+
+#[test]
+fn combiner_can_chain_finally_error() {
+    let result: Result<()> = handle! {
+        try { Err("body failed")?; }
+        finally { Err("cleanup failed")?; }
+        catch_finally_with |body_err, finally_err| {
+            body_err.chain_after(finally_err)
+        }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "body failed");
+    assert!(format!("{err:?}").contains("cleanup failed"));
+}
@@ -0,0 +1,96 @@
+//! Tests for `try { } catch panic p { }`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Panicked, Result};
+
+fn third(items: &[u32]) -> u32 {
+    items[2]
+}
+
+#[test]
+fn panic_is_caught() {
+    let result: Result<u32> = handle! {
+        try { third(&[1]) }
+        catch panic p { p.message().len() as u32 }
+    };
+    assert!(result.unwrap() > 0);
+}
+
+#[test]
+fn message_comes_from_payload() {
+    let seen = std::cell::RefCell::new(String::new());
+    let result: Result<&str> = handle! {
+        try { panic!("boom {}", 7) }
+        catch panic p { *seen.borrow_mut() = p.message().to_string(); "recovered" }
+    };
+    assert_eq!(result.unwrap(), "recovered");
+    assert_eq!(*seen.borrow(), "boom 7");
+}
+
+#[test]
+fn no_panic_passes_value_through() {
+    let result: Result<u32> = handle! {
+        try { third(&[1, 2, 3]) }
+        catch panic _ { 0 }
+    };
+    assert_eq!(result.unwrap(), 3);
+}
+
+#[test]
+fn ordinary_errors_skip_the_panic_handler() {
+    let result: Result<u32> = handle! {
+        try { "x".parse::<u32>()? }
+        catch panic _ { 0 }
+    };
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[test]
+fn panic_flows_through_other_handlers() {
+    let inspected = std::cell::Cell::new(false);
+    let result: Result<u32> = handle! {
+        try { panic!("deep") }
+        inspect e { inspected.set(e.downcast_ref::<Panicked>().is_some()); }
+        catch std::num::ParseIntError(_) { 1 }
+        catch panic p { p.message().len() as u32 }
+    };
+    assert!(inspected.get());
+    assert_eq!(result.unwrap(), 4);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn guard_can_decline() {
+    let result: Result<u32> = handle! {
+        try { panic!("fatal") }
+        catch panic p when p.message() == "minor" { 0 }
+    };
+    let err: Handled = result.unwrap_err();
+    assert_eq!(err.downcast_ref::<Panicked>().unwrap().message(), "fatal");
+    let frame = err.frames().next().unwrap();
+    assert_eq!(frame.file, "tests/catch_panic.rs");
+}
+
+#[test]
+fn catch_panic_is_a_binding_not_a_catch_all_named_panic() {
+    let result: Result<u32> = handle! {
+        try { Err("plain")? }
+        catch panic { panic.message().len() as u32 }
+    };
+    assert_eq!(result.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn async_panic_is_caught() {
+    let result: Result<u32> = handle! {
+        async try {
+            tokio::task::yield_now().await;
+            third(&[])
+        }
+        catch panic p { if p.message().contains("out of bounds") { 1 } else { 2 } }
+    };
+    assert_eq!(result.unwrap(), 1);
+}
@@ -0,0 +1,132 @@
+//! Tests for enum variant patterns in handler clauses: `catch MyError::NotFound { id } { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::fmt;
+
+#[derive(Debug)]
+enum StoreError {
+    NotFound { id: u64 },
+    Conflict(u64, u64),
+    Timeout,
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::NotFound { id } => write!(f, "{} not found", id),
+            StoreError::Conflict(a, b) => write!(f, "{} conflicts with {}", a, b),
+            StoreError::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+fn fail(err: StoreError) -> std::result::Result<u64, StoreError> {
+    Err(err)
+}
+
+#[test]
+fn struct_variant_binds_fields() {
+    let result: Result<u64> = handle! {
+        try { fail(StoreError::NotFound { id: 7 })? }
+        catch StoreError::NotFound { id } { *id * 10 }
+    };
+    assert_eq!(result.unwrap(), 70);
+}
+
+#[test]
+fn tuple_and_unit_variants() {
+    let conflict: Result<u64> = handle! {
+        try { fail(StoreError::Conflict(1, 2))? }
+        catch StoreError::Conflict(a, b) { a + b }
+    };
+    assert_eq!(conflict.unwrap(), 3);
+
+    let timeout: Result<u64> = handle! {
+        try { fail(StoreError::Timeout)? }
+        catch StoreError::Timeout { 0 }
+    };
+    assert_eq!(timeout.unwrap(), 0);
+}
+
+#[test]
+fn other_variants_fall_through() {
+    let result: Result<u64> = handle! {
+        try { fail(StoreError::Timeout)? }
+        catch StoreError::NotFound { .. } { 1 }
+        catch StoreError::Conflict(..) { 2 }
+        catch e { e.message().len() as u64 }
+    };
+    assert_eq!(result.unwrap(), "timed out".len() as u64);
+
+    let unhandled: Result<u64> = handle! {
+        try { fail(StoreError::Timeout)? }
+        catch StoreError::NotFound { id } { *id }
+    };
+    assert!(matches!(unhandled.unwrap_err().downcast_ref::<StoreError>(), Some(StoreError::Timeout)));
+}
+
+#[test]
+fn literal_subpatterns_and_guards() {
+    let result: Result<&str> = handle! {
+        try { fail(StoreError::Conflict(4, 4))?; "ok" }
+        catch StoreError::Conflict(a, b) when a != b { "different" }
+        catch StoreError::Conflict(4, _) { "four" }
+    };
+    assert_eq!(result.unwrap(), "four");
+
+    let guarded: Result<u64> = handle! {
+        try { fail(StoreError::NotFound { id: 3 })? }
+        catch StoreError::NotFound { id } when *id > 5 { 1 }
+        catch StoreError::NotFound { id } when *id <= 5 { 2 }
+    };
+    assert_eq!(guarded.unwrap(), 2);
+}
+
+#[test]
+fn any_searches_the_chain() {
+    let result: Result<u64> = handle! {
+        try {
+            handle! {
+                try { fail(StoreError::NotFound { id: 9 })? }
+                throw { "lookup failed" }
+            }?
+        }
+        catch any StoreError::NotFound { id } { *id }
+    };
+    assert_eq!(result.unwrap(), 9);
+}
+
+#[test]
+fn throw_and_inspect_take_patterns() {
+    let mut seen = None;
+    let result: Result<u64> = handle! {
+        try { fail(StoreError::NotFound { id: 5 })? }
+        inspect StoreError::NotFound { id } { seen = Some(*id); }
+        throw StoreError::NotFound { id } { format!("no record {}", id) }
+    };
+    assert_eq!(seen, Some(5));
+    assert_eq!(result.unwrap_err().message(), "no record 5");
+}
+
+#[tokio::test]
+async fn async_catch_takes_patterns() {
+    let result: Result<u64> = handle! {
+        async try { fail(StoreError::Conflict(2, 3))? }
+        catch StoreError::NotFound { .. } { 0 }
+        catch StoreError::Conflict(_, b) { *b }
+    };
+    assert_eq!(result.unwrap(), 3);
+}
+
+#[test]
+fn try_for_takes_patterns() {
+    let result: Result<u64> = handle! {
+        try for id in [1u64, 2] { fail(StoreError::NotFound { id })? }
+        catch StoreError::NotFound { id } { *id + 100 }
+    };
+    assert_eq!(result.unwrap(), 102);
+}
@@ -0,0 +1,121 @@
+//! Per-call-site circuit breaking with `ratelimit_propagate` under the `circuit-breaker` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "circuit-breaker")]
+
+use handle_this::circuit::{self, CircuitState};
+use handle_this::{handle, Result};
+use std::time::Duration;
+
+fn flaky(fail: bool, runs: &mut u32) -> Result<&'static str> {
+    *runs += 1;
+    if fail {
+        Err("upstream down")?;
+    }
+    Ok("fresh")
+}
+
+#[test]
+fn body_skipped_after_consecutive_failures() {
+    let mut runs = 0;
+    let call = |runs: &mut u32| -> Result<&'static str> {
+        handle! {
+            try { flaky(true, runs)? }
+            ratelimit_propagate 3/min else { "cached" }
+        }
+    };
+
+    for _ in 0..3 {
+        assert!(call(&mut runs).is_err());
+    }
+    assert_eq!(runs, 3);
+
+    assert_eq!(call(&mut runs).unwrap(), "cached");
+    assert_eq!(call(&mut runs).unwrap(), "cached");
+    assert_eq!(runs, 3);
+}
+
+#[test]
+fn success_resets_failure_count() {
+    let mut runs = 0;
+    let call = |fail: bool, runs: &mut u32| -> Result<&'static str> {
+        handle! {
+            try { flaky(fail, runs)? }
+            ratelimit_propagate 2/min else { "cached" }
+        }
+    };
+
+    assert!(call(true, &mut runs).is_err());
+    assert_eq!(call(false, &mut runs).unwrap(), "fresh");
+    assert!(call(true, &mut runs).is_err());
+    assert_eq!(call(false, &mut runs).unwrap(), "fresh");
+    assert_eq!(runs, 4);
+}
+
+#[test]
+fn half_open_trial_closes_or_reopens() {
+    let mut runs = 0;
+    let call = |fail: bool, runs: &mut u32| -> Result<&'static str> {
+        handle! {
+            try { flaky(fail, runs)? }
+            ratelimit_propagate 1/ms else { "cached" }
+        }
+    };
+
+    assert!(call(true, &mut runs).is_err());
+    assert_eq!(call(true, &mut runs).unwrap(), "cached");
+
+    // Cooldown over: the trial fails and the circuit reopens
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(call(true, &mut runs).is_err());
+    assert_eq!(call(false, &mut runs).unwrap(), "cached");
+
+    // Next trial succeeds and the circuit closes
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(call(false, &mut runs).unwrap(), "fresh");
+    assert_eq!(call(false, &mut runs).unwrap(), "fresh");
+    assert_eq!(runs, 4);
+}
+
+#[test]
+fn handlers_see_body_errors_while_closed() {
+    let call = || -> Result<&'static str> {
+        handle! {
+            try { Err("boom")? }
+            ratelimit_propagate 5/min else { "cached" }
+            catch e { if e.message() == "boom" { "caught" } else { "other" } }
+        }
+    };
+    assert_eq!(call().unwrap(), "caught");
+}
+
+#[tokio::test]
+async fn nested_and_async() {
+    let nested = || -> Result<&'static str> {
+        handle! {
+            try {
+                try { Err("down")? } ratelimit_propagate 1/min else { "cached" }
+            }
+        }
+    };
+    assert!(nested().is_err());
+    assert_eq!(nested().unwrap(), "cached");
+
+    let attempt = || async {
+        handle! { async try { Err("down")? } ratelimit_propagate 1/min else { 0 } }
+    };
+    assert!(attempt().await.is_err());
+    assert_eq!(attempt().await.unwrap(), 0);
+}
+
+#[test]
+fn registry_api() {
+    let site = "tests/circuit_breaker.rs:direct";
+    assert_eq!(circuit::state(site), CircuitState::Closed);
+    assert!(circuit::admit(site, Duration::from_secs(60)));
+    circuit::record_failure(site, 1, Duration::from_secs(60));
+    assert_eq!(circuit::state(site), CircuitState::Open);
+    assert!(!circuit::admit(site, Duration::from_secs(60)));
+    circuit::reset(site);
+    assert_eq!(circuit::state(site), CircuitState::Closed);
+}
@@ -0,0 +1,72 @@
+//! `classify` attaches a computed category as a typed field.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    Transient,
+    Fatal,
+}
+
+fn fail(kind: io::ErrorKind) -> Result<i32> {
+    Err(io::Error::new(kind, "boom"))?
+}
+
+fn categorize(e: &Handled) -> Category {
+    match e.downcast_ref::<io::Error>().map(|e| e.kind()) {
+        Some(io::ErrorKind::TimedOut) => Category::Transient,
+        _ => Category::Fatal,
+    }
+}
+
+#[test]
+fn classify_attaches_field() {
+    let result: Result<i32> = handle! {
+        try { fail(io::ErrorKind::TimedOut)? }
+        classify |e| -> Category { categorize(e) }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.field::<Category>(), Some(&Category::Transient));
+    assert_eq!(err.message(), "boom");
+}
+
+#[test]
+fn classify_visible_to_later_handlers() {
+    let result: Result<i32> = handle! {
+        try { fail(io::ErrorKind::NotFound)? }
+        classify |e| { categorize(e) }
+        catch e { if e.field::<Category>() == Some(&Category::Fatal) { 1 } else { 0 } }
+    };
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[test]
+fn classify_guard_skips() {
+    let result: Result<i32> = handle! {
+        try { fail(io::ErrorKind::NotFound)? }
+        classify |e| when e.message().is_empty() { Category::Fatal }
+    };
+    assert!(result.unwrap_err().field::<Category>().is_none());
+}
+
+#[test]
+fn classify_in_retry_loop() {
+    let mut attempts = 0;
+    let result: Result<i32> = handle! {
+        try while attempts < 2 { attempts += 1; fail(io::ErrorKind::TimedOut)? }
+        classify |e| -> Category { categorize(e) }
+    };
+    assert_eq!(result.unwrap_err().field::<Category>(), Some(&Category::Transient));
+}
+
+#[tokio::test]
+async fn classify_async() {
+    let result: Result<i32> = handle! {
+        async try { fail(io::ErrorKind::TimedOut)? }
+        classify |e| -> Category { categorize(e) }
+    };
+    assert_eq!(result.unwrap_err().field::<Category>(), Some(&Category::Transient));
+}
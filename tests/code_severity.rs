@@ -0,0 +1,109 @@
+//! Tests for error codes and severity: `with_code`, `with_severity`, `with code "..", severity ..`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result, Severity};
+
+#[test]
+fn builders_and_accessors() {
+    let err = Handled::msg("quota").with_code("E1042").with_severity(Severity::Warn);
+    assert_eq!(err.code(), Some("E1042"));
+    assert_eq!(err.severity(), Some(Severity::Warn));
+
+    let plain = Handled::msg("plain");
+    assert_eq!(plain.code(), None);
+    assert_eq!(plain.severity(), None);
+}
+
+#[test]
+fn later_values_replace_earlier_ones() {
+    let err = Handled::msg("x")
+        .with_code("E1")
+        .with_code(String::from("E2"))
+        .with_severity(Severity::Info)
+        .with_severity(Severity::Fatal);
+    assert_eq!(err.code(), Some("E2"));
+    assert_eq!(err.severity(), Some(Severity::Fatal));
+}
+
+#[test]
+fn severity_is_ordered_and_displays_lowercase() {
+    assert!(Severity::Debug < Severity::Info);
+    assert!(Severity::Warn < Severity::Error);
+    assert!(Severity::Error < Severity::Fatal);
+    assert_eq!(Severity::Warn.to_string(), "warn");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn with_clause_sets_code_and_severity() {
+    let result: Result<i32> = handle! {
+        try { "x".parse::<i32>()? }
+        with "parsing id", code "E1042", severity Warn
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.code(), Some("E1042"));
+    assert_eq!(err.severity(), Some(Severity::Warn));
+    assert!(err.frames().any(|f| f.context == Some("parsing id")));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn with_clause_after_kv_or_alone() {
+    const NOT_FOUND: &str = "E404";
+    let result: Result<i32> = handle! {
+        try { Err("missing")? }
+        with { id: 7 }, code NOT_FOUND
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.code(), Some("E404"));
+    assert!(err.frames().any(|f| f.attachments().any(|(k, _)| k == "id")));
+
+    let level = Severity::Error;
+    let result: Result<i32> = handle! {
+        try { Err("bad")? }
+        with severity level
+    };
+    assert_eq!(result.unwrap_err().severity(), Some(Severity::Error));
+
+    let result: Result<i32> = handle! {
+        try { Err("bad")? }
+        with severity Severity::Fatal, code format!("E{}", 500)
+    };
+    let err = result.unwrap_err();
+    assert_eq!((err.code(), err.severity()), (Some("E500"), Some(Severity::Fatal)));
+}
+
+#[test]
+fn handlers_see_the_code() {
+    let result: Result<&str> = handle! {
+        try { Err("busy")? }
+        with code "E503"
+        catch e when e.code() == Some("E503") { "retry later" }
+    };
+    assert_eq!(result.unwrap(), "retry later");
+}
+
+#[test]
+fn survives_throw() {
+    let result: Result<i32> = handle! {
+        try { Err("disk full")? }
+        with code "E507", severity Fatal
+        throw e { format!("save failed: {}", e.message()) }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "save failed: disk full");
+    assert_eq!(err.code(), Some("E507"));
+    assert_eq!(err.severity(), Some(Severity::Fatal));
+}
+
+#[test]
+fn nested_with_code() {
+    let result: Result<i32> = handle! {
+        try {
+            let n = try { "x".parse::<i32>()? } with code "E400";
+            n + 1
+        }
+    };
+    assert_eq!(result.unwrap_err().code(), Some("E400"));
+}
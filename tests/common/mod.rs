@@ -0,0 +1,29 @@
+//! Shared fixtures for the hand-written feature tests.
+//!
+//! Include with `mod common;` and import what the file needs. Not every test
+//! uses every fixture, hence the `dead_code` allow.
+
+#![allow(dead_code, clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::io;
+
+/// Parses an `i32` through `handle!`, so failures carry a frame.
+pub fn parse(s: &str) -> Result<i32> {
+    handle! { try { s.parse::<i32>()? } }
+}
+
+/// Fails with a bare `Handled` message of `"boom"` and no frames.
+pub fn fail() -> Result<()> {
+    Err(Handled::msg("boom"))
+}
+
+/// Fails with an `io::ErrorKind::Other` error reading `"boom"`.
+pub fn io_fail<T>() -> std::result::Result<T, io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "boom"))
+}
+
+/// Fails with an `io::ErrorKind::NotFound` error reading `"no such file"`.
+pub fn missing<T>() -> std::result::Result<T, io::Error> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "no such file"))
+}
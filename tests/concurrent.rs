@@ -0,0 +1,200 @@
+//! Tests for `async try concurrent(N) for/any/all x in iter { }`.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "futures")]
+
+use handle_this::{handle, Handled, Result};
+use std::cell::Cell;
+
+/// Tracks how many bodies are in flight and the highest count seen.
+#[derive(Default)]
+struct InFlight {
+    now: Cell<usize>,
+    peak: Cell<usize>,
+}
+
+impl InFlight {
+    async fn run<T>(&self, value: T) -> T {
+        self.now.set(self.now.get() + 1);
+        self.peak.set(self.peak.get().max(self.now.get()));
+        for _ in 0..3 {
+            tokio::task::yield_now().await;
+        }
+        self.now.set(self.now.get() - 1);
+        value
+    }
+}
+
+fn parse(s: &str) -> std::result::Result<i32, std::num::ParseIntError> {
+    s.parse()
+}
+
+#[tokio::test]
+async fn all_overlaps_up_to_the_limit() {
+    let tracker = InFlight::default();
+    let result: Result<Vec<i32>> = handle! {
+        async try concurrent(3) all x in 1..=7 {
+            tracker.run(x * 10).await
+        }
+    };
+    assert_eq!(result.unwrap(), vec![10, 20, 30, 40, 50, 60, 70]);
+    assert_eq!(tracker.peak.get(), 3);
+    assert_eq!(tracker.now.get(), 0);
+}
+
+#[tokio::test]
+async fn limit_of_one_is_sequential() {
+    let tracker = InFlight::default();
+    let result: Result<Vec<i32>> = handle! {
+        async try concurrent(1) all x in vec![1, 2, 3] {
+            tracker.run(x).await
+        }
+    };
+    assert_eq!(result.unwrap(), vec![1, 2, 3]);
+    assert_eq!(tracker.peak.get(), 1);
+}
+
+#[tokio::test]
+async fn all_keeps_input_order_when_completion_order_differs() {
+    // Earlier items yield more often, so later items finish first
+    let result: Result<Vec<usize>> = handle! {
+        async try concurrent(4) all n in [4usize, 3, 2, 1] {
+            for _ in 0..n {
+                tokio::task::yield_now().await;
+            }
+            n
+        }
+    };
+    assert_eq!(result.unwrap(), vec![4, 3, 2, 1]);
+}
+
+#[derive(Debug)]
+struct Bad(&'static str);
+
+impl std::fmt::Display for Bad {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bad {}", self.0)
+    }
+}
+
+impl std::error::Error for Bad {}
+
+fn check(s: &'static str) -> std::result::Result<&'static str, Bad> {
+    if s.starts_with('!') { Err(Bad(s)) } else { Ok(s) }
+}
+
+fn bad_names(err: &Handled) -> Vec<&'static str> {
+    err.chain_all::<Bad>().into_iter().map(|b| b.0).collect()
+}
+
+#[tokio::test]
+async fn all_chains_every_error_in_input_order() {
+    let items = ["!a", "b", "!c", "!d"];
+    let tracker = InFlight::default();
+    // Later items fail first; the chain still matches the sequential `try all`
+    let concurrent: Result<Vec<&str>> = handle! {
+        async try concurrent(4) all s in items {
+            let n = 4 - items.iter().position(|i| *i == s).unwrap();
+            for _ in 0..n {
+                tokio::task::yield_now().await;
+            }
+            tracker.run(()).await;
+            check(s)?
+        }
+    };
+    let sequential: Result<Vec<&str>> = handle! {
+        try all s in items { check(s)? }
+    };
+    let names = bad_names(&concurrent.unwrap_err());
+    assert_eq!(names.len(), 3);
+    assert_eq!(names, bad_names(&sequential.unwrap_err()));
+    assert!(tracker.peak.get() > 1);
+}
+
+#[tokio::test]
+async fn for_returns_first_success_and_drops_the_rest() {
+    let started = Cell::new(0);
+    let result: Result<i32> = handle! {
+        async try concurrent(2) for s in ["x", "7", "8", "9"] {
+            started.set(started.get() + 1);
+            tokio::task::yield_now().await;
+            parse(s)?
+        }
+    };
+    assert_eq!(result.unwrap(), 7);
+    // The winner was found before the last item was ever started
+    assert!(started.get() < 4);
+}
+
+#[tokio::test]
+async fn any_is_an_alias_for_for() {
+    let result: Result<i32> = handle! {
+        async try concurrent(2) any s in ["a", "b", "5"] { parse(s)? }
+    };
+    assert_eq!(result.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn for_aggregates_errors_when_all_fail() {
+    let result: Result<i32> = handle! {
+        async try concurrent(8) for s in ["a", "b", "c"] { parse(s)? }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.chain_all::<std::num::ParseIntError>().len(), 3);
+}
+
+#[tokio::test]
+async fn for_with_empty_iterator_fails() {
+    let result: Result<i32> = handle! {
+        async try concurrent(2) for s in Vec::<&str>::new() { parse(s)? }
+    };
+    assert!(result.unwrap_err().message().contains("empty iterator"));
+}
+
+#[tokio::test]
+async fn handlers_see_the_chained_error() {
+    let seen = Cell::new(0);
+    let result: Result<i32> = handle! {
+        async try concurrent(2) for s in ["a", "b"] { parse(s)? }
+        inspect e { seen.set(e.chain_all::<std::num::ParseIntError>().len()); }
+        catch std::num::ParseIntError(_) { -1 }
+    };
+    assert_eq!(result.unwrap(), -1);
+    assert_eq!(seen.get(), 2);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn with_and_finally_apply() {
+    let cleaned = Cell::new(false);
+    let result: Result<Vec<i32>> = handle! {
+        async try concurrent(2) all s in ["1", "z"] { parse(s)? }
+        with "parsing batch"
+        finally { cleaned.set(true); }
+    };
+    let err: Handled = result.unwrap_err();
+    assert!(err.to_string().contains("parsing batch"));
+    assert!(cleaned.get());
+}
+
+#[tokio::test]
+async fn limit_can_be_an_expression() {
+    let width = 2;
+    let tracker = InFlight::default();
+    let result: Result<Vec<i32>> = handle! {
+        async try concurrent(width * 2) all x in 0..8 { tracker.run(x).await }
+    };
+    assert_eq!(result.unwrap().len(), 8);
+    assert_eq!(tracker.peak.get(), 4);
+}
+
+#[tokio::test]
+async fn nested_in_async_try_propagates() {
+    let result: Result<i32> = handle! {
+        async try {
+            let parsed = async try concurrent(2) all s in ["1", "2", "3"] { parse(s)? };
+            parsed.iter().sum::<i32>()
+        }
+    };
+    assert_eq!(result.unwrap(), 6);
+}
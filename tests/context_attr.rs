@@ -0,0 +1,115 @@
+//! Tests for the `#[context(...)]` function attribute.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{context, traced, Handled, Result};
+
+fn parse(s: &str) -> std::result::Result<u32, std::num::ParseIntError> {
+    s.parse()
+}
+
+#[context("parsing port")]
+fn port(s: &str) -> Result<u32> {
+    if s.is_empty() {
+        return Ok(80);
+    }
+    let port = parse(s)?;
+    Ok(port)
+}
+
+#[context("loading user", { id: id })]
+fn user(id: u32) -> Result<u32> {
+    Err(Handled::msg("no such user"))
+}
+
+#[context("fetching")]
+async fn fetch(s: &str) -> Result<u32> {
+    tokio::task::yield_now().await;
+    Ok(parse(s)?)
+}
+
+#[traced]
+#[context("outer step")]
+fn outer(s: &str) -> Result<u32> {
+    Ok(port(s)? + 1)
+}
+
+#[context("listing ports")]
+fn ports(list: &str) -> Result<impl Iterator<Item = u32>> {
+    let ports = list.split(',').map(parse).collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(ports.into_iter())
+}
+
+#[context("fetching ports")]
+async fn fetch_ports(list: &str) -> Result<impl Iterator<Item = u32>> {
+    tokio::task::yield_now().await;
+    if list.is_empty() {
+        return Ok(Vec::new().into_iter());
+    }
+    ports(list).map(|ports| ports.collect::<Vec<_>>().into_iter())
+}
+
+struct Config(String);
+
+impl Config {
+    #[context("reading config port")]
+    fn port(&self) -> Result<u32> {
+        port(&self.0)
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn adds_context_to_errors() {
+    let err = port("x").unwrap_err();
+    assert_eq!(err.frames().last().unwrap().context, Some("parsing port"));
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[test]
+fn ok_and_early_return_pass_through() {
+    assert_eq!(port("8080").unwrap(), 8080);
+    assert_eq!(port("").unwrap(), 80);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn takes_a_full_with_clause() {
+    let err = user(7).unwrap_err();
+    assert_eq!(err.frames().last().unwrap().context, Some("loading user"));
+    assert_eq!(err.get_kv("id"), Some(&handle_this::Value::from(7u32)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn async_fns() {
+    let err = fetch("x").await.unwrap_err();
+    assert_eq!(err.frames().last().unwrap().context, Some("fetching"));
+    assert_eq!(fetch("3").await.unwrap(), 3);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn nests_and_works_on_methods() {
+    let err = outer("x").unwrap_err();
+    let contexts: Vec<_> = err.frames().filter_map(|f| f.context).collect();
+    assert_eq!(contexts, ["parsing port", "outer step"]);
+    assert_eq!(err.frames().last().unwrap().function, Some("context_attr::outer"));
+
+    let err = Config("x".into()).port().unwrap_err();
+    assert_eq!(err.frames().last().unwrap().context, Some("reading config port"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn impl_trait_returns() {
+    assert_eq!(ports("1,2").unwrap().sum::<u32>(), 3);
+    let err = ports("1,x").err().unwrap();
+    assert_eq!(err.frames().last().unwrap().context, Some("listing ports"));
+
+    assert_eq!(fetch_ports("").await.unwrap().count(), 0);
+    let err = fetch_ports("x").await.err().unwrap();
+    let contexts: Vec<_> = err.frames().filter_map(|f| f.context).collect();
+    assert_eq!(contexts, ["listing ports", "fetching ports"]);
+}
@@ -0,0 +1,56 @@
+//! `with |e| expr`: context message computed from the error being wrapped.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use std::cell::Cell;
+use std::io;
+
+fn fail() -> Result<()> {
+    Err(Handled::msg("boom").frame("inner.rs", 1, 1).frame("inner.rs", 2, 1))
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn closure_sees_the_wrapped_error() {
+    let result: Result<()> = handle! {
+        try { fail()? } with |e| format!("failed after {} frames: {}", e.depth(), e.message())
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.last_frame().unwrap().context, Some("failed after 3 frames: boom"));
+}
+
+#[test]
+fn skipped_on_success() {
+    let calls = Cell::new(0);
+    let result: Result<i32> = handle! {
+        try { 5 } with |_e| { calls.set(calls.get() + 1); "unused" }
+    };
+    assert_eq!(result.unwrap(), 5);
+    assert_eq!(calls.get(), 0);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn combines_with_kv_and_handlers() {
+    let result: Result<&str> = handle! {
+        try { Err(io::Error::new(io::ErrorKind::NotFound, "gone"))? }
+        catch io::Error(e) when e.kind() == io::ErrorKind::TimedOut { "retry" }
+        with |e| format!("kind {}", e.kind()), { table: "users" }, code "E404"
+    };
+    let err = result.unwrap_err();
+    let frame = err.last_frame().unwrap();
+    assert_eq!(frame.context, Some("kind not found"));
+    assert_eq!(err.get_kv("table").unwrap(), "users");
+    assert_eq!(err.code(), Some("E404"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn async_try() {
+    let result: Result<()> = handle! {
+        async try { fail()? } with |e| format!("from {}", e.first_frame().unwrap().file)
+    };
+    assert_eq!(result.unwrap_err().last_frame().unwrap().context, Some("from inner.rs"));
+}
@@ -0,0 +1,91 @@
+//! `continue_with` inside user-written loops.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::parse;
+use handle_this::{handle, Handled, Result};
+
+#[test]
+fn continue_with_skips_failed_iterations() -> Result<()> {
+    let mut total = 0;
+    let mut skipped: Vec<Handled> = Vec::new();
+
+    for s in ["1", "x", "2", "y", "3"] {
+        let v: Result<i32> = handle! {
+            try { parse(s)? }
+            continue_with |e| { skipped.push(e) }
+        };
+        total += v?;
+    }
+
+    assert_eq!(total, 6);
+    assert_eq!(skipped.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn continue_with_after_typed_catch() {
+    let mut seen = Vec::new();
+
+    for s in ["1", "bad", "3"] {
+        let v: Result<i32> = handle! {
+            try { parse(s)? }
+            catch std::io::Error(_) { -1 }
+            continue_with { seen.push(s) }
+        };
+        assert_ne!(v.unwrap(), -1);
+    }
+
+    assert_eq!(seen, ["bad"]);
+}
+
+#[test]
+fn continue_with_guard_falls_through() {
+    let mut outcomes = Vec::new();
+    let mut skipped = Vec::new();
+
+    for s in ["1", "x"] {
+        let r: Result<i32> = handle! {
+            try { parse(s)? }
+            continue_with |e| when e.message().is_empty() { skipped.push(e) }
+        };
+        outcomes.push(if r.is_ok() { "ok" } else { "err" });
+    }
+
+    assert_eq!(outcomes, ["ok", "err"]);
+    assert!(skipped.is_empty());
+}
+
+#[test]
+fn continue_with_guard_taken() {
+    let mut total = 0;
+    let mut skipped = Vec::new();
+
+    for s in ["1", "x", "2"] {
+        let v: Result<i32> = handle! {
+            try { parse(s)? }
+            continue_with |e| when e.message().contains("invalid digit") { skipped.push(e) }
+        };
+        total += v.unwrap();
+    }
+
+    assert_eq!(total, 3);
+    assert_eq!(skipped.len(), 1);
+}
+
+#[test]
+fn continue_with_option_body_recovers_on_some() {
+    let mut seen = Vec::new();
+
+    for s in ["1", "", "x", "3"] {
+        let v: Result<i32> = handle! {
+            try { parse(s)? }
+            continue_with |e| { if s.is_empty() { Some(0) } else { drop(e); None } }
+        };
+        seen.push(v.unwrap());
+    }
+
+    assert_eq!(seen, [1, 0, 3]);
+}
@@ -0,0 +1,67 @@
+//! Tests for the `convert Type` clause.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::parse;
+use handle_this::{handle, Handled, Result};
+use std::fmt;
+
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    cause: Handled,
+}
+
+impl From<Handled> for ApiError {
+    fn from(cause: Handled) -> Self {
+        Self { status: 500, cause }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "api error {}: {}", self.status, self.cause.message())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[test]
+fn propagated_error_downcasts_to_target() {
+    let err = handle! { try { parse("x")? } convert ApiError }.unwrap_err();
+    let api = err.downcast_ref::<ApiError>().unwrap();
+    assert_eq!(api.status, 500);
+    assert!(api.cause.message().contains("invalid digit"));
+    assert!(err.message().starts_with("api error 500"));
+}
+
+#[test]
+fn angle_bracket_form_and_success() {
+    let ok = handle! { try { parse("3")? } convert<ApiError> };
+    assert_eq!(ok.unwrap(), 3);
+    let err = handle! { try { parse("x")? } convert<ApiError> }.unwrap_err();
+    assert!(err.downcast_ref::<ApiError>().is_some());
+}
+
+#[test]
+fn later_handlers_see_converted_error() {
+    let status = handle! {
+        try { parse("x")? }
+        convert ApiError
+        catch ApiError(e) { i32::from(e.status) }
+    };
+    assert_eq!(status.unwrap(), 500);
+}
+
+#[test]
+fn nested_and_loop() {
+    let result: Result<i32> = handle! {
+        try { try { parse("x")? } convert ApiError }
+    };
+    assert!(result.unwrap_err().downcast_ref::<ApiError>().is_some());
+
+    let err = handle! { try for s in ["a", "b"] { parse(s)? } convert ApiError }.unwrap_err();
+    assert!(err.downcast_ref::<ApiError>().is_some());
+}
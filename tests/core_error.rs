@@ -0,0 +1,56 @@
+//! Tests for `CoreError`, the error trait typed catches and chain search use.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, CoreError, Handled, Result};
+use std::fmt;
+
+#[derive(Debug)]
+struct SensorFault(u8);
+
+impl fmt::Display for SensorFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sensor {} faulted", self.0)
+    }
+}
+
+impl CoreError for SensorFault {}
+
+fn read(id: u8) -> std::result::Result<u8, SensorFault> {
+    Err(SensorFault(id))
+}
+
+#[test]
+fn core_error_is_the_std_trait_with_std() {
+    let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(SensorFault(1));
+    let err = Handled::wrap_box(boxed);
+    assert_eq!(err.downcast_ref::<SensorFault>().map(|e| e.0), Some(1));
+}
+
+#[test]
+fn typed_catch_matches_a_core_error() {
+    let result: Result<u8> = handle! {
+        try { read(3)? }
+        catch SensorFault(e) when e.0 > 2 { e.0 * 10 }
+    };
+    assert_eq!(result.unwrap(), 30);
+}
+
+#[test]
+fn chain_search_finds_core_errors() {
+    let err = Handled::msg("all sensors down")
+        .chain_after(Handled::wrap(SensorFault(2)))
+        .chain_after(Handled::wrap(SensorFault(5)));
+    assert_eq!(err.chain_any::<SensorFault>().map(|e| e.0), Some(5));
+    let ids: Vec<u8> = err.chain_all::<SensorFault>().iter().map(|e| e.0).collect();
+    assert_eq!(ids, [5, 2]);
+}
+
+#[test]
+fn catch_all_collects_every_attempt() {
+    let result: Result<u8> = handle! {
+        try any id in [1u8, 2, 3] { read(id)? }
+        catch all SensorFault |faults| { faults.len() as u8 }
+    };
+    assert_eq!(result.unwrap(), 3);
+}
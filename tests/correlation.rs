@@ -0,0 +1,54 @@
+//! Tests for `with_correlation` and `Handled::correlation_id`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+
+fn fail() -> Result<()> {
+    handle! { try { Err("connection reset")? } }
+}
+
+#[test]
+fn clause_attaches_id() {
+    let err = handle! { try { fail()? } with_correlation("req-1") }.unwrap_err();
+    assert_eq!(err.correlation_id(), Some("req-1"));
+}
+
+#[test]
+fn id_survives_throw() {
+    let id = String::from("req-2");
+    let err = handle! {
+        try { fail()? }
+        with_correlation(id)
+        throw e { format!("upstream: {}", e) }
+    }
+    .unwrap_err();
+    assert!(err.message().starts_with("upstream"));
+    assert_eq!(err.correlation_id(), Some("req-2"));
+}
+
+#[test]
+fn nested_try_with_correlation() {
+    let result: Result<()> = handle! {
+        try {
+            try { fail()? } with_correlation("inner")
+        }
+    };
+    assert_eq!(result.unwrap_err().correlation_id(), Some("inner"));
+}
+
+#[test]
+fn in_loop_handlers() {
+    let err = handle! {
+        try for x in [1, 2] { Err(format!("bad {}", x))? } with_correlation("loop")
+    }
+    .unwrap_err();
+    assert_eq!(err.correlation_id(), Some("loop"));
+}
+
+#[test]
+fn latest_id_wins_and_none_by_default() {
+    assert_eq!(Handled::msg("x").correlation_id(), None);
+    let err = Handled::msg("x").with_correlation("a").with_correlation("b");
+    assert_eq!(err.correlation_id(), Some("b"));
+}
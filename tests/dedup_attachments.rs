@@ -0,0 +1,44 @@
+//! `Handled::dedup_attachments` removes repeated key-value pairs.
+
+#![allow(clippy::result_large_err)]
+#![cfg(not(feature = "no-trace"))]
+
+use handle_this::{handle, Handled, Result, Value};
+
+fn attachments(err: &Handled) -> Vec<(String, Value)> {
+    err.frames()
+        .flat_map(|f| f.attachments().map(|(k, v)| (k.to_string(), v.clone())).collect::<Vec<_>>())
+        .collect()
+}
+
+fn request() -> Result<()> {
+    handle! { try { Err("timeout")? } with { code: 500 } }
+}
+
+#[test]
+fn reattached_code_kept_once() {
+    let result: Result<()> = handle! { try { request()? } with { code: 500, attempt: 2 } };
+    let mut err = result.unwrap_err();
+    assert_eq!(attachments(&err).iter().filter(|(k, _)| k == "code").count(), 2);
+
+    err.dedup_attachments();
+    assert_eq!(
+        attachments(&err),
+        [("code".to_string(), Value::Int(500)), ("attempt".to_string(), Value::Int(2))]
+    );
+}
+
+#[test]
+fn same_key_different_value_kept() {
+    let result: Result<()> = handle! { try { request()? } with { code: 503 } };
+    let mut err = result.unwrap_err();
+    err.dedup_attachments();
+    assert_eq!(attachments(&err).len(), 2);
+}
+
+#[test]
+fn within_frame_duplicates_removed() {
+    let mut err = Handled::msg("x").frame("a.rs", 1, 1).kv("k", 1).kv("k", 1);
+    err.dedup_attachments();
+    assert_eq!(attachments(&err), [("k".to_string(), Value::Int(1))]);
+}
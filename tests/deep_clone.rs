@@ -0,0 +1,131 @@
+//! `Handled::deep_clone` and `Clone for Handled` under the `clone` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "clone")]
+
+use handle_this::Handled;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+struct ApiError {
+    status: u16,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "api returned {}", self.status)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+fn sample() -> Handled<ApiError> {
+    Handled::new(ApiError { status: 503 })
+        .frame("client.rs", 10, 5)
+        .ctx("calling billing")
+        .kv("attempt", 2)
+}
+
+#[test]
+fn clone_still_downcasts_to_source_type() {
+    let err = sample();
+    let copy = err.deep_clone();
+    assert_eq!(copy.source_ref(), &ApiError { status: 503 });
+
+    let erased = copy.erase();
+    assert_eq!(erased.downcast_ref::<ApiError>(), Some(&ApiError { status: 503 }));
+    // Original is untouched
+    assert_eq!(err.message(), "api returned 503");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn clones_trace_and_contexts() {
+    let copy = sample().deep_clone();
+    let frame = copy.frames().next().unwrap();
+    assert_eq!((frame.file, frame.line, frame.col), ("client.rs", 10, 5));
+    assert_eq!(frame.context, Some("calling billing"));
+    let attempt = frame.attachments().find(|(k, _)| *k == "attempt").unwrap().1;
+    assert_eq!(*attempt, 2i64);
+}
+
+#[test]
+fn clones_chain_recursively() {
+    let err = Handled::msg("top")
+        .chain_after(Handled::msg("second").frame("b.rs", 2, 1).chain_after(Handled::msg("first")))
+        .map_err(|_| ApiError { status: 502 });
+    let copy = err.deep_clone().erase();
+    assert_eq!(copy.context_messages(), ["api returned 502", "second", "first"]);
+}
+
+#[derive(Debug, PartialEq)]
+struct Opaque;
+
+#[test]
+fn only_the_crates_own_fields_are_cloned() {
+    let err = sample().tag("retryable").set_field(Opaque);
+    let copy = err.deep_clone();
+    assert!(copy.has_tag("retryable"));
+    assert_eq!(copy.field::<Opaque>(), None);
+}
+
+#[test]
+fn erased_clone_keeps_what_a_reporter_needs() {
+    use handle_this::{ErrorKind, Severity};
+
+    let err = Handled::msg("card declined")
+        .with_code("E402")
+        .with_severity(Severity::Warn)
+        .with_correlation("req-7")
+        .with_kind(ErrorKind::Invalid)
+        .tag("billing")
+        .in_group("payments");
+    let copy = err.clone();
+    assert_eq!(copy.code(), Some("E402"));
+    assert_eq!(copy.severity(), Some(Severity::Warn));
+    assert_eq!(copy.correlation_id(), Some("req-7"));
+    assert_eq!(copy.kind(), ErrorKind::Invalid);
+    assert!(copy.has_tag("billing"));
+    assert_eq!(copy.group(), Some("payments"));
+}
+
+#[cfg(feature = "timestamps")]
+#[test]
+fn clone_is_not_a_new_error() {
+    let err = sample().erase();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    assert_eq!(err.clone().timestamp(), err.timestamp());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn erased_clone_keeps_message_trace_and_chain() {
+    let err = sample().erase().chain_after(Handled::msg("upstream").frame("up.rs", 1, 1));
+    let copy = err.clone();
+    assert_eq!(copy.message(), "api returned 503");
+    assert_eq!(copy.context_messages(), ["api returned 503", "upstream"]);
+    assert_eq!(copy.frames().next().unwrap().context, Some("calling billing"));
+    // The source is re-boxed from its message
+    assert!(copy.downcast_ref::<ApiError>().is_none());
+    assert!(err.downcast_ref::<ApiError>().is_some());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn inspect_can_ship_a_clone_to_another_thread() {
+    use handle_this::{handle, Result};
+    use std::sync::mpsc;
+
+    let (tx, rx) = mpsc::channel::<Handled>();
+    let reporter = std::thread::spawn(move || rx.iter().map(|e| e.message().to_string()).collect::<Vec<_>>());
+
+    let result: Result<i32> = handle! {
+        try { Err(ApiError { status: 500 })? }
+        inspect e { tx.send(e.clone()).unwrap(); }
+        with "charging card"
+    };
+    drop(tx);
+
+    assert_eq!(reporter.join().unwrap(), ["api returned 500"]);
+    assert_eq!(result.unwrap_err().frames().next().unwrap().context, Some("charging card"));
+}
@@ -0,0 +1,112 @@
+//! Tests for `defer { }` inside try bodies.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::cell::RefCell;
+use std::io;
+
+fn open(log: &RefCell<Vec<String>>, name: &str, ok: bool) -> io::Result<String> {
+    if ok {
+        log.borrow_mut().push(format!("open {}", name));
+        Ok(name.to_string())
+    } else {
+        Err(io::Error::new(io::ErrorKind::NotFound, name.to_string()))
+    }
+}
+
+#[test]
+fn runs_in_reverse_order_on_success() {
+    let log = RefCell::new(Vec::new());
+    let result: Result<usize> = handle! {
+        try {
+            let a = open(&log, "a", true)?;
+            defer { log.borrow_mut().push(format!("close {}", a)) };
+            let b = open(&log, "b", true)?;
+            defer { log.borrow_mut().push(format!("close {}", b)) }
+            a.len() + b.len()
+        }
+    };
+    assert_eq!(result.unwrap(), 2);
+    assert_eq!(*log.borrow(), ["open a", "open b", "close b", "close a"]);
+}
+
+#[test]
+fn runs_when_the_body_fails_midway() {
+    let log = RefCell::new(Vec::new());
+    let result: Result<usize> = handle! {
+        try {
+            let a = open(&log, "a", true)?;
+            defer { log.borrow_mut().push(format!("close {}", a)) };
+            let b = open(&log, "b", false)?;
+            defer { log.borrow_mut().push(format!("close {}", b)) };
+            a.len() + b.len()
+        }
+    };
+    assert!(result.is_err());
+    // Only defers that were reached run
+    assert_eq!(*log.borrow(), ["open a", "close a"]);
+}
+
+#[test]
+fn runs_before_handlers() {
+    let log = RefCell::new(Vec::new());
+    let result: Result<&str> = handle! {
+        try {
+            defer { log.borrow_mut().push("deferred".to_string()) };
+            open(&log, "x", false)?;
+            "unreachable"
+        }
+        catch io::Error(_) {
+            log.borrow_mut().push("caught".to_string());
+            "recovered"
+        }
+        finally {
+            log.borrow_mut().push("finally".to_string());
+        }
+    };
+    assert_eq!(result.unwrap(), "recovered");
+    assert_eq!(*log.borrow(), ["deferred", "caught", "finally"]);
+}
+
+#[test]
+fn value_of_the_block_is_discarded() {
+    let log = RefCell::new(Vec::new());
+    let result: Result<i32> = handle! {
+        try {
+            defer { open(&log, "flush", false) };
+            1
+        }
+    };
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[test]
+fn scoped_to_the_enclosing_block() {
+    let log = RefCell::new(Vec::new());
+    let result: Result<()> = handle! {
+        try {
+            for i in 0..2 {
+                defer { log.borrow_mut().push(format!("end {}", i)) };
+                log.borrow_mut().push(format!("start {}", i));
+            }
+        }
+    };
+    result.unwrap();
+    assert_eq!(*log.borrow(), ["start 0", "end 0", "start 1", "end 1"]);
+}
+
+#[tokio::test]
+async fn works_in_async_bodies() {
+    let log = RefCell::new(Vec::new());
+    let log_ref = &log;
+    let result: Result<usize> = handle! {
+        async try {
+            let a = open(log_ref, "a", true)?;
+            defer { log_ref.borrow_mut().push(format!("close {}", a)) };
+            async { a.len() }.await
+        }
+    };
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(*log.borrow(), ["open a", "close a"]);
+}
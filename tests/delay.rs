@@ -0,0 +1,91 @@
+//! Tests for `catch e delay d { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::io;
+use std::time::{Duration, Instant};
+
+const DELAY: Duration = Duration::from_millis(50);
+
+fn fail() -> std::result::Result<i32, io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "busy"))
+}
+
+#[test]
+fn delay_before_catch_body() {
+    let start = Instant::now();
+    let result: Result<i32> = handle! {
+        try { fail()? }
+        catch e delay DELAY { e.message().len() as i32 }
+    };
+    assert_eq!(result.unwrap(), 4);
+    assert!(start.elapsed() >= DELAY);
+}
+
+#[test]
+fn delay_without_binding() {
+    let start = Instant::now();
+    let value = handle! {
+        try -> i32 { fail()? }
+        catch delay Duration::from_millis(50) { -1 }
+    };
+    assert_eq!(value, -1);
+    assert!(start.elapsed() >= DELAY);
+}
+
+#[test]
+fn typed_catch_with_delay_and_guard() {
+    let start = Instant::now();
+    let result: Result<i32> = handle! {
+        try { fail()? }
+        catch io::Error(e) delay DELAY when e.kind() == io::ErrorKind::Other { 1 }
+    };
+    assert_eq!(result.unwrap(), 1);
+    assert!(start.elapsed() >= DELAY);
+}
+
+#[test]
+fn no_delay_when_clause_does_not_match() {
+    let start = Instant::now();
+    let matches = false;
+    let result: Result<i32> = handle! {
+        try { fail()? }
+        catch io::Error(_) delay Duration::from_secs(10) when matches { 1 }
+        catch { 2 }
+    };
+    assert_eq!(result.unwrap(), 2);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn no_delay_on_success() {
+    let start = Instant::now();
+    let result: Result<i32> = handle! {
+        try { 3 }
+        catch delay Duration::from_secs(10) { 0 }
+    };
+    assert_eq!(result.unwrap(), 3);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn async_delay_yields() {
+    let start = Instant::now();
+    let result: Result<i32> = handle! {
+        async try { fail()? }
+        catch _ delay DELAY { 7 }
+    };
+    assert_eq!(result.unwrap(), 7);
+    assert!(start.elapsed() >= DELAY);
+}
+
+#[tokio::test]
+async fn async_delay_runs_before_the_body() {
+    let start = Instant::now();
+    let result: Result<Duration> = handle! {
+        async try { fail()?; Duration::ZERO }
+        catch io::Error(_) delay DELAY { start.elapsed() }
+    };
+    assert!(result.unwrap() >= DELAY);
+}
@@ -0,0 +1,102 @@
+//! `#[derive(HandleThis)]` on domain error types.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, HandleThis, Handled, IntoValue, Result, Value};
+use std::fmt;
+
+#[derive(Debug, thiserror::Error, HandleThis)]
+enum ApiError {
+    #[error("not found")]
+    NotFound,
+    #[error("rate limited for {0}s")]
+    RateLimited(u32),
+    #[error("bad field {name}")]
+    BadField { name: &'static str },
+}
+
+fn lookup(id: u32) -> Result<&'static str> {
+    match id {
+        0 => Err(ApiError::NotFound)?,
+        1 => Err(ApiError::RateLimited(30))?,
+        _ => Ok("found"),
+    }
+}
+
+#[test]
+fn question_mark_converts_outside_handle() {
+    assert_eq!(lookup(2).unwrap(), "found");
+    let err = lookup(1).unwrap_err();
+    assert_eq!(err.message(), "rate limited for 30s");
+    assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::RateLimited(30))));
+}
+
+#[test]
+fn typed_catch_sees_the_derived_type() {
+    let result: Result<u32> = handle! {
+        try { lookup(0)?.len() as u32 }
+        catch ApiError(e) when matches!(e, ApiError::NotFound) { 0 }
+    };
+    assert_eq!(result.unwrap(), 0);
+}
+
+#[test]
+fn unit_variants_attach_by_name() {
+    assert!(matches!(ApiError::NotFound.into_value(), Value::String(s) if s == "NotFound"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn data_variants_attach_by_display() {
+    assert_eq!(ApiError::RateLimited(5).into_value(), "rate limited for 5s");
+    assert_eq!(ApiError::BadField { name: "age" }.into_value(), "bad field age");
+
+    let err = Handled::msg("rejected").frame("src/api.rs", 3, 1).kv("cause", ApiError::NotFound);
+    let (key, value) = err.frames().next().unwrap().attachments().next().unwrap();
+    assert_eq!((key, value), ("cause", &Value::String("NotFound".into())));
+}
+
+/// No `Error` impl of its own; `#[handle_this(error)]` adds one.
+#[derive(Debug, HandleThis)]
+#[handle_this(error)]
+struct Timeout {
+    secs: u64,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {}s", self.secs)
+    }
+}
+
+#[test]
+fn error_option_implements_error() {
+    fn wait() -> Result<()> {
+        Err(Timeout { secs: 3 })?
+    }
+    let err = wait().unwrap_err();
+    assert_eq!(err.message(), "timed out after 3s");
+    assert_eq!(err.downcast_ref::<Timeout>().unwrap().secs, 3);
+    assert_eq!(Timeout { secs: 1 }.into_value(), "timed out after 1s");
+}
+
+#[derive(Debug, HandleThis)]
+#[handle_this(error)]
+enum Wrapped<T: fmt::Debug + fmt::Display + Send + Sync + 'static> {
+    Inner(T),
+}
+
+impl<T: fmt::Debug + fmt::Display + Send + Sync + 'static> fmt::Display for Wrapped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Wrapped::Inner(v) => write!(f, "wrapped {}", v),
+        }
+    }
+}
+
+#[test]
+fn generic_types_are_supported() {
+    let err: Handled = Wrapped::Inner(9).into();
+    assert_eq!(err.message(), "wrapped 9");
+}
@@ -0,0 +1,40 @@
+//! Display-only values keep their type through `Handled::from_display`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+struct Status(u16);
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "status {}", self.0)
+    }
+}
+
+fn fetch() -> Result<()> {
+    Err(Handled::from_display(Status(503)))
+}
+
+#[test]
+fn downcast_display_only_newtype() {
+    let err = fetch().unwrap_err();
+    assert_eq!(err.downcast_display::<Status>(), Some(&Status(503)));
+    assert_eq!(err.message(), "status 503");
+}
+
+#[test]
+fn downcast_display_wrong_type() {
+    let err = Handled::from_display(Status(404));
+    assert!(err.downcast_display::<String>().is_none());
+    assert!(Handled::msg("plain").downcast_display::<Status>().is_none());
+}
+
+#[test]
+fn downcast_display_survives_propagation() {
+    let result: Result<()> = handle! { try { fetch()? } with "outer" };
+    let err = result.unwrap_err();
+    assert_eq!(err.downcast_display::<Status>(), Some(&Status(503)));
+}
@@ -0,0 +1,89 @@
+//! Tests for `Handled::kind`, `register_kind_rule`, and `when kind` guards.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, register_kind_rule, ErrorKind, Handled, Result};
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct AccountLocked;
+
+impl fmt::Display for AccountLocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "account locked")
+    }
+}
+
+impl std::error::Error for AccountLocked {}
+
+fn io_err(kind: io::ErrorKind) -> Handled {
+    Handled::wrap(io::Error::new(kind, "io"))
+}
+
+#[test]
+fn std_errors_have_builtin_kinds() {
+    assert_eq!(io_err(io::ErrorKind::NotFound).kind(), ErrorKind::NotFound);
+    assert_eq!(io_err(io::ErrorKind::PermissionDenied).kind(), ErrorKind::Permission);
+    assert_eq!(io_err(io::ErrorKind::TimedOut).kind(), ErrorKind::Timeout);
+    assert_eq!(io_err(io::ErrorKind::ConnectionRefused).kind(), ErrorKind::Unavailable);
+    assert_eq!(io_err(io::ErrorKind::AlreadyExists).kind(), ErrorKind::Conflict);
+    assert_eq!(Handled::wrap("x".parse::<u8>().unwrap_err()).kind(), ErrorKind::Invalid);
+    assert_eq!(Handled::msg("something broke").kind(), ErrorKind::Internal);
+}
+
+#[test]
+fn registered_rules_see_the_root() {
+    register_kind_rule(|e| e.is::<AccountLocked>().then_some(ErrorKind::Permission));
+    let err = Handled::wrap(AccountLocked).frame("auth.rs", 1, 1).ctx("logging in");
+    assert_eq!(err.kind(), ErrorKind::Permission);
+    assert_eq!(err.kind().http_status(), 403);
+}
+
+#[test]
+fn explicit_kind_wins_and_follows_the_chain() {
+    let err = io_err(io::ErrorKind::NotFound).with_kind(ErrorKind::Invalid);
+    assert_eq!(err.kind(), ErrorKind::Invalid);
+
+    let outer = Handled::msg("request failed")
+        .chain_after(Handled::msg("upstream").with_kind(ErrorKind::Unavailable));
+    assert_eq!(outer.kind(), ErrorKind::Unavailable);
+}
+
+#[tokio::test]
+async fn timeouts_from_async_try_are_timeouts() {
+    let result: Result<()> = handle! {
+        async try timeout Duration::from_millis(1) {
+            std::future::pending::<()>().await
+        }
+    };
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Timeout);
+}
+
+fn status(err: io::ErrorKind) -> Result<u16> {
+    handle! {
+        try { Err(io::Error::new(err, "io"))? }
+        catch io::Error(e) when kind NotFound { 404 }
+        catch io::Error(e) when kind Timeout | Unavailable { 503 }
+        catch e when kind Permission { e.kind().http_status() }
+    }
+}
+
+#[test]
+fn kind_guards_in_catch() {
+    assert_eq!(status(io::ErrorKind::NotFound).unwrap(), 404);
+    assert_eq!(status(io::ErrorKind::TimedOut).unwrap(), 503);
+    assert_eq!(status(io::ErrorKind::BrokenPipe).unwrap(), 503);
+    assert_eq!(status(io::ErrorKind::PermissionDenied).unwrap(), 403);
+    assert_eq!(status(io::ErrorKind::Other).unwrap_err().kind(), ErrorKind::Internal);
+}
+
+#[test]
+fn long_form_guard() {
+    let result: Result<&str> = handle! {
+        try { Err(io::Error::new(io::ErrorKind::TimedOut, "slow"))? }
+        catch e when e.kind() == ErrorKind::Timeout { "retry later" }
+    };
+    assert_eq!(result.unwrap(), "retry later");
+}
@@ -0,0 +1,69 @@
+//! Tests for `escalate`: raise the caught error's severity and propagate it.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result, Severity};
+
+fn warn() -> Result<u32> {
+    Err(Handled::msg("cache miss storm").with_severity(Severity::Warn))
+}
+
+fn middle_layer(seen: &mut Vec<String>) -> Result<u32> {
+    handle! {
+        try { warn()? }
+        catch e { seen.push(e.message().to_string()); } escalate
+    }
+}
+
+#[test]
+fn trailing_escalate_raises_severity_after_the_body() {
+    let mut seen = Vec::new();
+    let err = middle_layer(&mut seen).unwrap_err();
+    assert_eq!(seen, ["cache miss storm"]);
+    assert_eq!(err.severity(), Some(Severity::Error));
+    assert_eq!(err.message(), "cache miss storm");
+}
+
+#[test]
+fn each_layer_escalates_once() {
+    let result: Result<u32> = handle! {
+        try { middle_layer(&mut Vec::new())? }
+        catch _ { } escalate
+    };
+    assert_eq!(result.unwrap_err().severity(), Some(Severity::Fatal));
+}
+
+#[test]
+fn escalate_in_the_body_is_conditional() {
+    let run = |retryable: bool| -> Result<u32> {
+        handle! {
+            try { warn()? }
+            catch e {
+                if !retryable { escalate }
+                e.message().len() as u32
+            }
+        }
+    };
+    assert_eq!(run(true).unwrap(), 16);
+    assert_eq!(run(false).unwrap_err().severity(), Some(Severity::Error));
+}
+
+#[test]
+fn unset_severity_counts_as_error() {
+    let result: Result<u32> = handle! {
+        try { Err(Handled::msg("down"))? }
+        catch _ { } escalate
+    };
+    assert_eq!(result.unwrap_err().severity(), Some(Severity::Fatal));
+}
+
+#[test]
+fn typed_catch_escalates_the_whole_error() {
+    let result: Result<u32> = handle! {
+        try { "x".parse::<u32>()? }
+        catch std::num::ParseIntError(_) { } escalate
+    };
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+    assert_eq!(err.severity(), Some(Severity::Fatal));
+}
@@ -0,0 +1,51 @@
+//! `exit(code)` terminates the process. Runs the failing path in a child
+//! process (this same test binary) and checks its exit code and stderr.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Result};
+use std::process::Command;
+
+const CHILD_ENV: &str = "HANDLE_THIS_EXIT_CHILD";
+
+fn fail() -> Result<i32> {
+    handle! { try { Err("config missing")? } with "loading config" }
+}
+
+#[test]
+fn exit_child() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    let _: i32 = handle! {
+        try { fail()? }
+        exit(3)
+    }
+    .unwrap();
+    unreachable!("exit clause must not return");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn exit_terminates_with_code_and_trace() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "exit_child", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error: config missing"), "stderr: {stderr}");
+    assert!(stderr.contains("loading config"), "stderr: {stderr}");
+}
+
+#[test]
+fn exit_not_reached_on_success() {
+    let v: Result<i32> = handle! {
+        try { Ok::<_, handle_this::Handled>(5)? }
+        exit 1
+    };
+    assert_eq!(v.unwrap(), 5);
+}
@@ -0,0 +1,37 @@
+//! Tests for `HandleExt::expect_handled`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, HandleExt, Result};
+use std::panic;
+
+fn load() -> Result<i32> {
+    handle! { try { "x".parse::<i32>()? } with "loading config" }
+}
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(p) => p.downcast_ref::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn ok_returns_value() {
+    let ok: Result<i32> = Ok(5);
+    assert_eq!(ok.expect_handled("should not panic"), 5);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn panic_includes_message_and_trace() {
+    let msg = panic_message(|| {
+        load().expect_handled("config must load");
+    });
+    assert!(msg.starts_with("config must load: invalid digit"), "{msg}");
+    assert!(msg.contains("Trace (most recent last):"), "{msg}");
+    assert!(msg.contains("expect_handled.rs:"), "{msg}");
+    assert!(msg.contains("loading config"), "{msg}");
+}
@@ -0,0 +1,127 @@
+//! Tests for async cleanup: `async try { } finally async { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+struct Conn {
+    closed: AtomicUsize,
+    log: Mutex<Vec<&'static str>>,
+}
+
+impl Conn {
+    fn new() -> Self {
+        Conn { closed: AtomicUsize::new(0), log: Mutex::new(Vec::new()) }
+    }
+
+    async fn query(&self, ok: bool) -> std::result::Result<i32, std::io::Error> {
+        self.log.lock().unwrap().push("query");
+        tokio::task::yield_now().await;
+        if ok {
+            Ok(7)
+        } else {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "query failed"))
+        }
+    }
+
+    async fn close(&self) {
+        tokio::task::yield_now().await;
+        self.log.lock().unwrap().push("close");
+        self.closed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    async fn close_fallible(&self) -> std::result::Result<(), std::io::Error> {
+        self.close().await;
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "close failed"))
+    }
+}
+
+#[tokio::test]
+async fn runs_after_success() {
+    let conn = &Conn::new();
+    let result: Result<i32> = handle! {
+        async try { conn.query(true).await? }
+        finally async { conn.close().await; }
+    };
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(conn.closed.load(Ordering::SeqCst), 1);
+    assert_eq!(*conn.log.lock().unwrap(), ["query", "close"]);
+}
+
+#[tokio::test]
+async fn runs_after_failure() {
+    let conn = &Conn::new();
+    let result: Result<i32> = handle! {
+        async try { conn.query(false).await? }
+        finally async { conn.close().await; }
+    };
+    assert!(result.is_err());
+    assert_eq!(conn.closed.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn runs_after_handler() {
+    let conn = &Conn::new();
+    let result: Result<i32> = handle! {
+        async try { conn.query(false).await? }
+        catch {
+            conn.log.lock().unwrap().push("catch");
+            0
+        }
+        finally async { conn.close().await; }
+    };
+    assert_eq!(result.unwrap(), 0);
+    assert_eq!(*conn.log.lock().unwrap(), ["query", "catch", "close"]);
+}
+
+#[tokio::test]
+async fn question_mark_stays_in_cleanup() {
+    let conn = &Conn::new();
+    let result: Result<i32> = handle! {
+        async try { conn.query(true).await? }
+        finally async {
+            let _: std::result::Result<(), std::io::Error> = async {
+                conn.close_fallible().await?;
+                Ok(())
+            }
+            .await;
+        }
+    };
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(conn.closed.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn return_stays_in_cleanup() {
+    async fn run(conn: &Conn) -> i32 {
+        let result: Result<i32> = handle! {
+            async try { conn.query(true).await? }
+            finally async {
+                if conn.closed.load(Ordering::SeqCst) == 0 {
+                    conn.close().await;
+                    return;
+                }
+                unreachable!();
+            }
+        };
+        result.unwrap() + 1
+    }
+
+    let conn = &Conn::new();
+    assert_eq!(run(conn).await, 8);
+    assert_eq!(conn.closed.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn in_async_then_chain() {
+    let conn = &Conn::new();
+    let result: Result<i32> = handle! {
+        async try { conn.query(true).await? },
+        then |x| { x * 2 }
+        finally async { conn.close().await; }
+    };
+    assert_eq!(result.unwrap(), 14);
+    assert_eq!(conn.closed.load(Ordering::SeqCst), 1);
+}
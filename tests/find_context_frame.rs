@@ -0,0 +1,41 @@
+//! Tests for `Handled::find_context_frame`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+
+fn leaf() -> Result<()> {
+    handle! { try { Err("disk full")? } }
+}
+
+fn save() -> Result<()> {
+    handle! { scope "saving profile", try { leaf()? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn finds_scope_frame_with_location() {
+    let line = line!() + 1;
+    let err = handle! { scope "request 42", try { save()? } }.unwrap_err();
+
+    let frame = err.find_context_frame("request").unwrap();
+    assert_eq!(frame.context, Some("request 42"));
+    assert!(frame.file.ends_with("find_context_frame.rs"));
+    assert_eq!(frame.line, line);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn matches_substring_and_returns_first() {
+    let err = save().unwrap_err();
+    let frame = err.find_context_frame("profile").unwrap();
+    assert_eq!(frame.context, Some("saving profile"));
+    assert!(frame.line > 0);
+}
+
+#[test]
+fn none_when_no_context_matches() {
+    let err = save().unwrap_err();
+    assert!(err.find_context_frame("nope").is_none());
+    assert!(leaf().unwrap_err().find_context_frame("").is_none());
+}
@@ -0,0 +1,83 @@
+//! Repeated frames collapse into one with a `repeat_count`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+
+fn descend(n: u32) -> Result<()> {
+    if n == 0 {
+        return Err(Handled::msg("bottom"));
+    }
+    handle! { try { descend(n - 1)? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn recursion_collapses_into_one_frame() {
+    let err = descend(240).unwrap_err();
+    assert_eq!(err.depth(), 1);
+    assert_eq!(err.frames().next().unwrap().repeat_count(), 240);
+    let shown = err.to_string();
+    assert!(shown.contains("... repeated 240 times"));
+    assert_eq!(shown.matches("frame_repeat.rs").count(), 1);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn different_frames_are_kept() {
+    let err = Handled::msg("x").frame("a.rs", 1, 1).frame("a.rs", 2, 1).frame("a.rs", 2, 1);
+    let counts: Vec<_> = err.frames().map(|f| (f.line, f.repeat_count())).collect();
+    assert_eq!(counts, [(1, 1), (2, 2)]);
+    assert_eq!(err.to_string().matches("repeated").count(), 1);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frames_with_context_are_not_collapsed() {
+    let err = Handled::msg("x")
+        .frame("a.rs", 1, 1)
+        .ctx("first")
+        .frame("a.rs", 1, 1)
+        .ctx("second");
+    let contexts: Vec<_> = err.frames().map(|f| f.context).collect();
+    assert_eq!(contexts, [Some("first"), Some("second")]);
+    assert!(err.frames().all(|f| f.repeat_count() == 1));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn context_after_repeats_applies_to_the_run() {
+    let err = Handled::msg("x").frame("a.rs", 1, 1).frame("a.rs", 1, 1).ctx("loop");
+    let frame = err.frames().next().unwrap();
+    assert_eq!(frame.repeat_count(), 2);
+    assert_eq!(frame.context, Some("loop"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn repeats_do_not_count_toward_the_limit() {
+    let err = Handled::msg("x").push_frames([("a.rs", 1, 1); 1000]).frame("b.rs", 2, 1);
+    assert_eq!(err.depth(), 2);
+    assert_eq!(err.frames().next().unwrap().repeat_count(), 1000);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn json_records_repeat() {
+    let err = Handled::msg("x").frame("a.rs", 1, 1).frame("a.rs", 1, 1).frame("b.rs", 2, 1);
+    let json = err.to_json();
+    assert!(json.contains(r#"{"file":"a.rs","line":1,"col":1,"repeat":2}"#));
+    assert!(json.contains(r#"{"file":"b.rs","line":2,"col":1}"#));
+}
+
+#[cfg(feature = "serde")]
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn serde_round_trip_keeps_repeat() {
+    let err = Handled::msg("x").push_frames([("a.rs", 1, 1); 3]);
+    let json = serde_json::to_string(&err).unwrap();
+    assert!(json.contains("\"repeat\":3"));
+    let back: Handled = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.frames().next().unwrap().repeat_count(), 3);
+}
@@ -0,0 +1,169 @@
+//! thiserror-style enums: `FromHandled`, `Classifier`, `Handled::classify`, `throw as`.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::missing;
+use handle_this::{handle, Classifier, FromHandled, Handled, Result};
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+enum AppError {
+    Io(Handled<io::Error>),
+    Parse(ParseIntError),
+    Read { path: String, source: io::Error },
+    Other(Handled),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Io(e) => write!(f, "io: {}", e),
+            AppError::Parse(e) => write!(f, "parse: {}", e),
+            AppError::Read { path, source } => write!(f, "reading {}: {}", path, source),
+            AppError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl FromHandled for AppError {
+    fn from_handled(err: Handled) -> std::result::Result<Self, Handled> {
+        Classifier::new(err).traced(AppError::Io).variant(AppError::Parse).finish()
+    }
+}
+
+impl From<Handled> for AppError {
+    fn from(err: Handled) -> Self {
+        err.classify().unwrap_or_else(AppError::Other)
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn classify_keeps_frames_in_traced_variant() {
+    let result: Result<()> = handle! { try { missing()? } with "opening config" };
+    let app: AppError = result.unwrap_err().classify().unwrap();
+    match &app {
+        AppError::Io(e) => assert_eq!(e.source_ref().kind(), io::ErrorKind::NotFound),
+        other => panic!("unexpected {:?}", other),
+    }
+    let shown = app.to_string();
+    assert!(shown.starts_with("io: no such file"));
+    assert!(shown.contains("from_handled.rs"));
+    assert!(shown.contains("opening config"));
+}
+
+#[test]
+fn classify_plain_variant() {
+    let result: Result<i32> = handle! { try { "x".parse::<i32>()? } };
+    let app: AppError = result.unwrap_err().classify().unwrap();
+    assert!(matches!(app, AppError::Parse(_)));
+}
+
+#[test]
+fn classify_hands_back_unmatched() {
+    let err = Handled::msg("neither");
+    let err = err.classify::<AppError>().unwrap_err();
+    assert_eq!(err.message(), "neither");
+    assert!(matches!(AppError::from(err), AppError::Other(_)));
+}
+
+#[test]
+fn classifier_or_fallback() {
+    let app = Classifier::new(Handled::msg("x")).variant(AppError::Parse).or(AppError::Other);
+    assert!(matches!(app, AppError::Other(_)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn downcast_handled_mismatch_returns_self() {
+    let err = Handled::msg("text").frame("a.rs", 1, 1);
+    let err = err.downcast_handled::<io::Error>().unwrap_err();
+    assert_eq!(err.depth(), 1);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn throw_as_tuple_variant() {
+    let result: Result<i32> = handle! {
+        try { "x".parse::<i32>()? }
+        throw as AppError::Parse
+    };
+    let err = result.unwrap_err();
+    assert!(matches!(err.downcast_ref::<AppError>(), Some(AppError::Parse(_))));
+    // Original frame plus the conversion frame, no chained cause
+    assert_eq!(err.depth(), 2);
+    assert!(err.to_string().starts_with("parse: invalid digit"));
+}
+
+#[test]
+fn throw_as_struct_variant() {
+    let path = String::from("/etc/app.toml");
+    let result: Result<()> = handle! {
+        try { missing()? }
+        throw as AppError::Read { path: path.clone() }
+    };
+    match result.unwrap_err().downcast::<AppError>() {
+        Ok(AppError::Read { path, source }) => {
+            assert_eq!(path, "/etc/app.toml");
+            assert_eq!(source.kind(), io::ErrorKind::NotFound);
+        }
+        other => panic!("unexpected {:?}", other.map_err(|e| e.message().to_string())),
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn throw_as_skips_other_types() {
+    let result: Result<()> = handle! {
+        try { missing()? }
+        throw as AppError::Parse
+    };
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<io::Error>().is_some());
+    assert_eq!(err.depth(), 1);
+}
+
+#[test]
+fn throw_as_then_typed_catch() {
+    let result: Result<&str> = handle! {
+        try { "x".parse::<i32>()?; "ok" }
+        throw as AppError::Parse
+        catch AppError(e) { if matches!(e, AppError::Parse(_)) { "parse" } else { "other" } }
+    };
+    assert_eq!(result.unwrap(), "parse");
+}
+
+#[tokio::test]
+async fn throw_as_in_async_try() {
+    let result: Result<i32> = handle! {
+        async try { "x".parse::<i32>()? }
+        throw as AppError::Parse
+    };
+    assert!(matches!(result.unwrap_err().downcast_ref::<AppError>(), Some(AppError::Parse(_))));
+}
+
+#[test]
+fn throw_as_in_try_for() {
+    let result: Result<i32> = handle! {
+        try for s in ["a", "b"] { s.parse::<i32>()? }
+        throw as AppError::Parse
+    };
+    assert!(matches!(result.unwrap_err().downcast_ref::<AppError>(), Some(AppError::Parse(_))));
+}
+
+#[test]
+fn nested_throw_as() {
+    let result: Result<i32> = handle! {
+        try {
+            let n = try { "x".parse::<i32>()? } throw as AppError::Parse;
+            n + 1
+        }
+    };
+    assert!(matches!(result.unwrap_err().downcast_ref::<AppError>(), Some(AppError::Parse(_))));
+}
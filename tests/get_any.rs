@@ -0,0 +1,63 @@
+//! Tests for `Handled::get_any`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::fmt;
+use std::fs;
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug, PartialEq)]
+struct RequestId(u64);
+
+#[derive(Debug, PartialEq)]
+struct Payload {
+    code: u16,
+}
+
+impl fmt::Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "payload {}", self.code)
+    }
+}
+
+#[test]
+fn typed_error_source() {
+    let err = Handled::new(IoError::new(ErrorKind::NotFound, "missing"));
+    let io = err.get_any::<IoError>().unwrap();
+    assert_eq!(io.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn erased_error_from_handle() {
+    let result: Result<Vec<u8>> = handle! { try { fs::read("/definitely/not/here")? } };
+    let err = result.unwrap_err();
+    assert_eq!(err.get_any::<IoError>().unwrap().kind(), ErrorKind::NotFound);
+    assert!(err.get_any::<Payload>().is_none());
+}
+
+#[test]
+fn erased_error_from_wrap() {
+    let err = Handled::wrap(IoError::new(ErrorKind::TimedOut, "slow"));
+    assert_eq!(err.get_any::<IoError>().unwrap().kind(), ErrorKind::TimedOut);
+}
+
+#[test]
+fn non_error_field() {
+    let err = Handled::msg("boom").set_field(RequestId(42));
+    assert_eq!(err.get_any::<RequestId>(), Some(&RequestId(42)));
+}
+
+#[test]
+fn display_only_payload() {
+    let err = Handled::from_display(Payload { code: 503 });
+    assert_eq!(err.get_any::<Payload>(), Some(&Payload { code: 503 }));
+}
+
+#[test]
+fn field_takes_precedence_and_missing_is_none() {
+    let err = Handled::new(IoError::new(ErrorKind::Other, "io"))
+        .set_field(IoError::new(ErrorKind::TimedOut, "field"));
+    assert_eq!(err.get_any::<IoError>().unwrap().kind(), ErrorKind::TimedOut);
+    assert!(err.get_any::<Payload>().is_none());
+}
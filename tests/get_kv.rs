@@ -0,0 +1,59 @@
+//! Tests for `Handled::get_kv` and `Handled::all_kv`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result, Value};
+
+fn leaf(id: u64) -> Result<()> {
+    handle! { try { Err("db down")? } with "querying", { request_id: id } }
+}
+
+fn middle(id: u64) -> Result<()> {
+    handle! { try { leaf(id)? } with "loading user" }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn finds_a_key_deep_in_the_trace() {
+    let err = handle! { try { middle(42)? } with { route: "/users" } }.unwrap_err();
+    assert_eq!(err.get_kv("request_id"), Some(&Value::Uint(42)));
+    assert_eq!(err.get_kv("route").unwrap(), "/users");
+    assert_eq!(err.get_kv("missing"), None);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn most_recent_wins() {
+    let err = Handled::msg("boom")
+        .frame("a.rs", 1, 1)
+        .kv("attempt", 1)
+        .frame("b.rs", 2, 1)
+        .kv("attempt", 2);
+    assert_eq!(err.get_kv("attempt"), Some(&Value::Int(2)));
+
+    let err = Handled::msg("boom").frame("a.rs", 1, 1).kv("attempt", 1).kv("attempt", 3);
+    assert_eq!(err.get_kv("attempt"), Some(&Value::Int(3)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn all_kv_runs_newest_first() {
+    let err = Handled::msg("boom")
+        .frame("a.rs", 1, 1)
+        .kv("a1", 1)
+        .kv("a2", 2)
+        .frame("b.rs", 2, 1)
+        .ctx("no attachments")
+        .frame("c.rs", 3, 1)
+        .kv("c1", 3);
+    let keys: Vec<_> = err.all_kv().map(|(k, _)| k).collect();
+    assert_eq!(keys, ["c1", "a2", "a1"]);
+}
+
+#[test]
+fn empty_trace_has_no_attachments() {
+    let err = Handled::msg("boom");
+    assert_eq!(err.all_kv().count(), 0);
+    assert!(err.get_kv("anything").is_none());
+}
@@ -0,0 +1,50 @@
+//! `group "name"` tags errors so `Handled::combine` sections them.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, CombinedError, Handled, Result};
+
+fn check(name: &str, ok: bool) -> Result<()> {
+    if ok { Ok(()) } else { Err(Handled::msg(format!("{name} failed"))) }
+}
+
+#[test]
+fn combine_reflects_two_named_groups() {
+    let mut errors = Vec::new();
+
+    let db: Result<Vec<()>> = handle! {
+        try all name in ["users", "orders"] { check(name, name != "orders")? }
+        group "db"
+    };
+    errors.extend(db.err());
+
+    for name in ["redis", "memcached"] {
+        let cache: Result<()> = handle! { try { check(name, false)? } group "cache" };
+        errors.extend(cache.err());
+    }
+
+    let err = Handled::combine(errors);
+    let combined = err.downcast_ref::<CombinedError>().unwrap();
+    assert_eq!(combined.groups(), ["db", "cache"]);
+    assert_eq!(combined.in_group("cache").count(), 2);
+    assert_eq!(
+        err.message(),
+        "3 errors\n[db]\n  - orders failed\n[cache]\n  - redis failed\n  - memcached failed"
+    );
+}
+
+#[test]
+fn group_visible_to_later_handlers() {
+    let result: Result<String> = handle! {
+        try { check("a", false)?; String::new() }
+        group "io"
+        catch e { e.group().unwrap_or("none").to_string() }
+    };
+    assert_eq!(result.unwrap(), "io");
+}
+
+#[test]
+fn ungrouped_listed_first() {
+    let err = Handled::combine([Handled::msg("x").in_group("g"), Handled::msg("y")]);
+    assert_eq!(err.message(), "2 errors\n  - y\n[g]\n  - x");
+}
@@ -0,0 +1,101 @@
+//! Tests for the global error hook: `set_hook` / `clear_hook`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{clear_hook, handle, set_hook, Handled, Result};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The hook is process-wide; tests that install one take turns.
+static HOOK: Mutex<()> = Mutex::new(());
+
+/// Records the message and depth of every error the hook sees,
+/// until dropped.
+struct Recorder {
+    seen: Arc<Mutex<Vec<(String, usize)>>>,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        let guard = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        set_hook(move |err: &Handled| {
+            sink.lock().unwrap().push((err.message().to_string(), err.depth()));
+        });
+        Recorder { seen, _guard: guard }
+    }
+
+    fn seen(&self) -> Vec<(String, usize)> {
+        self.seen.lock().unwrap().clone()
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        clear_hook();
+    }
+}
+
+fn fail() -> Result<i32> {
+    handle! { try { Err(Handled::msg("inner"))? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn sees_wrapped_errors() {
+    let hook = Recorder::new();
+    let depth = handle! { try -> usize { "x".parse::<usize>()? } catch e { e.depth() } };
+    assert_eq!(depth, 1);
+    let seen = hook.seen();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].1, 1);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn reports_each_error_once() {
+    let hook = Recorder::new();
+    let result: Result<i32> = handle! { try { fail()? } with "outer" };
+    assert_eq!(result.unwrap_err().depth(), 2);
+    assert_eq!(hook.seen(), [("inner".to_string(), 1)]);
+}
+
+#[test]
+fn successes_are_not_reported() {
+    let hook = Recorder::new();
+    let result: Result<i32> = handle! { try { "7".parse::<i32>()? } };
+    assert_eq!(result.unwrap(), 7);
+    assert!(hook.seen().is_empty());
+}
+
+#[test]
+fn cleared_hook_is_silent() {
+    let hook = Recorder::new();
+    clear_hook();
+    let _ = fail();
+    assert!(hook.seen().is_empty());
+}
+
+#[test]
+fn replacing_the_hook() {
+    let first = Recorder::new();
+    let second = Arc::new(Mutex::new(0));
+    let count = Arc::clone(&second);
+    set_hook(move |_| *count.lock().unwrap() += 1);
+    let _ = fail();
+    assert!(first.seen().is_empty());
+    assert_eq!(*second.lock().unwrap(), 1);
+}
+
+#[test]
+fn errors_inside_the_hook_do_not_recurse() {
+    let hook = Recorder::new();
+    let inner = Arc::clone(&hook.seen);
+    set_hook(move |err: &Handled| {
+        inner.lock().unwrap().push((err.message().to_string(), err.depth()));
+        let _ = fail();
+    });
+    let _ = fail();
+    assert_eq!(hook.seen().len(), 1);
+}
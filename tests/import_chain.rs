@@ -0,0 +1,75 @@
+//! Tests for `Handled::import_chain` and `Handled::context_messages`.
+
+use handle_this::{Handled, StringError};
+use std::error::Error;
+use std::{fmt, io};
+
+#[derive(Debug)]
+struct Layer {
+    msg: &'static str,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.msg)
+    }
+}
+
+impl Error for Layer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+fn three_levels() -> Layer {
+    Layer {
+        msg: "request failed",
+        source: Some(Box::new(Layer {
+            msg: "connection reset",
+            source: Some(Box::new(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))),
+        })),
+    }
+}
+
+#[test]
+fn every_level_becomes_a_link() {
+    let err = Handled::import_chain(three_levels());
+    assert_eq!(
+        err.context_messages(),
+        ["request failed", "connection reset", "broken pipe"]
+    );
+    assert_eq!(err.chain_all::<StringError>().len(), 2);
+}
+
+#[test]
+fn root_keeps_original_type() {
+    let err = Handled::import_chain(three_levels());
+    assert_eq!(err.downcast_ref::<Layer>().unwrap().msg, "request failed");
+}
+
+#[test]
+fn error_without_source_has_no_links() {
+    let mut err = Handled::import_chain(io::Error::new(io::ErrorKind::Other, "alone"));
+    assert_eq!(err.context_messages(), ["alone"]);
+    assert!(err.take_chain().is_none());
+}
+
+#[test]
+fn handled_input_keeps_its_chain() {
+    let inner = Handled::msg("later").chain_after(Handled::msg("earlier"));
+    let err = Handled::import_chain(inner);
+    assert_eq!(err.context_messages(), ["later", "earlier"]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn chain_survives_serialization() {
+    let err = Handled::import_chain(three_levels());
+    let json = serde_json::to_string(&err).unwrap();
+    let back: Handled = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        back.context_messages(),
+        ["request failed", "connection reset", "broken pipe"]
+    );
+}
@@ -0,0 +1,54 @@
+//! `Handled::into_anyhow` under the `anyhow` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "anyhow")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use std::io;
+
+fn read_config() -> Result<String> {
+    handle! {
+        try { Err(io::Error::new(io::ErrorKind::NotFound, "config.toml missing"))? }
+        with "reading config"
+    }
+}
+
+fn start() -> Result<String> {
+    handle! {
+        try { read_config()? }
+        with "starting server"
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn alternate_display_shows_layers_in_order() {
+    let err = start().unwrap_err().into_anyhow();
+    assert_eq!(
+        format!("{:#}", err),
+        "starting server: reading config: config.toml missing"
+    );
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn chain_lists_outermost_first() {
+    let err = start().unwrap_err().into_anyhow();
+    let chain: Vec<String> = err.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain, ["starting server", "reading config", "config.toml missing"]);
+}
+
+#[test]
+fn no_context_is_just_the_root() {
+    let err = Handled::msg("plain").frame("a.rs", 1, 1).into_anyhow();
+    assert_eq!(format!("{:#}", err), "plain");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn round_trips_back_into_handled() {
+    let err: Handled = start().unwrap_err().into_anyhow().into();
+    assert_eq!(err.message(), "starting server");
+}
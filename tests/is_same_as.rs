@@ -0,0 +1,41 @@
+//! `Handled::is_same_as` compares message, trace, and attachments exactly.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+
+fn fetch(id: u32) -> Result<()> {
+    handle! { try { Err("not found")? } with "fetching", { id: id } }
+}
+
+#[test]
+fn identically_built_errors_are_same() {
+    let a = Handled::msg("boom").frame("a.rs", 1, 2).ctx("loading").kv("attempt", 1);
+    let b = Handled::msg("boom").frame("a.rs", 1, 2).ctx("loading").kv("attempt", 1);
+    assert!(a.is_same_as(&b));
+    assert!(b.is_same_as(&a));
+}
+
+#[test]
+fn same_call_site_is_same() {
+    assert!(fetch(7).unwrap_err().is_same_as(&fetch(7).unwrap_err()));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn differing_attachment_is_not_same() {
+    let a = fetch(7).unwrap_err();
+    let b = fetch(8).unwrap_err();
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert!(!a.is_same_as(&b));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn differing_message_or_frames_is_not_same() {
+    let base = Handled::msg("boom").frame("a.rs", 1, 2);
+    assert!(!base.is_same_as(&Handled::msg("bang").frame("a.rs", 1, 2)));
+    assert!(!base.is_same_as(&Handled::msg("boom").frame("a.rs", 1, 3)));
+    assert!(!base.is_same_as(&Handled::msg("boom").frame("a.rs", 1, 2).frame("b.rs", 4, 1)));
+    assert!(!base.is_same_as(&Handled::msg("boom").frame("a.rs", 1, 2).ctx("extra")));
+}
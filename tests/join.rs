@@ -0,0 +1,125 @@
+//! Tests for `async try join { a: fut_a(), b: fut_b() }`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use std::cell::{Cell, RefCell};
+
+/// Records the order in which futures make progress.
+#[derive(Default)]
+struct Log(RefCell<Vec<&'static str>>);
+
+impl Log {
+    async fn step(&self, name: &'static str, value: i32) -> std::result::Result<i32, Bad> {
+        for _ in 0..2 {
+            self.0.borrow_mut().push(name);
+            tokio::task::yield_now().await;
+        }
+        if value < 0 {
+            Err(Bad(name))
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bad(&'static str);
+
+impl std::fmt::Display for Bad {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bad {}", self.0)
+    }
+}
+
+impl std::error::Error for Bad {}
+
+fn join_label(err: &Handled) -> Option<String> {
+    err.frames()
+        .flat_map(|f| f.attachments())
+        .find(|(k, _)| *k == "join")
+        .map(|(_, v)| v.to_string())
+}
+
+#[tokio::test]
+async fn returns_a_tuple_and_interleaves() {
+    let log = Log::default();
+    let result: Result<(i32, i32)> = handle! {
+        async try join { a: log.step("a", 1), b: log.step("b", 2) }
+    };
+    assert_eq!(result.unwrap(), (1, 2));
+    assert_eq!(*log.0.borrow(), ["a", "b", "a", "b"]);
+}
+
+#[tokio::test]
+async fn labels_are_optional() {
+    let result: Result<(i32, &str, bool)> = handle! {
+        async try join { async { Ok::<_, Bad>(1) }, async { Ok::<_, Bad>("two") }, async { Ok::<_, Bad>(true) } }
+    };
+    assert_eq!(result.unwrap(), (1, "two", true));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn every_failure_is_chained() {
+    let log = Log::default();
+    let result: Result<(i32, i32, i32)> = handle! {
+        async try join { a: log.step("a", -1), b: log.step("b", 2), c: log.step("c", -3) }
+    };
+    let err = result.unwrap_err();
+    // No short-circuit: every future ran to completion
+    assert_eq!(log.0.borrow().len(), 6);
+    let names: Vec<_> = err.chain_all::<Bad>().into_iter().map(|b| b.0).collect();
+    assert_eq!(names, ["c", "a"]);
+    assert_eq!(join_label(&err).as_deref(), Some("c"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn unlabeled_failures_are_tagged_by_index() {
+    let result: Result<(i32, i32)> = handle! {
+        async try join { async { Ok::<_, Bad>(1) }, async { Err::<i32, _>(Bad("second")) } }
+    };
+    assert_eq!(join_label(&result.unwrap_err()).as_deref(), Some("1"));
+}
+
+#[tokio::test]
+async fn catch_all_sees_each_failure() {
+    let log = Log::default();
+    let result: Result<(i32, i32)> = handle! {
+        async try join { a: log.step("a", -1), b: log.step("b", -2) }
+        catch all Bad |errors| { (errors.len() as i32, 0) }
+    };
+    assert_eq!(result.unwrap(), (2, 0));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn handlers_run_once_with_context_and_finally() {
+    let log = Log::default();
+    let caught = Cell::new(0);
+    let cleaned = Cell::new(false);
+    let result: Result<(i32, i32)> = handle! {
+        async try join { a: log.step("a", -1), b: log.step("b", 2) }
+        with "loading pair"
+        inspect _e { caught.set(caught.get() + 1); }
+        finally { cleaned.set(true); }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(caught.get(), 1);
+    assert!(cleaned.get());
+    assert!(err.frames().any(|f| f.context == Some("loading pair")));
+}
+
+#[tokio::test]
+async fn nests_inside_async_try() {
+    let log = Log::default();
+    let result: Result<i32> = handle! {
+        async try {
+            let (a, b) = async try join { a: log.step("a", 1), b: log.step("b", 2) };
+            a + b
+        }
+    };
+    assert_eq!(result.unwrap(), 3);
+}
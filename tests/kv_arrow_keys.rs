@@ -0,0 +1,56 @@
+//! `with { "key" => value }` arrow syntax for non-identifier keys.
+
+#![allow(clippy::result_large_err)]
+#![cfg(not(feature = "no-trace"))]
+
+mod common;
+
+use common::fail;
+use handle_this::{handle, Handled, Result, Value};
+
+fn attachments(err: &Handled) -> Vec<(String, Value)> {
+    err.frames()
+        .flat_map(|f| f.attachments().map(|(k, v)| (k.to_string(), v.clone())).collect::<Vec<_>>())
+        .collect()
+}
+
+#[test]
+fn dashed_key_appears_verbatim() {
+    let id = 42;
+    let result: Result<()> = handle! {
+        try { fail()? } with { "x-request-id" => id, user: "ann" }
+    };
+    let kv = attachments(&result.unwrap_err());
+    assert!(kv.contains(&("x-request-id".to_string(), Value::Int(42))));
+    assert!(kv.contains(&("user".to_string(), Value::String("ann".into()))));
+}
+
+#[test]
+fn runtime_string_key() {
+    let header = format!("x-{}", "trace");
+    let result: Result<()> = handle! {
+        try { fail()? } with "request", { header.clone() => "abc" }
+    };
+    let kv = attachments(&result.unwrap_err());
+    assert_eq!(kv, [("x-trace".to_string(), Value::String("abc".into()))]);
+}
+
+#[test]
+fn scope_arrow_key() {
+    let result: Result<()> = handle! {
+        scope "handler", { "content-type" => "json" }, try { fail()? }
+    };
+    let kv = attachments(&result.unwrap_err());
+    assert!(kv.contains(&("content-type".to_string(), Value::String("json".into()))));
+}
+
+#[test]
+fn nested_scope_arrow_key() {
+    let result: Result<()> = handle! {
+        try {
+            scope "inner", { "x-span" => 7u32 }, try { fail()? }
+        }
+    };
+    let kv = attachments(&result.unwrap_err());
+    assert!(kv.contains(&("x-span".to_string(), Value::Uint(7))));
+}
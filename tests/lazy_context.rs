@@ -0,0 +1,85 @@
+//! `scope lazy || expr` and `with lazy || expr`: context built only on error.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+mod common;
+
+use common::fail;
+use handle_this::{handle, Handled, Result, Value};
+use std::cell::Cell;
+
+fn contexts(err: &Handled) -> Vec<String> {
+    err.frames().filter_map(|f| f.context.map(str::to_string)).collect()
+}
+
+#[test]
+fn with_lazy_skipped_on_success() {
+    let calls = Cell::new(0);
+    for id in 0..3 {
+        let result: Result<i32> = handle! {
+            try { id } with lazy || { calls.set(calls.get() + 1); format!("user {}", id) }
+        };
+        assert_eq!(result.unwrap(), id);
+    }
+    assert_eq!(calls.get(), 0);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn with_lazy_runs_on_error() {
+    let id = 7;
+    let result: Result<()> = handle! {
+        try { fail()? } with lazy || format!("user {}", id)
+    };
+    assert_eq!(contexts(&result.unwrap_err()), ["user 7"]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn with_lazy_then_kv_and_code() {
+    let id = 7;
+    let result: Result<()> = handle! {
+        try { fail()? } with lazy || format!("user {}", id), { id: id }, code "E7"
+    };
+    let err = result.unwrap_err();
+    assert_eq!(contexts(&err), ["user 7"]);
+    assert_eq!(err.get_kv("id"), Some(&Value::Int(7)));
+    assert_eq!(err.code(), Some("E7"));
+}
+
+#[test]
+fn scope_lazy_skipped_on_success() {
+    let calls = Cell::new(0);
+    let result: Result<i32> = handle! {
+        scope lazy || { calls.set(calls.get() + 1); "loading" }, try { 1 }
+    };
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(calls.get(), 0);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn scope_lazy_runs_on_error() {
+    let id = 3;
+    let result: Result<()> = handle! {
+        scope lazy || format!("user {}", id), { id: id }, try { fail()? }
+    };
+    let err = result.unwrap_err();
+    assert!(contexts(&err).contains(&"user 3".to_string()));
+    assert_eq!(err.get_kv("id"), Some(&Value::Int(3)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn nested_scope_lazy() {
+    let id = 9;
+    let result: Result<()> = handle! {
+        scope "outer", try {
+            scope lazy || format!("user {}", id), try { fail()? }
+        }
+    };
+    let ctx = contexts(&result.unwrap_err());
+    assert!(ctx.contains(&"user 9".to_string()));
+    assert!(ctx.contains(&"outer".to_string()));
+}
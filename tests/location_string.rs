@@ -0,0 +1,48 @@
+//! Tests for `Handled::origin_string` and `Handled::location_string`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+
+fn inner() -> Result<()> {
+    handle! { try { Err("boom")? } }
+}
+
+fn outer() -> Result<()> {
+    handle! { try { inner()? } }
+}
+
+fn is_file_line_col(s: &str) -> bool {
+    let mut parts = s.rsplitn(3, ':');
+    let col = parts.next().unwrap_or("");
+    let line = parts.next().unwrap_or("");
+    let file = parts.next().unwrap_or("");
+    file.ends_with(".rs") && line.parse::<u32>().is_ok() && col.parse::<u32>().is_ok()
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn formats_as_file_line_col() {
+    let err = outer().unwrap_err();
+    let origin = err.origin_string().unwrap();
+    let latest = err.location_string().unwrap();
+    assert!(is_file_line_col(&origin), "{origin}");
+    assert!(is_file_line_col(&latest), "{latest}");
+    assert!(origin.contains("location_string.rs:"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn origin_is_oldest_and_location_is_newest() {
+    let err = Handled::msg("x").frame("a.rs", 1, 2).frame("b.rs", 3, 4);
+    assert_eq!(err.origin_string().as_deref(), Some("a.rs:1:2"));
+    assert_eq!(err.location_string().as_deref(), Some("b.rs:3:4"));
+}
+
+#[test]
+fn none_without_frames() {
+    let err = Handled::msg("x");
+    assert_eq!(err.origin_string(), None);
+    assert_eq!(err.location_string(), None);
+}
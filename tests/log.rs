@@ -0,0 +1,153 @@
+//! `log` crate bridge: `Handled::log`, `log_line`, and `inspect log LEVEL`.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "log")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::{Mutex, Once};
+
+struct Captured {
+    level: Level,
+    target: String,
+    line: Option<u32>,
+    message: String,
+    correlation: Option<String>,
+}
+
+static RECORDS: Mutex<Vec<Captured>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn log(&self, record: &Record<'_>) {
+        RECORDS.lock().unwrap().push(Captured {
+            level: record.level(),
+            target: record.target().to_string(),
+            line: record.line(),
+            message: record.args().to_string(),
+            correlation: record.key_values().get("correlation_id".into()).map(|v| v.to_string()),
+        });
+    }
+    fn flush(&self) {}
+}
+
+/// Captured records whose message starts with `prefix`.
+fn records(prefix: &str) -> Vec<(Level, String, String, Option<u32>)> {
+    RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|r| r.message.starts_with(prefix))
+        .map(|r| (r.level, r.target.clone(), r.message.clone(), r.line))
+        .collect()
+}
+
+fn install() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&Capture).unwrap();
+        log::set_max_level(LevelFilter::Info);
+    });
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn log_emits_one_line_record() {
+    install();
+    let err = Handled::msg("direct call").frame("db.rs", 1, 2).kv("id", 7);
+    let line = line!() + 1;
+    err.log(Level::Warn);
+
+    let found = records("direct call");
+    assert_eq!(found.len(), 1);
+    let (level, target, message, at) = &found[0];
+    assert_eq!(*level, Level::Warn);
+    assert_eq!(target, "handle_this");
+    assert_eq!(message, "direct call @ db.rs:1:2 id=7");
+    assert_eq!(*at, Some(line));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn correlation_id_is_a_key_value() {
+    install();
+    let err = Handled::msg("correlated call").frame("db.rs", 1, 2).with_correlation("req-7");
+    assert_eq!(err.log_line(), r#"correlated call correlation_id="req-7" @ db.rs:1:2"#);
+    err.log(Level::Warn);
+
+    let records = RECORDS.lock().unwrap();
+    let record = records.iter().find(|r| r.message.starts_with("correlated call")).unwrap();
+    assert_eq!(record.correlation.as_deref(), Some("req-7"));
+    assert!(records.iter().filter(|r| r.message.starts_with("direct call")).all(|r| r.correlation.is_none()));
+}
+
+#[test]
+fn bare_report_and_continue_logs_an_error_record() {
+    install();
+    let mut ok = Vec::new();
+    for s in ["1", "nope"] {
+        let v: Result<i32> = handle! {
+            try { s.parse::<i32>().map_err(|_| Handled::msg("report me"))? }
+            report_and_continue
+        };
+        ok.push(v.unwrap());
+    }
+    assert_eq!(ok, [1]);
+    let found = records("report me");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].0, Level::Error);
+}
+
+#[test]
+fn levels_below_max_are_skipped() {
+    install();
+    Handled::msg("too chatty").log(Level::Debug);
+    assert!(records("too chatty").is_empty());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn log_line_is_always_one_line() {
+    let err = Handled::msg("multi\nline").frame("a.rs", 1, 1).ctx("step\r\ntwo").kv("note", "x\ny");
+    let line = err.log_line();
+    assert!(!line.contains('\n') && !line.contains('\r'), "{}", line);
+    assert_eq!(line, r#"multi\nline @ a.rs:1:1 "step\r\ntwo" note="x\ny""#);
+    assert_eq!(Handled::msg("bare").log_line(), "bare");
+}
+
+fn parse_port(raw: &str) -> Result<u16> {
+    handle! {
+        try { raw.parse::<u16>()? }
+        with "parsing port", { raw: raw }
+        inspect log error
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn inspect_log_emits_and_propagates() {
+    install();
+    let err = parse_port("port-abc").unwrap_err();
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+
+    let found = records("invalid digit");
+    assert_eq!(found.len(), 1);
+    let (level, _, message, _) = &found[0];
+    assert_eq!(*level, Level::Error);
+    assert!(message.contains(r#""parsing port" raw="port-abc""#), "{}", message);
+}
+
+#[test]
+fn inspect_log_is_still_a_binding() {
+    let result: Result<usize> = handle! {
+        try { Err(Handled::msg("bound"))? }
+        inspect log { assert_eq!(log.message(), "bound") }
+        catch e { e.message().len() }
+    };
+    assert_eq!(result.unwrap(), 5);
+}
@@ -0,0 +1,70 @@
+//! Tests for the `log_once` clause.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::cell::RefCell;
+
+thread_local! {
+    static LINES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn lines() -> Vec<String> {
+    LINES.with(|l| l.borrow().clone())
+}
+
+fn fails(msg: &str) -> Result<()> {
+    handle! { try { Err(msg.to_string())? } }
+}
+
+fn poll(msg: &str) -> Result<()> {
+    handle! {
+        try { fails(msg)? }
+        log_once |e| { LINES.with(|l| l.borrow_mut().push(e.to_string())) }
+    }
+}
+
+#[test]
+fn same_error_twice_logs_once() {
+    assert!(poll("disk full").is_err());
+    assert!(poll("disk full").is_err());
+    assert_eq!(lines().iter().filter(|l| l.contains("disk full")).count(), 1);
+}
+
+#[test]
+fn distinct_errors_each_log() {
+    let _ = poll("timeout a");
+    let _ = poll("timeout b");
+    let _ = poll("timeout a");
+    let logged: Vec<_> = lines().into_iter().filter(|l| l.contains("timeout")).collect();
+    assert_eq!(logged.len(), 2);
+}
+
+#[test]
+fn error_keeps_propagating_and_bare_form_compiles() {
+    let err = handle! { try { fails("quiet")? } log_once }.unwrap_err();
+    assert_eq!(err.message(), "quiet");
+
+    let recovered = handle! {
+        try { fails("loop")?; 0 } log_once |_e| {} catch { 1 }
+    };
+    assert_eq!(recovered.unwrap(), 1);
+}
+
+#[test]
+fn nested_and_loop_handlers() {
+    let mut count = 0;
+    for _ in 0..3 {
+        let _: Result<()> = handle! {
+            try {
+                try { fails("nested")? } log_once |_e| { count += 1 }
+            }
+        };
+    }
+    assert_eq!(count, 1);
+
+    let err = handle! {
+        try for x in [1, 2] { fails(&format!("item {}", x))? } log_once |_e| {}
+    };
+    assert!(err.is_err());
+}
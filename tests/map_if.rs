@@ -0,0 +1,82 @@
+//! Tests for `Handled::map_if`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::{fmt, io};
+
+#[derive(Debug, PartialEq)]
+enum StoreError {
+    Missing,
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Missing => write!(f, "record missing"),
+            StoreError::Io(kind) => write!(f, "store io: {:?}", kind),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => StoreError::Missing,
+            kind => StoreError::Io(kind),
+        }
+    }
+}
+
+fn read() -> Result<String> {
+    handle! {
+        try { Err(io::Error::new(io::ErrorKind::NotFound, "gone"))? }
+        with "reading record", { id: 7 }
+    }
+}
+
+#[test]
+fn matching_root_is_transformed() {
+    let err = read().unwrap_err().map_if::<io::Error, _>(StoreError::from);
+    assert_eq!(err.downcast_ref::<StoreError>(), Some(&StoreError::Missing));
+    assert_eq!(err.message(), "record missing");
+    assert!(err.downcast_ref::<io::Error>().is_none());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn trace_context_and_kv_survive() {
+    let before = read().unwrap_err();
+    let depth = before.depth();
+    let line = before.frames().next().unwrap().line;
+
+    let err = before.map_if::<io::Error, _>(StoreError::from);
+    assert_eq!(err.depth(), depth);
+    let frame = err.frames().next().unwrap();
+    assert_eq!(frame.line, line);
+    assert_eq!(frame.context, Some("reading record"));
+    assert_eq!(err.get_kv("id").map(|v| v.to_string()), Some("7".to_string()));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn other_roots_are_untouched() {
+    let err = Handled::msg("plain").frame("a.rs", 1, 1);
+    let err = err.map_if::<io::Error, _>(StoreError::from);
+    assert_eq!(err.message(), "plain");
+    assert_eq!(err.depth(), 1);
+}
+
+#[test]
+fn calls_chain_for_several_types() {
+    let err = "x"
+        .parse::<i32>()
+        .map_err(Handled::wrap)
+        .unwrap_err()
+        .map_if::<io::Error, _>(StoreError::from)
+        .map_if::<std::num::ParseIntError, _>(|_| StoreError::Io(io::ErrorKind::InvalidData));
+    assert_eq!(err.downcast_ref::<StoreError>(), Some(&StoreError::Io(io::ErrorKind::InvalidData)));
+}
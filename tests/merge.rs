@@ -0,0 +1,50 @@
+//! Tests for `Handled::merge` and `MergeStrategy`.
+
+use handle_this::{Handled, MergeStrategy};
+
+fn first() -> Handled {
+    Handled::msg("read failed").frame("a.rs", 10, 1).ctx("reading config").kv("path", "app.toml")
+}
+
+fn second() -> Handled {
+    Handled::msg("close failed").frame("b.rs", 20, 1).ctx("closing file").kv("fd", 3)
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn keep_first_drops_second() {
+    let err = first().merge(second(), MergeStrategy::KeepFirst);
+    assert_eq!(err.message(), "read failed");
+    assert_eq!(err.depth(), 1);
+    assert_eq!(err.context_messages(), ["read failed"]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn keep_last_drops_first() {
+    let err = first().merge(second(), MergeStrategy::KeepLast);
+    assert_eq!(err.message(), "close failed");
+    assert_eq!(err.frames().next().unwrap().file, "b.rs");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn combine_messages_joins_and_unions_attachments() {
+    let err = first().merge(second(), MergeStrategy::CombineMessages);
+    assert_eq!(err.message(), "read failed; close failed");
+
+    let frames: Vec<_> = err.frames().collect();
+    assert_eq!(frames.len(), 2);
+    assert_eq!((frames[0].file, frames[0].context), ("a.rs", Some("reading config")));
+    assert_eq!((frames[1].file, frames[1].context), ("b.rs", Some("closing file")));
+
+    let keys: Vec<_> = err.frames().flat_map(|f| f.attachments().map(|(k, _)| k)).collect();
+    assert_eq!(keys, ["path", "fd"]);
+}
+
+#[test]
+fn keep_first_chain_second_links_errors() {
+    let err = first().merge(second(), MergeStrategy::KeepFirstChainSecond);
+    assert_eq!(err.message(), "read failed");
+    assert_eq!(err.context_messages(), ["read failed", "close failed"]);
+}
@@ -0,0 +1,70 @@
+//! Built-in counters and the `metric_inc` clause under the `metrics` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "metrics")]
+
+mod common;
+
+use common::io_fail;
+use handle_this::{handle, metrics, Result};
+
+fn counted() -> Result<i32> {
+    handle! {
+        try { io_fail()? }
+        metric_inc "tests.counted"
+    }
+}
+
+#[test]
+fn two_errors_count_two() {
+    let _ = counted();
+    let _ = counted();
+    assert_eq!(metrics::counter("tests.counted").get(), 2);
+}
+
+#[test]
+fn success_path_does_not_count() {
+    let _: Result<i32> = handle! {
+        try { 1 }
+        metric_inc "tests.success"
+    };
+    assert_eq!(metrics::counter("tests.success").get(), 0);
+}
+
+#[test]
+fn later_handlers_still_run() {
+    let result: Result<i32> = handle! {
+        try { io_fail()? }
+        metric_inc "tests.then_catch"
+        catch { 5 }
+    };
+    assert_eq!(result.unwrap(), 5);
+    assert_eq!(metrics::counter("tests.then_catch").get(), 1);
+}
+
+#[test]
+fn name_can_be_an_expression() {
+    let site = "tests.dynamic";
+    let _: Result<i32> = handle! { try { io_fail()? } metric_inc site };
+    assert_eq!(metrics::counter(site).get(), 1);
+}
+
+#[test]
+fn snapshot_lists_counters_sorted() {
+    metrics::counter("tests.snap.b").add(3);
+    metrics::counter("tests.snap.a").inc();
+    let snap: Vec<_> = metrics::snapshot()
+        .into_iter()
+        .filter(|(name, _)| name.starts_with("tests.snap."))
+        .collect();
+    assert_eq!(snap, [("tests.snap.a".to_string(), 1), ("tests.snap.b".to_string(), 3)]);
+}
+
+#[tokio::test]
+async fn metric_inc_async() {
+    let _: Result<i32> = handle! {
+        async try { io_fail()? }
+        metric_inc "tests.async"
+    };
+    assert_eq!(metrics::counter("tests.async").get(), 1);
+}
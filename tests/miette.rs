@@ -0,0 +1,79 @@
+//! `miette::Diagnostic` for `Handled` (`miette` feature).
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "miette")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result, Severity};
+use miette::{Diagnostic, NarratableReportHandler};
+
+fn failing() -> Result<()> {
+    handle! {
+        try { Err("disk full")? }
+        with "saving", { path: "/tmp/out", attempt: 2 }, code "E42", severity Warn
+    }
+}
+
+fn narrate(err: &Handled) -> String {
+    let mut out = String::new();
+    NarratableReportHandler::new().render_report(&mut out, err).unwrap();
+    out
+}
+
+#[test]
+fn code_and_severity() {
+    let err = failing().unwrap_err();
+    let diag: &dyn Diagnostic = &err;
+    assert_eq!(diag.code().unwrap().to_string(), "E42");
+    assert_eq!(diag.severity(), Some(miette::Severity::Warning));
+
+    let fatal = Handled::msg("down").with_severity(Severity::Fatal);
+    assert_eq!(Diagnostic::severity(&fatal), Some(miette::Severity::Error));
+    assert!(Diagnostic::code(&Handled::msg("plain")).is_none());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn attachments_become_help() {
+    let err = failing().unwrap_err();
+    let help = Diagnostic::help(&err).unwrap().to_string();
+    assert_eq!(help, "attempt: 2\npath: /tmp/out");
+    assert!(Diagnostic::help(&Handled::msg("plain")).is_none());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frames_become_related() {
+    let err = Handled::msg("boom").frame("src/db.rs", 12, 5).frame("src/api.rs", 40, 9);
+    let related: Vec<_> = Diagnostic::related(&err).unwrap().map(|d| d.to_string()).collect();
+    assert_eq!(related, ["at src/db.rs:12:5", "at src/api.rs:40:9"]);
+    assert!(Diagnostic::related(&Handled::msg("plain")).is_none());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn chained_errors_follow_the_frames() {
+    let err = Handled::msg("second").frame("b.rs", 2, 1).chain_after(Handled::msg("first"));
+    let related: Vec<_> = Diagnostic::related(&err).unwrap().collect();
+    assert_eq!(related.len(), 2);
+    assert!(related[1].to_string().starts_with("first"));
+}
+
+#[test]
+fn converts_into_a_report() {
+    fn run() -> miette::Result<()> {
+        failing()?;
+        Ok(())
+    }
+    let report = run().unwrap_err();
+    assert_eq!(report.code().unwrap().to_string(), "E42");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn renders_with_a_report_handler() {
+    let out = narrate(&failing().unwrap_err());
+    assert!(out.contains("disk full"), "{}", out);
+    assert!(out.contains("help: attempt: 2"), "{}", out);
+    assert!(out.contains("tests/miette.rs"), "{}", out);
+}
@@ -0,0 +1,110 @@
+//! Tests for multi-type `catch A | B (e) { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::error::Error;
+use std::{fmt, io, num};
+
+fn fail_io() -> std::result::Result<i32, io::Error> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "missing"))
+}
+
+fn fail_parse() -> std::result::Result<i32, num::ParseIntError> {
+    "x".parse()
+}
+
+fn fail_fmt() -> std::result::Result<i32, fmt::Error> {
+    Err(fmt::Error)
+}
+
+#[test]
+fn each_listed_type_is_caught() {
+    let io: Result<String> = handle! {
+        try { fail_io()?.to_string() }
+        catch io::Error | num::ParseIntError (e) { format!("recovered: {}", e) }
+    };
+    assert_eq!(io.unwrap(), "recovered: missing");
+
+    let parse: Result<String> = handle! {
+        try { fail_parse()?.to_string() }
+        catch io::Error | num::ParseIntError (e) { format!("recovered: {}", e) }
+    };
+    assert_eq!(parse.unwrap(), "recovered: invalid digit found in string");
+}
+
+#[test]
+fn unlisted_types_propagate() {
+    let result: Result<i32> = handle! {
+        try { fail_fmt()? }
+        catch io::Error | num::ParseIntError (_) { -1 }
+    };
+    assert!(result.unwrap_err().downcast_ref::<fmt::Error>().is_some());
+}
+
+#[test]
+fn binding_is_a_dyn_error_that_can_be_downcast() {
+    let result: Result<&str> = handle! {
+        try { fail_parse()?; "ok" }
+        catch io::Error | num::ParseIntError (e) {
+            let e: &(dyn Error + 'static) = e;
+            if e.downcast_ref::<num::ParseIntError>().is_some() { "parse" } else { "io" }
+        }
+    };
+    assert_eq!(result.unwrap(), "parse");
+}
+
+#[test]
+fn three_types_and_a_following_catch_all() {
+    let result: Result<i32> = handle! {
+        try { fail_fmt()? }
+        catch io::Error | num::ParseIntError | fmt::Error (_) { 1 }
+        catch { 2 }
+    };
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[test]
+fn guard_sees_the_dyn_binding() {
+    let result: Result<i32> = handle! {
+        try { fail_io()? }
+        catch io::Error | fmt::Error (e) when e.to_string() == "missing" { 1 }
+        catch { 2 }
+    };
+    assert_eq!(result.unwrap(), 1);
+
+    let result: Result<i32> = handle! {
+        try { fail_fmt()? }
+        catch io::Error | fmt::Error (e) when e.to_string() == "missing" { 1 }
+        catch { 2 }
+    };
+    assert_eq!(result.unwrap(), 2);
+}
+
+#[test]
+fn else_follows_a_multi_type_catch() {
+    let result: Result<i32> = handle! {
+        try { fail_fmt()? }
+        catch io::Error | num::ParseIntError (_) { 1 } else { 2 }
+    };
+    assert_eq!(result.unwrap(), 2);
+}
+
+#[test]
+fn works_in_loops() {
+    let inputs = ["a", "b"];
+    let result: Result<i32> = handle! {
+        try for s in inputs { s.parse::<i32>()? }
+        catch io::Error | num::ParseIntError (e) { e.to_string().len() as i32 }
+    };
+    assert!(result.unwrap() > 0);
+}
+
+#[tokio::test]
+async fn works_in_async_try() {
+    let result: Result<i32> = handle! {
+        async try { fail_io()? }
+        catch num::ParseIntError | io::Error (e) { e.to_string().len() as i32 }
+    };
+    assert_eq!(result.unwrap(), 7);
+}
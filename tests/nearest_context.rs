@@ -0,0 +1,43 @@
+//! `Handled::nearest_context` inherits context from enclosing frames.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+
+fn leaf() -> Result<()> {
+    handle! { try { Err("boom")? } }
+}
+
+fn middle() -> Result<()> {
+    handle! { try { leaf()? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn leaf_inherits_enclosing_scope() {
+    let result: Result<()> = handle! { scope "handling request", try { middle()? } };
+    let err = result.unwrap_err();
+
+    assert_eq!(err.frames().next().unwrap().context, None);
+    assert_eq!(err.nearest_context(0), Some("handling request"));
+    assert_eq!(err.nearest_context(1), Some("handling request"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn own_context_wins() {
+    let result: Result<()> = handle! {
+        scope "outer", try { handle! { try { leaf()? } with "inner" }? }
+    };
+    let err = result.unwrap_err();
+    let idx = err.frames().position(|f| f.context == Some("inner")).unwrap();
+    assert_eq!(err.nearest_context(idx), Some("inner"));
+    assert_eq!(err.nearest_context(0), Some("inner"));
+}
+
+#[test]
+fn no_context_anywhere() {
+    let err = middle().unwrap_err();
+    assert_eq!(err.nearest_context(0), None);
+    assert_eq!(err.nearest_context(99), None);
+}
@@ -0,0 +1,70 @@
+//! `handle!` under `#![no_std]`, built against handle-this without `std`.
+//!
+//! Run with `cargo test --manifest-path tests/no_std/Cargo.toml`.
+
+#![no_std]
+#![allow(clippy::result_large_err)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use handle_this::{handle, CoreError, Handled, Result};
+
+/// A sensor that stopped answering.
+#[derive(Debug)]
+pub struct SensorFault(pub u8);
+
+impl fmt::Display for SensorFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sensor {} faulted", self.0)
+    }
+}
+
+impl CoreError for SensorFault {}
+
+fn read(id: u8) -> core::result::Result<u8, SensorFault> {
+    if id % 2 == 0 {
+        Ok(id * 10)
+    } else {
+        Err(SensorFault(id))
+    }
+}
+
+/// Typed catch with a guard: faults on high sensors read as 0.
+pub fn read_or_zero(id: u8) -> Result<u8> {
+    handle! {
+        try { read(id)? }
+        catch SensorFault(e) when e.0 > 2 { 0 }
+    }
+}
+
+/// The first sensor that faulted in a chain of attempts, via `chain_any`.
+pub fn first_fault(ids: &[u8]) -> Option<u8> {
+    let mut err = Handled::msg("all sensors down");
+    for &id in ids {
+        if let Err(fault) = read(id) {
+            err = err.chain_after(Handled::wrap(fault));
+        }
+    }
+    err.chain_any::<SensorFault>().map(|e| e.0)
+}
+
+/// The first reading, or the number of faults if every attempt failed.
+pub fn count_faults(ids: &[u8]) -> Result<u8> {
+    handle! {
+        try any id in ids.iter().copied() { read(id)? }
+        catch all SensorFault |faults| { faults.len() as u8 }
+    }
+}
+
+/// Ids of every fault in the chain, via `chain_all`.
+pub fn faults(err: &Handled) -> Vec<u8> {
+    err.chain_all::<SensorFault>().iter().map(|e| e.0).collect()
+}
+
+/// A framed error with a field, for the serde round trip.
+pub fn framed(id: u8) -> Result<u8> {
+    handle! { try { read(id)? } with "reading sensor", { id: id } }
+}
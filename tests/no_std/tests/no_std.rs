@@ -0,0 +1,50 @@
+//! Behavior of the `#![no_std]` crate's `handle!` uses.
+
+use handle_this::{set_redaction_key, Handled, Value, UNKEYED_REDACTION};
+use handle_this_no_std::{count_faults, faults, first_fault, framed, read_or_zero, SensorFault};
+
+#[test]
+fn typed_catch_with_core_error() {
+    assert_eq!(read_or_zero(4).unwrap(), 40);
+    assert_eq!(read_or_zero(3).unwrap(), 0);
+    let err = read_or_zero(1).unwrap_err();
+    assert_eq!(err.downcast_ref::<SensorFault>().map(|e| e.0), Some(1));
+}
+
+#[test]
+fn chain_search() {
+    assert_eq!(first_fault(&[1, 2, 5]), Some(5));
+    assert_eq!(first_fault(&[2, 4]), None);
+
+    let err = Handled::msg("down")
+        .chain_after(Handled::wrap(SensorFault(3)))
+        .chain_after(Handled::wrap(SensorFault(7)));
+    assert_eq!(faults(&err), [7, 3]);
+}
+
+#[test]
+fn catch_all_collects_attempts() {
+    assert_eq!(count_faults(&[1, 3, 5]).unwrap(), 3);
+}
+
+#[test]
+fn serde_round_trip() {
+    let err = framed(9).unwrap_err();
+    let json = serde_json::to_string(&err).unwrap();
+    let back: Handled = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.message(), err.message());
+    assert_eq!(back.depth(), err.depth());
+    assert!(back.get_kv("id").is_some());
+}
+
+#[test]
+fn redaction_without_key_records_nothing() {
+    let before = Value::redact("hunter2");
+    assert_eq!(before, Value::Redacted(UNKEYED_REDACTION));
+    assert_eq!(before, Value::redact("hunter3"));
+
+    assert!(set_redaction_key(*b"per-deploy key!!"));
+    let after = Value::redact("hunter2");
+    assert_ne!(after, Value::Redacted(UNKEYED_REDACTION));
+    assert_ne!(after, Value::redact("hunter3"));
+}
@@ -0,0 +1,54 @@
+//! `Handled::note_at` annotates a source position without claiming propagation.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+
+fn load() -> Result<u64> {
+    handle! { try { "soon".parse::<u64>()? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn note_frame_is_flagged() {
+    let err = load()
+        .map_err(|e| e.note_at("config/app.toml", 12, 1, "timeout set here"))
+        .unwrap_err();
+    let flags: Vec<bool> = err.frames().map(|f| f.is_note()).collect();
+    assert_eq!(flags, [false, true]);
+
+    let note = err.frames().last().unwrap();
+    assert_eq!((note.file, note.line, note.col), ("config/app.toml", 12, 1));
+    assert_eq!(note.context, Some("timeout set here"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn note_renders_distinctly() {
+    let err = Handled::msg("bad timeout")
+        .frame("src/client.rs", 40, 9)
+        .note_at("config/app.toml", 12, 1, "timeout set here");
+    let text = err.to_string();
+    assert!(text.contains("  src/client.rs:40:9\n"));
+    assert!(text.contains("  config/app.toml:12:1 (note)\n    \u{2192} timeout set here"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn plain_context_is_not_a_note() {
+    let err = Handled::msg("bad").frame("a.rs", 1, 1).ctx("loading");
+    assert!(!err.frames().next().unwrap().is_note());
+    assert!(!err.to_string().contains("(note)"));
+}
+
+#[cfg(feature = "serde")]
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn serde_round_trip_keeps_note() {
+    let err = Handled::msg("bad").frame("a.rs", 1, 1).note_at("b.toml", 2, 3, "here");
+    let json = serde_json::to_string(&err).unwrap();
+    assert!(json.contains("\"note\":true"));
+    let back: Handled = serde_json::from_str(&json).unwrap();
+    assert!(back.is_same_as(&err));
+}
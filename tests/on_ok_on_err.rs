@@ -0,0 +1,126 @@
+//! Tests for the `on_ok |v| { }` and `on_err |e| { }` tap clauses.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::io_fail;
+use handle_this::{handle, Result};
+
+#[derive(Default)]
+struct Metrics {
+    success: u32,
+    failure: u32,
+    last: Option<i32>,
+}
+
+fn run(ok: bool, m: &mut Metrics) -> Result<i32> {
+    handle! {
+        try { if ok { 21 * 2 } else { io_fail()? } }
+        on_ok |v| { m.success += 1; m.last = Some(*v) }
+        on_err |_e| { m.failure += 1 }
+    }
+}
+
+#[test]
+fn on_ok_fires_on_success_only() {
+    let mut m = Metrics::default();
+    assert_eq!(run(true, &mut m).unwrap(), 42);
+    assert_eq!((m.success, m.failure, m.last), (1, 0, Some(42)));
+}
+
+#[test]
+fn on_err_fires_on_error_only() {
+    let mut m = Metrics::default();
+    assert!(run(false, &mut m).is_err());
+    assert_eq!((m.success, m.failure, m.last), (0, 1, None));
+}
+
+#[test]
+fn on_err_does_not_change_error() {
+    let result: Result<i32> = handle! {
+        try { io_fail()? }
+        on_err |e| { assert_eq!(e.message(), "boom") }
+    };
+    assert_eq!(result.unwrap_err().message(), "boom");
+}
+
+#[test]
+fn on_err_with_guard() {
+    let mut hits = 0;
+    let _: Result<i32> = handle! {
+        try { io_fail()? }
+        on_err |e| when e.message() == "other" { hits += 1 }
+    };
+    assert_eq!(hits, 0);
+}
+
+#[test]
+fn on_ok_skipped_when_catch_recovers() {
+    let mut taps = 0;
+    let result: Result<i32> = handle! {
+        try { io_fail()? }
+        catch { 7 }
+        on_ok |_v| { taps += 1 }
+    };
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(taps, 0);
+}
+
+#[test]
+fn on_ok_in_direct_mode_sees_non_copy_value() {
+    let mut seen = String::new();
+    let value = handle! {
+        try -> String { "hello".to_string() }
+        on_ok |s| { seen = s.clone() }
+        else { String::new() }
+    };
+    assert_eq!(value, "hello");
+    assert_eq!(seen, "hello");
+}
+
+#[test]
+fn multiple_taps_run_in_order() {
+    let mut order = Vec::new();
+    let _: Result<i32> = handle! {
+        try { 1 }
+        on_ok |_v| { order.push("first") }
+        on_ok |_v| { order.push("second") }
+    };
+    assert_eq!(order, ["first", "second"]);
+}
+
+#[test]
+fn on_err_in_loop_pattern() {
+    let mut failures = 0;
+    let result: Result<i32> = handle! {
+        try for x in [1, 2, 3] { if x < 3 { io_fail()? } else { x } }
+        on_err |_e| { failures += 1 }
+    };
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(failures, 0);
+
+    let result: Result<i32> = handle! {
+        try for _x in [1, 2] { io_fail()? }
+        on_err |_e| { failures += 1 }
+    };
+    assert!(result.is_err());
+    assert_eq!(failures, 1);
+}
+
+#[tokio::test]
+async fn taps_in_async() {
+    let mut ok = 0;
+    let mut err = 0;
+    let _: Result<i32> = handle! {
+        async try { 5 }
+        on_ok |_v| { ok += 1 }
+        on_err |_e| { err += 1 }
+    };
+    let _: Result<i32> = handle! {
+        async try { io_fail()? }
+        on_ok |_v| { ok += 1 }
+        on_err |_e| { err += 1 }
+    };
+    assert_eq!((ok, err), (1, 1));
+}
@@ -0,0 +1,48 @@
+//! `only_in_tests` runs its body under `cfg(test)` and propagates like inspect.
+//!
+//! The non-test path is exercised by `examples/only_in_tests.rs`, which
+//! references a `#[cfg(test)]` item inside the handler and only compiles
+//! if the body is stripped.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::cell::Cell;
+
+fn fail() -> Result<i32> {
+    Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))?
+}
+
+#[test]
+fn only_in_tests_runs_under_cfg_test() {
+    let hits = Cell::new(0);
+    let result: Result<i32> = handle! {
+        try { fail()? }
+        only_in_tests e { hits.set(hits.get() + 1); assert_eq!(e.message(), "boom"); }
+    };
+    assert!(result.is_err());
+    assert_eq!(hits.get(), 1);
+}
+
+#[test]
+fn only_in_tests_before_catch() {
+    let hits = Cell::new(0);
+    let result: Result<i32> = handle! {
+        try { fail()? }
+        only_in_tests { hits.set(hits.get() + 1) }
+        catch { 7 }
+    };
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(hits.get(), 1);
+}
+
+#[test]
+fn only_in_tests_typed() {
+    let hits = Cell::new(0);
+    let _: Result<i32> = handle! {
+        try { fail()? }
+        only_in_tests std::num::ParseIntError(_e) { hits.set(100) }
+        only_in_tests std::io::Error(e) { assert_eq!(e.kind(), std::io::ErrorKind::Other); hits.set(hits.get() + 1) }
+    };
+    assert_eq!(hits.get(), 1);
+}
@@ -0,0 +1,46 @@
+//! Tests for `OptionExt::or_err` / `or_err_kv`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, OptionExt, Result, Value};
+
+#[test]
+fn some_passes_through() {
+    assert_eq!(Some(3).or_err("missing").unwrap(), 3);
+    assert_eq!(Some("x").or_err_kv("missing", "id", 1).unwrap(), "x");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn none_is_framed_at_the_caller() {
+    let line = line!() + 1;
+    let err = None::<i32>.or_err("missing user").unwrap_err();
+    assert_eq!(err.message(), "missing user");
+    assert_eq!(err.depth(), 1);
+    let frame = err.frames().next().unwrap();
+    assert_eq!((frame.file, frame.line), (file!(), line));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn kv_is_attached_to_the_frame() {
+    let err = None::<i32>.or_err_kv("missing user", "user_id", 42).unwrap_err();
+    let attachments: Vec<_> = err.frames().next().unwrap().attachments().collect();
+    assert_eq!(attachments, [("user_id", &Value::Int(42))]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn works_with_question_mark_in_handle() {
+    fn lookup(name: &str) -> Result<usize> {
+        handle! {
+            try { ["ada", "bob"].iter().position(|n| *n == name).or_err_kv("unknown name", "name", name.to_string())? }
+            with "looking up"
+        }
+    }
+    assert_eq!(lookup("bob").unwrap(), 1);
+    let err = lookup("eve").unwrap_err();
+    assert_eq!(err.message(), "unknown name");
+    assert!(err.frames().any(|f| f.context == Some("looking up")));
+}
@@ -0,0 +1,84 @@
+//! Tests for `try all x in iter { } partial |oks, errs| { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, CombinedError, Handled, Result};
+
+fn parse_all(items: &[&str]) -> Result<(Vec<i32>, Vec<Handled>)> {
+    handle! {
+        try all s in items.iter() { s.parse::<i32>()? }
+        partial |oks, errs| { (oks, errs) }
+    }
+}
+
+#[test]
+fn keeps_every_success_and_failure() {
+    let (oks, errs) = parse_all(&["1", "x", "3", "y"]).unwrap();
+    assert_eq!(oks, vec![1, 3]);
+    assert_eq!(errs.len(), 2);
+    assert!(errs.iter().all(|e| e.downcast_ref::<std::num::ParseIntError>().is_some()));
+}
+
+#[test]
+fn runs_when_everything_succeeds() {
+    let (oks, errs) = parse_all(&["1", "2"]).unwrap();
+    assert_eq!(oks, vec![1, 2]);
+    assert!(errs.is_empty());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn failures_carry_frames_and_context() {
+    let errs: Vec<Handled> = handle! {
+        try all s in ["a", "b"] { s.parse::<i32>()? }
+        partial |_, errs| { errs }
+        with "parsing batch", { batch: 7 }
+    }
+    .unwrap();
+    assert_eq!(errs.len(), 2);
+    for err in &errs {
+        assert!(err.frames().any(|f| f.context == Some("parsing batch")));
+        assert!(err.frames().any(|f| f.attachments().any(|(k, _)| k == "batch")));
+    }
+}
+
+#[test]
+fn report_errors_flow_to_handlers() {
+    let result: Result<usize> = handle! {
+        try all s in ["1", "x"] { s.parse::<i32>()? }
+        partial |oks, errs| {
+            if !errs.is_empty() {
+                Err(Handled::combine(errs))?
+            }
+            oks.len()
+        }
+        catch CombinedError(c) { c.errors().len() + 100 }
+    };
+    assert_eq!(result.unwrap(), 101);
+}
+
+#[test]
+fn report_errors_propagate_without_handlers() {
+    let result: Result<Vec<i32>> = handle! {
+        try all s in ["x"] { s.parse::<i32>()? }
+        partial |oks, errs| {
+            if let Some(first) = errs.into_iter().next() {
+                Err(first)?
+            }
+            oks
+        }
+    };
+    assert!(result.unwrap_err().chain_any::<std::num::ParseIntError>().is_some());
+}
+
+#[test]
+fn nests_inside_try() {
+    let result: Result<usize> = handle! {
+        try {
+            let counts = try all s in ["1", "x", "y"] { s.parse::<i32>()? }
+                partial |oks, errs| { (oks.len(), errs.len()) };
+            counts.0 * 10 + counts.1
+        }
+    };
+    assert_eq!(result.unwrap(), 12);
+}
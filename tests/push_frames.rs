@@ -0,0 +1,28 @@
+//! Tests for `Handled::push_frames`.
+
+#![cfg(not(feature = "no-trace"))]
+
+use handle_this::{Handled, DEFAULT_LOCATION_LIMIT as LIMIT};
+
+#[test]
+fn appends_frames_in_order() {
+    let frames = [("a.rs", 1, 1), ("b.rs", 2, 2), ("c.rs", 3, 3), ("d.rs", 4, 4), ("e.rs", 5, 5)];
+    let err = Handled::msg("replayed").push_frames(frames);
+    assert_eq!(err.depth(), 5);
+    let files: Vec<_> = err.frames().map(|f| f.file).collect();
+    assert_eq!(files, ["a.rs", "b.rs", "c.rs", "d.rs", "e.rs"]);
+}
+
+#[test]
+fn appends_after_existing_frames() {
+    let err = Handled::msg("x").frame("first.rs", 9, 1).push_frames(vec![("next.rs", 10, 2)]);
+    assert_eq!(err.origin_string().as_deref(), Some("first.rs:9:1"));
+    assert_eq!(err.location_string().as_deref(), Some("next.rs:10:2"));
+}
+
+#[test]
+fn respects_frame_limit() {
+    let frames = (0..LIMIT as u32 + 10).map(|i| ("deep.rs", i, 1));
+    let err = Handled::msg("deep").frame("top.rs", 1, 1).push_frames(frames);
+    assert_eq!(err.depth(), LIMIT);
+}
@@ -0,0 +1,122 @@
+//! Tests for `async try race { primary(), fallback() }`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use std::cell::RefCell;
+
+/// Records which futures started and which finished.
+#[derive(Default)]
+struct Log(RefCell<Vec<String>>);
+
+impl Log {
+    /// Yield `steps` times, then succeed with `value` (or fail if negative).
+    async fn step(&self, name: &'static str, steps: usize, value: i32) -> std::result::Result<i32, Bad> {
+        self.0.borrow_mut().push(format!("{} start", name));
+        for _ in 0..steps {
+            tokio::task::yield_now().await;
+        }
+        self.0.borrow_mut().push(format!("{} done", name));
+        if value < 0 {
+            Err(Bad(name))
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bad(&'static str);
+
+impl std::fmt::Display for Bad {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bad {}", self.0)
+    }
+}
+
+impl std::error::Error for Bad {}
+
+fn race_label(err: &Handled) -> Option<String> {
+    err.frames()
+        .flat_map(|f| f.attachments())
+        .find(|(k, _)| *k == "race")
+        .map(|(_, v)| v.to_string())
+}
+
+#[tokio::test]
+async fn first_success_wins_and_cancels_the_rest() {
+    let log = Log::default();
+    let result: Result<i32> = handle! {
+        async try race { slow: log.step("slow", 5, 1), fast: log.step("fast", 1, 2) }
+    };
+    assert_eq!(result.unwrap(), 2);
+    // The slow future was dropped before it finished
+    assert_eq!(*log.0.borrow(), ["slow start", "fast start", "fast done"]);
+}
+
+#[tokio::test]
+async fn failures_before_a_success_are_dropped() {
+    let log = Log::default();
+    let result: Result<i32> = handle! {
+        async try race { log.step("primary", 0, -1), log.step("fallback", 2, 7) }
+    };
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn all_failures_are_chained() {
+    let log = Log::default();
+    let result: Result<i32> = handle! {
+        async try race { a: log.step("a", 2, -1), b: log.step("b", 0, -2), c: log.step("c", 1, -3) }
+    };
+    let err = result.unwrap_err();
+    // Chained in entry order, the last entry on top
+    let names: Vec<_> = err.chain_all::<Bad>().into_iter().map(|b| b.0).collect();
+    assert_eq!(names, ["c", "b", "a"]);
+    assert_eq!(race_label(&err).as_deref(), Some("c"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn unlabeled_failures_are_tagged_by_index() {
+    let result: Result<i32> = handle! {
+        async try race { async { Err::<i32, _>(Bad("only")) } }
+    };
+    assert_eq!(race_label(&result.unwrap_err()).as_deref(), Some("0"));
+}
+
+#[tokio::test]
+async fn catch_all_sees_every_failure() {
+    let log = Log::default();
+    let value = handle! {
+        async try race { log.step("a", 0, -1), log.step("b", 0, -2) }
+        catch all Bad |errs| { errs.len() as i32 }
+    };
+    assert_eq!(value.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn finally_runs() {
+    let log = Log::default();
+    let cleaned = RefCell::new(false);
+    let result: Result<i32> = handle! {
+        async try race { log.step("a", 0, 1) }
+        finally { *cleaned.borrow_mut() = true; }
+    };
+    assert_eq!(result.unwrap(), 1);
+    assert!(*cleaned.borrow());
+}
+
+#[tokio::test]
+async fn nested_in_try() {
+    let log = Log::default();
+    let result: Result<i32> = handle! {
+        async try {
+            let v = async try race { log.step("a", 1, 4), log.step("b", 0, 5) };
+            v * 10
+        }
+    };
+    assert_eq!(result.unwrap(), 50);
+}
@@ -0,0 +1,88 @@
+//! Redacted attachments: `kv_secret` and `with { key: secret value }`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result, Value};
+
+fn login(user: &str, password: &str) -> Result<()> {
+    handle! {
+        try { Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "bad credentials"))? }
+        with "logging in", { user: user, password: secret password }
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn secret_values_never_reach_the_output() {
+    let err = login("ada", "hunter2").unwrap_err();
+    assert_eq!(err.get_kv("user"), Some(&Value::from("ada")));
+    assert_eq!(err.get_kv("password"), Some(&Value::redact("hunter2")));
+
+    let text = format!("{} {:?} {}", err, err, err.to_json());
+    assert!(!text.contains("hunter2"), "{}", text);
+    assert!(err.to_string().contains("password: ***"), "{}", err);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn same_secret_same_hash() {
+    let a = login("ada", "hunter2").unwrap_err();
+    let b = Handled::msg("retry").kv_secret("password", String::from("hunter2"));
+    let c = login("ada", "letmein").unwrap_err();
+    assert_eq!(a.get_kv("password"), b.get_kv("password"));
+    assert_ne!(a.get_kv("password"), c.get_kv("password"));
+
+    // Redacting twice keeps the original hash
+    assert_eq!(Value::redact(Value::redact(42)), Value::redact(42));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn json_carries_the_hash() {
+    let Value::Redacted(hash) = Value::redact("hunter2") else { panic!("not redacted") };
+    let json = Handled::msg("x").frame("a.rs", 1, 1).kv_secret("pw", "hunter2").to_json();
+    assert!(json.contains(&format!(r#""pw":{{"$redacted":"{:016x}"}}"#, hash)), "{}", json);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn secret_is_still_a_variable_name() {
+    let secret = "visible";
+    let result: Result<()> = handle! {
+        try { Err(Handled::msg("x"))? }
+        with { plain: secret, arrow: secret.len(), hidden: secret &secret }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.get_kv("plain"), Some(&Value::from("visible")));
+    assert_eq!(err.get_kv("arrow"), Some(&Value::from(7usize)));
+    assert_eq!(err.get_kv("hidden"), Some(&Value::redact("visible")));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let value = Value::redact("hunter2");
+    let json = serde_json::to_string(&value).unwrap();
+    assert!(json.starts_with(r#"{"$redacted":""#), "{}", json);
+    let back: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, value);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn plain_strings_stay_strings() {
+    let back: Value = serde_json::from_str(r#""redacted:0123456789abcdef""#).unwrap();
+    assert_eq!(back, Value::from("redacted:0123456789abcdef"));
+    let back: Value = serde_json::from_str(r#"{"$redacted":"not hex"}"#).unwrap();
+    assert!(matches!(back, Value::Map(_)));
+}
+
+#[test]
+fn hash_is_keyed() {
+    // Unkeyed 64-bit FNV-1a of "hunter2", which anyone could precompute
+    let fnv = "hunter2".bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    assert_ne!(Value::redact("hunter2"), Value::Redacted(fnv));
+}
@@ -0,0 +1,75 @@
+//! Tests for `Handled::render_pretty` (color feature).
+#![cfg(feature = "color")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::Handled;
+
+fn sample() -> Handled {
+    Handled::msg("root cause")
+        .frame("src/db.rs", 2, 5)
+        .ctx("in inner")
+        .kv("order_id", 12345)
+        .frame("src/api.rs", 6, 5)
+        .ctx("in outer")
+        .note_at("config/app.toml", 12, 1, "timeout set here")
+}
+
+/// Lines after the header, i.e. the trace.
+fn trace(rendered: &str) -> Vec<&str> {
+    rendered.split("\n\n").nth(1).unwrap().lines().collect()
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn plain_layout_is_aligned() {
+    let rendered = sample().render_pretty_with(false);
+    assert!(rendered.starts_with("error: root cause\n"));
+    assert_eq!(
+        trace(&rendered),
+        [
+            "  1  src/db.rs:2:5",
+            "     \u{2192} in inner",
+            "     order_id: 12345",
+            "  2  src/api.rs:6:5",
+            "     \u{2192} in outer",
+            "  3  config/app.toml:12:1 (note)",
+            "     \u{2192} timeout set here",
+        ]
+    );
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frame_numbers_are_right_aligned() {
+    let err = Handled::msg("deep").push_frames((1..=10).map(|i| ("deep.rs", i, 1)));
+    let rendered = err.render_pretty_with(false);
+    let lines = trace(&rendered);
+    assert_eq!(lines[0], "   1  deep.rs:1:1");
+    assert_eq!(lines[9], "  10  deep.rs:10:1");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn colors_dim_paths_and_highlight_context() {
+    let rendered = sample().render_pretty_with(true);
+    assert!(rendered.starts_with("\x1b[1;31merror:\x1b[0m \x1b[1mroot cause\x1b[0m\n"));
+    assert!(rendered.contains("\x1b[2msrc/db.rs\x1b[0m:2:5"));
+    assert!(rendered.contains("\x1b[1;36m\u{2192} in inner\x1b[0m"));
+    assert!(rendered.contains("\x1b[35m(note)\x1b[0m"));
+}
+
+#[test]
+fn no_color_without_a_terminal() {
+    // Test output is captured, so stderr isn't a terminal
+    let err = sample();
+    if !std::io::IsTerminal::is_terminal(&std::io::stderr()) {
+        assert_eq!(err.render_pretty(), err.render_pretty_with(false));
+    }
+}
+
+#[test]
+fn no_frames_is_just_the_message() {
+    let rendered = Handled::msg("bare").render_pretty_with(false);
+    assert!(rendered.starts_with("error: bare\n"));
+    assert!(!rendered.contains("\n\n"));
+}
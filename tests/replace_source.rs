@@ -0,0 +1,69 @@
+//! Tests for `Handled::replace_source`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result, StringError};
+use std::{fmt, io};
+
+#[derive(Debug, PartialEq)]
+struct Unavailable(&'static str);
+
+impl fmt::Display for Unavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} unavailable", self.0)
+    }
+}
+
+impl std::error::Error for Unavailable {}
+
+fn inner() -> Result<i32> {
+    handle! {
+        try { Err(io::Error::new(io::ErrorKind::ConnectionRefused, "refused"))? }
+        with "connecting", { port: 5432 }
+    }
+}
+
+fn outer() -> Result<i32> {
+    handle! { try { inner()? } with "loading users" }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frames_and_contexts_survive() {
+    let err = outer().unwrap_err();
+    let depth = err.depth();
+    let contexts: Vec<_> = err.frames().map(|f| f.context.map(str::to_string)).collect();
+
+    let err = err.replace_source(Unavailable("database"));
+    assert_eq!(err.depth(), depth);
+    let after: Vec<_> = err.frames().map(|f| f.context.map(str::to_string)).collect();
+    assert_eq!(after, contexts);
+    assert_eq!(err.frames().next().unwrap().attachments().count(), 1);
+}
+
+#[test]
+fn new_type_is_downcastable_and_old_is_gone() {
+    let err = outer().unwrap_err().replace_source(Unavailable("database"));
+    assert_eq!(err.downcast_ref::<Unavailable>(), Some(&Unavailable("database")));
+    assert!(err.chain_any::<io::Error>().is_none());
+    assert_eq!(err.message(), "database unavailable");
+}
+
+#[test]
+fn chain_and_fields_are_kept() {
+    let err = Handled::msg("second")
+        .chain_after(Handled::msg("first"))
+        .tag("retryable")
+        .replace_source(Unavailable("cache"));
+    assert_eq!(err.chain_all::<StringError>().len(), 1);
+    assert!(err.has_tag("retryable"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn works_on_typed_handled() {
+    let typed = Handled::new(io::Error::new(io::ErrorKind::Other, "x")).frame("a.rs", 1, 2);
+    let err = typed.replace_source(Unavailable("queue"));
+    assert_eq!(err.frames().next().unwrap().line, 1);
+    assert!(err.downcast_ref::<Unavailable>().is_some());
+}
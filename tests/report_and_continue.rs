@@ -0,0 +1,124 @@
+//! `report_and_continue` inside loops.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::parse;
+use handle_this::{handle, Handled, Result};
+
+#[test]
+fn reports_every_failure_and_completes_iteration() -> Result<()> {
+    let mut total = 0;
+    let mut reported: Vec<Handled> = Vec::new();
+    let mut visited = 0;
+
+    for s in ["1", "x", "2", "y", "3"] {
+        visited += 1;
+        let v: Result<i32> = handle! {
+            try { parse(s)? }
+            report_and_continue |e| { reported.push(e) }
+        };
+        total += v?;
+    }
+
+    assert_eq!(visited, 5);
+    assert_eq!(total, 6);
+    assert_eq!(reported.len(), 2);
+    assert!(reported.iter().all(|e| e.message().contains("invalid digit")));
+    Ok(())
+}
+
+#[test]
+fn inside_try_for_body() -> Result<()> {
+    let mut reported = Vec::new();
+    let found: Result<i32> = handle! {
+        try for s in ["a", "b", "7", "8"] {
+            let mut last = 0;
+            for part in s.split(',') {
+                let v: Result<i32> = handle! {
+                    try { parse(part)? }
+                    report_and_continue |e| { reported.push(e.to_string()) }
+                };
+                last = v?;
+            }
+            if last == 0 {
+                Err("no value")?;
+            }
+            last
+        }
+    };
+
+    assert_eq!(found?, 7);
+    assert_eq!(reported.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn try_for_and_try_while_handlers() {
+    let mut reported = 0;
+    let mut firsts = Vec::new();
+    for group in [["x", "4"], ["y", "z"]] {
+        // A catch-all that continues makes the loop form yield the value
+        let first: i32 = handle! {
+            try for s in group { parse(s)? }
+            report_and_continue |_e| { reported += 1 }
+        };
+        firsts.push(first);
+    }
+    assert_eq!(firsts, [4]);
+    assert_eq!(reported, 1);
+
+    let mut tries = 0;
+    let mut skipped = 0;
+    for _ in 0..2 {
+        let _: i32 = handle! {
+            try while tries < 2 limit 1 { tries += 1; Err("busy")? }
+            report_and_continue |_e| { skipped += 1 }
+        };
+    }
+    assert_eq!(skipped, 2);
+}
+
+#[test]
+fn bare_form_reports_to_stderr() {
+    let mut ok = Vec::new();
+    for s in ["1", "oops", "3"] {
+        let v: Result<i32> = handle! { try { parse(s)? } report_and_continue };
+        ok.push(v.unwrap());
+    }
+    assert_eq!(ok, [1, 3]);
+}
+
+#[test]
+fn typed_catch_runs_first() {
+    let mut reported = 0;
+    let mut values = Vec::new();
+    for s in ["1", "x"] {
+        let v: Result<i32> = handle! {
+            try { parse(s)? }
+            catch std::num::ParseIntError(_) { -1 }
+            report_and_continue |_e| { reported += 1 }
+        };
+        values.push(v.unwrap());
+    }
+    assert_eq!(values, [1, -1]);
+    assert_eq!(reported, 0);
+}
+
+#[test]
+fn nested_in_outer_try() {
+    let mut reported = 0;
+    let total: Result<i32> = handle! {
+        try {
+            let mut sum = 0;
+            for s in ["4", "z", "5"] {
+                let v: Result<i32> = try { parse(s)? } report_and_continue |_e| { reported += 1 };
+                sum += v?;
+            }
+            sum
+        }
+    };
+    assert_eq!(total.unwrap(), 9);
+    assert_eq!(reported, 1);
+}
@@ -0,0 +1,109 @@
+//! Tests for `require COND else <error>, ...` with error values after `else`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+enum ConfigError {
+    EmptyPath,
+    TooLarge { limit: u32 },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EmptyPath => write!(f, "path is empty"),
+            ConfigError::TooLarge { limit } => write!(f, "limit {} is too large", limit),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn open(path: &str, limit: u32) -> Result<String> {
+    handle! {
+        require !path.is_empty() else ConfigError::EmptyPath,
+        require limit > 0 else "limit must be positive",
+        require limit <= 100 else ConfigError::TooLarge { limit },
+        try { format!("{}:{}", path, limit) }
+    }
+}
+
+#[test]
+fn passes_when_every_condition_holds() {
+    assert_eq!(open("a.toml", 10).unwrap(), "a.toml:10");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn typed_error_is_the_root() {
+    let err = open("", 10).unwrap_err();
+    assert_eq!(err.downcast_ref::<ConfigError>(), Some(&ConfigError::EmptyPath));
+    assert_eq!(err.depth(), 1);
+
+    let err = open("a.toml", 500).unwrap_err();
+    assert_eq!(err.downcast_ref::<ConfigError>(), Some(&ConfigError::TooLarge { limit: 500 }));
+}
+
+#[test]
+fn conditions_are_checked_in_order() {
+    // Fails the second check; the third would also fail
+    let err = open("a.toml", 0).unwrap_err();
+    assert_eq!(err.message(), "limit must be positive");
+    assert!(err.downcast_ref::<ConfigError>().is_none());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn message_expressions_and_handled_values() {
+    const EMPTY: &str = "nothing to do";
+    let items: Vec<i32> = Vec::new();
+
+    let result: Result<i32> = handle! {
+        require !items.is_empty() else EMPTY,
+        try { items[0] }
+    };
+    assert_eq!(result.unwrap_err().message(), "nothing to do");
+
+    let result: Result<i32> = handle! {
+        require !items.is_empty() else format!("{} items", items.len()),
+        try { items[0] }
+    };
+    assert_eq!(result.unwrap_err().message(), "0 items");
+
+    let result: Result<i32> = handle! {
+        require !items.is_empty() else Handled::msg("empty").kv("len", items.len()),
+        try { items[0] }
+    };
+    assert_eq!(result.unwrap_err().get_kv("len").map(|v| v.to_string()), Some("0".to_string()));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn typed_error_with_context() {
+    let result: Result<i32> = handle! {
+        require false else ConfigError::EmptyPath with "loading config",
+        try { 1 }
+    };
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<ConfigError>().is_some());
+    assert_eq!(err.frames().next().unwrap().context, Some("loading config"));
+}
+
+#[test]
+fn nested_inside_try_body() {
+    fn check(path: &str) -> Result<usize> {
+        handle! {
+            try {
+                require !path.is_empty() else ConfigError::EmptyPath with "nested",
+                try { path.len() }
+            }
+        }
+    }
+    assert_eq!(check("abc").unwrap(), 3);
+    let err = check("").unwrap_err();
+    assert_eq!(err.downcast_ref::<ConfigError>(), Some(&ConfigError::EmptyPath));
+}
@@ -0,0 +1,63 @@
+//! Tests for `require let PAT = EXPR else "msg", ...`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::collections::HashMap;
+
+fn lookup(map: &HashMap<&str, i32>, key: &str) -> Result<i32> {
+    handle! {
+        require let Some(v) = map.get(key) else "missing key",
+        try { *v * 2 }
+    }
+}
+
+#[test]
+fn binds_value_from_option() {
+    let map = HashMap::from([("a", 21)]);
+    assert_eq!(lookup(&map, "a").unwrap(), 42);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn fails_with_message_when_pattern_does_not_match() {
+    let map = HashMap::new();
+    let err = lookup(&map, "a").unwrap_err();
+    assert_eq!(err.message(), "missing key");
+    assert_eq!(err.depth(), 1);
+}
+
+#[test]
+fn binds_multiple_from_result_and_tuple() {
+    let parsed: std::result::Result<(i32, &str), ()> = Ok((7, "seven"));
+    let result: Result<String> = handle! {
+        require let Ok((n, name)) = parsed else { format!("bad input: {:?}", parsed) },
+        try { format!("{name}={n}") }
+    };
+    assert_eq!(result.unwrap(), "seven=7");
+}
+
+#[test]
+fn chains_with_boolean_require_and_context() {
+    let maybe: Option<i32> = Some(-1);
+    let result: Result<i32> = handle! {
+        require let Some(x) = maybe else "no value" with "validating",
+        require x > 0 else "must be positive",
+        try { x }
+    };
+    assert_eq!(result.unwrap_err().message(), "must be positive");
+}
+
+#[test]
+fn nested_inside_try_body() {
+    fn first_number(s: &str) -> Result<u32> {
+        handle! {
+            try {
+                require let Some(w) = s.split_whitespace().next() else "empty input",
+                try { w.parse::<u32>()? }
+            }
+        }
+    }
+    assert_eq!(first_number("12 apples").unwrap(), 12);
+    assert!(first_number("   ").is_err());
+}
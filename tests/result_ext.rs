@@ -0,0 +1,67 @@
+//! Tests for the `HandleExt` builders (`ctx`, `kv`, `here`) on plain and `Handled` results.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+mod common;
+
+use common::missing;
+use handle_this::{handle, HandleExt, Handled, Result};
+use std::io;
+
+fn load(path: &str) -> Result<String> {
+    missing().ctx("loading config").kv("path", path.to_string())
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn foreign_errors_are_wrapped_and_framed() {
+    let err = load("app.toml").unwrap_err();
+    assert_eq!(err.downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::NotFound);
+    assert_eq!(err.depth(), 2);
+
+    let frames: Vec<_> = err.frames().collect();
+    assert_eq!(frames[0].file, file!());
+    assert_eq!(frames[0].context, Some("loading config"));
+    assert_eq!(err.get_kv("path").map(|v| v.to_string()), Some("app.toml".to_string()));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn here_adds_a_bare_frame() {
+    let line = line!() + 1;
+    let err = "x".parse::<i32>().here().unwrap_err();
+    assert_eq!(err.depth(), 1);
+    let frame = err.frames().next().unwrap();
+    assert_eq!(frame.line, line);
+    assert_eq!(frame.context, None);
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn handled_results_are_not_rewrapped() {
+    let inner: Result<i32> = handle! { try { Err(Handled::msg("inner"))? } };
+    let err = inner.ctx("outer").unwrap_err();
+    assert_eq!(err.message(), "inner");
+    assert_eq!(err.depth(), 2);
+    assert!(err.downcast_ref::<Handled>().is_none());
+}
+
+#[test]
+fn ok_values_pass_through() {
+    let ok: std::result::Result<i32, io::Error> = Ok(3);
+    assert_eq!(ok.ctx("unused").kv("k", 1).here().unwrap(), 3);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn older_builders_accept_foreign_errors_too() {
+    let err = missing::<()>().context("reading").attach("attempt", 2).unwrap_err();
+    assert_eq!(err.depth(), 2);
+    assert_eq!(err.get_kv("attempt").map(|v| v.to_string()), Some("2".to_string()));
+
+    let err = missing::<String>().then(|s| Ok(s.len())).unwrap_err();
+    assert_eq!(err.depth(), 0);
+    assert_eq!(err.message(), "no such file");
+}
@@ -0,0 +1,124 @@
+//! Tests for `rethrow` inside catch bodies.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::io;
+
+fn io_err(kind: io::ErrorKind) -> std::result::Result<u32, io::Error> {
+    Err(io::Error::new(kind, "io failed"))
+}
+
+fn load(kind: io::ErrorKind) -> Result<u32> {
+    handle! {
+        try { io_err(kind)? }
+        catch io::Error(e) {
+            if e.kind() != io::ErrorKind::NotFound {
+                rethrow
+            }
+            0
+        }
+    }
+}
+
+#[test]
+fn handled_case_recovers() {
+    assert_eq!(load(io::ErrorKind::NotFound).unwrap(), 0);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn rethrow_keeps_the_original_error_and_trace() {
+    let err = load(io::ErrorKind::PermissionDenied).unwrap_err();
+    assert_eq!(err.downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::PermissionDenied);
+    assert_eq!(err.message(), "io failed");
+    assert_eq!(err.context_messages(), ["io failed"]);
+    // The rethrow frame lands on the same `handle!` line as the catch frame
+    assert_eq!(err.frames().last().unwrap().repeat_count(), 2);
+}
+
+#[test]
+fn rethrow_expr_chains_the_new_error() {
+    let result: Result<u32> = handle! {
+        try { io_err(io::ErrorKind::TimedOut)? }
+        catch io::Error(e) {
+            if e.kind() == io::ErrorKind::TimedOut {
+                rethrow format!("gave up: {}", e);
+            }
+            0
+        }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "gave up: io failed");
+    assert_eq!(err.context_messages(), ["gave up: io failed", "io failed"]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn untyped_catch_rethrows_its_binding() {
+    let mut logged = Vec::new();
+    let result: Result<u32> = handle! {
+        try { Err(Handled::msg("fatal"))? }
+        catch e {
+            logged.push(e.message().to_string());
+            if e.message() == "fatal" { rethrow }
+            1
+        }
+        with "loading"
+    };
+    assert_eq!(logged, ["fatal"]);
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "fatal");
+    assert_eq!(err.frames().next().unwrap().context, Some("loading"));
+}
+
+#[test]
+fn later_handlers_are_skipped() {
+    let result: Result<u32> = handle! {
+        try { io_err(io::ErrorKind::Other)? }
+        catch io::Error(_) { rethrow "not here" }
+        catch { 7 }
+    };
+    assert_eq!(result.unwrap_err().message(), "not here");
+}
+
+#[test]
+fn rethrow_in_a_match_arm() {
+    let classify = |kind| -> Result<&'static str> {
+        handle! {
+            try { io_err(kind).map(|_| "found")? }
+            catch io::Error(e) match e.kind() {
+                io::ErrorKind::NotFound => "missing",
+                io::ErrorKind::PermissionDenied => rethrow "denied",
+                _ => rethrow,
+            }
+        }
+    };
+    assert_eq!(classify(io::ErrorKind::NotFound).unwrap(), "missing");
+    assert_eq!(classify(io::ErrorKind::PermissionDenied).unwrap_err().message(), "denied");
+    assert_eq!(classify(io::ErrorKind::Other).unwrap_err().message(), "io failed");
+}
+
+#[test]
+fn else_body_can_rethrow() {
+    let result: Result<u32> = handle! {
+        try { Err(Handled::msg("other"))? }
+        catch io::Error(_) { 1 } else { rethrow }
+    };
+    assert_eq!(result.unwrap_err().message(), "other");
+}
+
+#[test]
+fn nested_rethrow_belongs_to_the_inner_try() {
+    let result: Result<u32> = handle! {
+        try { io_err(io::ErrorKind::Other)? }
+        catch io::Error(_) {
+            let inner: Result<u32> = handle! {
+                try { Err(Handled::msg("inner"))? }
+                catch { rethrow }
+            };
+            inner.map_or(5, |v| v)
+        }
+    };
+    assert_eq!(result.unwrap(), 5);
+}
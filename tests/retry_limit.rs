@@ -0,0 +1,101 @@
+//! Tests for `try while cond limit N { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result, Value};
+use std::io;
+
+fn refused(n: u32) -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionRefused, format!("attempt {}", n))
+}
+
+#[test]
+fn stops_at_the_limit_and_chains_every_failure() {
+    let mut calls = 0u32;
+    let result: Result<i32> = handle! {
+        try while true limit 3 {
+            calls += 1;
+            Err(refused(calls))?
+        }
+    };
+    assert_eq!(calls, 3);
+    let err = result.unwrap_err();
+    assert_eq!(err.get_kv("attempts"), Some(&Value::Uint(3)));
+    let messages: Vec<_> = err.chain_all::<io::Error>().iter().map(|e| e.to_string()).collect();
+    assert_eq!(messages, ["attempt 3", "attempt 2", "attempt 1"]);
+}
+
+#[test]
+fn condition_still_ends_the_loop_early() {
+    let mut calls = 0u32;
+    let result: Result<i32> = handle! {
+        try while calls < 2 limit 5 {
+            calls += 1;
+            Err("busy")?
+        }
+    };
+    assert_eq!(calls, 2);
+    assert_eq!(result.unwrap_err().get_kv("attempts"), Some(&Value::Uint(2)));
+}
+
+#[test]
+fn success_before_the_limit() {
+    let mut calls = 0u32;
+    let result: Result<u32> = handle! {
+        try while true limit 5 {
+            calls += 1;
+            if calls < 3 { Err("not yet")? }
+            calls
+        }
+    };
+    assert_eq!(result.unwrap(), 3);
+}
+
+#[test]
+fn limit_is_an_expression_and_a_variable_name() {
+    let limit = 4;
+    let mut calls = 0;
+    let result: Result<()> = handle! {
+        try while calls < limit limit limit / 2 {
+            calls += 1;
+            Err("busy")?
+        }
+    };
+    assert_eq!(calls, 2);
+    assert!(result.is_err());
+}
+
+#[test]
+fn combines_with_backoff_and_handlers() {
+    let mut calls = 0u32;
+    let result: Result<u32> = handle! {
+        try while true, backoff fixed(1ms) limit 2 {
+            calls += 1;
+            Err(refused(calls))?
+        }
+        catch e { e.chain_all::<io::Error>().len() as u32 * 10 }
+    };
+    assert_eq!(result.unwrap(), 20);
+
+    let mut calls = 0u32;
+    let result: Result<()> = handle! {
+        try while true limit 2, backoff fixed(1ms) {
+            calls += 1;
+            Err("down")?
+        }
+    };
+    assert_eq!(calls, 2);
+    assert!(result.is_err());
+}
+
+#[test]
+fn handler_control_flow() {
+    let mut skipped = 0;
+    for _ in 0..2 {
+        handle! {
+            try while true limit 2 { Err("flaky")? }
+            catch e { if e.get_kv("attempts") == Some(&Value::Uint(2)) { skipped += 1; continue } }
+        }
+    }
+    assert_eq!(skipped, 2);
+}
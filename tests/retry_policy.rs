@@ -0,0 +1,52 @@
+//! `RetryPolicy` and `Handled::is_retryable`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, DefaultRetryPolicy, Handled, Result};
+use std::io;
+
+fn attempt(err: impl Fn() -> Handled, calls: &mut u32) -> Result<()> {
+    *calls += 1;
+    handle! { try { Err(err())? } }
+}
+
+fn retry(err: impl Fn() -> Handled) -> u32 {
+    let mut calls = 0;
+    while let Err(e) = attempt(&err, &mut calls) {
+        if calls >= 3 || !e.is_retryable(&DefaultRetryPolicy) {
+            break;
+        }
+    }
+    calls
+}
+
+#[test]
+fn tagged_retryable_is_retried() {
+    assert_eq!(retry(|| Handled::msg("busy").tag("retryable")), 3);
+}
+
+#[test]
+fn tagged_fatal_is_not_retried() {
+    assert_eq!(retry(|| Handled::msg("bad input").tag("fatal")), 1);
+}
+
+#[test]
+fn fatal_tag_wins_over_retryable() {
+    let err = Handled::msg("x").tag("retryable").tag("fatal");
+    assert!(!err.is_retryable(&DefaultRetryPolicy));
+}
+
+#[test]
+fn transient_io_errors_are_retryable() {
+    let timed_out = Handled::wrap(io::Error::new(io::ErrorKind::TimedOut, "slow"));
+    let not_found = Handled::wrap(io::Error::new(io::ErrorKind::NotFound, "gone"));
+    assert!(timed_out.is_retryable(&DefaultRetryPolicy));
+    assert!(!not_found.is_retryable(&DefaultRetryPolicy));
+}
+
+#[test]
+fn closure_policy() {
+    let err = Handled::msg("x").tag("db");
+    assert!(err.is_retryable(&|e: &Handled| e.has_tag("db")));
+    assert_eq!(err.tags().collect::<Vec<_>>(), ["db"]);
+}
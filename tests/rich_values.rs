@@ -0,0 +1,88 @@
+//! List, map, bytes and duration attachment values.
+
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{Handled, IntoValue, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+fn attachment(err: &Handled, key: &str) -> Value {
+    err.frames()
+        .flat_map(|f| f.attachments())
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.clone())
+        .unwrap()
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn vecs_become_lists() {
+    let err = Handled::msg("retries").frame("a.rs", 1, 1).kv("delays_ms", vec![10u32, 20, 40]);
+    assert_eq!(
+        attachment(&err, "delays_ms"),
+        Value::List(vec![Value::Uint(10), Value::Uint(20), Value::Uint(40)])
+    );
+    assert_eq!(attachment(&err, "delays_ms").to_string(), "[10, 20, 40]");
+}
+
+#[test]
+fn maps_become_sorted_maps() {
+    let mut headers = HashMap::new();
+    headers.insert("x-request-id", "abc");
+    headers.insert("accept", "json");
+    let value = headers.into_value();
+    assert_eq!(value.to_string(), "{accept: json, x-request-id: abc}");
+
+    let mut nested = BTreeMap::new();
+    nested.insert(String::from("tries"), vec![Some(1i32), None]);
+    assert_eq!(nested.into_value().to_string(), "{tries: [1, null]}");
+}
+
+#[test]
+fn byte_slices_become_bytes() {
+    let value = b"\x00\xffok".as_slice().into_value();
+    assert_eq!(value, Value::Bytes(vec![0, 255, b'o', b'k']));
+    assert_eq!(value.to_string(), "0x00ff6f6b");
+}
+
+#[test]
+fn durations_keep_their_type() {
+    let value = Duration::from_millis(1500).into_value();
+    assert_eq!(value, Value::Duration(Duration::from_millis(1500)));
+    assert_eq!(value.to_string(), "1.5s");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn to_json_renders_nested_values() {
+    let mut map = BTreeMap::new();
+    map.insert("a", Value::List(vec![Value::Bool(true)]));
+    let err = Handled::msg("x")
+        .frame("a.rs", 1, 1)
+        .kv("map", map)
+        .kv("raw", b"\x01\x02".as_slice())
+        .kv("took", Duration::new(2, 5));
+    let json = err.frames().next().unwrap().format_json();
+    assert!(json.contains(r#""map":{"a":[true]}"#), "{}", json);
+    assert!(json.contains(r#""raw":[1,2]"#), "{}", json);
+    assert!(json.contains(r#""took":{"secs":2,"nanos":5}"#), "{}", json);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let mut map = BTreeMap::new();
+    map.insert(String::from("attempts"), Value::List(vec![Value::Uint(1), Value::Uint(2)]));
+    map.insert(String::from("label"), Value::String("db".into()));
+    for value in [Value::Map(map), Value::Duration(Duration::new(3, 250))] {
+        let json = serde_json::to_string(&value).unwrap();
+        let back: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, value);
+    }
+
+    // JSON has no bytes type: they come back as a list of numbers
+    let json = serde_json::to_string(&Value::Bytes(vec![7, 8])).unwrap();
+    assert_eq!(json, "[7,8]");
+    let back: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, Value::List(vec![Value::Uint(7), Value::Uint(8)]));
+}
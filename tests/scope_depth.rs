@@ -0,0 +1,49 @@
+//! Tests for `Handled::scope_depth_at` and `FrameView::is_scope`.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Result};
+
+fn query() -> Result<()> {
+    handle! { try { Err("connection refused")? } }
+}
+
+fn load_user() -> Result<()> {
+    handle! { scope "loading user", try { query()? } }
+}
+
+fn handle_request() -> Result<()> {
+    handle! { scope "handling request", try { load_user()? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frame_inside_two_scopes_has_depth_two() {
+    let err = handle_request().unwrap_err();
+    let frames: Vec<_> = err.frames().collect();
+
+    assert!(!frames[0].is_scope());
+    assert_eq!(err.scope_depth_at(0), 2);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn outer_scopes_are_shallower() {
+    let err = handle_request().unwrap_err();
+    let scopes: Vec<_> = err
+        .frames()
+        .enumerate()
+        .filter(|(_, f)| f.is_scope())
+        .map(|(i, f)| (f.context.unwrap(), err.scope_depth_at(i)))
+        .collect();
+    assert_eq!(scopes, [("loading user", 2), ("handling request", 1)]);
+}
+
+#[test]
+fn ctx_frames_are_not_scopes() {
+    let err = handle! { try { query()? } with "plain context" }.unwrap_err();
+    assert!(err.frames().all(|f| !f.is_scope()));
+    assert_eq!(err.scope_depth_at(0), 0);
+    assert_eq!(err.scope_depth_at(99), 0);
+}
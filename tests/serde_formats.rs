@@ -0,0 +1,126 @@
+//! Tests for `Handled::to_serializable` and serde round trips through JSON and CBOR.
+#![cfg(feature = "serde")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{FrameRepr, Handled, HandledRepr, Value};
+
+fn sample() -> Handled {
+    Handled::msg("request failed")
+        .frame("src/api.rs", 10, 5)
+        .ctx("handling request")
+        .kv("id", 42u32)
+        .chain_after(
+            Handled::msg("connection reset")
+                .frame("src/net.rs", 3, 1)
+                .kv("peer", "10.0.0.1")
+                .chain_after(Handled::msg("broken pipe").frame("src/io.rs", 7, 2)),
+        )
+}
+
+fn to_cbor<T: serde::Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn repr_holds_frames_and_chain() {
+    let repr = sample().to_serializable();
+    assert_eq!(repr.message, "request failed");
+    assert_eq!(repr.trace[0].file, "src/api.rs");
+    assert_eq!(repr.trace[0].message.as_deref(), Some("handling request"));
+    assert_eq!(repr.trace[0].attachments.get("id"), Some(&Value::Uint(42)));
+
+    let chain: Vec<_> = repr.chain.iter().map(|l| l.message.as_str()).collect();
+    assert_eq!(chain, ["connection reset", "broken pipe"]);
+    assert_eq!(repr.chain[1].trace[0].line, 7);
+    assert!(repr.chain.iter().all(|l| l.chain.is_empty()));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn chained_frames_survive_json() {
+    let err = sample();
+    let mut back: Handled = serde_json::from_str(&serde_json::to_string(&err).unwrap()).unwrap();
+    assert!(back.is_same_as(&err));
+    assert_eq!(back.context_messages(), ["request failed", "connection reset", "broken pipe"]);
+
+    let link = back.take_chain().unwrap();
+    let frame = link.frames().next().unwrap();
+    assert_eq!((frame.file, frame.line), ("src/net.rs", 3));
+    assert_eq!(link.get_kv("peer").map(|v| v.to_string()), Some("10.0.0.1".to_string()));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn round_trips_through_cbor() {
+    let err = sample();
+    let back: Handled = ciborium::from_reader(to_cbor(&err).as_slice()).unwrap();
+    assert_eq!(back.to_serializable(), err.to_serializable());
+    assert_eq!(back.frames().next().unwrap().file, "src/api.rs");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn binary_formats_get_every_field() {
+    // Non-human-readable formats never skip fields, so positional decoders line up
+    let repr: ciborium::Value = ciborium::from_reader(to_cbor(&Handled::msg("x").frame("a.rs", 1, 1)).as_slice()).unwrap();
+    let frame = &repr.as_map().unwrap()[1].1.as_array().unwrap()[0];
+    let keys: Vec<_> = frame.as_map().unwrap().iter().map(|(k, _)| k.as_text().unwrap()).collect();
+    assert_eq!(keys, ["file", "line", "col", "function", "message", "attachments", "scope", "note", "repeat"]);
+
+    // JSON stays compact
+    let json = serde_json::to_string(&Handled::msg("x").frame("a.rs", 1, 1)).unwrap();
+    assert!(json.starts_with(r#"{"message":"x","trace":[{"file":"a.rs","line":1,"col":1}]"#));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frame_views_decode_from_cbor() {
+    // Optional fields vary per frame; the encoded length has to follow them
+    let err = sample().note_at("config.toml", 3, 1, "set here");
+    let frames: Vec<_> = err.frames().collect();
+    let back: Vec<FrameRepr> = ciborium::from_reader(to_cbor(&frames).as_slice()).unwrap();
+    assert_eq!(back, err.to_serializable().trace);
+    assert!(back[1].note);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frame_views_match_trace_json() {
+    let err = sample();
+    let frame = err.frames().next().unwrap();
+    assert_eq!(serde_json::to_string(&frame).unwrap(), frame.format_json());
+}
+
+#[test]
+fn repr_converts_back_without_a_format() {
+    let repr = HandledRepr {
+        message: "gone".to_string(),
+        trace: Vec::new(),
+        chain: vec![sample().to_serializable()],
+        #[cfg(feature = "thread-info")]
+        thread: None,
+        #[cfg(feature = "timestamps")]
+        timestamp: None,
+    };
+    let err = Handled::from(repr);
+    assert_eq!(err.message(), "gone");
+    // A link's own chain follows it
+    assert_eq!(err.context_messages(), ["gone", "request failed", "connection reset", "broken pipe"]);
+    assert_eq!(Handled::from(err.to_serializable()).to_serializable(), err.to_serializable());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn deserialized_files_are_interned() {
+    let json = r#"{"message":"m","trace":[{"file":"generated/remote.rs","line":1,"col":1}]}"#;
+    let err: Handled = serde_json::from_str(json).unwrap();
+    assert_eq!(err.origin().unwrap().file, "generated/remote.rs");
+    assert_eq!(err.origin_string().as_deref(), Some("generated/remote.rs:1:1"));
+
+    // Deserializing the same path again reuses the stored name
+    let again: Handled = serde_json::from_str(json).unwrap();
+    assert!(std::ptr::eq(err.origin().unwrap().file, again.origin().unwrap().file));
+}
@@ -0,0 +1,88 @@
+//! Tests for the `snapshot |rec| { }` clause and `Handled::snapshot`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, ErrorRecord, Handled, Result};
+use std::collections::HashSet;
+use std::io;
+
+fn fail() -> std::result::Result<i32, io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+}
+
+fn op(records: &mut Vec<ErrorRecord>) -> Result<i32> {
+    handle! {
+        try { fail()? }
+        snapshot |rec| { records.push(rec) }
+    }
+}
+
+#[test]
+fn identical_errors_share_fingerprint() {
+    let mut records = Vec::new();
+    let _ = op(&mut records);
+    let _ = op(&mut records);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].fingerprint, records[1].fingerprint);
+    assert_eq!(records[0], records[1]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn record_carries_message_and_origin() {
+    let mut records = Vec::new();
+    let err = op(&mut records).unwrap_err();
+    let rec = &records[0];
+    assert_eq!(rec.message, "disk full");
+    assert_eq!(rec.origin, err.origin());
+    assert!(rec.origin.unwrap().file.ends_with("snapshot.rs"));
+}
+
+#[test]
+fn different_messages_differ() {
+    let a = Handled::msg("a").frame("x.rs", 1, 1);
+    let b = Handled::msg("b").frame("x.rs", 1, 1);
+    assert_ne!(a.fingerprint(), b.fingerprint());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn different_origins_differ() {
+    let a = Handled::msg("a").frame("x.rs", 1, 1);
+    let b = Handled::msg("a").frame("x.rs", 2, 1);
+    assert_ne!(a.fingerprint(), b.fingerprint());
+}
+
+#[test]
+fn dedup_with_hashset() {
+    let mut seen = HashSet::new();
+    let mut reported = 0;
+    for _ in 0..3 {
+        let _: Result<i32> = handle! {
+            try { fail()? }
+            snapshot |rec| when seen.insert(rec.fingerprint) { reported += 1 }
+        };
+    }
+    assert_eq!(reported, 1);
+}
+
+#[test]
+fn error_keeps_propagating() {
+    let result: Result<i32> = handle! {
+        try { fail()? }
+        snapshot |_rec| { }
+        catch { 7 }
+    };
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[tokio::test]
+async fn snapshot_async() {
+    let mut message = String::new();
+    let result: Result<i32> = handle! {
+        async try { fail()? }
+        snapshot |rec| { message = rec.message }
+    };
+    assert!(result.is_err());
+    assert_eq!(message, "disk full");
+}
@@ -0,0 +1,84 @@
+//! Tests for `async try spawn { }` (`tokio` feature).
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "tokio")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Elapsed, Panicked, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+async fn work(n: i32) -> std::result::Result<i32, std::io::Error> {
+    tokio::task::yield_now().await;
+    if n < 0 {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "negative"));
+    }
+    Ok(n * 2)
+}
+
+#[tokio::test]
+async fn joins_the_task_value() {
+    let result: Result<i32> = handle! {
+        async try spawn { work(21).await? }
+    };
+    assert_eq!(result.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn body_errors_reach_the_handlers() {
+    let result: Result<i32> = handle! {
+        async try spawn { work(-1).await? }
+        catch std::io::Error(e) { e.to_string().len() as i32 }
+    };
+    assert_eq!(result.unwrap(), 8);
+}
+
+#[tokio::test]
+async fn task_panics_become_panicked() {
+    let result: Result<&str> = handle! {
+        async try spawn {
+            if work(1).await? > 0 { panic!("worker died") }
+            "done"
+        }
+        catch panic p { if p.message() == "worker died" { "recovered" } else { "other" } }
+    };
+    assert_eq!(result.unwrap(), "recovered");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn unhandled_panic_is_framed_at_the_macro() {
+    let line = line!() + 1;
+    let result: Result<()> = handle! {
+        async try spawn { panic!("boom") }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.downcast_ref::<Panicked>().unwrap().message(), "boom");
+    let frame = err.frames().next().unwrap();
+    assert!(frame.file.ends_with("spawn.rs"));
+    assert_eq!(frame.line, line);
+}
+
+#[tokio::test]
+async fn body_moves_its_captures() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&hits);
+    let result: Result<usize> = handle! {
+        async try spawn { counter.fetch_add(1, Ordering::SeqCst) + 1 }
+    };
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn timeout_bounds_the_join() {
+    let result: Result<i32> = handle! {
+        async try spawn timeout Duration::from_millis(10) {
+            std::future::pending::<()>().await;
+            0
+        }
+        catch Elapsed(_) { -1 }
+    };
+    assert_eq!(result.unwrap(), -1);
+}
@@ -0,0 +1,39 @@
+//! `Handled::strip_synthetic_frames` drops interop pseudo-frames.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "anyhow")]
+#![cfg(not(feature = "no-trace"))]
+
+use handle_this::{handle, Handled, Result};
+
+fn load() -> Result<()> {
+    handle! {
+        try {
+            let err = anyhow::anyhow!("disk full").context("writing cache").context("saving state");
+            Err(Handled::from(err))?
+        }
+        with "loading"
+    }
+}
+
+#[test]
+fn anyhow_frames_removed_real_frames_kept() {
+    let mut err = load().unwrap_err();
+    assert!(err.frames().any(|f| f.file == "<anyhow>"));
+    let real = err.frames().filter(|f| !f.file.starts_with('<')).count();
+    assert!(real > 0);
+
+    err.strip_synthetic_frames();
+    assert!(err.frames().all(|f| !f.file.starts_with('<')));
+    assert_eq!(err.frames().count(), real);
+    assert!(err.frames().any(|f| f.context == Some("loading")));
+    assert_eq!(err.message(), "saving state");
+}
+
+#[test]
+fn no_synthetic_frames_is_noop() {
+    let mut err = Handled::msg("x").frame("a.rs", 1, 1).ctx("first").frame("b.rs", 2, 2);
+    err.strip_synthetic_frames();
+    assert_eq!(err.frames().count(), 2);
+    assert_eq!(err.frames().next().unwrap().context, Some("first"));
+}
@@ -0,0 +1,43 @@
+//! `Handled::take_attachments` drains key-value attachments from every frame.
+
+#![allow(clippy::result_large_err)]
+#![cfg(not(feature = "no-trace"))]
+
+use handle_this::{handle, Result, Value};
+
+fn request() -> Result<()> {
+    handle! { try { Err("timeout")? } with "calling upstream", { code: 500 } }
+}
+
+fn handler() -> Result<()> {
+    handle! { try { request()? } with { route: "/users", attempt: 2 } }
+}
+
+#[test]
+fn returns_all_attachments_in_frame_order() {
+    let mut err = handler().unwrap_err();
+    let taken = err.take_attachments();
+    assert_eq!(
+        taken,
+        [
+            ("code".to_string(), Value::Int(500)),
+            ("route".to_string(), Value::String("/users".into())),
+            ("attempt".to_string(), Value::Int(2)),
+        ]
+    );
+}
+
+#[test]
+fn error_keeps_trace_and_messages_but_no_attachments() {
+    let mut err = handler().unwrap_err();
+    let depth = err.depth();
+    err.take_attachments();
+
+    assert_eq!(err.depth(), depth);
+    assert!(err.frames().all(|f| f.attachments().next().is_none()));
+    assert_eq!(
+        err.frames().filter_map(|f| f.context).collect::<Vec<_>>(),
+        ["calling upstream"]
+    );
+    assert!(err.take_attachments().is_empty());
+}
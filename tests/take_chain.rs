@@ -0,0 +1,28 @@
+//! `Handled::take_chain` detaches the chain; `chain_after` reattaches it.
+
+use handle_this::{Handled, StringError};
+
+fn messages(err: &Handled) -> Vec<String> {
+    err.chain_all::<StringError>().iter().map(|e| e.to_string()).collect()
+}
+
+#[test]
+fn detach_then_reattach_preserves_chain() {
+    let mut err = Handled::msg("third")
+        .chain_after(Handled::msg("second").chain_after(Handled::msg("first")));
+    let before = messages(&err);
+
+    let chain = err.take_chain().expect("chain present");
+    assert_eq!(messages(&err), ["third"]);
+    assert_eq!(chain.message(), "second");
+
+    let err = err.chain_after(*chain);
+    assert_eq!(messages(&err), before);
+}
+
+#[test]
+fn take_chain_without_chain() {
+    let mut err = Handled::msg("alone");
+    assert!(err.take_chain().is_none());
+    assert_eq!(err.message(), "alone");
+}
@@ -0,0 +1,99 @@
+//! Tests for async `then` steps: `then async |x| { other(x).await? }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+
+#[derive(Debug)]
+struct Missing(u32);
+
+impl std::fmt::Display for Missing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "missing {}", self.0)
+    }
+}
+
+impl std::error::Error for Missing {}
+
+async fn lookup(id: u32) -> std::result::Result<u32, Missing> {
+    tokio::task::yield_now().await;
+    if id == 0 {
+        Err(Missing(id))
+    } else {
+        Ok(id * 10)
+    }
+}
+
+fn parse(s: &str) -> Result<u32> {
+    s.parse::<u32>().map_err(Handled::wrap)
+}
+
+#[tokio::test]
+async fn async_source_steps_can_await() {
+    let result: Result<u32> = handle! {
+        async try { lookup(1).await? },
+        then |x| { lookup(x).await? + 1 }
+    };
+    assert_eq!(result.unwrap(), 101);
+}
+
+#[tokio::test]
+async fn async_source_with_async_step() {
+    let result: Result<u32> = handle! {
+        async try { lookup(2).await? },
+        then async |x| { lookup(x).await? }
+    };
+    assert_eq!(result.unwrap(), 200);
+}
+
+#[tokio::test]
+async fn sync_source_then_async_step() {
+    let result: Result<u32> = handle! {
+        try { parse("3")? },
+        then async |id| { lookup(id).await? },
+        then |x| { x + 1 }
+    };
+    assert_eq!(result.unwrap(), 31);
+}
+
+#[tokio::test]
+async fn async_step_error_reaches_catch() {
+    let result: Result<u32> = handle! {
+        try { parse("0")? },
+        then async |id| { lookup(id).await? }
+        catch Missing(m) { m.0 + 7 }
+    };
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn async_step_error_propagates_with_context() {
+    let err = handle! {
+        try { parse("0")? },
+        then async |id: u32| { lookup(id).await? } with "looking up", { id: id }
+    }
+    .unwrap_err();
+    assert!(err.downcast_ref::<Missing>().is_some());
+    assert!(err.frames().any(|f| f.context == Some("looking up")));
+}
+
+#[tokio::test]
+async fn source_error_skips_async_steps() {
+    let result: Result<u32> = handle! {
+        try { parse("nope")? },
+        then async |id| { lookup(id).await? }
+        catch { 99 }
+    };
+    assert_eq!(result.unwrap(), 99);
+}
+
+#[tokio::test]
+async fn direct_source_then_async_step() {
+    let value = handle! {
+        try -> u32 { 5 },
+        then async |id| { lookup(id).await? }
+        catch { 0 }
+    };
+    assert_eq!(value, 50);
+}
@@ -0,0 +1,69 @@
+//! Originating thread capture under the `thread-info` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "thread-info")]
+
+use handle_this::{handle, Handled, Result};
+use std::thread;
+
+fn fail() -> Result<i32> {
+    handle! { try { Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))? } }
+}
+
+fn on_thread<T: Send + 'static>(name: &str, f: impl FnOnce() -> T + Send + 'static) -> T {
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+#[test]
+fn named_thread_is_reported() {
+    let err = on_thread("worker-7", || fail().unwrap_err());
+    assert_eq!(err.thread_name(), Some("worker-7"));
+    assert!(err.to_string().contains("Thread: worker-7"));
+}
+
+#[test]
+fn thread_id_matches_creator() {
+    let (err, id) = on_thread("ids", || (fail().unwrap_err(), thread::current().id()));
+    assert_eq!(err.thread_id(), Some(id));
+    assert_ne!(err.thread_id(), Some(thread::current().id()));
+}
+
+#[test]
+fn unnamed_thread_has_no_name() {
+    let err = thread::spawn(|| Handled::msg("boom")).join().unwrap();
+    assert_eq!(err.thread_name(), None);
+    assert!(!err.to_string().contains("Thread:"));
+}
+
+#[test]
+fn with_thread_name_reattributes() {
+    let err = on_thread("producer", || Handled::msg("boom"));
+    let err = on_thread("consumer", move || err.with_thread_name());
+    assert_eq!(err.thread_name(), Some("consumer"));
+}
+
+#[test]
+fn survives_erase_and_frames() {
+    let err = on_thread("eraser", || {
+        Handled::new(std::io::Error::new(std::io::ErrorKind::Other, "io"))
+            .frame("a.rs", 1, 1)
+            .erase()
+    });
+    assert_eq!(err.thread_name(), Some("eraser"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip_keeps_name() {
+    let err = on_thread("serializer", || fail().unwrap_err());
+    let json = serde_json::to_string(&err).unwrap();
+    assert!(json.contains("\"thread\":\"serializer\""));
+    let back: Handled = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.thread_name(), Some("serializer"));
+    assert_eq!(back.thread_id(), None);
+}
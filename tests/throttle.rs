@@ -0,0 +1,102 @@
+//! Per-key rate limiting with the `throttle` clause under the `throttle` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "throttle")]
+
+use handle_this::throttle::{self, Throttled};
+use handle_this::{handle, Result};
+use std::time::Duration;
+
+fn call(key: &str, runs: &mut u32) -> Result<&'static str> {
+    handle! {
+        try {
+            *runs += 1;
+            "ran"
+        }
+        throttle key, 2/sec
+        catch Throttled(_) { "throttled" }
+    }
+}
+
+#[test]
+fn rapid_calls_hit_throttled_path() {
+    let mut runs = 0;
+    let results: Vec<_> = (0..4).map(|_| call("rapid", &mut runs).unwrap()).collect();
+    assert_eq!(results, ["ran", "ran", "throttled", "throttled"]);
+    assert_eq!(runs, 2);
+}
+
+#[test]
+fn keys_are_independent() {
+    let mut runs = 0;
+    assert_eq!(call("key-a", &mut runs).unwrap(), "ran");
+    assert_eq!(call("key-a", &mut runs).unwrap(), "ran");
+    assert_eq!(call("key-b", &mut runs).unwrap(), "ran");
+    assert_eq!(call("key-a", &mut runs).unwrap(), "throttled");
+}
+
+#[test]
+fn window_expires() {
+    let limited = || -> Result<()> {
+        handle! { try { () } throttle "expiring", 1/ms }
+    };
+    assert!(limited().is_ok());
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(limited().is_ok());
+}
+
+#[test]
+fn throttled_error_propagates_with_retry_after() {
+    let limited = || -> Result<u8> {
+        handle! { try { 1 } throttle String::from("propagate"), 1/min }
+    };
+    assert_eq!(limited().unwrap(), 1);
+    let err = limited().unwrap_err();
+    let throttled = err.downcast_ref::<Throttled>().unwrap();
+    assert_eq!(throttled.key(), "propagate");
+    assert!(throttled.retry_after() <= Duration::from_secs(60));
+    assert!(throttled.retry_after() > Duration::from_secs(50));
+}
+
+#[tokio::test]
+async fn nested_and_async() {
+    let nested = || -> Result<&'static str> {
+        handle! {
+            try {
+                try { "inner" } throttle "nested", 1/sec catch Throttled(_) { "skipped" }
+            }
+        }
+    };
+    assert_eq!(nested().unwrap(), "inner");
+    assert_eq!(nested().unwrap(), "skipped");
+
+    let attempt = || async { handle! { async try { 1 } throttle "async", 1/sec } };
+    assert!(attempt().await.is_ok());
+    assert!(attempt().await.is_err());
+}
+
+#[test]
+fn check_directly() {
+    assert!(throttle::check("direct", 1, Duration::from_secs(60)).is_ok());
+    assert!(throttle::check("direct", 1, Duration::from_secs(60)).is_err());
+}
+
+#[test]
+fn idle_keys_are_evicted() {
+    for round in 0..10 {
+        for i in 0..100 {
+            throttle::check(format!("idle-{}-{}", round, i), 1, Duration::from_millis(1)).unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    assert!(throttle::tracked() < 500, "tracked {}", throttle::tracked());
+}
+
+#[test]
+fn units_match_backoff_durations() {
+    let limited = || -> Result<()> {
+        handle! { try { () } throttle "short-unit", 1/s }
+    };
+    assert!(limited().is_ok());
+    assert!(limited().is_err());
+}
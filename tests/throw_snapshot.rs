@@ -0,0 +1,97 @@
+//! Tests for `Handled::snapshot_of`, `into_snapshot` and `throw snapshot`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result, StringError};
+use std::fmt;
+
+/// A parser error that borrows its input.
+#[derive(Debug)]
+struct Unexpected<'a> {
+    token: &'a str,
+}
+
+impl fmt::Display for Unexpected<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected token {:?}", self.token)
+    }
+}
+
+fn first_token(input: &str) -> std::result::Result<u32, Unexpected<'_>> {
+    let token = input.split_whitespace().next().unwrap_or("");
+    token.parse().map_err(|_| Unexpected { token })
+}
+
+#[test]
+fn snapshot_of_keeps_the_message() {
+    let line = String::from("abc 1");
+    let err = Handled::snapshot_of(&first_token(&line).unwrap_err());
+    drop(line);
+    assert_eq!(err.message(), "unexpected token \"abc\"");
+    assert!(err.downcast_ref::<StringError>().is_some());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn snapshot_of_inside_try() {
+    let line = String::from("x");
+    let result: Result<u32> = handle! {
+        try { first_token(&line).map_err(|e| Handled::snapshot_of(&e))? } with "parsing"
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "unexpected token \"x\"");
+    assert_eq!(err.frames().next().unwrap().context, Some("parsing"));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn into_snapshot_keeps_trace_and_kv() {
+    let line = String::from("oops");
+    let err = Handled::new(first_token(&line).unwrap_err())
+        .frame("lexer.rs", 10, 2)
+        .kv("column", 0u32)
+        .into_snapshot();
+    drop(line);
+    assert_eq!(err.message(), "unexpected token \"oops\"");
+    assert_eq!(err.depth(), 1);
+    assert_eq!(err.get_kv("column").unwrap(), &0u64);
+}
+
+#[test]
+fn throw_snapshot_borrowing_error() {
+    let line = String::from("bad input");
+    let result: Result<u32> = handle! {
+        try { line.len().checked_sub(100).ok_or("too short")? as u32 }
+        throw snapshot { Unexpected { token: &line[..3] } }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "unexpected token \"bad\"");
+    assert_eq!(err.context_messages(), ["unexpected token \"bad\"", "too short"]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn throw_snapshot_with_binding_and_guard() {
+    let line = String::from("zz");
+    let result: Result<u32> = handle! {
+        try { Err(std::io::Error::other("io failed"))? }
+        throw snapshot std::io::Error(e) when e.to_string().contains("io") {
+            Handled::new(first_token(&line).unwrap_err()).kv("source", e.to_string())
+        }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "unexpected token \"zz\"");
+    assert_eq!(err.get_kv("source").unwrap(), "io failed");
+}
+
+#[test]
+fn throw_snapshot_in_nested_handle() {
+    let line = String::from("q");
+    let result: Result<u32> = handle! {
+        try {
+            let inner: u32 = try { Err("inner")? } throw snapshot _ { Unexpected { token: &line } };
+            inner
+        }
+    };
+    assert_eq!(result.unwrap_err().message(), "unexpected token \"q\"");
+}
@@ -0,0 +1,100 @@
+//! Tests for `async try timeout DURATION { }`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Elapsed, Result};
+use std::time::Duration;
+
+async fn slow() -> std::result::Result<i32, std::io::Error> {
+    std::future::pending().await
+}
+
+async fn fast() -> std::result::Result<i32, std::io::Error> {
+    tokio::task::yield_now().await;
+    Ok(7)
+}
+
+#[tokio::test]
+async fn finishes_before_deadline() {
+    let result: Result<i32> = handle! {
+        async try timeout Duration::from_secs(5) { fast().await? }
+    };
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn elapsed_becomes_an_error_framed_at_the_macro() {
+    let line = line!() + 1;
+    let result: Result<i32> = handle! {
+        async try timeout Duration::from_millis(20) { slow().await? }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.downcast_ref::<Elapsed>().unwrap().after(), Duration::from_millis(20));
+    let frame = err.frames().next().unwrap();
+    assert!(frame.file.ends_with("timeout.rs"));
+    assert_eq!(frame.line, line);
+}
+
+#[tokio::test]
+async fn catch_elapsed_recovers() {
+    let result: Result<i32> = handle! {
+        async try timeout Duration::from_millis(10) { slow().await? }
+        catch Elapsed(e) { e.after().as_millis() as i32 }
+    };
+    assert_eq!(result.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn body_errors_pass_through_untouched() {
+    let result: Result<i32> = handle! {
+        async try timeout Duration::from_secs(5) { "nope".parse::<i32>()? }
+        catch Elapsed(_) { -1 }
+    };
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn works_with_context_and_finally() {
+    let cleaned = std::cell::Cell::new(false);
+    let result: Result<i32> = handle! {
+        async try timeout Duration::from_millis(10) { slow().await? }
+        with "loading profile"
+        finally { cleaned.set(true); }
+    };
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("loading profile"));
+    assert!(err.to_string().contains("timed out after 10ms"));
+    assert!(cleaned.get());
+}
+
+#[tokio::test]
+async fn nested_timeout_propagates() {
+    let result: Result<i32> = handle! {
+        async try {
+            let inner = async try timeout Duration::from_millis(10) { slow().await? };
+            inner + 1
+        }
+        catch Elapsed(_) { 0 }
+    };
+    assert_eq!(result.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn deadlines_share_one_timer_in_order() {
+    let order = std::sync::Mutex::new(Vec::new());
+    let wait = |ms: u64| {
+        let order = &order;
+        async move {
+            let result: Result<i32> = handle! {
+                async try timeout Duration::from_millis(ms) { slow().await? }
+            };
+            assert!(result.is_err());
+            order.lock().unwrap().push(ms);
+        }
+    };
+    tokio::join!(wait(60), wait(20), wait(40));
+    assert_eq!(*order.lock().unwrap(), [20, 40, 60]);
+}
@@ -0,0 +1,90 @@
+//! Per-frame timestamps under the `timestamps` feature.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "timestamps")]
+
+use handle_this::{handle, Handled, Result};
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn inner() -> Result<()> {
+    handle! { try { Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))? } }
+}
+
+fn middle() -> Result<()> {
+    handle! { try { inner().map_err(|e| { sleep(Duration::from_millis(5)); e })? } }
+}
+
+fn outer() -> Result<()> {
+    handle! { try { middle().map_err(|e| { sleep(Duration::from_millis(5)); e })? } }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn later_frames_have_larger_elapsed() {
+    let err = outer().unwrap_err();
+    let elapsed: Vec<Duration> = err.frames().map(|f| f.elapsed_since_origin()).collect();
+
+    assert_eq!(elapsed.len(), 3);
+    assert_eq!(elapsed[0], Duration::ZERO);
+    assert!(elapsed[1] >= Duration::from_millis(5));
+    assert!(elapsed[2] >= elapsed[1] + Duration::from_millis(5));
+}
+
+#[test]
+fn frame_timestamps_are_monotonic() {
+    let err = outer().unwrap_err();
+    let stamps: Vec<_> = err.frame_timestamps().collect();
+    assert_eq!(stamps.len(), err.depth());
+    assert!(stamps.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn creation_time_is_recent() {
+    let before = SystemTime::now();
+    let err = outer().unwrap_err();
+    let stamp = err.timestamp().expect("created errors are stamped");
+    assert!(stamp >= before);
+    assert!(SystemTime::now().duration_since(stamp).unwrap() < Duration::from_secs(5));
+}
+
+#[test]
+fn with_now_restamps() {
+    let err = Handled::msg("boom");
+    let first = err.timestamp().unwrap();
+    sleep(Duration::from_millis(5));
+    let err = err.with_now();
+    assert!(err.timestamp().unwrap() >= first + Duration::from_millis(5));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip_keeps_rfc3339_timestamp() {
+    let err = outer().unwrap_err();
+    let json: serde_json::Value = serde_json::to_value(&err).unwrap();
+    let text = json["timestamp"].as_str().unwrap();
+    assert_eq!(text.len(), "2026-01-01T00:00:00.000000Z".len());
+    assert!(text.ends_with('Z'));
+
+    let back: Handled = serde_json::from_value(json).unwrap();
+    let original = err.timestamp().unwrap().duration_since(UNIX_EPOCH).unwrap();
+    let restored = back.timestamp().unwrap().duration_since(UNIX_EPOCH).unwrap();
+    assert_eq!(restored.as_micros(), original.as_micros());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_parses_known_instants() {
+    let at = |ts: &str| {
+        let json = format!(r#"{{"message":"m","trace":[],"timestamp":"{}"}}"#, ts);
+        let err: Handled = serde_json::from_str(&json).unwrap();
+        err.timestamp().map(|t| t.duration_since(UNIX_EPOCH).unwrap())
+    };
+    assert_eq!(at("1970-01-01T00:00:00Z"), Some(Duration::ZERO));
+    assert_eq!(at("2000-03-01T00:00:00.5Z"), Some(Duration::from_millis(951_868_800_500)));
+    assert_eq!(at("2000-03-01T02:00:00+02:00"), Some(Duration::from_secs(951_868_800)));
+    assert_eq!(at("not a time"), None);
+
+    let err: Handled = serde_json::from_str(r#"{"message":"m","trace":[]}"#).unwrap();
+    assert_eq!(err.timestamp(), None);
+}
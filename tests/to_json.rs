@@ -0,0 +1,84 @@
+//! `Handled::to_json` and `FrameView::format_json` without serde.
+
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+
+#[test]
+fn message_and_empty_trace() {
+    let err = Handled::msg("boom");
+    assert!(err.to_json().starts_with(r#"{"message":"boom","trace":[]"#));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frames_carry_context_and_typed_attachments() {
+    let err = Handled::msg("failed")
+        .frame("src/a.rs", 1, 2)
+        .ctx("loading")
+        .kv("name", "cfg")
+        .kv("size", -4)
+        .kv("count", 7u32)
+        .kv("ratio", 0.5)
+        .kv("ok", false)
+        .kv("missing", None::<i32>);
+    let frame = err.frames().next().unwrap();
+    assert_eq!(
+        frame.format_json(),
+        concat!(
+            r#"{"file":"src/a.rs","line":1,"col":2,"message":"loading","attachments":"#,
+            r#"{"count":7,"missing":null,"name":"cfg","ok":false,"ratio":0.5,"size":-4}}"#,
+        ),
+    );
+}
+
+#[test]
+fn strings_are_escaped() {
+    let err = Handled::msg("say \"hi\"\n\tpath C:\\tmp \u{1}");
+    assert!(err.to_json().contains(r#""message":"say \"hi\"\n\tpath C:\\tmp \u0001""#));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn non_finite_floats_become_null() {
+    let err = Handled::msg("x").frame("f.rs", 1, 1).kv("nan", f64::NAN);
+    assert!(err.frames().next().unwrap().format_json().contains(r#""nan":null"#));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn chain_and_note_are_included() {
+    let err = Handled::msg("outer")
+        .chain_after(Handled::msg("middle").chain_after(Handled::msg("inner")))
+        .note_at("config.toml", 3, 1, "set here");
+    let json = err.to_json();
+    assert!(json.contains(r#""chain":["middle","inner"]"#));
+    assert!(json.contains(r#"{"file":"config.toml","line":3,"col":1,"message":"set here","note":true}"#));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn macro_errors_render() {
+    let result: Result<()> = handle! { try { Err("bad input")? } with "parsing", { id: 9 } };
+    let json = result.unwrap_err().to_json();
+    assert!(json.starts_with(r#"{"message":"bad input","trace":[{"file":"tests/to_json.rs""#));
+    assert!(json.contains(r#""message":"parsing","attachments":{"id":9}"#));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn matches_serde_json() {
+    let err = Handled::msg("outer \"quoted\"")
+        .frame("src/a.rs", 1, 2)
+        .ctx("step")
+        .kv("z", 1.25)
+        .kv("a", "first")
+        .chain_after(Handled::msg("root"));
+    let parsed: serde_json::Value = serde_json::from_str(&err.to_json()).unwrap();
+    let mut expected = serde_json::to_value(&err).unwrap();
+    // serde writes chained links whole so they round-trip; to_json lists their messages
+    for link in expected["chain"].as_array_mut().unwrap() {
+        *link = link["message"].take();
+    }
+    assert_eq!(parsed, expected);
+}
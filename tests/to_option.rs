@@ -0,0 +1,59 @@
+//! Tests for the `to_option` marker.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::parse;
+use handle_this::{handle, Result};
+
+#[test]
+fn some_on_success() {
+    let value: Option<i32> = handle! { try { parse("42")? } to_option };
+    assert_eq!(value, Some(42));
+}
+
+#[test]
+fn none_on_error() {
+    let value: Option<i32> = handle! { try { parse("nope")? } to_option };
+    assert_eq!(value, None);
+}
+
+#[test]
+fn inspect_runs_before_error_is_dropped() {
+    let mut seen = None;
+    let value = handle! {
+        try { parse("nope")? }
+        inspect e { seen = Some(e.message().to_string()); }
+        to_option
+    };
+    assert_eq!(value, None);
+    assert!(seen.unwrap().contains("invalid digit"));
+}
+
+#[test]
+fn typed_catch_recovery_is_kept() {
+    let value = handle! {
+        try { parse("nope")? }
+        catch std::num::ParseIntError { -1 }
+        to_option
+    };
+    assert_eq!(value, Some(-1));
+}
+
+#[test]
+fn nested_inside_outer_try() {
+    let result: Result<Option<i32>> = handle! {
+        try {
+            let inner = try { parse("x")? } to_option;
+            inner.map(|v| v + 1)
+        }
+    };
+    assert_eq!(result.unwrap(), None);
+}
+
+#[tokio::test]
+async fn async_to_option() {
+    assert_eq!(handle! { async try { parse("7")? } to_option }, Some(7));
+    assert_eq!(handle! { async try { parse("x")? } to_option }, None);
+}
@@ -0,0 +1,99 @@
+//! Tests for `Handled::trace_eq` and `Handled::hash_trace`.
+
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::Handled;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// Dedup key built from the two helpers.
+struct TraceKey(Handled);
+
+impl PartialEq for TraceKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.trace_eq(&other.0)
+    }
+}
+
+impl Eq for TraceKey {}
+
+impl Hash for TraceKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash_trace(state);
+    }
+}
+
+fn hash_of(err: &Handled) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    err.hash_trace(&mut hasher);
+    hasher.finish()
+}
+
+fn sample(user: u64) -> Handled {
+    Handled::msg("query failed")
+        .frame("db.rs", 12, 5)
+        .ctx("loading user")
+        .kv("user", user)
+        .chain_after(Handled::msg("connection reset").frame("net.rs", 3, 1))
+}
+
+#[test]
+fn equal_traces_are_equal_and_hash_alike() {
+    assert!(sample(1).trace_eq(&sample(1)));
+    assert_eq!(hash_of(&sample(1)), hash_of(&sample(1)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn any_difference_breaks_equality() {
+    assert!(!sample(1).trace_eq(&sample(2)));
+    assert!(!sample(1).trace_eq(&sample(1).frame("api.rs", 1, 1)));
+    assert!(!sample(1).trace_eq(&sample(1).ctx("again")));
+    assert_ne!(hash_of(&sample(1)), hash_of(&sample(2)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn chain_is_compared_unlike_is_same_as() {
+    let a = sample(1);
+    let b = Handled::msg("query failed")
+        .frame("db.rs", 12, 5)
+        .ctx("loading user")
+        .kv("user", 1u64)
+        .chain_after(Handled::msg("connection reset").frame("net.rs", 3, 1).ctx("retried"));
+    assert!(a.is_same_as(&b));
+    assert!(!a.trace_eq(&b));
+
+    let shorter = Handled::msg("query failed").frame("db.rs", 12, 5).ctx("loading user").kv("user", 1u64);
+    assert!(a.is_same_as(&shorter));
+    assert!(!a.trace_eq(&shorter));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+// The lazily-cached message is the only interior mutability, and it doesn't change the hash
+#[allow(clippy::mutable_key_type)]
+fn dedups_in_a_hash_set() {
+    let mut seen = HashSet::new();
+    for user in [1, 2, 1, 1, 2, 3] {
+        seen.insert(TraceKey(sample(user)));
+    }
+    assert_eq!(seen.len(), 3);
+}
+
+#[test]
+fn signed_zero_floats_hash_alike() {
+    let a = Handled::msg("x").frame("a.rs", 1, 1).kv("ratio", 0.0f64);
+    let b = Handled::msg("x").frame("a.rs", 1, 1).kv("ratio", -0.0f64);
+    assert!(a.trace_eq(&b));
+    assert_eq!(hash_of(&a), hash_of(&b));
+}
+
+#[cfg(feature = "clone")]
+#[test]
+fn clones_are_trace_eq() {
+    let err = sample(7);
+    assert!(err.clone().trace_eq(&err));
+    assert_eq!(hash_of(&err.clone()), hash_of(&err));
+}
@@ -0,0 +1,126 @@
+//! Tests for `set_trace_limits`: frame/context limits and drop policy.
+
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{set_trace_limits, trace_limits, DropPolicy, Handled, TraceLimits, MAX_CONTEXT_LIMIT, MAX_LOCATION_LIMIT};
+use std::sync::{Mutex, MutexGuard};
+
+/// Limits are process-wide; tests that change them take turns.
+static LIMITS: Mutex<()> = Mutex::new(());
+
+/// Apply `limits` until the guard is dropped, then restore the defaults.
+struct Limited {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Limited {
+    fn new(limits: TraceLimits) -> Self {
+        let guard = LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+        set_trace_limits(limits);
+        Limited { _guard: guard }
+    }
+}
+
+impl Drop for Limited {
+    fn drop(&mut self) {
+        set_trace_limits(TraceLimits::new());
+    }
+}
+
+fn deep(n: u32) -> Handled {
+    Handled::msg("deep").push_frames((1..=n).map(|i| ("deep.rs", i, 1)))
+}
+
+fn lines(err: &Handled) -> Vec<u32> {
+    err.frames().map(|f| f.line).collect()
+}
+
+#[test]
+fn defaults() {
+    let limits = TraceLimits::default();
+    assert_eq!(limits.location_limit(), 32);
+    assert_eq!(limits.context_limit(), 8);
+    assert_eq!(limits.drop_policy(), DropPolicy::Newest);
+    assert_eq!(TraceLimits::new().locations(1000).location_limit(), MAX_LOCATION_LIMIT);
+}
+
+#[test]
+fn set_limits_are_reported() {
+    let limits = TraceLimits::new().locations(5).contexts(2).policy(DropPolicy::Oldest);
+    let _limited = Limited::new(limits);
+    assert_eq!(trace_limits(), limits);
+}
+
+#[test]
+fn largest_limits_are_reported() {
+    let limits = TraceLimits::new().locations(usize::MAX).contexts(usize::MAX).policy(DropPolicy::Oldest);
+    assert_eq!(limits.location_limit(), MAX_LOCATION_LIMIT);
+    assert_eq!(limits.context_limit(), MAX_CONTEXT_LIMIT);
+    let _limited = Limited::new(limits);
+    assert_eq!(trace_limits(), limits);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn drop_newest_keeps_the_origin() {
+    let _limited = Limited::new(TraceLimits::new().locations(3));
+    assert_eq!(lines(&deep(5)), [1, 2, 3]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn drop_oldest_keeps_the_latest_frames() {
+    let _limited = Limited::new(TraceLimits::new().locations(6).policy(DropPolicy::Oldest));
+    assert_eq!(lines(&deep(10)), [5, 6, 7, 8, 9, 10]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn raised_limit_keeps_deeper_traces() {
+    let _limited = Limited::new(TraceLimits::new().locations(100));
+    assert_eq!(deep(80).depth(), 80);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn dropped_frames_take_their_context() {
+    let _limited = Limited::new(TraceLimits::new().locations(2).policy(DropPolicy::Oldest));
+    let err = Handled::msg("x")
+        .frame("a.rs", 1, 1)
+        .ctx("first")
+        .frame("b.rs", 2, 1)
+        .ctx("second")
+        .frame("c.rs", 3, 1)
+        .kv("third", 3);
+    let contexts: Vec<_> = err.frames().map(|f| (f.file, f.context)).collect();
+    assert_eq!(contexts, [("b.rs", Some("second")), ("c.rs", None)]);
+    assert_eq!(err.frames().last().unwrap().attachments().count(), 1);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn context_limit_drop_newest() {
+    let _limited = Limited::new(TraceLimits::new().contexts(1));
+    let err = Handled::msg("x").frame("a.rs", 1, 1).ctx("kept").frame("b.rs", 2, 1).ctx("dropped");
+    let contexts: Vec<_> = err.frames().filter_map(|f| f.context).collect();
+    assert_eq!(contexts, ["kept"]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn context_limit_drop_oldest() {
+    let _limited = Limited::new(TraceLimits::new().contexts(2).policy(DropPolicy::Oldest));
+    let mut err = Handled::msg("x");
+    for (i, msg) in ["one", "two", "three"].into_iter().enumerate() {
+        err = err.frame("a.rs", i as u32 + 1, 1).ctx(msg);
+    }
+    let contexts: Vec<_> = err.frames().filter_map(|f| f.context).collect();
+    assert_eq!(contexts, ["two", "three"]);
+    assert_eq!(err.depth(), 3);
+}
+
+#[test]
+fn zero_location_limit_records_nothing() {
+    let _limited = Limited::new(TraceLimits::new().locations(0).policy(DropPolicy::Oldest));
+    assert_eq!(deep(3).depth(), 0);
+}
@@ -0,0 +1,62 @@
+//! Tests for `frames_rev`, `first_frame`, `last_frame`, `frames_in_file`, and `truncate_trace`.
+
+#![cfg(not(feature = "no-trace"))]
+
+use handle_this::Handled;
+
+fn trace() -> Handled {
+    Handled::msg("boom")
+        .frame("vendor/pg/conn.rs", 88, 1)
+        .frame("src/db/query.rs", 12, 5).ctx("running query")
+        .frame("src/db.rs", 30, 1).kv("table", "users")
+        .frame("src/api.rs", 40, 9).ctx("handling request")
+}
+
+#[test]
+fn frames_rev_is_most_recent_first() {
+    let err = trace();
+    let forward: Vec<_> = err.frames().map(|f| f.line).collect();
+    let mut backward: Vec<_> = err.frames_rev().map(|f| f.line).collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(err.frames().len(), 4);
+}
+
+#[test]
+fn first_and_last_frame() {
+    let err = trace();
+    assert_eq!(err.first_frame().unwrap().file, "vendor/pg/conn.rs");
+    let last = err.last_frame().unwrap();
+    assert_eq!((last.file, last.context), ("src/api.rs", Some("handling request")));
+
+    let empty = Handled::msg("no frames");
+    assert!(empty.first_frame().is_none());
+    assert!(empty.last_frame().is_none());
+}
+
+#[test]
+fn frames_in_file_matches_by_prefix() {
+    let err = trace();
+    let db: Vec<_> = err.frames_in_file("src/db").map(|f| f.file).collect();
+    assert_eq!(db, ["src/db/query.rs", "src/db.rs"]);
+    assert_eq!(err.frames_in_file("src/").count(), 3);
+    assert_eq!(err.frames_in_file("tests/").count(), 0);
+}
+
+#[test]
+fn truncate_trace_keeps_the_oldest_frames() {
+    let mut err = trace();
+    err.truncate_trace(3);
+    assert_eq!(err.depth(), 3);
+    assert_eq!(err.last_frame().unwrap().file, "src/db.rs");
+    assert_eq!(err.get_kv("table").unwrap(), "users");
+    assert!(err.find_context_frame("handling").is_none());
+
+    err.truncate_trace(10);
+    assert_eq!(err.depth(), 3);
+
+    err.truncate_trace(0);
+    assert!(err.is_empty());
+    assert_eq!(err.context_count(), 0);
+    assert_eq!(err.message(), "boom");
+}
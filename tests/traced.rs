@@ -0,0 +1,112 @@
+//! Tests for `#[traced]` and function paths in frames.
+
+#![allow(clippy::result_large_err)]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, traced, Handled, Result};
+
+fn parse(s: &str) -> std::result::Result<u32, std::num::ParseIntError> {
+    s.parse()
+}
+
+#[traced]
+fn load(s: &str) -> Result<u32> {
+    handle! { try { parse(s)? } with "loading" }
+}
+
+#[traced]
+fn outer(s: &str) -> Result<u32> {
+    handle! { try { load(s)? } }
+}
+
+fn untraced(s: &str) -> Result<u32> {
+    handle! { try { parse(s)? } }
+}
+
+#[traced]
+async fn fetch(s: &str) -> Result<u32> {
+    tokio::task::yield_now().await;
+    handle! { try { parse(s)? } }
+}
+
+fn functions(err: &Handled) -> Vec<Option<&str>> {
+    err.frames().map(|f| f.function).collect()
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn frames_record_the_traced_function() {
+    let err = load("x").unwrap_err();
+    assert_eq!(functions(&err), [Some("traced::load")]);
+    assert!(load("7").is_ok());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn each_frame_names_its_own_function() {
+    let err = outer("x").unwrap_err();
+    assert_eq!(functions(&err), [Some("traced::load"), Some("traced::outer")]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn functions_stay_on_their_frames() {
+    let err = outer("x").unwrap_err().frame("<anyhow>", 0, 0).frame("main.rs", 1, 1);
+    assert_eq!(
+        functions(&err),
+        [Some("traced::load"), Some("traced::outer"), None, None]
+    );
+
+    let mut stripped = err;
+    stripped.strip_synthetic_frames();
+    assert_eq!(functions(&stripped), [Some("traced::load"), Some("traced::outer"), None]);
+
+    stripped.truncate_trace(1);
+    assert_eq!(functions(&stripped), [Some("traced::load")]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn untraced_frames_have_no_function() {
+    let _ = load("x");
+    let err = untraced("x").unwrap_err();
+    assert_eq!(functions(&err), [None]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn async_fns_are_current_while_polled() {
+    let err = fetch("x").await.unwrap_err();
+    assert_eq!(functions(&err), [Some("traced::fetch")]);
+    assert_eq!(functions(&untraced("x").unwrap_err()), [None]);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn rendered_after_the_location() {
+    let err = load("x").unwrap_err();
+    assert!(err.to_string().contains(" in traced::load\n"));
+    assert!(err.to_json().contains(r#""function":"traced::load""#));
+}
+
+#[cfg(feature = "serde")]
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn survives_serde() {
+    let err = load("x").unwrap_err();
+    let back = Handled::from(err.to_serializable());
+    assert_eq!(functions(&back), [Some("traced::load")]);
+}
+
+#[cfg(feature = "serde")]
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn serde_field_order_matches_to_json() {
+    let err = load("x").unwrap_err();
+    let frame = err.frames().next().unwrap();
+    let json = serde_json::to_string(&frame).unwrap();
+    assert_eq!(json, frame.format_json());
+    assert!(json.contains(r#""function":"traced::load","message":"loading""#));
+    let repr = serde_json::to_string(&err.to_serializable().trace[0]).unwrap();
+    assert_eq!(repr, json);
+}
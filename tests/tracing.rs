@@ -0,0 +1,158 @@
+//! `tracing` integration: `trace_error`, creation events and span ids.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "tracing")]
+
+use handle_this::{handle, trace, Handled, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_core::span::Current;
+use tracing::{Event, Metadata, Subscriber};
+
+type Fields = HashMap<String, String>;
+
+/// Records every event's fields; spans get sequential ids.
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<Fields>>>,
+    next_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<HashMap<u64, &'static Metadata<'static>>>>,
+    entered: Arc<Mutex<Vec<Id>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.spans.lock().unwrap().insert(id, attrs.metadata());
+        Id::from_u64(id)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+    fn enter(&self, id: &Id) {
+        self.entered.lock().unwrap().push(id.clone());
+    }
+    fn exit(&self, _: &Id) {
+        self.entered.lock().unwrap().pop();
+    }
+    fn current_span(&self) -> Current {
+        match self.entered.lock().unwrap().last() {
+            Some(id) => Current::new(id.clone(), self.spans.lock().unwrap()[&id.into_u64()]),
+            None => Current::none(),
+        }
+    }
+}
+
+/// Run `f` under a fresh recorder, returning its events.
+///
+/// `emit_on_create_is_opt_in` flips a global switch, so tests that count
+/// `trace_error` events filter on the message.
+fn recorded<T>(f: impl FnOnce() -> T) -> (T, Vec<Fields>) {
+    let recorder = Recorder::default();
+    let value = tracing::subscriber::with_default(recorder.clone(), f);
+    let events = recorder.events.lock().unwrap().clone();
+    (value, events)
+}
+
+fn handled(events: &[Fields]) -> Vec<&Fields> {
+    events.iter().filter(|e| e["message"] == "error handled").collect()
+}
+
+fn load() -> Result<i32> {
+    handle! {
+        try { "x".parse::<i32>()? }
+        with "loading", { file: "app.toml" }
+        trace_error
+    }
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn trace_error_emits_frames_and_attachments() {
+    let (result, events) = recorded(load);
+    assert!(result.is_err());
+    let events = handled(&events);
+    assert_eq!(events.len(), 1);
+    let event = events[0];
+    assert!(event["error"].contains("invalid digit"));
+    assert!(event["frames"].starts_with("tests/tracing.rs:"));
+    assert_eq!(event["attachments"], "file=app.toml");
+}
+
+#[test]
+fn trace_error_carries_the_correlation_id() {
+    let (_, events) = recorded(|| -> Result<i32> {
+        handle! {
+            try { Err("boom")? }
+            with_correlation "req-7"
+            trace_error
+        }
+    });
+    assert_eq!(handled(&events)[0]["correlation_id"], "\"req-7\"");
+
+    let (_, events) = recorded(load);
+    assert!(!handled(&events)[0].contains_key("correlation_id"));
+}
+
+#[test]
+fn trace_error_lets_the_error_propagate() {
+    let (result, events) = recorded(|| -> Result<i32> {
+        handle! {
+            try { Err("boom")? }
+            trace_error
+            catch { 7 }
+        }
+    });
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(handled(&events).len(), 1);
+}
+
+#[test]
+fn success_emits_nothing() {
+    let (result, events) = recorded(|| -> Result<i32> { handle! { try { 1 } trace_error } });
+    assert_eq!(result.unwrap(), 1);
+    assert!(events.is_empty());
+}
+
+#[test]
+fn records_the_current_span() {
+    let (err, _) = recorded(|| {
+        let span = tracing::error_span!("request");
+        let _guard = span.enter();
+        (span.id(), Handled::msg("inside"))
+    });
+    let (span_id, err) = err;
+    assert!(span_id.is_some());
+    assert_eq!(err.span_id(), span_id.as_ref());
+    assert!(Handled::msg("outside").span_id().is_none());
+}
+
+#[test]
+fn emit_on_create_is_opt_in() {
+    let (_, quiet) = recorded(|| Handled::msg("quiet"));
+    assert!(quiet.is_empty());
+
+    trace::set_emit_on_create(true);
+    let (_, events) = recorded(|| Handled::msg("loud"));
+    trace::set_emit_on_create(false);
+    assert!(events.iter().any(|e| e["message"] == "error created" && e["error"] == "loud"));
+}
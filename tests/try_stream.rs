@@ -0,0 +1,121 @@
+//! Tests for `async try stream x in stream { }` per-item stream processing.
+
+#![allow(clippy::result_large_err)]
+#![cfg(feature = "futures")]
+#![cfg_attr(feature = "no-trace", allow(dead_code, unused_imports))]
+
+use handle_this::{handle, Handled, Result};
+use handle_this::__futures::{stream, StreamExt};
+use std::cell::RefCell;
+use std::num::ParseIntError;
+
+async fn parse(s: &str) -> std::result::Result<i32, ParseIntError> {
+    s.parse()
+}
+
+#[tokio::test]
+async fn processes_every_item() {
+    let seen = RefCell::new(Vec::new());
+    let result: Result<()> = handle! {
+        async try stream s in stream::iter(["1", "2", "3"]) {
+            let n = parse(s).await?;
+            seen.borrow_mut().push(n);
+        }
+    };
+    result.unwrap();
+    assert_eq!(*seen.borrow(), [1, 2, 3]);
+}
+
+#[tokio::test]
+async fn continue_skips_a_failing_item() {
+    let seen = RefCell::new(Vec::new());
+    let skipped = RefCell::new(Vec::new());
+    let result: Result<()> = handle! {
+        async try stream s in stream::iter(["1", "x", "3"]) {
+            let n = parse(s).await?;
+            seen.borrow_mut().push(n);
+        }
+        catch ParseIntError(e) {
+            skipped.borrow_mut().push(e.to_string());
+            continue
+        }
+    };
+    result.unwrap();
+    assert_eq!(*seen.borrow(), [1, 3]);
+    assert_eq!(skipped.borrow().len(), 1);
+}
+
+#[tokio::test]
+async fn break_stops_the_stream() {
+    let seen = RefCell::new(Vec::new());
+    let result: Result<()> = handle! {
+        async try stream s in stream::iter(["1", "x", "3"]) {
+            let n = parse(s).await?;
+            seen.borrow_mut().push(n);
+        }
+        catch { break }
+    };
+    result.unwrap();
+    assert_eq!(*seen.borrow(), [1]);
+}
+
+#[tokio::test]
+async fn unit_catch_moves_on() {
+    let errors = RefCell::new(0);
+    let result: Result<()> = handle! {
+        async try stream s in stream::iter(["x", "y", "3"]) {
+            parse(s).await?;
+        }
+        catch { *errors.borrow_mut() += 1; }
+    };
+    result.unwrap();
+    assert_eq!(*errors.borrow(), 2);
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[tokio::test]
+async fn unmatched_error_ends_the_stream_with_an_item_frame() {
+    let seen = RefCell::new(Vec::new());
+    let line = line!() + 1;
+    let result: Result<()> = handle! {
+        async try stream (i, s) in stream::iter(["1", "", "x"]).enumerate() {
+            if s.is_empty() { Err(Handled::msg("empty"))? }
+            let n = parse(s).await?;
+            seen.borrow_mut().push(n);
+        }
+        catch ParseIntError(_) { continue }
+        with "reading numbers"
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.message(), "empty");
+    assert_eq!(*seen.borrow(), [1]);
+
+    let frame = err.frames().last().unwrap();
+    assert_eq!(frame.line, line);
+    assert_eq!(frame.context, Some("reading numbers"));
+    assert_eq!(err.get_kv("attempt").map(|v| v.to_string()), Some("1".to_string()));
+}
+
+#[tokio::test]
+async fn throw_ends_the_stream() {
+    let result: Result<()> = handle! {
+        async try stream s in stream::iter(["x", "2"]) {
+            parse(s).await?;
+        }
+        throw ParseIntError(e) { format!("bad item: {}", e) }
+    };
+    assert!(result.unwrap_err().message().starts_with("bad item"));
+}
+
+#[tokio::test]
+async fn finally_runs_after_the_stream() {
+    let log = RefCell::new(Vec::new());
+    let result: Result<()> = handle! {
+        async try stream n in stream::iter(1..=2) {
+            log.borrow_mut().push(format!("item {}", n));
+        }
+        finally { log.borrow_mut().push("done".to_string()); }
+    };
+    result.unwrap();
+    assert_eq!(*log.borrow(), ["item 1", "item 2", "done"]);
+}
@@ -0,0 +1,102 @@
+//! Tests for typed `try<E> { }`, yielding `Result<T, Handled<E>>`.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Handled, Result};
+use std::{fmt, io};
+
+fn read(ok: bool) -> std::result::Result<String, io::Error> {
+    if ok {
+        Ok("data".to_string())
+    } else {
+        Err(io::Error::new(io::ErrorKind::NotFound, "missing"))
+    }
+}
+
+fn load(ok: bool) -> Result<String, Handled<io::Error>> {
+    handle! { try<io::Error> { read(ok)? } }
+}
+
+#[test]
+fn success_passes_through() {
+    assert_eq!(load(true).unwrap(), "data");
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn error_keeps_its_concrete_type() {
+    let err = load(false).unwrap_err();
+    // No downcast needed
+    assert_eq!(err.source_ref().kind(), io::ErrorKind::NotFound);
+    assert_eq!(err.depth(), 1);
+    assert_eq!(err.frames().next().unwrap().file, file!());
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn with_adds_context_and_kv() {
+    let id = 4;
+    let result = handle! {
+        try<io::Error> { read(false)? }
+        with "loading record", { id: id }
+    };
+    let err = result.unwrap_err();
+    assert_eq!(err.frames().next().unwrap().context, Some("loading record"));
+    assert_eq!(err.get_kv("id").map(|v| v.to_string()), Some("4".to_string()));
+}
+
+#[derive(Debug)]
+enum StoreError {
+    Io(io::Error),
+    Parse(std::num::ParseIntError),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "io: {}", e),
+            StoreError::Parse(e) => write!(f, "parse: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+impl From<std::num::ParseIntError> for StoreError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        StoreError::Parse(e)
+    }
+}
+
+#[test]
+fn question_mark_uses_from_conversions() {
+    let parse = |s: &str| -> Result<u32, Handled<StoreError>> {
+        handle! {
+            try<StoreError> {
+                let text = read(true)?;
+                s.parse::<u32>()? + text.len() as u32
+            }
+        }
+    };
+    assert_eq!(parse("1").unwrap(), 5);
+    let err = parse("x").unwrap_err();
+    assert!(matches!(err.source_ref(), StoreError::Parse(_)));
+}
+
+#[cfg(not(feature = "no-trace"))]
+#[test]
+fn erases_into_the_default_result() {
+    fn erased() -> Result<String> {
+        let data = load(false).map_err(Handled::erase)?;
+        Ok(data)
+    }
+    let err = erased().unwrap_err();
+    assert!(err.downcast_ref::<io::Error>().is_some());
+    assert_eq!(err.depth(), 1);
+}
@@ -0,0 +1,11 @@
+//! Error: `catch all T` takes every error `catch any T` could match
+
+use handle_this::handle;
+
+fn main() {
+    let _ = handle! {
+        try { std::fs::read_to_string("x")? }
+        catch all std::io::Error |errs| { errs.len().to_string() }
+        catch any std::io::Error(e) { e.to_string() } // unreachable!
+    };
+}
@@ -0,0 +1,12 @@
+//! Error: `#[context]` needs a function returning `handle_this::Result`
+
+use handle_this::context;
+
+#[context("printing")]
+fn print(s: &str) {
+    println!("{}", s);
+}
+
+fn main() {
+    print("x");
+}
@@ -0,0 +1,11 @@
+//! Error: a second identical typed catch is unreachable
+
+use handle_this::handle;
+
+fn main() {
+    let _ = handle! {
+        try { std::fs::read_to_string("x")? }
+        catch std::io::Error(_) { String::new() }
+        catch std::io::Error(e) { e.to_string() } // unreachable!
+    };
+}
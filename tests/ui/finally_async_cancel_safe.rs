@@ -0,0 +1,13 @@
+//! Error: `finally async` can't run from a drop guard
+
+use handle_this::handle;
+
+async fn run() {
+    let _ = handle! {
+        async try { Err::<i32, &str>("error")? }
+        finally async { std::future::ready(()).await; }
+        cancel_safe
+    };
+}
+
+fn main() {}
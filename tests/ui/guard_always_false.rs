@@ -0,0 +1,10 @@
+//! Error: a `when false` guard makes the handler dead
+
+use handle_this::handle;
+
+fn main() {
+    let _ = handle! {
+        try { std::fs::read_to_string("x")? }
+        catch std::io::Error(e) when false { e.to_string() }
+    };
+}
@@ -0,0 +1,10 @@
+//! Error: `when kind` checks the binding, so the clause needs one
+
+use handle_this::handle;
+
+fn main() {
+    let _ = handle! {
+        try { std::fs::read_to_string("x")? }
+        catch std::io::Error when kind NotFound { String::new() }
+    };
+}
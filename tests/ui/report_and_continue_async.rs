@@ -0,0 +1,14 @@
+//! Error: `report_and_continue` can't continue a loop from an `async try` body
+
+use handle_this::{handle, Result};
+
+async fn run(items: &[&str]) {
+    for s in items {
+        let _: Result<i32> = handle! {
+            async try { s.parse::<i32>()? }
+            report_and_continue
+        };
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+//! Error: `rethrow` is only supported in sync `try` catch handlers
+
+use handle_this::{handle, Result};
+
+async fn load() -> Result<u32> {
+    handle! {
+        async try { "x".parse::<u32>()? }
+        catch e { rethrow }
+    }
+}
+
+fn main() {
+    let _ = load();
+}
@@ -0,0 +1,15 @@
+//! Error: `rethrow` needs the handler closure, which break/continue rule out
+
+use handle_this::{handle, Result};
+
+fn main() {
+    for i in 0..3 {
+        let _: Result<u32> = handle! {
+            try { "x".parse::<u32>()? }
+            catch e {
+                if i == 0 { continue }
+                rethrow
+            }
+        };
+    }
+}
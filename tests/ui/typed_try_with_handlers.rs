@@ -0,0 +1,10 @@
+//! Error: typed try doesn't take handlers
+
+use handle_this::handle;
+
+fn main() {
+    let _ = handle! {
+        try<std::io::Error> { std::fs::read_to_string("x")? }
+        catch { String::new() }
+    };
+}
@@ -0,0 +1,10 @@
+//! Error: a `with` closure takes exactly the error
+
+use handle_this::{handle, Result};
+
+fn main() {
+    let _: Result<()> = handle! {
+        try { Err("boom")? }
+        with |a, b| format!("{} {}", a, b)
+    };
+}
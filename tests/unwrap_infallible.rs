@@ -0,0 +1,56 @@
+//! Tests for the `unwrap_infallible` marker.
+
+#![allow(clippy::result_large_err)]
+
+use handle_this::{handle, Result};
+use std::io;
+use std::num::ParseIntError;
+
+fn load(kind: u8) -> Result<i32> {
+    handle! {
+        try {
+            match kind {
+                0 => Err(io::Error::new(io::ErrorKind::NotFound, "missing"))?,
+                1 => "x".parse::<i32>()?,
+                _ => 7,
+            }
+        }
+    }
+}
+
+fn load_or_default(kind: u8) -> i32 {
+    handle! {
+        try { load(kind)? }
+        catch io::Error(_) { -1 }
+        catch ParseIntError(_) { -2 }
+        unwrap_infallible
+    }
+}
+
+#[test]
+fn typed_catches_covering_all_errors_yield_value() {
+    assert_eq!(load_or_default(0), -1);
+    assert_eq!(load_or_default(1), -2);
+    assert_eq!(load_or_default(2), 7);
+}
+
+#[test]
+fn works_inside_an_outer_try() {
+    let result: Result<i32> = handle! {
+        try {
+            let value: i32 = try { load(1)? } catch ParseIntError(_) { 0 } unwrap_infallible;
+            value + 1
+        }
+    };
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[test]
+#[should_panic(expected = "an error escaped every handler")]
+fn escaped_error_panics() {
+    let _: i32 = handle! {
+        try { load(0)? }
+        catch ParseIntError(_) { 0 }
+        unwrap_infallible
+    };
+}